@@ -0,0 +1,94 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const KEYBOARD_MODELS_DIRECTORY: &'static str = "keyboard_models";
+const KEYBOARD_MODEL_EXTENSION: &'static str = "kbm";
+const BUILTIN_DEVICE_MODELS_FILE: &'static str = "builtin_device_models.rs";
+
+fn get_keyboard_model_paths(directory: &Path) -> Vec<PathBuf> {
+    let mut paths = fs::read_dir(directory)
+        .expect("Cannot open keyboard model directory.")
+        .map(|entry| {
+            entry
+                .expect("Cannot read keyboard model directory contents.")
+                .path()
+        })
+        .filter(|path| {
+            path.extension().and_then(|extension| extension.to_str())
+                == Some(KEYBOARD_MODEL_EXTENSION)
+        })
+        .collect::<Vec<PathBuf>>();
+
+    paths.sort();
+
+    paths
+}
+
+// Mirrors DeviceModel::from_string: the model size followed by x, y, width,
+// height and key code of every key.
+fn validate_keyboard_model(path: &Path, model_string: &str) {
+    let integer_count = model_string
+        .split_whitespace()
+        .map(|part| {
+            part.parse::<i32>().unwrap_or_else(|_| {
+                panic!("Invalid keyboard model {:?}: {:?}.", path, part)
+            })
+        })
+        .count();
+
+    if integer_count < 2 || (integer_count - 2) % 5 != 0 {
+        panic!(
+            "Invalid keyboard model {:?}: incomplete key description.",
+            path
+        );
+    }
+}
+
+// Generates a table of (model name, model file contents) pairs, so that the
+// shipped keyboard models are available regardless of the working directory.
+fn main() {
+    let manifest_directory =
+        env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set.");
+    let out_directory = env::var("OUT_DIR").expect("OUT_DIR is not set.");
+
+    let keyboard_models_directory =
+        Path::new(&manifest_directory).join(KEYBOARD_MODELS_DIRECTORY);
+
+    println!(
+        "cargo:rerun-if-changed={}",
+        keyboard_models_directory.display()
+    );
+
+    let mut generated = String::from(
+        "pub const BUILTIN_DEVICE_MODELS: &'static [(&'static str, &'static str)] = &[\n",
+    );
+
+    for path in get_keyboard_model_paths(&keyboard_models_directory) {
+        let model_name = path
+            .file_stem()
+            .and_then(|file_stem| file_stem.to_str())
+            .expect("Invalid keyboard model file name.");
+        let model_path = path.to_str().expect("Invalid keyboard model path.");
+
+        println!("cargo:rerun-if-changed={}", model_path);
+
+        let model_string = fs::read_to_string(&path)
+            .expect("Cannot read keyboard model file.");
+
+        validate_keyboard_model(&path, &model_string);
+
+        generated.push_str(&format!(
+            "    ({:?}, include_str!({:?})),\n",
+            model_name, model_path
+        ));
+    }
+
+    generated.push_str("];\n");
+
+    let generated_path =
+        Path::new(&out_directory).join(BUILTIN_DEVICE_MODELS_FILE);
+
+    fs::write(generated_path, generated)
+        .expect("Cannot write builtin keyboard models.");
+}
@@ -0,0 +1,62 @@
+use std::sync::{Arc, Mutex};
+
+use protobuf::Message;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{NiaRequest, Serializable};
+use crate::server::SERVER_ADDRESS;
+
+// Sends a request to the running server and waits for the response the
+// predicate accepts. Other responses, e.g. broadcasted notifications, are
+// skipped.
+pub fn send_request<F>(
+    nia_request: NiaRequest,
+    is_expected_response: F,
+) -> NiaServerResult<nia_protocol_rust::Response>
+where
+    F: Fn(&nia_protocol_rust::Response) -> bool + Clone,
+{
+    let bytes = nia_request.to_bytes()?;
+    let response_handle = Arc::new(Mutex::new(None));
+    let response_handle_clone = response_handle.clone();
+
+    ws::connect(
+        format!("ws://{}", SERVER_ADDRESS),
+        move |out: ws::Sender| {
+            let response_handle = response_handle_clone.clone();
+            let is_expected_response = is_expected_response.clone();
+
+            if let Err(error) = out.send(ws::Message::Binary(bytes.clone())) {
+                println!("Cannot send request: {:?}", error);
+            }
+
+            move |msg: ws::Message| {
+                if let ws::Message::Binary(bytes) = msg {
+                    let mut response = nia_protocol_rust::Response::new();
+
+                    if response.merge_from_bytes(&bytes).is_ok()
+                        && is_expected_response(&response)
+                    {
+                        *response_handle.lock().unwrap() = Some(response);
+
+                        return out.close(ws::CloseCode::Normal);
+                    }
+                }
+
+                Ok(())
+            }
+        },
+    )
+    .map_err(|error| {
+        NiaServerError::unknown(format!(
+            "Cannot connect to server at {}: {}",
+            SERVER_ADDRESS, error
+        ))
+    })?;
+
+    let response = response_handle.lock().unwrap().take();
+
+    response.ok_or_else(|| {
+        NiaServerError::unknown("Server closed connection without response.")
+    })
+}
@@ -0,0 +1,69 @@
+use crate::protocol::NiaConfigImportMode;
+
+use crate::cli::{activate_profile, diff_config, export_config, import_config};
+
+const USAGE: &'static str = "Usage:
+    nia_server
+    nia_server export-config [FILE]
+    nia_server import-config FILE [--replace]
+    nia_server diff-config OLD NEW [--protobuf]
+    nia_server activate-profile PROFILE
+
+OLD and NEW of diff-config are configuration files or :live, the
+configuration the server has now.";
+
+// Runs a command given by command line arguments, the first one being the
+// program name. Returns false when no command was given.
+pub fn run_command(args: &[String]) -> bool {
+    let result = match args.get(1).map(|arg| arg.as_str()) {
+        None => return false,
+        Some("export-config") => {
+            export_config(args.get(2).map(|arg| arg.as_str()))
+        }
+        Some("import-config") => match args.get(2) {
+            Some(file_path) => {
+                let import_mode =
+                    if args.iter().skip(3).any(|arg| arg == "--replace") {
+                        NiaConfigImportMode::Replace
+                    } else {
+                        NiaConfigImportMode::Merge
+                    };
+
+                import_config(file_path, import_mode)
+            }
+            None => {
+                println!("{}", USAGE);
+                return true;
+            }
+        },
+        Some("diff-config") => match (args.get(2), args.get(3)) {
+            (Some(old_argument), Some(new_argument)) => {
+                let print_protobuf =
+                    args.iter().skip(4).any(|arg| arg == "--protobuf");
+
+                diff_config(old_argument, new_argument, print_protobuf)
+            }
+            _ => {
+                println!("{}", USAGE);
+                return true;
+            }
+        },
+        Some("activate-profile") => match args.get(2) {
+            Some(profile_name) => activate_profile(profile_name),
+            None => {
+                println!("{}", USAGE);
+                return true;
+            }
+        },
+        Some(_) => {
+            println!("{}", USAGE);
+            return true;
+        }
+    };
+
+    if let Err(error) = result {
+        println!("{}", error.get_message());
+    }
+
+    true
+}
@@ -0,0 +1,157 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{
+    NiaConfigChange, NiaConfigChangeKind, NiaConfigImportMode, NiaConfigSource,
+    NiaDiffConfigRequest, NiaExportConfigRequest, NiaImportConfigRequest,
+    Serializable,
+};
+
+use crate::cli::send_request;
+
+pub fn export_config(file_path: Option<&str>) -> NiaServerResult<()> {
+    let mut response =
+        send_request(NiaExportConfigRequest::new().into(), |response| {
+            response.has_export_config_response()
+        })?;
+
+    let mut export_config_response = response.take_export_config_response();
+
+    if export_config_response.has_failure_result() {
+        return NiaServerError::unknown(
+            export_config_response.get_failure_result().get_message(),
+        )
+        .into();
+    }
+
+    let config = export_config_response
+        .take_success_result()
+        .get_config()
+        .to_string();
+
+    match file_path {
+        Some(file_path) => {
+            std::fs::write(file_path, config).map_err(|error| {
+                NiaServerError::unknown(format!(
+                    "Cannot write {}: {}",
+                    file_path, error
+                ))
+            })?;
+        }
+        None => println!("{}", config),
+    }
+
+    Ok(())
+}
+
+pub fn import_config(
+    file_path: &str,
+    import_mode: NiaConfigImportMode,
+) -> NiaServerResult<()> {
+    let config = std::fs::read_to_string(file_path).map_err(|error| {
+        NiaServerError::unknown(format!("Cannot read {}: {}", file_path, error))
+    })?;
+
+    let mut response = send_request(
+        NiaImportConfigRequest::new(config, import_mode).into(),
+        |response| response.has_import_config_response(),
+    )?;
+
+    let mut import_config_response = response.take_import_config_response();
+
+    if import_config_response.has_error_result() {
+        return NiaServerError::invalid_request(
+            import_config_response.get_error_result().get_message(),
+        )
+        .into();
+    }
+
+    if import_config_response.has_failure_result() {
+        return NiaServerError::unknown(
+            import_config_response.get_failure_result().get_message(),
+        )
+        .into();
+    }
+
+    let changes = import_config_response
+        .take_success_result()
+        .take_changes()
+        .into_iter()
+        .map(|change_pb| NiaConfigChange::from_pb(change_pb))
+        .collect::<NiaServerResult<Vec<NiaConfigChange>>>()?;
+
+    if changes.is_empty() {
+        println!("Nothing changed.");
+    }
+
+    for change in changes {
+        let kind = match change.get_kind() {
+            NiaConfigChangeKind::Added => "Added",
+            NiaConfigChangeKind::Changed => "Changed",
+            NiaConfigChangeKind::Removed => "Removed",
+        };
+
+        println!("{} {}", kind, change.get_description());
+    }
+
+    Ok(())
+}
+
+// The live configuration is referred to as ":live", anything else is a path
+// of a configuration file.
+const LIVE_CONFIG_ARGUMENT: &'static str = ":live";
+
+fn read_config_source(argument: &str) -> NiaServerResult<NiaConfigSource> {
+    if argument == LIVE_CONFIG_ARGUMENT {
+        return Ok(NiaConfigSource::Live);
+    }
+
+    let config = std::fs::read_to_string(argument).map_err(|error| {
+        NiaServerError::unknown(format!("Cannot read {}: {}", argument, error))
+    })?;
+
+    Ok(NiaConfigSource::Text(config))
+}
+
+pub fn diff_config(
+    old_argument: &str,
+    new_argument: &str,
+    print_protobuf: bool,
+) -> NiaServerResult<()> {
+    let old_source = read_config_source(old_argument)?;
+    let new_source = read_config_source(new_argument)?;
+
+    let mut response = send_request(
+        NiaDiffConfigRequest::new(old_source, new_source).into(),
+        |response| response.has_diff_config_response(),
+    )?;
+
+    let mut diff_config_response = response.take_diff_config_response();
+
+    if diff_config_response.has_error_result() {
+        return NiaServerError::invalid_request(
+            diff_config_response.get_error_result().get_message(),
+        )
+        .into();
+    }
+
+    if diff_config_response.has_failure_result() {
+        return NiaServerError::unknown(
+            diff_config_response.get_failure_result().get_message(),
+        )
+        .into();
+    }
+
+    let mut success_result = diff_config_response.take_success_result();
+
+    if print_protobuf {
+        print!(
+            "{}",
+            protobuf::text_format::print_to_string(
+                success_result.get_config_diff()
+            )
+        );
+    } else {
+        println!("{}", success_result.take_text());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+mod client;
+mod command;
+mod config_commands;
+mod profile_commands;
+
+pub use client::*;
+pub use command::*;
+pub use config_commands::*;
+pub use profile_commands::*;
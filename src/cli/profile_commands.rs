@@ -0,0 +1,29 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::NiaActivateProfileRequest;
+
+use crate::cli::send_request;
+
+pub fn activate_profile(profile_name: &str) -> NiaServerResult<()> {
+    let mut response = send_request(
+        NiaActivateProfileRequest::new(profile_name).into(),
+        |response| response.has_activate_profile_response(),
+    )?;
+
+    let activate_profile_response = response.take_activate_profile_response();
+
+    if activate_profile_response.has_error_result() {
+        return NiaServerError::invalid_request(
+            activate_profile_response.get_error_result().get_message(),
+        )
+        .into();
+    }
+
+    if activate_profile_response.has_failure_result() {
+        return NiaServerError::unknown(
+            activate_profile_response.get_failure_result().get_message(),
+        )
+        .into();
+    }
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+mod cli;
 mod error;
 mod protocol;
 mod server;
@@ -12,6 +13,12 @@ use log::LevelFilter;
 use std::io::Write;
 
 fn main() {
+    let args = std::env::args().collect::<Vec<String>>();
+
+    if cli::run_command(&args) {
+        return;
+    }
+
     Builder::new()
         .format(|buf, record| {
             writeln!(
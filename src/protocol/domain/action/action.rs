@@ -2,12 +2,18 @@ use crate::error::NiaServerError;
 use crate::error::NiaServerResult;
 
 use crate::protocol::Serializable;
+use crate::protocol::{
+    interpreter_actions_from_code, interpreter_actions_to_code, is_server_code,
+    ActionList, SEQUENCE_MARKER,
+};
 use crate::protocol::{NiaActionEnum, NiaConvertable};
 
 use crate::protocol::domain::action::basic_actions::*;
-use nia_interpreter_core::{Action, SymbolId};
+use nia_interpreter_core::Action;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct NiaAction {
     action: NiaActionEnum,
 }
@@ -20,6 +26,184 @@ impl NiaAction {
     pub fn get_action(&self) -> &NiaActionEnum {
         &self.action
     }
+
+    // Actions are checked before they are sent to the interpreter.
+    pub fn check(&self) -> NiaServerResult<()> {
+        match &self.action {
+            NiaActionEnum::ExecuteCode(action_execute_code) => {
+                action_execute_code.check()
+            }
+            NiaActionEnum::EmitChord(action_emit_chord) => {
+                action_emit_chord.check()
+            }
+            NiaActionEnum::EmitRawEvent(action_emit_raw_event) => {
+                action_emit_raw_event.check()
+            }
+            NiaActionEnum::MouseScroll(action_mouse_scroll) => {
+                action_mouse_scroll.check()
+            }
+            NiaActionEnum::Sequence(action_list) => {
+                for action in action_list.get_actions() {
+                    action.check()?;
+                }
+
+                Ok(())
+            }
+            NiaActionEnum::Repeat(action_repeat) => action_repeat.check(),
+            NiaActionEnum::RepeatWhileHeld(action_repeat_while_held) => {
+                action_repeat_while_held.check()
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn refers_to_named_action(&self, action_name: &str) -> bool {
+        match &self.action {
+            NiaActionEnum::ExecuteNamedAction(action_execute_named_action) => {
+                action_execute_named_action.get_action_name() == action_name
+            }
+            NiaActionEnum::Sequence(action_list) => action_list
+                .get_actions()
+                .iter()
+                .any(|action| action.refers_to_named_action(action_name)),
+            NiaActionEnum::Repeat(action_repeat) => action_repeat
+                .get_action()
+                .refers_to_named_action(action_name),
+            NiaActionEnum::RepeatWhileHeld(action_repeat_while_held) => {
+                action_repeat_while_held
+                    .get_action()
+                    .refers_to_named_action(action_name)
+            }
+            _ => false,
+        }
+    }
+
+    // The same action that executes the named action by its new name.
+    pub fn rename_named_action(
+        &self,
+        old_action_name: &str,
+        new_action_name: &str,
+    ) -> NiaAction {
+        if !self.refers_to_named_action(old_action_name) {
+            return self.clone();
+        }
+
+        match &self.action {
+            NiaActionEnum::Sequence(action_list) => {
+                let actions = action_list
+                    .get_actions()
+                    .iter()
+                    .map(|action| {
+                        action.rename_named_action(
+                            old_action_name,
+                            new_action_name,
+                        )
+                    })
+                    .collect();
+
+                NiaAction::new(ActionList::new(actions).into())
+            }
+            NiaActionEnum::Repeat(action_repeat) => {
+                let action = action_repeat
+                    .get_action()
+                    .rename_named_action(old_action_name, new_action_name);
+
+                NiaAction::new(
+                    ActionRepeat::new(
+                        action,
+                        action_repeat.get_times(),
+                        action_repeat.get_interval_ms(),
+                    )
+                    .into(),
+                )
+            }
+            NiaActionEnum::RepeatWhileHeld(action_repeat_while_held) => {
+                let action = action_repeat_while_held
+                    .get_action()
+                    .rename_named_action(old_action_name, new_action_name);
+
+                NiaAction::new(
+                    ActionRepeatWhileHeld::new(
+                        action,
+                        action_repeat_while_held.get_interval_ms(),
+                    )
+                    .into(),
+                )
+            }
+            _ => NiaAction::new(NiaActionEnum::ExecuteNamedAction(
+                ActionExecuteNamedAction::new(new_action_name),
+            )),
+        }
+    }
+
+    // Actions the interpreter knows nothing about are sent to it as code,
+    // the code is recognized here when it comes back.
+    fn from_interpreter_code(code: &str) -> NiaServerResult<NiaAction> {
+        if let Some(action_activate_profile) =
+            ActionActivateProfile::from_code(code)
+        {
+            return Ok(NiaAction::new(action_activate_profile.into()));
+        }
+
+        if let Some(action_emit_chord) = ActionEmitChord::from_code(code) {
+            return Ok(NiaAction::new(action_emit_chord.into()));
+        }
+
+        if let Some(action_emit_raw_event) = ActionEmitRawEvent::from_code(code)
+        {
+            return Ok(NiaAction::new(action_emit_raw_event.into()));
+        }
+
+        if let Some(action_mouse_scroll) = ActionMouseScroll::from_code(code) {
+            return Ok(NiaAction::new(action_mouse_scroll.into()));
+        }
+
+        if let Some(action_execute_interpreter_value) =
+            ActionExecuteInterpreterValue::from_code(code)
+        {
+            return Ok(NiaAction::new(action_execute_interpreter_value.into()));
+        }
+
+        if let Some(action_repeat) = ActionRepeat::from_code(code)? {
+            return Ok(NiaAction::new(action_repeat.into()));
+        }
+
+        if let Some(action_repeat_while_held) =
+            ActionRepeatWhileHeld::from_code(code)?
+        {
+            return Ok(NiaAction::new(action_repeat_while_held.into()));
+        }
+
+        if let Some(interpreter_actions) =
+            interpreter_actions_from_code(SEQUENCE_MARKER, code)
+        {
+            let actions = interpreter_actions
+                .iter()
+                .map(|action| NiaAction::from_interpreter_repr(action))
+                .collect::<NiaServerResult<Vec<NiaAction>>>()?;
+
+            return Ok(NiaAction::new(ActionList::new(actions).into()));
+        }
+
+        if is_server_code(code) {
+            return NiaServerError::interpreter_error(format!(
+                "Invalid action code: {}.",
+                code
+            ))
+            .into();
+        }
+
+        Ok(NiaAction::new(ActionExecuteCode::new(code).into()))
+    }
+}
+
+// Actions are checked before they are converted, so the actions of
+// sequences and repetitions always have code.
+fn to_checked_code(code: NiaServerResult<String>) -> Action {
+    match code {
+        Ok(code) => Action::ExecuteCode(code),
+        Err(error) => unreachable!("{}", error.get_message()),
+    }
 }
 
 impl From<NiaActionEnum> for NiaAction {
@@ -47,6 +231,17 @@ impl NiaConvertable<NiaAction, nia_interpreter_core::Action> for NiaAction {
                 )
             }
 
+            NiaActionEnum::EmitChord(action_emit_chord) => {
+                nia_interpreter_core::Action::ExecuteCode(
+                    action_emit_chord.to_code(),
+                )
+            }
+            NiaActionEnum::EmitRawEvent(action_emit_raw_event) => {
+                nia_interpreter_core::Action::ExecuteCode(
+                    action_emit_raw_event.to_code(),
+                )
+            }
+
             NiaActionEnum::MouseButtonClick(action_mouse_button_click) => {
                 nia_interpreter_core::Action::MouseButtonClick(
                     action_mouse_button_click.get_button_code(),
@@ -111,6 +306,11 @@ impl NiaConvertable<NiaAction, nia_interpreter_core::Action> for NiaAction {
                     action_mouse_relative_move.get_dy(),
                 )
             }
+            NiaActionEnum::MouseScroll(action_mouse_scroll) => {
+                nia_interpreter_core::Action::ExecuteCode(
+                    action_mouse_scroll.to_code(),
+                )
+            }
 
             NiaActionEnum::Wait(action_wait) => {
                 nia_interpreter_core::Action::Wait(action_wait.get_ms())
@@ -143,9 +343,34 @@ impl NiaConvertable<NiaAction, nia_interpreter_core::Action> for NiaAction {
             }
             NiaActionEnum::ExecuteInterpreterValue(
                 action_execute_interpreter_value,
-            ) => nia_interpreter_core::Action::ExecuteFunctionValue(
-                nia_interpreter_core::Value::Symbol(SymbolId::new(0)),
+            ) => nia_interpreter_core::Action::ExecuteCode(
+                action_execute_interpreter_value.to_code(),
             ),
+
+            NiaActionEnum::ActivateProfile(action_activate_profile) => {
+                nia_interpreter_core::Action::ExecuteCode(
+                    action_activate_profile.to_code(),
+                )
+            }
+
+            NiaActionEnum::Sequence(action_list) => {
+                let interpreter_actions = action_list
+                    .get_actions()
+                    .iter()
+                    .map(|action| action.to_interpreter_repr())
+                    .collect::<Vec<nia_interpreter_core::Action>>();
+
+                to_checked_code(interpreter_actions_to_code(
+                    SEQUENCE_MARKER,
+                    &interpreter_actions,
+                ))
+            }
+            NiaActionEnum::Repeat(action_repeat) => {
+                to_checked_code(action_repeat.to_code())
+            }
+            NiaActionEnum::RepeatWhileHeld(action_repeat_while_held) => {
+                to_checked_code(action_repeat_while_held.to_code())
+            }
         };
 
         action
@@ -220,9 +445,7 @@ impl NiaConvertable<NiaAction, nia_interpreter_core::Action> for NiaAction {
                 action: ActionTextType::new(text_to_type).into(),
             },
             nia_interpreter_core::Action::ExecuteCode(code_to_execute) => {
-                NiaAction {
-                    action: ActionExecuteCode::new(code_to_execute).into(),
-                }
+                NiaAction::from_interpreter_code(code_to_execute)?
             }
             nia_interpreter_core::Action::ExecuteFunction(function_name) => {
                 NiaAction {
@@ -239,11 +462,12 @@ impl NiaConvertable<NiaAction, nia_interpreter_core::Action> for NiaAction {
                     action: ActionExecuteNamedAction::new(action_name).into(),
                 }
             }
-            nia_interpreter_core::Action::ExecuteFunctionValue(
-                function_value,
-            ) => NiaAction {
-                action: ActionExecuteInterpreterValue::new().into(),
-            },
+            nia_interpreter_core::Action::ExecuteFunctionValue(_) => {
+                return NiaServerError::interpreter_error(
+                    "Function values must be printed before they are read.",
+                )
+                .into()
+            }
         };
 
         Ok(action)
@@ -271,6 +495,17 @@ impl Serializable<NiaAction, nia_protocol_rust::Action> for NiaAction {
                 action_pb.set_action_key_release(action_key_release_pb)
             }
 
+            NiaActionEnum::EmitChord(action_emit_chord) => {
+                let action_emit_chord_pb = action_emit_chord.to_pb();
+
+                action_pb.set_action_emit_chord(action_emit_chord_pb)
+            }
+            NiaActionEnum::EmitRawEvent(action_emit_raw_event) => {
+                let action_emit_raw_event_pb = action_emit_raw_event.to_pb();
+
+                action_pb.set_action_emit_raw_event(action_emit_raw_event_pb)
+            }
+
             NiaActionEnum::MouseButtonClick(action_mouse_button_click) => {
                 let action_mouse_button_click_pb =
                     action_mouse_button_click.to_pb();
@@ -360,6 +595,11 @@ impl Serializable<NiaAction, nia_protocol_rust::Action> for NiaAction {
                     action_mouse_relative_move_pb,
                 )
             }
+            NiaActionEnum::MouseScroll(action_mouse_scroll) => {
+                let action_mouse_scroll_pb = action_mouse_scroll.to_pb();
+
+                action_pb.set_action_mouse_scroll(action_mouse_scroll_pb)
+            }
 
             NiaActionEnum::Wait(action_wait) => {
                 let action_wait_pb = action_wait.to_pb();
@@ -409,6 +649,32 @@ impl Serializable<NiaAction, nia_protocol_rust::Action> for NiaAction {
                     action_execute_interpreter_value_pb,
                 )
             }
+
+            NiaActionEnum::ActivateProfile(action_activate_profile) => {
+                let action_activate_profile_pb =
+                    action_activate_profile.to_pb();
+
+                action_pb
+                    .set_action_activate_profile(action_activate_profile_pb)
+            }
+
+            NiaActionEnum::Sequence(action_list) => {
+                let action_list_pb = action_list.to_pb();
+
+                action_pb.set_action_sequence(action_list_pb)
+            }
+            NiaActionEnum::Repeat(action_repeat) => {
+                let action_repeat_pb = action_repeat.to_pb();
+
+                action_pb.set_action_repeat(action_repeat_pb)
+            }
+            NiaActionEnum::RepeatWhileHeld(action_repeat_while_held) => {
+                let action_repeat_while_held_pb =
+                    action_repeat_while_held.to_pb();
+
+                action_pb
+                    .set_action_repeat_while_held(action_repeat_while_held_pb)
+            }
         }
 
         action_pb
@@ -431,6 +697,15 @@ impl Serializable<NiaAction, nia_protocol_rust::Action> for NiaAction {
             let action_key_release_pb = object_pb.take_action_key_release();
 
             ActionKeyRelease::from_pb(action_key_release_pb)?.into()
+        } else if object_pb.has_action_emit_chord() {
+            let action_emit_chord_pb = object_pb.take_action_emit_chord();
+
+            ActionEmitChord::from_pb(action_emit_chord_pb)?.into()
+        } else if object_pb.has_action_emit_raw_event() {
+            let action_emit_raw_event_pb =
+                object_pb.take_action_emit_raw_event();
+
+            ActionEmitRawEvent::from_pb(action_emit_raw_event_pb)?.into()
         } else if object_pb.has_action_mouse_button_click() {
             let action_mouse_button_click_pb =
                 object_pb.take_action_mouse_button_click();
@@ -500,6 +775,10 @@ impl Serializable<NiaAction, nia_protocol_rust::Action> for NiaAction {
 
             ActionMouseRelativeMove::from_pb(action_mouse_relative_move_pb)?
                 .into()
+        } else if object_pb.has_action_mouse_scroll() {
+            let action_mouse_scroll_pb = object_pb.take_action_mouse_scroll();
+
+            ActionMouseScroll::from_pb(action_mouse_scroll_pb)?.into()
         } else if object_pb.has_action_wait() {
             let action_wait_pb = object_pb.take_action_wait();
 
@@ -537,6 +816,24 @@ impl Serializable<NiaAction, nia_protocol_rust::Action> for NiaAction {
                 action_execute_interpreter_value_pb,
             )?
             .into()
+        } else if object_pb.has_action_activate_profile() {
+            let action_activate_profile_pb =
+                object_pb.take_action_activate_profile();
+
+            ActionActivateProfile::from_pb(action_activate_profile_pb)?.into()
+        } else if object_pb.has_action_sequence() {
+            let action_list_pb = object_pb.take_action_sequence();
+
+            ActionList::from_pb(action_list_pb)?.into()
+        } else if object_pb.has_action_repeat() {
+            let action_repeat_pb = object_pb.take_action_repeat();
+
+            ActionRepeat::from_pb(action_repeat_pb)?.into()
+        } else if object_pb.has_action_repeat_while_held() {
+            let action_repeat_while_held_pb =
+                object_pb.take_action_repeat_while_held();
+
+            ActionRepeatWhileHeld::from_pb(action_repeat_while_held_pb)?.into()
         } else {
             return NiaServerError::deserialization_error(
                 "Invalid action type.",
@@ -548,6 +845,10 @@ impl Serializable<NiaAction, nia_protocol_rust::Action> for NiaAction {
             action: action_enum,
         };
 
+        action.check().map_err(|error| {
+            NiaServerError::deserialization_error(error.get_message())
+        })?;
+
         Ok(action)
     }
 }
@@ -557,6 +858,75 @@ mod tests {
     #[allow(unused_imports)]
     use super::*;
 
+    // Press ctrl, click c, wait 50ms, release ctrl.
+    fn make_macro() -> ActionList {
+        ActionList::new(vec![
+            NiaAction::new(ActionKeyPress::new(29).into()),
+            NiaAction::new(ActionKeyClick::new(46).into()),
+            NiaAction::new(ActionWait::new(50).into()),
+            NiaAction::new(ActionKeyRelease::new(29).into()),
+        ])
+    }
+
+    fn make_repeat() -> ActionRepeat {
+        ActionRepeat::new(NiaAction::new(make_macro().into()), 3, 100)
+    }
+
+    fn make_repeat_while_held() -> ActionRepeatWhileHeld {
+        ActionRepeatWhileHeld::new(
+            NiaAction::new(ActionMouseRelativeMove::new(0, 10).into()),
+            20,
+        )
+    }
+
+    #[cfg(test)]
+    mod renaming {
+        #[allow(unused_imports)]
+        use super::*;
+
+        #[test]
+        fn renames_executed_named_action() {
+            let action =
+                NiaAction::new(ActionExecuteNamedAction::new("old").into());
+
+            let expected =
+                NiaAction::new(ActionExecuteNamedAction::new("new").into());
+            let result = action.rename_named_action("old", "new");
+
+            assert_eq!(expected, result);
+            assert_eq!(action, action.rename_named_action("other", "new"));
+        }
+
+        #[test]
+        fn renames_named_actions_in_sequences() {
+            let action = NiaAction::new(
+                ActionList::new(vec![
+                    NiaAction::new(ActionExecuteNamedAction::new("old").into()),
+                    NiaAction::new(ActionWait::new(50).into()),
+                ])
+                .into(),
+            );
+
+            let expected = NiaAction::new(
+                ActionList::new(vec![
+                    NiaAction::new(ActionExecuteNamedAction::new("new").into()),
+                    NiaAction::new(ActionWait::new(50).into()),
+                ])
+                .into(),
+            );
+
+            assert!(action.refers_to_named_action("old"));
+            assert_eq!(expected, action.rename_named_action("old", "new"));
+        }
+
+        #[test]
+        fn leaves_other_actions_intact() {
+            let action = NiaAction::new(ActionKeyClick::new(1).into());
+
+            assert_eq!(action, action.rename_named_action("old", "new"));
+        }
+    }
+
     #[cfg(test)]
     mod serialization {
         #[allow(unused_imports)]
@@ -598,6 +968,18 @@ mod tests {
             assert_eq!(expected, actual);
         }
 
+        #[test]
+        fn serializes_and_deserializes_action_emit_chord() {
+            let action = ActionEmitChord::new(vec![29, 42], 20, 0).into();
+
+            let expected = NiaAction { action };
+
+            let bytes = expected.to_bytes().unwrap();
+            let actual = NiaAction::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
         #[test]
         fn serializes_and_deserializes_action_mouse_button_click() {
             let action = ActionMouseButtonClick::new(1).into();
@@ -736,6 +1118,39 @@ mod tests {
             assert_eq!(expected, actual);
         }
 
+        #[test]
+        fn serializes_and_deserializes_action_mouse_scroll() {
+            let action = ActionMouseScroll::new(
+                NiaScrollDirection::Horizontal,
+                -1,
+                -150,
+            )
+            .into();
+
+            let expected = NiaAction { action };
+
+            let bytes = expected.to_bytes().unwrap();
+            let actual = NiaAction::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
+        #[test]
+        fn serializes_and_deserializes_action_emit_raw_event() {
+            let action = ActionEmitRawEvent::new(vec![
+                NiaRawEvent::new(4, 4, 458756),
+                NiaRawEvent::new(1, 30, 2),
+            ])
+            .into();
+
+            let expected = NiaAction { action };
+
+            let bytes = expected.to_bytes().unwrap();
+            let actual = NiaAction::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
         #[test]
         fn serializes_and_deserializes_action_wait() {
             let action = ActionWait::new(1000).into();
@@ -809,7 +1224,57 @@ mod tests {
 
         #[test]
         fn serializes_and_deserializes_action_execute_interpreter_value() {
-            let action = ActionExecuteInterpreterValue::new().into();
+            let action = ActionExecuteInterpreterValue::new("(fn () (+ 1 2))")
+                .unwrap()
+                .into();
+
+            let expected = NiaAction { action };
+
+            let bytes = expected.to_bytes().unwrap();
+            let actual = NiaAction::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
+        #[test]
+        fn serializes_and_deserializes_action_activate_profile() {
+            let action = ActionActivateProfile::new("gaming").into();
+
+            let expected = NiaAction { action };
+
+            let bytes = expected.to_bytes().unwrap();
+            let actual = NiaAction::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
+        #[test]
+        fn serializes_and_deserializes_action_repeat() {
+            let action = make_repeat().into();
+
+            let expected = NiaAction { action };
+
+            let bytes = expected.to_bytes().unwrap();
+            let actual = NiaAction::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
+        #[test]
+        fn serializes_and_deserializes_action_repeat_while_held() {
+            let action = make_repeat_while_held().into();
+
+            let expected = NiaAction { action };
+
+            let bytes = expected.to_bytes().unwrap();
+            let actual = NiaAction::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
+        #[test]
+        fn serializes_and_deserializes_action_sequence() {
+            let action = make_macro().into();
 
             let expected = NiaAction { action };
 
@@ -863,6 +1328,19 @@ mod tests {
             assert_eq!(expected, actual);
         }
 
+        #[test]
+        fn serializes_and_deserializes_action_emit_chord() {
+            let action = ActionEmitChord::new(vec![29, 42], 20, 50).into();
+
+            let expected = NiaAction { action };
+
+            let interpreter_action = expected.to_interpreter_repr();
+            let actual =
+                NiaAction::from_interpreter_repr(&interpreter_action).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
         #[test]
         fn serializes_and_deserializes_action_mouse_button_click() {
             let action = ActionMouseButtonClick::new(1).into();
@@ -1013,6 +1491,41 @@ mod tests {
             assert_eq!(expected, actual);
         }
 
+        #[test]
+        fn serializes_and_deserializes_action_mouse_scroll() {
+            let action = ActionMouseScroll::new(
+                NiaScrollDirection::Horizontal,
+                -1,
+                -150,
+            )
+            .into();
+
+            let expected = NiaAction { action };
+
+            let interpreter_action = expected.to_interpreter_repr();
+            let actual =
+                NiaAction::from_interpreter_repr(&interpreter_action).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
+        #[test]
+        fn serializes_and_deserializes_action_emit_raw_event() {
+            let action = ActionEmitRawEvent::new(vec![
+                NiaRawEvent::new(4, 4, 458756),
+                NiaRawEvent::new(1, 30, 2),
+            ])
+            .into();
+
+            let expected = NiaAction { action };
+
+            let interpreter_action = expected.to_interpreter_repr();
+            let actual =
+                NiaAction::from_interpreter_repr(&interpreter_action).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
         #[test]
         fn serializes_and_deserializes_action_wait() {
             let action = ActionWait::new(1000).into();
@@ -1092,7 +1605,9 @@ mod tests {
 
         #[test]
         fn serializes_and_deserializes_action_execute_interpreter_value() {
-            let action = ActionExecuteInterpreterValue::new().into();
+            let action = ActionExecuteInterpreterValue::new("(fn () (+ 1 2))")
+                .unwrap()
+                .into();
 
             let expected = NiaAction { action };
 
@@ -1102,5 +1617,170 @@ mod tests {
 
             assert_eq!(expected, actual);
         }
+
+        #[test]
+        fn serializes_and_deserializes_action_activate_profile() {
+            let action = ActionActivateProfile::new("gaming").into();
+
+            let expected = NiaAction { action };
+
+            let interpreter_action = expected.to_interpreter_repr();
+            let actual =
+                NiaAction::from_interpreter_repr(&interpreter_action).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
+        #[test]
+        fn keeps_os_commands_that_look_like_profile_activation() {
+            let action = ActionExecuteOSCommand::new(
+                "nia_server activate-profile gaming",
+            )
+            .into();
+
+            let expected = NiaAction { action };
+
+            let interpreter_action = expected.to_interpreter_repr();
+            let actual =
+                NiaAction::from_interpreter_repr(&interpreter_action).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
+        #[test]
+        fn serializes_and_deserializes_action_sequence() {
+            let action = make_macro().into();
+
+            let expected = NiaAction { action };
+
+            let interpreter_action = expected.to_interpreter_repr();
+            let actual =
+                NiaAction::from_interpreter_repr(&interpreter_action).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
+        #[test]
+        fn serializes_and_deserializes_action_repeat() {
+            let action = make_repeat().into();
+
+            let expected = NiaAction { action };
+
+            let interpreter_action = expected.to_interpreter_repr();
+            let actual =
+                NiaAction::from_interpreter_repr(&interpreter_action).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
+        #[test]
+        fn serializes_and_deserializes_action_repeat_while_held() {
+            let action = make_repeat_while_held().into();
+
+            let expected = NiaAction { action };
+
+            let interpreter_action = expected.to_interpreter_repr();
+            let actual =
+                NiaAction::from_interpreter_repr(&interpreter_action).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
+        #[test]
+        fn serializes_and_deserializes_nested_action_sequence() {
+            let action = ActionList::new(vec![
+                NiaAction::new(make_macro().into()),
+                NiaAction::new(ActionActivateProfile::new("gaming").into()),
+                NiaAction::new(make_repeat().into()),
+                NiaAction::new(make_repeat_while_held().into()),
+                NiaAction::new(
+                    ActionExecuteInterpreterValue::new("(fn () 1)")
+                        .unwrap()
+                        .into(),
+                ),
+            ])
+            .into();
+
+            let expected = NiaAction { action };
+
+            let interpreter_action = expected.to_interpreter_repr();
+            let actual =
+                NiaAction::from_interpreter_repr(&interpreter_action).unwrap();
+
+            assert_eq!(expected, actual);
+        }
+
+        #[test]
+        fn keeps_sequences_that_look_like_other_actions() {
+            let actions = vec![
+                ActionList::new(vec![
+                    NiaAction::new(ActionKeyPress::new(29).into()),
+                    NiaAction::new(ActionKeyClick::new(46).into()),
+                    NiaAction::new(ActionKeyRelease::new(29).into()),
+                ]),
+                ActionList::new(vec![
+                    NiaAction::new(ActionKeyClick::new(46).into()),
+                    NiaAction::new(ActionWait::new(50).into()),
+                    NiaAction::new(ActionKeyClick::new(46).into()),
+                ]),
+            ];
+
+            for action_list in actions {
+                let expected = NiaAction::new(action_list.into());
+
+                let interpreter_action = expected.to_interpreter_repr();
+                let actual =
+                    NiaAction::from_interpreter_repr(&interpreter_action)
+                        .unwrap();
+
+                assert_eq!(expected, actual);
+            }
+        }
+
+        #[test]
+        fn keeps_code_that_looks_like_actions() {
+            let codes = vec![
+                "(progn (action:key-click 46))",
+                "(progn (action:key-press 29) (action:key-click 46) \
+                 (action:key-release 29))",
+            ];
+
+            for code in codes {
+                let expected =
+                    NiaAction::new(ActionExecuteCode::new(code).into());
+
+                let interpreter_action = expected.to_interpreter_repr();
+                let actual =
+                    NiaAction::from_interpreter_repr(&interpreter_action)
+                        .unwrap();
+
+                assert_eq!(expected, actual);
+            }
+        }
+
+        #[test]
+        fn refuses_code_reserved_for_the_server() {
+            let actions = vec![
+                NiaAction::new(
+                    ActionExecuteCode::new(
+                        "(progn \"nia-server:interpreter-value\" (1))",
+                    )
+                    .into(),
+                ),
+                NiaAction::new(
+                    ActionExecuteCode::new(
+                        "(progn \"nia-server:sequence\" (action:wait 1))",
+                    )
+                    .into(),
+                ),
+            ];
+
+            for action in actions {
+                assert!(action.check().is_err());
+                assert!(
+                    NiaAction::from_bytes(action.to_bytes().unwrap()).is_err()
+                );
+            }
+        }
     }
 }
@@ -0,0 +1,1169 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::NiaScrollDirection;
+use nia_interpreter_core::Action;
+
+const SEQUENCE_CODE_HEAD: &'static str = "progn";
+const UNWIND_PROTECT_FUNCTION: &'static str = "unwind-protect";
+const SERVER_MARKER_PREFIX: &'static str = "nia-server:";
+const ACTIVATE_PROFILE_MARKER: &'static str = "nia-server:activate-profile";
+
+// Markers of the code the server makes for actions the interpreter knows
+// nothing about. Only marked code is read back as such actions, any other
+// code stays code.
+pub const SEQUENCE_MARKER: &'static str = "nia-server:sequence";
+const REPEAT_MARKER: &'static str = "nia-server:repeat";
+const REPEAT_WHILE_HELD_MARKER: &'static str = "nia-server:repeat-while-held";
+const EMIT_CHORD_MARKER: &'static str = "nia-server:emit-chord";
+const INTERPRETER_VALUE_MARKER: &'static str = "nia-server:interpreter-value";
+const MOUSE_SCROLL_MARKER: &'static str = "nia-server:mouse-scroll";
+const EMIT_RAW_EVENTS_MARKER: &'static str = "nia-server:emit-raw-events";
+
+const FUNCTION_CODE_HEAD: &'static str = "fn";
+
+// The interpreter channel profile switching actions send the name of the
+// profile to activate to. The server receives from it.
+pub const PROFILE_REQUEST_CHANNEL: &'static str = "nia-server:profile-requests";
+const CHANNEL_SEND_FUNCTION: &'static str = "channel:send";
+
+// Interpreter functions that execute code in a loop. The loops are run by
+// the interpreter between key events, and the loop of `repeat-while-held`
+// stops once the key that fired the mapping is released.
+const REPEAT_FUNCTION: &'static str = "action:repeat";
+const REPEAT_WHILE_HELD_FUNCTION: &'static str = "action:repeat-while-held";
+
+// Interpreter function that emits input events through the virtual device of
+// the interpreter, each event given by its type, code and value.
+const EMIT_RAW_EVENTS_FUNCTION: &'static str = "action:emit-raw-events";
+
+// Event types and codes of linux/input-event-codes.h.
+const EV_SYN: i32 = 0x00;
+const EV_REL: i32 = 0x02;
+const SYN_REPORT: i32 = 0x00;
+const REL_HWHEEL: i32 = 0x06;
+const REL_WHEEL: i32 = 0x08;
+const REL_WHEEL_HI_RES: i32 = 0x0b;
+const REL_HWHEEL_HI_RES: i32 = 0x0c;
+
+// Interpreter actions that take a key or button code.
+const CODE_ACTIONS: &[&str] = &[
+    "action:key-press",
+    "action:key-click",
+    "action:key-release",
+    "action:mouse-button-press",
+    "action:mouse-button-click",
+    "action:mouse-button-release",
+    "action:text-key-click",
+    "action:number-key-click",
+    "action:function-key-click",
+    "action:control-key-click",
+    "action:kp-key-click",
+    "action:multimedia-key-click",
+    "action:mouse-button-key-click",
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Argument {
+    Integer(i32),
+    String(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    Symbol(String),
+    Argument(Argument),
+}
+
+fn escape_string(string: &str) -> String {
+    let mut result = String::from("\"");
+
+    for character in string.chars() {
+        if character == '"' || character == '\\' {
+            result.push('\\');
+        }
+
+        result.push(character);
+    }
+
+    result.push('"');
+    result
+}
+
+fn make_call(function_name: &str, arguments: Vec<Argument>) -> String {
+    let mut call = format!("({}", function_name);
+
+    for argument in arguments {
+        call.push(' ');
+
+        match argument {
+            Argument::Integer(integer) => call.push_str(&integer.to_string()),
+            Argument::String(string) => call.push_str(&escape_string(&string)),
+        }
+    }
+
+    call.push(')');
+    call
+}
+
+// The call of the interpreter `action` module function that submits the
+// same action. Function values are sent to the interpreter as code by the
+// server, so they have no call.
+fn action_to_call(action: &Action) -> NiaServerResult<String> {
+    let call = match action {
+        Action::KeyPress(key_code) => {
+            make_call(CODE_ACTIONS[0], vec![Argument::Integer(*key_code)])
+        }
+        Action::KeyClick(key_code) => {
+            make_call(CODE_ACTIONS[1], vec![Argument::Integer(*key_code)])
+        }
+        Action::KeyRelease(key_code) => {
+            make_call(CODE_ACTIONS[2], vec![Argument::Integer(*key_code)])
+        }
+        Action::MouseButtonPress(button_code) => {
+            make_call(CODE_ACTIONS[3], vec![Argument::Integer(*button_code)])
+        }
+        Action::MouseButtonClick(button_code) => {
+            make_call(CODE_ACTIONS[4], vec![Argument::Integer(*button_code)])
+        }
+        Action::MouseButtonRelease(button_code) => {
+            make_call(CODE_ACTIONS[5], vec![Argument::Integer(*button_code)])
+        }
+        Action::TextKeyClick(key_code) => {
+            make_call(CODE_ACTIONS[6], vec![Argument::Integer(*key_code)])
+        }
+        Action::NumberKeyClick(key_code) => {
+            make_call(CODE_ACTIONS[7], vec![Argument::Integer(*key_code)])
+        }
+        Action::FunctionKeyClick(key_code) => {
+            make_call(CODE_ACTIONS[8], vec![Argument::Integer(*key_code)])
+        }
+        Action::ControlKeyClick(key_code) => {
+            make_call(CODE_ACTIONS[9], vec![Argument::Integer(*key_code)])
+        }
+        Action::KPKeyClick(key_code) => {
+            make_call(CODE_ACTIONS[10], vec![Argument::Integer(*key_code)])
+        }
+        Action::MultimediaKeyClick(key_code) => {
+            make_call(CODE_ACTIONS[11], vec![Argument::Integer(*key_code)])
+        }
+        Action::MouseButtonKeyClick(key_code) => {
+            make_call(CODE_ACTIONS[12], vec![Argument::Integer(*key_code)])
+        }
+        Action::MouseAbsoluteMove(x, y) => make_call(
+            "action:mouse-absolute-move",
+            vec![Argument::Integer(*x), Argument::Integer(*y)],
+        ),
+        Action::MouseRelativeMove(dx, dy) => make_call(
+            "action:mouse-relative-move",
+            vec![Argument::Integer(*dx), Argument::Integer(*dy)],
+        ),
+        Action::Wait(ms) => {
+            make_call("action:wait", vec![Argument::Integer(*ms)])
+        }
+        Action::TextType(text) => {
+            make_call("action:text-type", vec![Argument::String(text.clone())])
+        }
+        Action::ExecuteCode(code) => make_call(
+            "action:execute-code",
+            vec![Argument::String(code.clone())],
+        ),
+        Action::ExecuteFunction(function_name) => make_call(
+            "action:execute-function",
+            vec![Argument::String(function_name.clone())],
+        ),
+        Action::ExecuteOSCommand(os_command) => make_call(
+            "action:execute-os-command",
+            vec![Argument::String(os_command.clone())],
+        ),
+        Action::ExecuteNamedAction(action_name) => make_call(
+            "action:execute-named-action",
+            vec![Argument::String(action_name.clone())],
+        ),
+        _ => {
+            return NiaServerError::invalid_request(
+                "Interpreter values can't be a part of other actions.",
+            )
+            .into()
+        }
+    };
+
+    Ok(call)
+}
+
+fn call_to_action(
+    function_name: &str,
+    arguments: &[Argument],
+) -> Option<Action> {
+    if let Some(index) = CODE_ACTIONS
+        .iter()
+        .position(|code_action| *code_action == function_name)
+    {
+        let code = match arguments {
+            [Argument::Integer(code)] => *code,
+            _ => return None,
+        };
+
+        let action = match index {
+            0 => Action::KeyPress(code),
+            1 => Action::KeyClick(code),
+            2 => Action::KeyRelease(code),
+            3 => Action::MouseButtonPress(code),
+            4 => Action::MouseButtonClick(code),
+            5 => Action::MouseButtonRelease(code),
+            6 => Action::TextKeyClick(code),
+            7 => Action::NumberKeyClick(code),
+            8 => Action::FunctionKeyClick(code),
+            9 => Action::ControlKeyClick(code),
+            10 => Action::KPKeyClick(code),
+            11 => Action::MultimediaKeyClick(code),
+            _ => Action::MouseButtonKeyClick(code),
+        };
+
+        return Some(action);
+    }
+
+    let action = match (function_name, arguments) {
+        (
+            "action:mouse-absolute-move",
+            [Argument::Integer(x), Argument::Integer(y)],
+        ) => Action::MouseAbsoluteMove(*x, *y),
+        (
+            "action:mouse-relative-move",
+            [Argument::Integer(dx), Argument::Integer(dy)],
+        ) => Action::MouseRelativeMove(*dx, *dy),
+        ("action:wait", [Argument::Integer(ms)]) => Action::Wait(*ms),
+        ("action:text-type", [Argument::String(text)]) => {
+            Action::TextType(text.clone())
+        }
+        ("action:execute-code", [Argument::String(code)]) => {
+            Action::ExecuteCode(code.clone())
+        }
+        ("action:execute-function", [Argument::String(function_name)]) => {
+            Action::ExecuteFunction(function_name.clone())
+        }
+        ("action:execute-os-command", [Argument::String(os_command)]) => {
+            Action::ExecuteOSCommand(os_command.clone())
+        }
+        ("action:execute-named-action", [Argument::String(action_name)]) => {
+            Action::ExecuteNamedAction(action_name.clone())
+        }
+        _ => return None,
+    };
+
+    Some(action)
+}
+
+fn read_string(
+    characters: &mut std::iter::Peekable<std::str::Chars>,
+) -> Option<String> {
+    let mut string = String::new();
+
+    loop {
+        match characters.next()? {
+            '"' => return Some(string),
+            '\\' => string.push(characters.next()?),
+            character => string.push(character),
+        }
+    }
+}
+
+fn tokenize(code: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut characters = code.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        match character {
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            '"' => tokens.push(Token::Argument(Argument::String(read_string(
+                &mut characters,
+            )?))),
+            character if character.is_whitespace() => {}
+            character => {
+                let mut word = character.to_string();
+
+                while let Some(next) = characters.peek() {
+                    if next.is_whitespace() || *next == '(' || *next == ')' {
+                        break;
+                    }
+
+                    word.push(*next);
+                    characters.next();
+                }
+
+                let token = match word.parse::<i32>() {
+                    Ok(integer) => Token::Argument(Argument::Integer(integer)),
+                    Err(_) => Token::Symbol(word),
+                };
+
+                tokens.push(token);
+            }
+        }
+    }
+
+    Some(tokens)
+}
+
+// Marked code that submits the actions one after another, e.g.
+// "(progn "nia-server:sequence" (action:key-press 29) (action:wait 50))".
+// Function values have no call and are refused.
+pub fn interpreter_actions_to_code(
+    marker: &str,
+    actions: &[Action],
+) -> NiaServerResult<String> {
+    let mut code = format!("({} {}", SEQUENCE_CODE_HEAD, escape_string(marker));
+
+    for action in actions {
+        code.push(' ');
+        code.push_str(&action_to_call(action)?);
+    }
+
+    code.push(')');
+    Ok(code)
+}
+
+// Reads back the code made by `interpreter_actions_to_code` with the same
+// marker. Any other code is not a list of actions.
+pub fn interpreter_actions_from_code(
+    marker: &str,
+    code: &str,
+) -> Option<Vec<Action>> {
+    let tokens = tokenize(code)?;
+    let mut tokens = tokens.iter();
+
+    match (tokens.next()?, tokens.next()?, tokens.next()?) {
+        (
+            Token::Open,
+            Token::Symbol(head),
+            Token::Argument(Argument::String(code_marker)),
+        ) if head == SEQUENCE_CODE_HEAD && code_marker == marker => {}
+        _ => return None,
+    }
+
+    let mut actions = Vec::new();
+
+    loop {
+        match tokens.next()? {
+            Token::Close => break,
+            Token::Open => {}
+            _ => return None,
+        }
+
+        let function_name = match tokens.next()? {
+            Token::Symbol(function_name) => function_name,
+            _ => return None,
+        };
+
+        let mut arguments = Vec::new();
+
+        loop {
+            match tokens.next()? {
+                Token::Close => break,
+                Token::Argument(argument) => arguments.push(argument.clone()),
+                _ => return None,
+            }
+        }
+
+        actions.push(call_to_action(function_name, &arguments)?);
+    }
+
+    if tokens.next().is_some() {
+        return None;
+    }
+
+    Some(actions)
+}
+
+// Presses the key, executes the form and releases the key. The release is
+// the cleanup form of `unwind-protect`, so it is executed even if the form
+// fails.
+fn protect_key_press(key_code: i32, form: String) -> String {
+    format!(
+        "({} {} ({} {} {}))",
+        SEQUENCE_CODE_HEAD,
+        make_call(CODE_ACTIONS[0], vec![Argument::Integer(key_code)]),
+        UNWIND_PROTECT_FUNCTION,
+        form,
+        make_call(CODE_ACTIONS[2], vec![Argument::Integer(key_code)])
+    )
+}
+
+// Code that presses the modifiers, clicks the key, or holds it down for
+// `hold_ms` milliseconds, and releases the pressed keys in the reverse order.
+// Every release is executed even if a step before it fails, e.g.
+// (progn "nia-server:emit-chord" (progn (action:key-press 29)
+// (unwind-protect (action:key-click 20) (action:key-release 29)))).
+pub fn emit_chord_to_code(
+    modifier_key_codes: &[i32],
+    key_code: i32,
+    hold_ms: i32,
+) -> String {
+    let mut form = if hold_ms > 0 {
+        protect_key_press(
+            key_code,
+            make_call("action:wait", vec![Argument::Integer(hold_ms)]),
+        )
+    } else {
+        make_call(CODE_ACTIONS[1], vec![Argument::Integer(key_code)])
+    };
+
+    for modifier_key_code in modifier_key_codes.iter().rev() {
+        form = protect_key_press(*modifier_key_code, form);
+    }
+
+    format!(
+        "({} {} {})",
+        SEQUENCE_CODE_HEAD,
+        escape_string(EMIT_CHORD_MARKER),
+        form
+    )
+}
+
+// Reads back the code made by `emit_chord_to_code`, as the modifier key
+// codes, the key code and the hold duration.
+pub fn emit_chord_from_code(code: &str) -> Option<(Vec<i32>, i32, i32)> {
+    let tokens = tokenize(code)?;
+
+    if tokens.get(2)
+        != Some(&Token::Argument(Argument::String(String::from(
+            EMIT_CHORD_MARKER,
+        ))))
+    {
+        return None;
+    }
+
+    let calls = tokens
+        .windows(2)
+        .filter_map(|window| match (&window[0], &window[1]) {
+            (
+                Token::Symbol(function_name),
+                Token::Argument(Argument::Integer(argument)),
+            ) => Some((function_name.as_str(), *argument)),
+            _ => None,
+        })
+        .collect::<Vec<(&str, i32)>>();
+
+    let mut pressed_key_codes = calls
+        .iter()
+        .take_while(|(function_name, _)| *function_name == CODE_ACTIONS[0])
+        .map(|(_, key_code)| *key_code)
+        .collect::<Vec<i32>>();
+
+    let (key_code, hold_ms) = match calls.get(pressed_key_codes.len())? {
+        (function_name, key_code) if *function_name == CODE_ACTIONS[1] => {
+            (*key_code, 0)
+        }
+        ("action:wait", hold_ms) => (pressed_key_codes.pop()?, *hold_ms),
+        _ => return None,
+    };
+
+    if tokenize(&emit_chord_to_code(&pressed_key_codes, key_code, hold_ms))?
+        != tokens
+    {
+        return None;
+    }
+
+    Some((pressed_key_codes, key_code, hold_ms))
+}
+
+// Marked code that calls the loop function with the arguments and the code of
+// the action, e.g. (progn "nia-server:repeat" (action:repeat 3 100 "(progn
+// \"nia-server:sequence\" (action:key-click 46))")).
+fn loop_to_code(
+    marker: &str,
+    function_name: &str,
+    arguments: &[i32],
+    action: &Action,
+) -> NiaServerResult<String> {
+    let mut arguments = arguments
+        .iter()
+        .map(|argument| Argument::Integer(*argument))
+        .collect::<Vec<Argument>>();
+
+    arguments.push(Argument::String(interpreter_actions_to_code(
+        SEQUENCE_MARKER,
+        &[action.clone()],
+    )?));
+
+    Ok(format!(
+        "({} {} {})",
+        SEQUENCE_CODE_HEAD,
+        escape_string(marker),
+        make_call(function_name, arguments)
+    ))
+}
+
+// Reads back the arguments and the action of the code made by
+// `loop_to_code`.
+fn loop_from_code(
+    marker: &str,
+    function_name: &str,
+    code: &str,
+) -> Option<(Vec<i32>, Action)> {
+    let tokens = tokenize(code)?;
+
+    let expected_tokens = [
+        Token::Open,
+        Token::Symbol(String::from(SEQUENCE_CODE_HEAD)),
+        Token::Argument(Argument::String(String::from(marker))),
+        Token::Open,
+        Token::Symbol(String::from(function_name)),
+    ];
+
+    if tokens.len() < expected_tokens.len() + 3
+        || tokens[..expected_tokens.len()] != expected_tokens[..]
+        || tokens[tokens.len() - 2..] != [Token::Close, Token::Close]
+    {
+        return None;
+    }
+
+    let (action_code, arguments) =
+        tokens[expected_tokens.len()..tokens.len() - 2].split_last()?;
+
+    let action_code = match action_code {
+        Token::Argument(Argument::String(action_code)) => action_code,
+        _ => return None,
+    };
+
+    let arguments = arguments
+        .iter()
+        .map(|argument| match argument {
+            Token::Argument(Argument::Integer(argument)) => Some(*argument),
+            _ => None,
+        })
+        .collect::<Option<Vec<i32>>>()?;
+
+    let mut actions =
+        interpreter_actions_from_code(SEQUENCE_MARKER, action_code)?;
+
+    if actions.len() != 1 {
+        return None;
+    }
+
+    Some((arguments, actions.remove(0)))
+}
+
+// Code that executes the action `times` times and waits `interval_ms`
+// milliseconds between executions.
+pub fn repeat_to_code(
+    times: i32,
+    interval_ms: i32,
+    action: &Action,
+) -> NiaServerResult<String> {
+    loop_to_code(
+        REPEAT_MARKER,
+        REPEAT_FUNCTION,
+        &[times, interval_ms],
+        action,
+    )
+}
+
+// Reads back the code made by `repeat_to_code`, as the count, the interval
+// and the action.
+pub fn repeat_from_code(code: &str) -> Option<(i32, i32, Action)> {
+    let (arguments, action) =
+        loop_from_code(REPEAT_MARKER, REPEAT_FUNCTION, code)?;
+
+    match arguments.as_slice() {
+        [times, interval_ms] => Some((*times, *interval_ms, action)),
+        _ => None,
+    }
+}
+
+// Code that executes the action every `interval_ms` milliseconds until the
+// key that fired the mapping is released.
+pub fn repeat_while_held_to_code(
+    interval_ms: i32,
+    action: &Action,
+) -> NiaServerResult<String> {
+    loop_to_code(
+        REPEAT_WHILE_HELD_MARKER,
+        REPEAT_WHILE_HELD_FUNCTION,
+        &[interval_ms],
+        action,
+    )
+}
+
+// Reads back the code made by `repeat_while_held_to_code`, as the interval
+// and the action.
+pub fn repeat_while_held_from_code(code: &str) -> Option<(i32, Action)> {
+    let (arguments, action) = loop_from_code(
+        REPEAT_WHILE_HELD_MARKER,
+        REPEAT_WHILE_HELD_FUNCTION,
+        code,
+    )?;
+
+    match arguments.as_slice() {
+        [interval_ms] => Some((*interval_ms, action)),
+        _ => None,
+    }
+}
+
+// Marked code that emits the events, e.g. (progn "nia-server:mouse-scroll"
+// (action:emit-raw-events 2 8 -1 2 11 -120 0 0 0)).
+fn raw_events_to_code(marker: &str, events: &[(i32, i32, i32)]) -> String {
+    let arguments = events
+        .iter()
+        .flat_map(|(event_type, code, value)| vec![*event_type, *code, *value])
+        .map(Argument::Integer)
+        .collect::<Vec<Argument>>();
+
+    format!(
+        "({} {} {})",
+        SEQUENCE_CODE_HEAD,
+        escape_string(marker),
+        make_call(EMIT_RAW_EVENTS_FUNCTION, arguments)
+    )
+}
+
+// Reads back the events of the code made by `raw_events_to_code` with the
+// same marker.
+fn raw_events_from_code(
+    marker: &str,
+    code: &str,
+) -> Option<Vec<(i32, i32, i32)>> {
+    let tokens = tokenize(code)?;
+
+    let expected_tokens = [
+        Token::Open,
+        Token::Symbol(String::from(SEQUENCE_CODE_HEAD)),
+        Token::Argument(Argument::String(String::from(marker))),
+        Token::Open,
+        Token::Symbol(String::from(EMIT_RAW_EVENTS_FUNCTION)),
+    ];
+
+    if tokens.len() < expected_tokens.len() + 2
+        || tokens[..expected_tokens.len()] != expected_tokens[..]
+        || tokens[tokens.len() - 2..] != [Token::Close, Token::Close]
+    {
+        return None;
+    }
+
+    let arguments = tokens[expected_tokens.len()..tokens.len() - 2]
+        .iter()
+        .map(|argument| match argument {
+            Token::Argument(Argument::Integer(argument)) => Some(*argument),
+            _ => None,
+        })
+        .collect::<Option<Vec<i32>>>()?;
+
+    if arguments.len() % 3 != 0 {
+        return None;
+    }
+
+    let events = arguments
+        .chunks(3)
+        .map(|event| (event[0], event[1], event[2]))
+        .collect();
+
+    Some(events)
+}
+
+// Code that emits the wheel event of the scroll along with the
+// high-resolution event, and reports them, e.g. (progn
+// "nia-server:mouse-scroll" (action:emit-raw-events 2 8 -1 2 11 -120 0 0 0))
+// scrolls down by one detent. Scrolls shorter than a detent emit only the
+// high-resolution event.
+pub fn mouse_scroll_to_code(
+    direction: NiaScrollDirection,
+    amount: i32,
+    hi_res_amount: i32,
+) -> String {
+    let (wheel_code, hi_res_wheel_code) = match direction {
+        NiaScrollDirection::Vertical => (REL_WHEEL, REL_WHEEL_HI_RES),
+        NiaScrollDirection::Horizontal => (REL_HWHEEL, REL_HWHEEL_HI_RES),
+    };
+
+    let mut events = Vec::new();
+
+    if amount != 0 {
+        events.push((EV_REL, wheel_code, amount));
+    }
+
+    events.push((EV_REL, hi_res_wheel_code, hi_res_amount));
+    events.push((EV_SYN, SYN_REPORT, 0));
+
+    raw_events_to_code(MOUSE_SCROLL_MARKER, &events)
+}
+
+// Reads back the code made by `mouse_scroll_to_code`, as the direction, the
+// amount and the high-resolution amount.
+pub fn mouse_scroll_from_code(
+    code: &str,
+) -> Option<(NiaScrollDirection, i32, i32)> {
+    let events = raw_events_from_code(MOUSE_SCROLL_MARKER, code)?;
+
+    let (hi_res_wheel_code, hi_res_amount) = match events.iter().rev().nth(1)? {
+        (EV_REL, hi_res_wheel_code, hi_res_amount) => {
+            (*hi_res_wheel_code, *hi_res_amount)
+        }
+        _ => return None,
+    };
+
+    let direction = match hi_res_wheel_code {
+        REL_WHEEL_HI_RES => NiaScrollDirection::Vertical,
+        REL_HWHEEL_HI_RES => NiaScrollDirection::Horizontal,
+        _ => return None,
+    };
+
+    let amount = match events.as_slice() {
+        [(_, _, amount), _, _] => *amount,
+        _ => 0,
+    };
+
+    if mouse_scroll_to_code(direction, amount, hi_res_amount) != code {
+        return None;
+    }
+
+    Some((direction, amount, hi_res_amount))
+}
+
+// Code that emits the events as they are given, followed by a report, e.g.
+// the code for an autorepeat of A is
+// (progn "nia-server:emit-raw-events" (action:emit-raw-events 1 30 2 0 0 0)).
+pub fn emit_raw_events_to_code(events: &[(i32, i32, i32)]) -> String {
+    let mut events = events.to_vec();
+
+    events.push((EV_SYN, SYN_REPORT, 0));
+
+    raw_events_to_code(EMIT_RAW_EVENTS_MARKER, &events)
+}
+
+// Reads back the events of the code made by `emit_raw_events_to_code`,
+// without the report.
+pub fn emit_raw_events_from_code(code: &str) -> Option<Vec<(i32, i32, i32)>> {
+    let mut events = raw_events_from_code(EMIT_RAW_EVENTS_MARKER, code)?;
+
+    if events.pop()? != (EV_SYN, SYN_REPORT, 0) {
+        return None;
+    }
+
+    Some(events)
+}
+
+// Code that asks the server to activate a profile, e.g. (progn
+// "nia-server:activate-profile" (channel:send "nia-server:profile-requests"
+// "gaming")). The interpreter only sends the request, the server carries it
+// out.
+pub fn activate_profile_to_code(profile_name: &str) -> String {
+    format!(
+        "({} {} {})",
+        SEQUENCE_CODE_HEAD,
+        escape_string(ACTIVATE_PROFILE_MARKER),
+        make_call(
+            CHANNEL_SEND_FUNCTION,
+            vec![
+                Argument::String(PROFILE_REQUEST_CHANNEL.to_string()),
+                Argument::String(profile_name.to_string()),
+            ],
+        )
+    )
+}
+
+// Reads back the code made by `activate_profile_to_code`.
+pub fn activate_profile_from_code(code: &str) -> Option<String> {
+    let tokens = tokenize(code)?;
+
+    let expected_tokens = [
+        Token::Open,
+        Token::Symbol(String::from(SEQUENCE_CODE_HEAD)),
+        Token::Argument(Argument::String(String::from(
+            ACTIVATE_PROFILE_MARKER,
+        ))),
+        Token::Open,
+        Token::Symbol(String::from(CHANNEL_SEND_FUNCTION)),
+        Token::Argument(Argument::String(String::from(
+            PROFILE_REQUEST_CHANNEL,
+        ))),
+    ];
+
+    if tokens.len() != expected_tokens.len() + 3
+        || tokens[..expected_tokens.len()] != expected_tokens[..]
+        || tokens[expected_tokens.len() + 1..] != [Token::Close, Token::Close]
+    {
+        return None;
+    }
+
+    match &tokens[expected_tokens.len()] {
+        Token::Argument(Argument::String(profile_name)) => {
+            Some(profile_name.clone())
+        }
+        _ => None,
+    }
+}
+
+// Whether the code is a single function form, e.g. (fn () (println "nya")).
+pub fn is_function_code(code: &str) -> bool {
+    let tokens = match tokenize(code) {
+        Some(tokens) => tokens,
+        None => return false,
+    };
+
+    match (tokens.get(0), tokens.get(1)) {
+        (Some(Token::Open), Some(Token::Symbol(head)))
+            if head == FUNCTION_CODE_HEAD => {}
+        _ => return false,
+    }
+
+    let mut depth = 0;
+
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Open => depth += 1,
+            Token::Close => depth -= 1,
+            _ => {}
+        }
+
+        if depth == 0 {
+            return index == tokens.len() - 1;
+        }
+    }
+
+    false
+}
+
+// Code that calls the function, e.g. (progn "nia-server:interpreter-value"
+// ((fn () (println "nya")))).
+pub fn interpreter_value_to_code(function_code: &str) -> String {
+    format!(
+        "({} {} ({}))",
+        SEQUENCE_CODE_HEAD,
+        escape_string(INTERPRETER_VALUE_MARKER),
+        function_code
+    )
+}
+
+// Reads back the function code from the code made by
+// `interpreter_value_to_code`.
+pub fn interpreter_value_from_code(code: &str) -> Option<String> {
+    let prefix = format!(
+        "({} {} (",
+        SEQUENCE_CODE_HEAD,
+        escape_string(INTERPRETER_VALUE_MARKER)
+    );
+    let function_code = code.strip_prefix(&prefix)?.strip_suffix("))")?;
+
+    if !is_function_code(function_code) {
+        return None;
+    }
+
+    Some(String::from(function_code))
+}
+
+// Whether the code is marked as made by the server. Users can't define code
+// like that, it would be read back as another action.
+pub fn is_server_code(code: &str) -> bool {
+    let tokens = match tokenize(code) {
+        Some(tokens) => tokens,
+        None => return false,
+    };
+
+    match (tokens.get(0), tokens.get(1), tokens.get(2)) {
+        (
+            Some(Token::Open),
+            Some(Token::Symbol(head)),
+            Some(Token::Argument(Argument::String(marker))),
+        ) => {
+            head == SEQUENCE_CODE_HEAD
+                && marker.starts_with(SERVER_MARKER_PREFIX)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn converts_actions_to_code_and_back() {
+        let actions = vec![
+            Action::KeyPress(29),
+            Action::KeyClick(46),
+            Action::Wait(50),
+            Action::KeyRelease(29),
+            Action::MouseAbsoluteMove(100, -20),
+            Action::TextType(String::from("say \"hi\" \\ bye")),
+            Action::ExecuteCode(String::from("(progn (action:wait 1))")),
+        ];
+
+        let code =
+            interpreter_actions_to_code(SEQUENCE_MARKER, &actions).unwrap();
+        let result = interpreter_actions_from_code(SEQUENCE_MARKER, &code);
+
+        assert_eq!(Some(actions), result);
+        assert_eq!(None, interpreter_actions_from_code(REPEAT_MARKER, &code));
+    }
+
+    #[test]
+    fn refuses_actions_without_code() {
+        let actions = vec![
+            Action::KeyClick(46),
+            Action::ExecuteFunctionValue(nia_interpreter_core::Value::Integer(
+                1,
+            )),
+        ];
+
+        let result = interpreter_actions_to_code(SEQUENCE_MARKER, &actions);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn releases_chord_keys_even_if_steps_fail() {
+        let code = emit_chord_to_code(&[29, 42], 20, 0);
+
+        assert_eq!(
+            concat!(
+                "(progn \"nia-server:emit-chord\" ",
+                "(progn (action:key-press 29) (unwind-protect ",
+                "(progn (action:key-press 42) (unwind-protect ",
+                "(action:key-click 20) ",
+                "(action:key-release 42))) ",
+                "(action:key-release 29))))"
+            ),
+            code
+        );
+        assert_eq!(Some((vec![29, 42], 20, 0)), emit_chord_from_code(&code));
+
+        let code = emit_chord_to_code(&[29], 20, 100);
+
+        assert_eq!(
+            concat!(
+                "(progn \"nia-server:emit-chord\" ",
+                "(progn (action:key-press 29) (unwind-protect ",
+                "(progn (action:key-press 20) (unwind-protect ",
+                "(action:wait 100) ",
+                "(action:key-release 20))) ",
+                "(action:key-release 29))))"
+            ),
+            code
+        );
+        assert_eq!(Some((vec![29], 20, 100)), emit_chord_from_code(&code));
+    }
+
+    #[test]
+    fn does_not_read_other_chord_code() {
+        let codes = vec![
+            "(progn \"nia-server:sequence\" (action:key-press 29) \
+             (action:key-click 20) (action:key-release 29))",
+            "(progn \"nia-server:emit-chord\" (action:key-press 29) \
+             (action:key-click 20) (action:key-release 29))",
+            "(progn \"nia-server:emit-chord\" (progn (action:key-press 29) \
+             (unwind-protect (action:key-click 20) (action:key-release 42))))",
+        ];
+
+        for code in codes {
+            assert_eq!(None, emit_chord_from_code(code));
+        }
+    }
+
+    #[test]
+    fn converts_profile_activation_to_code_and_back() {
+        let code = activate_profile_to_code("gaming");
+        let result = activate_profile_from_code(&code);
+
+        assert_eq!(
+            concat!(
+                "(progn \"nia-server:activate-profile\" ",
+                "(channel:send \"nia-server:profile-requests\" \"gaming\"))"
+            ),
+            code
+        );
+        assert_eq!(Some(String::from("gaming")), result);
+        assert_eq!(
+            None,
+            activate_profile_from_code(
+                "(progn (channel:send \"nia-server:profile-requests\" \"a\"))"
+            )
+        );
+        assert_eq!(
+            None,
+            activate_profile_from_code(concat!(
+                "(progn \"nia-server:activate-profile\" ",
+                "(channel:send \"other\" \"gaming\"))"
+            ))
+        );
+    }
+
+    #[test]
+    fn converts_repetitions_to_code_and_back() {
+        let action = Action::MouseRelativeMove(0, 10);
+
+        let code = repeat_to_code(3, 20, &action).unwrap();
+
+        assert_eq!(
+            concat!(
+                "(progn \"nia-server:repeat\" (action:repeat 3 20 \"(progn ",
+                "\\\"nia-server:sequence\\\" ",
+                "(action:mouse-relative-move 0 10))\"))"
+            ),
+            code
+        );
+        assert_eq!(Some((3, 20, action.clone())), repeat_from_code(&code));
+        assert_eq!(None, repeat_while_held_from_code(&code));
+
+        let code = repeat_while_held_to_code(20, &action).unwrap();
+
+        assert_eq!(Some((20, action)), repeat_while_held_from_code(&code));
+        assert_eq!(None, repeat_from_code(&code));
+    }
+
+    #[test]
+    fn does_not_read_other_loop_code() {
+        let codes = vec![
+            concat!(
+                "(action:repeat 3 20 ",
+                "\"(progn \\\"nia-server:sequence\\\" (action:wait 1))\")"
+            ),
+            concat!(
+                "(progn \"nia-server:repeat\" ",
+                "(action:repeat 3 \"(action:wait 1)\"))"
+            ),
+            concat!(
+                "(progn \"nia-server:repeat\" ",
+                "(action:repeat 3 20 (action:wait 1)))"
+            ),
+            concat!(
+                "(progn \"nia-server:repeat\" ",
+                "(action:repeat 3 20 \"(progn \\\"nia-server:sequence\\\")\"))"
+            ),
+        ];
+
+        for code in codes {
+            assert_eq!(None, repeat_from_code(code));
+        }
+    }
+
+    #[test]
+    fn converts_mouse_scroll_to_code_and_back() {
+        let code = mouse_scroll_to_code(NiaScrollDirection::Horizontal, 2, 240);
+
+        assert_eq!(
+            concat!(
+                "(progn \"nia-server:mouse-scroll\" ",
+                "(action:emit-raw-events 2 6 2 2 12 240 0 0 0))"
+            ),
+            code
+        );
+        assert_eq!(
+            Some((NiaScrollDirection::Horizontal, 2, 240)),
+            mouse_scroll_from_code(&code)
+        );
+
+        let code = mouse_scroll_to_code(NiaScrollDirection::Vertical, 0, -30);
+
+        assert_eq!(
+            concat!(
+                "(progn \"nia-server:mouse-scroll\" ",
+                "(action:emit-raw-events 2 11 -30 0 0 0))"
+            ),
+            code
+        );
+        assert_eq!(
+            Some((NiaScrollDirection::Vertical, 0, -30)),
+            mouse_scroll_from_code(&code)
+        );
+    }
+
+    #[test]
+    fn does_not_read_other_scroll_code() {
+        let codes = vec![
+            "(action:emit-raw-events 2 8 1 2 11 120 0 0 0)",
+            "(progn \"nia-server:mouse-scroll\" (action:emit-raw-events 2 8))",
+            concat!(
+                "(progn \"nia-server:mouse-scroll\" ",
+                "(action:emit-raw-events 2 11 120 2 8 1 0 0 0))"
+            ),
+            concat!(
+                "(progn \"nia-server:mouse-scroll\" ",
+                "(action:emit-raw-events 2 8 1 2 12 120 0 0 0))"
+            ),
+            concat!(
+                "(progn \"nia-server:mouse-scroll\" ",
+                "(action:emit-raw-events 2 11 120))"
+            ),
+        ];
+
+        for code in codes {
+            assert_eq!(None, mouse_scroll_from_code(code));
+        }
+    }
+
+    #[test]
+    fn converts_raw_events_to_code_and_back() {
+        let events = vec![(1, 30, 2), (0, 0, 0)];
+
+        let code = emit_raw_events_to_code(&events);
+
+        assert_eq!(
+            concat!(
+                "(progn \"nia-server:emit-raw-events\" ",
+                "(action:emit-raw-events 1 30 2 0 0 0 0 0 0))"
+            ),
+            code
+        );
+        assert_eq!(Some(events), emit_raw_events_from_code(&code));
+        assert_eq!(None, mouse_scroll_from_code(&code));
+        assert_eq!(
+            None,
+            emit_raw_events_from_code(concat!(
+                "(progn \"nia-server:emit-raw-events\" ",
+                "(action:emit-raw-events 1 30 2))"
+            ))
+        );
+        assert_eq!(
+            None,
+            emit_raw_events_from_code(concat!(
+                "(progn \"nia-server:emit-raw-events\" ",
+                "(action:emit-raw-events 1 30 2 0 0))"
+            ))
+        );
+    }
+
+    #[test]
+    fn makes_readable_code() {
+        let actions = vec![Action::KeyPress(29), Action::Wait(50)];
+
+        assert_eq!(
+            concat!(
+                "(progn \"nia-server:sequence\" ",
+                "(action:key-press 29) (action:wait 50))"
+            ),
+            interpreter_actions_to_code(SEQUENCE_MARKER, &actions).unwrap()
+        );
+    }
+
+    #[test]
+    fn recognizes_server_code() {
+        assert!(is_server_code(&activate_profile_to_code("gaming")));
+        assert!(is_server_code(
+            "(progn \"nia-server:emit-chord\" (action:key-press 29))"
+        ));
+        assert!(!is_server_code("(progn (action:key-press 29))"));
+        assert!(!is_server_code("(progn \"other\" (action:key-press 29))"));
+        assert!(!is_server_code("(println \"nia-server:sequence\")"));
+    }
+
+    #[test]
+    fn does_not_read_other_code() {
+        let codes = vec![
+            "(+ 1 2)",
+            "(progn (action:wait 50))",
+            "(progn \"nia-server:repeat\" (action:wait 50))",
+            "(progn \"nia-server:sequence\" (println 1))",
+            "(progn \"nia-server:sequence\" (action:wait \"50\"))",
+            "(progn \"nia-server:sequence\" (action:wait 50)",
+            "(progn \"nia-server:sequence\") (action:wait 50)",
+        ];
+
+        for code in codes {
+            assert_eq!(
+                None,
+                interpreter_actions_from_code(SEQUENCE_MARKER, code)
+            );
+        }
+    }
+}
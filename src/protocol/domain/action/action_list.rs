@@ -1,7 +1,8 @@
 use crate::error::NiaServerResult;
 use crate::protocol::{NiaAction, Serializable};
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ActionList {
     actions: Vec<NiaAction>,
 }
@@ -0,0 +1,99 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{
+    activate_profile_from_code, activate_profile_to_code, check_profile_name,
+    Serializable,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionActivateProfile {
+    profile_name: String,
+}
+
+impl ActionActivateProfile {
+    pub fn new<S>(profile_name: S) -> ActionActivateProfile
+    where
+        S: Into<String>,
+    {
+        ActionActivateProfile {
+            profile_name: profile_name.into(),
+        }
+    }
+
+    pub fn get_profile_name(&self) -> &String {
+        &self.profile_name
+    }
+
+    // The interpreter knows nothing about profiles, so the action is run as
+    // code that leaves the request to the server.
+    pub fn to_code(&self) -> String {
+        activate_profile_to_code(&self.profile_name)
+    }
+
+    pub fn from_code(code: &str) -> Option<ActionActivateProfile> {
+        let profile_name = activate_profile_from_code(code)?;
+
+        match check_profile_name(&profile_name) {
+            Ok(()) => Some(ActionActivateProfile::new(profile_name)),
+            Err(_) => None,
+        }
+    }
+}
+
+impl
+    Serializable<
+        ActionActivateProfile,
+        nia_protocol_rust::ActionActivateProfile,
+    > for ActionActivateProfile
+{
+    fn to_pb(&self) -> nia_protocol_rust::ActionActivateProfile {
+        let mut action_activate_profile_pb =
+            nia_protocol_rust::ActionActivateProfile::new();
+
+        action_activate_profile_pb
+            .set_profile_name(protobuf::Chars::from(self.profile_name.clone()));
+
+        action_activate_profile_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ActionActivateProfile,
+    ) -> NiaServerResult<ActionActivateProfile> {
+        let profile_name = object_pb.get_profile_name();
+
+        check_profile_name(profile_name).map_err(|error| {
+            NiaServerError::deserialization_error(error.get_message())
+        })?;
+
+        let action_activate_profile = ActionActivateProfile::new(profile_name);
+
+        Ok(action_activate_profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializable_and_deserializable() {
+        let expected = ActionActivateProfile::new("gaming");
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = ActionActivateProfile::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn converts_to_code_and_back() {
+        let expected = ActionActivateProfile::new("coding");
+
+        let code = expected.to_code();
+        let result = ActionActivateProfile::from_code(&code);
+
+        assert_eq!(Some(expected), result);
+        assert_eq!(None, ActionActivateProfile::from_code("(println 1)"));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{
+    emit_chord_from_code, emit_chord_to_code, get_key_name, resolve_key_code,
+};
+use serde::{Deserialize, Serialize};
+
+const MAX_KEY_CODE: i32 = 767;
+
+// Presses the modifiers, clicks the key and releases the modifiers in the
+// reverse order, e.g. Ctrl+Shift+T. With a hold duration the key is held
+// down that long instead of being clicked. The keys are released even if a
+// step fails, so that no modifier stays stuck.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionEmitChord {
+    modifier_key_codes: Vec<i32>,
+    key_code: i32,
+    hold_ms: i32,
+}
+
+impl ActionEmitChord {
+    pub fn new(
+        modifier_key_codes: Vec<i32>,
+        key_code: i32,
+        hold_ms: i32,
+    ) -> ActionEmitChord {
+        ActionEmitChord {
+            modifier_key_codes,
+            key_code,
+            hold_ms,
+        }
+    }
+
+    pub fn get_modifier_key_codes(&self) -> &Vec<i32> {
+        &self.modifier_key_codes
+    }
+
+    pub fn get_key_code(&self) -> i32 {
+        self.key_code
+    }
+
+    pub fn get_hold_ms(&self) -> i32 {
+        self.hold_ms
+    }
+
+    // Every step is checked beforehand, so that invalid chords are refused
+    // before anything is pressed.
+    pub fn check(&self) -> NiaServerResult<()> {
+        if self.modifier_key_codes.is_empty() {
+            return NiaServerError::invalid_request("Chord has no modifiers.")
+                .into();
+        }
+
+        let key_codes = self
+            .modifier_key_codes
+            .iter()
+            .chain(std::iter::once(&self.key_code));
+
+        for key_code in key_codes {
+            if *key_code <= 0 || *key_code > MAX_KEY_CODE {
+                return NiaServerError::invalid_request(format!(
+                    "Invalid key code: {}.",
+                    key_code
+                ))
+                .into();
+            }
+        }
+
+        for (index, modifier_key_code) in
+            self.modifier_key_codes.iter().enumerate()
+        {
+            if *modifier_key_code == self.key_code
+                || self.modifier_key_codes[..index].contains(modifier_key_code)
+            {
+                return NiaServerError::invalid_request(format!(
+                    "Key code is repeated in the chord: {}.",
+                    modifier_key_code
+                ))
+                .into();
+            }
+        }
+
+        if self.hold_ms < 0 {
+            return NiaServerError::invalid_request(format!(
+                "Invalid hold duration: {}.",
+                self.hold_ms
+            ))
+            .into();
+        }
+
+        Ok(())
+    }
+
+    pub fn to_code(&self) -> String {
+        emit_chord_to_code(
+            &self.modifier_key_codes,
+            self.key_code,
+            self.hold_ms,
+        )
+    }
+
+    // Recognizes the code made by `to_code`.
+    pub fn from_code(code: &str) -> Option<ActionEmitChord> {
+        let (modifier_key_codes, key_code, hold_ms) =
+            emit_chord_from_code(code)?;
+        let action_emit_chord =
+            ActionEmitChord::new(modifier_key_codes, key_code, hold_ms);
+
+        if action_emit_chord.check().is_err() {
+            return None;
+        }
+
+        Some(action_emit_chord)
+    }
+}
+
+impl Serializable<ActionEmitChord, nia_protocol_rust::ActionEmitChord>
+    for ActionEmitChord
+{
+    fn to_pb(&self) -> nia_protocol_rust::ActionEmitChord {
+        let mut action_emit_chord_pb =
+            nia_protocol_rust::ActionEmitChord::new();
+
+        action_emit_chord_pb
+            .set_modifier_key_codes(self.modifier_key_codes.clone());
+        action_emit_chord_pb.set_key_code(self.key_code);
+        action_emit_chord_pb.set_hold_ms(self.hold_ms);
+
+        if let Some(key_name) = get_key_name(self.key_code) {
+            action_emit_chord_pb.set_key_name(protobuf::Chars::from(key_name));
+        }
+
+        action_emit_chord_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ActionEmitChord,
+    ) -> NiaServerResult<ActionEmitChord> {
+        let key_code = resolve_key_code(
+            object_pb.get_key_code(),
+            object_pb.get_key_name(),
+        )?;
+        let action_emit_chord = ActionEmitChord::new(
+            object_pb.get_modifier_key_codes().to_vec(),
+            key_code,
+            object_pb.get_hold_ms(),
+        );
+
+        action_emit_chord.check().map_err(|error| {
+            NiaServerError::deserialization_error(error.get_message())
+        })?;
+
+        Ok(action_emit_chord)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = ActionEmitChord::new(vec![29, 42], 20, 0);
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = ActionEmitChord::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn recognizes_own_code() {
+        let actions_emit_chord = vec![
+            ActionEmitChord::new(vec![29, 42], 20, 0),
+            ActionEmitChord::new(vec![29], 20, 100),
+        ];
+
+        for expected in actions_emit_chord {
+            let result = ActionEmitChord::from_code(&expected.to_code());
+
+            assert_eq!(Some(expected), result);
+        }
+
+        let code = ActionEmitChord::new(vec![29, 29], 20, 0).to_code();
+
+        assert_eq!(None, ActionEmitChord::from_code(&code));
+    }
+
+    #[test]
+    fn rejects_invalid_chords() {
+        let actions_emit_chord = vec![
+            ActionEmitChord::new(vec![], 20, 0),
+            ActionEmitChord::new(vec![29, 29], 20, 0),
+            ActionEmitChord::new(vec![20], 20, 0),
+            ActionEmitChord::new(vec![29], 0, 0),
+            ActionEmitChord::new(vec![29], 20, -1),
+        ];
+
+        for action_emit_chord in actions_emit_chord {
+            assert!(action_emit_chord.check().is_err());
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{emit_raw_events_from_code, emit_raw_events_to_code};
+use serde::{Deserialize, Serialize};
+
+const MAX_EVENT_TYPE: i32 = 0x1f;
+const MAX_EVENT_CODE: i32 = 0x2ff;
+
+// An input event as the kernel defines it, e.g. EV_KEY (1) KEY_A (30) with
+// the value 2 is an autorepeat of A.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NiaRawEvent {
+    event_type: i32,
+    code: i32,
+    value: i32,
+}
+
+impl NiaRawEvent {
+    pub fn new(event_type: i32, code: i32, value: i32) -> NiaRawEvent {
+        NiaRawEvent {
+            event_type,
+            code,
+            value,
+        }
+    }
+
+    pub fn get_event_type(&self) -> i32 {
+        self.event_type
+    }
+
+    pub fn get_code(&self) -> i32 {
+        self.code
+    }
+
+    pub fn get_value(&self) -> i32 {
+        self.value
+    }
+}
+
+impl Serializable<NiaRawEvent, nia_protocol_rust::RawEvent> for NiaRawEvent {
+    fn to_pb(&self) -> nia_protocol_rust::RawEvent {
+        let mut raw_event_pb = nia_protocol_rust::RawEvent::new();
+
+        raw_event_pb.set_event_type(self.event_type);
+        raw_event_pb.set_code(self.code);
+        raw_event_pb.set_value(self.value);
+
+        raw_event_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::RawEvent,
+    ) -> NiaServerResult<NiaRawEvent> {
+        let raw_event = NiaRawEvent::new(
+            object_pb.get_event_type(),
+            object_pb.get_code(),
+            object_pb.get_value(),
+        );
+
+        Ok(raw_event)
+    }
+}
+
+// Emits the events through the virtual device of the interpreter as they are
+// given, for the events no other action covers: EV_KEY autorepeats, EV_REL
+// axes, EV_MSC scan codes and so on. The server emits a synchronization
+// report after the last event, so the events need not end with one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionEmitRawEvent {
+    events: Vec<NiaRawEvent>,
+}
+
+impl ActionEmitRawEvent {
+    pub fn new(events: Vec<NiaRawEvent>) -> ActionEmitRawEvent {
+        ActionEmitRawEvent { events }
+    }
+
+    pub fn get_events(&self) -> &Vec<NiaRawEvent> {
+        &self.events
+    }
+
+    pub fn check(&self) -> NiaServerResult<()> {
+        if self.events.is_empty() {
+            return NiaServerError::invalid_request("No events to emit.")
+                .into();
+        }
+
+        for event in &self.events {
+            if event.event_type < 0 || event.event_type > MAX_EVENT_TYPE {
+                return NiaServerError::invalid_request(format!(
+                    "Invalid event type: {}.",
+                    event.event_type
+                ))
+                .into();
+            }
+
+            if event.code < 0 || event.code > MAX_EVENT_CODE {
+                return NiaServerError::invalid_request(format!(
+                    "Invalid event code: {}.",
+                    event.code
+                ))
+                .into();
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn to_code(&self) -> String {
+        let events = self
+            .events
+            .iter()
+            .map(|event| (event.event_type, event.code, event.value))
+            .collect::<Vec<(i32, i32, i32)>>();
+
+        emit_raw_events_to_code(&events)
+    }
+
+    pub fn from_code(code: &str) -> Option<ActionEmitRawEvent> {
+        let events = emit_raw_events_from_code(code)?
+            .into_iter()
+            .map(|(event_type, code, value)| {
+                NiaRawEvent::new(event_type, code, value)
+            })
+            .collect::<Vec<NiaRawEvent>>();
+
+        let action_emit_raw_event = ActionEmitRawEvent::new(events);
+
+        if action_emit_raw_event.check().is_err() {
+            return None;
+        }
+
+        Some(action_emit_raw_event)
+    }
+}
+
+impl Serializable<ActionEmitRawEvent, nia_protocol_rust::ActionEmitRawEvent>
+    for ActionEmitRawEvent
+{
+    fn to_pb(&self) -> nia_protocol_rust::ActionEmitRawEvent {
+        let events_pb = self
+            .events
+            .iter()
+            .map(|event| event.to_pb())
+            .collect::<Vec<nia_protocol_rust::RawEvent>>();
+
+        let mut action_emit_raw_event_pb =
+            nia_protocol_rust::ActionEmitRawEvent::new();
+
+        action_emit_raw_event_pb
+            .set_events(protobuf::RepeatedField::from(events_pb));
+
+        action_emit_raw_event_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ActionEmitRawEvent,
+    ) -> NiaServerResult<ActionEmitRawEvent> {
+        let mut object_pb = object_pb;
+
+        let mut events = vec![];
+        for event_pb in object_pb.take_events().into_iter() {
+            let event = NiaRawEvent::from_pb(event_pb)?;
+
+            events.push(event)
+        }
+
+        let action_emit_raw_event = ActionEmitRawEvent::new(events);
+
+        action_emit_raw_event.check().map_err(|error| {
+            NiaServerError::deserialization_error(error.get_message())
+        })?;
+
+        Ok(action_emit_raw_event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = ActionEmitRawEvent::new(vec![
+            NiaRawEvent::new(4, 4, 458756),
+            NiaRawEvent::new(1, 30, 2),
+        ]);
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = ActionEmitRawEvent::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn converts_to_code_and_back() {
+        let expected = ActionEmitRawEvent::new(vec![
+            NiaRawEvent::new(2, 8, -1),
+            NiaRawEvent::new(2, 11, -120),
+        ]);
+
+        let result = ActionEmitRawEvent::from_code(&expected.to_code());
+
+        assert_eq!(Some(expected), result);
+    }
+
+    #[test]
+    fn rejects_invalid_events() {
+        let actions_emit_raw_event = vec![
+            ActionEmitRawEvent::new(vec![]),
+            ActionEmitRawEvent::new(vec![NiaRawEvent::new(32, 30, 1)]),
+            ActionEmitRawEvent::new(vec![NiaRawEvent::new(1, -1, 1)]),
+            ActionEmitRawEvent::new(vec![NiaRawEvent::new(1, 768, 1)]),
+        ];
+
+        for action_emit_raw_event in actions_emit_raw_event {
+            assert!(action_emit_raw_event.check().is_err());
+        }
+    }
+}
@@ -1,11 +1,16 @@
 use crate::protocol::domain::action::basic_actions::*;
+use crate::protocol::ActionList;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NiaActionEnum {
     KeyClick(ActionKeyClick),
     KeyPress(ActionKeyPress),
     KeyRelease(ActionKeyRelease),
 
+    EmitChord(ActionEmitChord),
+    EmitRawEvent(ActionEmitRawEvent),
+
     MouseButtonClick(ActionMouseButtonClick),
     MouseButtonPress(ActionMouseButtonPress),
     MouseButtonRelease(ActionMouseButtonRelease),
@@ -20,6 +25,7 @@ pub enum NiaActionEnum {
 
     MouseAbsoluteMove(ActionMouseAbsoluteMove),
     MouseRelativeMove(ActionMouseRelativeMove),
+    MouseScroll(ActionMouseScroll),
 
     Wait(ActionWait),
     TextType(ActionTextType),
@@ -29,6 +35,83 @@ pub enum NiaActionEnum {
     ExecuteOSCommand(ActionExecuteOSCommand),
     ExecuteNamedAction(ActionExecuteNamedAction),
     ExecuteInterpreterValue(ActionExecuteInterpreterValue),
+
+    ActivateProfile(ActionActivateProfile),
+
+    Sequence(ActionList),
+    Repeat(ActionRepeat),
+    RepeatWhileHeld(ActionRepeatWhileHeld),
+}
+
+pub const ACTION_TYPE_NAMES: &[&str] = &[
+    "KeyClick",
+    "KeyPress",
+    "KeyRelease",
+    "EmitChord",
+    "EmitRawEvent",
+    "MouseButtonClick",
+    "MouseButtonPress",
+    "MouseButtonRelease",
+    "ControlKeyClick",
+    "FunctionKeyClick",
+    "KPKeyClick",
+    "MouseButtonKeyClick",
+    "MultimediaKeyClick",
+    "NumberKeyClick",
+    "TextKeyClick",
+    "MouseAbsoluteMove",
+    "MouseRelativeMove",
+    "MouseScroll",
+    "Wait",
+    "TextType",
+    "ExecuteCode",
+    "ExecuteFunction",
+    "ExecuteOSCommand",
+    "ExecuteNamedAction",
+    "ExecuteInterpreterValue",
+    "ActivateProfile",
+    "Sequence",
+    "Repeat",
+    "RepeatWhileHeld",
+];
+
+impl NiaActionEnum {
+    // Name of the variant, as listed in ACTION_TYPE_NAMES.
+    pub fn get_type_name(&self) -> &'static str {
+        match self {
+            NiaActionEnum::KeyClick(_) => "KeyClick",
+            NiaActionEnum::KeyPress(_) => "KeyPress",
+            NiaActionEnum::KeyRelease(_) => "KeyRelease",
+            NiaActionEnum::EmitChord(_) => "EmitChord",
+            NiaActionEnum::EmitRawEvent(_) => "EmitRawEvent",
+            NiaActionEnum::MouseButtonClick(_) => "MouseButtonClick",
+            NiaActionEnum::MouseButtonPress(_) => "MouseButtonPress",
+            NiaActionEnum::MouseButtonRelease(_) => "MouseButtonRelease",
+            NiaActionEnum::ControlKeyClick(_) => "ControlKeyClick",
+            NiaActionEnum::FunctionKeyClick(_) => "FunctionKeyClick",
+            NiaActionEnum::KPKeyClick(_) => "KPKeyClick",
+            NiaActionEnum::MouseButtonKeyClick(_) => "MouseButtonKeyClick",
+            NiaActionEnum::MultimediaKeyClick(_) => "MultimediaKeyClick",
+            NiaActionEnum::NumberKeyClick(_) => "NumberKeyClick",
+            NiaActionEnum::TextKeyClick(_) => "TextKeyClick",
+            NiaActionEnum::MouseAbsoluteMove(_) => "MouseAbsoluteMove",
+            NiaActionEnum::MouseRelativeMove(_) => "MouseRelativeMove",
+            NiaActionEnum::MouseScroll(_) => "MouseScroll",
+            NiaActionEnum::Wait(_) => "Wait",
+            NiaActionEnum::TextType(_) => "TextType",
+            NiaActionEnum::ExecuteCode(_) => "ExecuteCode",
+            NiaActionEnum::ExecuteFunction(_) => "ExecuteFunction",
+            NiaActionEnum::ExecuteOSCommand(_) => "ExecuteOSCommand",
+            NiaActionEnum::ExecuteNamedAction(_) => "ExecuteNamedAction",
+            NiaActionEnum::ExecuteInterpreterValue(_) => {
+                "ExecuteInterpreterValue"
+            }
+            NiaActionEnum::ActivateProfile(_) => "ActivateProfile",
+            NiaActionEnum::Sequence(_) => "Sequence",
+            NiaActionEnum::Repeat(_) => "Repeat",
+            NiaActionEnum::RepeatWhileHeld(_) => "RepeatWhileHeld",
+        }
+    }
 }
 
 macro_rules! make_from_impl {
@@ -45,6 +128,9 @@ make_from_impl!(ActionKeyClick, NiaActionEnum::KeyClick);
 make_from_impl!(ActionKeyPress, NiaActionEnum::KeyPress);
 make_from_impl!(ActionKeyRelease, NiaActionEnum::KeyRelease);
 
+make_from_impl!(ActionEmitChord, NiaActionEnum::EmitChord);
+make_from_impl!(ActionEmitRawEvent, NiaActionEnum::EmitRawEvent);
+
 make_from_impl!(ActionMouseButtonClick, NiaActionEnum::MouseButtonClick);
 make_from_impl!(ActionMouseButtonPress, NiaActionEnum::MouseButtonPress);
 make_from_impl!(ActionMouseButtonRelease, NiaActionEnum::MouseButtonRelease);
@@ -59,6 +145,7 @@ make_from_impl!(ActionNumberKeyClick, NiaActionEnum::NumberKeyClick);
 make_from_impl!(ActionTextKeyClick, NiaActionEnum::TextKeyClick);
 
 make_from_impl!(ActionMouseRelativeMove, NiaActionEnum::MouseRelativeMove);
+make_from_impl!(ActionMouseScroll, NiaActionEnum::MouseScroll);
 make_from_impl!(ActionMouseAbsoluteMove, NiaActionEnum::MouseAbsoluteMove);
 
 make_from_impl!(ActionWait, NiaActionEnum::Wait);
@@ -70,3 +157,9 @@ make_from_impl!(ActionExecuteOSCommand, NiaActionEnum::ExecuteOSCommand);
 make_from_impl!(ActionExecuteNamedAction, NiaActionEnum::ExecuteNamedAction);
 #[rustfmt::skip]
 make_from_impl!(ActionExecuteInterpreterValue, NiaActionEnum::ExecuteInterpreterValue);
+
+make_from_impl!(ActionActivateProfile, NiaActionEnum::ActivateProfile);
+
+make_from_impl!(ActionList, NiaActionEnum::Sequence);
+make_from_impl!(ActionRepeat, NiaActionEnum::Repeat);
+make_from_impl!(ActionRepeatWhileHeld, NiaActionEnum::RepeatWhileHeld);
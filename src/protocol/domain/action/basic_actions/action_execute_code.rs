@@ -1,8 +1,9 @@
 use crate::error::{from_protobuf_error, NiaServerError, NiaServerResult};
-use crate::protocol::Serializable;
+use crate::protocol::{is_server_code, Serializable};
 use protobuf::Message;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ActionExecuteCode {
     code: String,
 }
@@ -18,6 +19,20 @@ impl ActionExecuteCode {
     pub fn get_code(&self) -> &String {
         &self.code
     }
+
+    // Code marked as made by the server would be read back as another
+    // action.
+    pub fn check(&self) -> NiaServerResult<()> {
+        if is_server_code(&self.code) {
+            return NiaServerError::invalid_request(format!(
+                "Code is reserved for actions of the server: {}.",
+                self.code
+            ))
+            .into();
+        }
+
+        Ok(())
+    }
 }
 
 impl Serializable<ActionExecuteCode, nia_protocol_rust::ActionExecuteCode>
@@ -39,6 +54,10 @@ impl Serializable<ActionExecuteCode, nia_protocol_rust::ActionExecuteCode>
         let action_execute_code =
             ActionExecuteCode::new(String::from(object_pb.get_code()));
 
+        action_execute_code.check().map_err(|error| {
+            NiaServerError::deserialization_error(error.get_message())
+        })?;
+
         Ok(action_execute_code)
     }
 }
@@ -60,4 +79,17 @@ mod tests {
 
         assert_eq!(expected, result)
     }
+
+    #[test]
+    fn refuses_server_code() {
+        let action_execute_code = ActionExecuteCode::new(
+            "(progn \"nia-server:sequence\" (action:wait 1))",
+        );
+
+        assert!(action_execute_code.check().is_err());
+        assert!(ActionExecuteCode::from_bytes(
+            action_execute_code.to_bytes().unwrap()
+        )
+        .is_err());
+    }
 }
@@ -1,13 +1,73 @@
-use crate::error::{from_protobuf_error, NiaServerError, NiaServerResult};
-use crate::protocol::Serializable;
-use protobuf::Message;
+use std::convert::TryFrom;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub struct ActionExecuteInterpreterValue {}
+use serde::{Deserialize, Serialize};
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{
+    interpreter_value_from_code, interpreter_value_to_code, is_function_code,
+    Serializable,
+};
+
+// An interpreter value, usually a lambda, that is called when the action is
+// executed. The action keeps the value as the interpreter prints it, e.g.
+// "(fn () (println \"lambda\"))", and the code is evaluated again every time
+// the action is executed. So the server holds no values of the interpreter,
+// and the action survives restarts. Variables captured by closures are not
+// printed, the code is evaluated in the main environment.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "InterpreterValueCode")]
+pub struct ActionExecuteInterpreterValue {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct InterpreterValueCode {
+    code: String,
+}
 
 impl ActionExecuteInterpreterValue {
-    pub fn new() -> ActionExecuteInterpreterValue {
-        ActionExecuteInterpreterValue {}
+    pub fn new<S>(code: S) -> NiaServerResult<ActionExecuteInterpreterValue>
+    where
+        S: Into<String>,
+    {
+        let code = code.into();
+
+        if !is_function_code(&code) {
+            return NiaServerError::invalid_request(format!(
+                "Interpreter value is not a function: {}.",
+                code
+            ))
+            .into();
+        }
+
+        Ok(ActionExecuteInterpreterValue { code })
+    }
+
+    pub fn get_code(&self) -> &String {
+        &self.code
+    }
+
+    // The interpreter executes function values it has, so the action is run
+    // as code that calls the printed function.
+    pub fn to_code(&self) -> String {
+        interpreter_value_to_code(&self.code)
+    }
+
+    pub fn from_code(code: &str) -> Option<ActionExecuteInterpreterValue> {
+        let function_code = interpreter_value_from_code(code)?;
+
+        ActionExecuteInterpreterValue::new(function_code).ok()
+    }
+}
+
+impl TryFrom<InterpreterValueCode> for ActionExecuteInterpreterValue {
+    type Error = String;
+
+    fn try_from(
+        interpreter_value_code: InterpreterValueCode,
+    ) -> Result<ActionExecuteInterpreterValue, String> {
+        ActionExecuteInterpreterValue::new(interpreter_value_code.code)
+            .map_err(|error| error.get_message())
     }
 }
 
@@ -21,16 +81,19 @@ impl
         let mut action_execute_interpreter_value_pb =
             nia_protocol_rust::ActionExecuteInterpreterValue::new();
 
+        action_execute_interpreter_value_pb
+            .set_code(protobuf::Chars::from(self.code.clone()));
+
         action_execute_interpreter_value_pb
     }
 
     fn from_pb(
         object_pb: nia_protocol_rust::ActionExecuteInterpreterValue,
     ) -> NiaServerResult<ActionExecuteInterpreterValue> {
-        let action_execute_interpreter_value =
-            ActionExecuteInterpreterValue::new();
-
-        Ok(action_execute_interpreter_value)
+        ActionExecuteInterpreterValue::new(String::from(object_pb.get_code()))
+            .map_err(|error| {
+                NiaServerError::deserialization_error(error.get_message())
+            })
     }
 }
 
@@ -41,13 +104,40 @@ mod tests {
 
     #[test]
     fn serializable_and_deserializable() {
-        let action_execute_function = ActionExecuteInterpreterValue::new();
-        let bytes = action_execute_function.to_bytes().unwrap();
-        let action_execute_function =
-            ActionExecuteInterpreterValue::from_bytes(bytes).unwrap();
+        let expected =
+            ActionExecuteInterpreterValue::new("(fn () (println \"lambda\"))")
+                .unwrap();
 
-        let result = action_execute_function.action_name;
+        let bytes = expected.to_bytes().unwrap();
+        let result = ActionExecuteInterpreterValue::from_bytes(bytes).unwrap();
 
         assert_eq!(expected, result)
     }
+
+    #[test]
+    fn reads_back_its_code() {
+        let expected = ActionExecuteInterpreterValue::new(
+            "(fn () (action:text-type \"(nya)\"))",
+        )
+        .unwrap();
+
+        let result =
+            ActionExecuteInterpreterValue::from_code(&expected.to_code());
+
+        assert_eq!(Some(expected), result);
+    }
+
+    #[test]
+    fn refuses_values_that_are_not_functions() {
+        let codes = vec!["3", "(+ 1 2)", "(fn () 1) (fn () 2)", "(fn () (1)"];
+
+        for code in codes {
+            assert!(ActionExecuteInterpreterValue::new(code).is_err());
+        }
+
+        assert!(serde_json::from_str::<ActionExecuteInterpreterValue>(
+            "{\"code\":\"(+ 1 2)\"}"
+        )
+        .is_err());
+    }
 }
@@ -1,8 +1,10 @@
 use crate::error::{from_protobuf_error, NiaServerError, NiaServerResult};
 use crate::protocol::Serializable;
+use crate::protocol::{get_key_name, resolve_key_code};
 use protobuf::Message;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ActionFunctionKeyClick {
     key_code: i32,
 }
@@ -29,14 +31,21 @@ impl
 
         action_key_click_pb.set_key_code(self.get_key_code());
 
+        if let Some(key_name) = get_key_name(self.key_code) {
+            action_key_click_pb.set_key_name(protobuf::Chars::from(key_name));
+        }
+
         action_key_click_pb
     }
 
     fn from_pb(
         object_pb: nia_protocol_rust::ActionFunctionKeyClick,
     ) -> NiaServerResult<ActionFunctionKeyClick> {
-        let action_key_click =
-            ActionFunctionKeyClick::new(object_pb.get_key_code());
+        let key_code = resolve_key_code(
+            object_pb.get_key_code(),
+            object_pb.get_key_name(),
+        )?;
+        let action_key_click = ActionFunctionKeyClick::new(key_code);
 
         Ok(action_key_click)
     }
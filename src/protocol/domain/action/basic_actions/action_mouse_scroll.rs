@@ -0,0 +1,182 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{mouse_scroll_from_code, mouse_scroll_to_code};
+use serde::{Deserialize, Serialize};
+
+// The high-resolution amount of one wheel detent, as devices report it in
+// REL_WHEEL_HI_RES and REL_HWHEEL_HI_RES events.
+pub const HI_RES_AMOUNT_PER_DETENT: i32 = 120;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NiaScrollDirection {
+    // REL_WHEEL, positive amounts scroll up.
+    Vertical,
+    // REL_HWHEEL, positive amounts scroll right.
+    Horizontal,
+}
+
+// Emits a wheel event with the amount in detents along with the
+// high-resolution event, so that applications reading either of them scroll
+// the same distance. Scrolling less than a detent takes a zero amount and
+// only the high-resolution part. The interpreter emits the events through
+// its virtual device.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionMouseScroll {
+    direction: NiaScrollDirection,
+    amount: i32,
+    hi_res_amount: i32,
+}
+
+impl ActionMouseScroll {
+    pub fn new(
+        direction: NiaScrollDirection,
+        amount: i32,
+        hi_res_amount: i32,
+    ) -> ActionMouseScroll {
+        ActionMouseScroll {
+            direction,
+            amount,
+            hi_res_amount,
+        }
+    }
+
+    pub fn from_detents(
+        direction: NiaScrollDirection,
+        amount: i32,
+    ) -> ActionMouseScroll {
+        ActionMouseScroll::new(
+            direction,
+            amount,
+            amount * HI_RES_AMOUNT_PER_DETENT,
+        )
+    }
+
+    pub fn get_direction(&self) -> NiaScrollDirection {
+        self.direction
+    }
+
+    pub fn get_amount(&self) -> i32 {
+        self.amount
+    }
+
+    pub fn get_hi_res_amount(&self) -> i32 {
+        self.hi_res_amount
+    }
+
+    // The detents must be the whole part of the high-resolution amount,
+    // otherwise the two events would describe different scrolls.
+    pub fn check(&self) -> NiaServerResult<()> {
+        if self.hi_res_amount == 0 {
+            return NiaServerError::invalid_request("Scroll amount is zero.")
+                .into();
+        }
+
+        if self.amount != self.hi_res_amount / HI_RES_AMOUNT_PER_DETENT {
+            return NiaServerError::invalid_request(format!(
+                "Scroll amount {} does not match high-resolution amount {}.",
+                self.amount, self.hi_res_amount
+            ))
+            .into();
+        }
+
+        Ok(())
+    }
+
+    pub fn to_code(&self) -> String {
+        mouse_scroll_to_code(self.direction, self.amount, self.hi_res_amount)
+    }
+
+    pub fn from_code(code: &str) -> Option<ActionMouseScroll> {
+        let (direction, amount, hi_res_amount) = mouse_scroll_from_code(code)?;
+        let action_mouse_scroll =
+            ActionMouseScroll::new(direction, amount, hi_res_amount);
+
+        if action_mouse_scroll.check().is_err() {
+            return None;
+        }
+
+        Some(action_mouse_scroll)
+    }
+}
+
+impl Serializable<ActionMouseScroll, nia_protocol_rust::ActionMouseScroll>
+    for ActionMouseScroll
+{
+    fn to_pb(&self) -> nia_protocol_rust::ActionMouseScroll {
+        let mut action_mouse_scroll_pb =
+            nia_protocol_rust::ActionMouseScroll::new();
+
+        action_mouse_scroll_pb
+            .set_horizontal(self.direction == NiaScrollDirection::Horizontal);
+        action_mouse_scroll_pb.set_amount(self.amount);
+        action_mouse_scroll_pb.set_hi_res_amount(self.hi_res_amount);
+
+        action_mouse_scroll_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ActionMouseScroll,
+    ) -> NiaServerResult<ActionMouseScroll> {
+        let direction = if object_pb.get_horizontal() {
+            NiaScrollDirection::Horizontal
+        } else {
+            NiaScrollDirection::Vertical
+        };
+
+        let action_mouse_scroll = ActionMouseScroll::new(
+            direction,
+            object_pb.get_amount(),
+            object_pb.get_hi_res_amount(),
+        );
+
+        action_mouse_scroll.check().map_err(|error| {
+            NiaServerError::deserialization_error(error.get_message())
+        })?;
+
+        Ok(action_mouse_scroll)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let actions_mouse_scroll = vec![
+            ActionMouseScroll::from_detents(NiaScrollDirection::Vertical, -1),
+            ActionMouseScroll::new(NiaScrollDirection::Horizontal, 0, 30),
+        ];
+
+        for expected in actions_mouse_scroll {
+            let bytes = expected.to_bytes().unwrap();
+            let result = ActionMouseScroll::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, result);
+        }
+    }
+
+    #[test]
+    fn converts_to_code_and_back() {
+        let expected =
+            ActionMouseScroll::new(NiaScrollDirection::Vertical, 1, 150);
+
+        let result = ActionMouseScroll::from_code(&expected.to_code());
+
+        assert_eq!(Some(expected), result);
+    }
+
+    #[test]
+    fn rejects_mismatched_amounts() {
+        let actions_mouse_scroll = vec![
+            ActionMouseScroll::new(NiaScrollDirection::Vertical, 0, 0),
+            ActionMouseScroll::new(NiaScrollDirection::Vertical, 1, 60),
+            ActionMouseScroll::new(NiaScrollDirection::Vertical, -1, 120),
+        ];
+
+        for action_mouse_scroll in actions_mouse_scroll {
+            assert!(action_mouse_scroll.check().is_err());
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{
+    repeat_from_code, repeat_to_code, NiaAction, NiaConvertable, Serializable,
+};
+use serde::{Deserialize, Serialize};
+
+// Executes the action the given number of times, waiting the interval
+// between executions. The interpreter runs the loop, so the action is sent
+// once whatever the count is.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionRepeat {
+    action: Box<NiaAction>,
+    times: i32,
+    interval_ms: i32,
+}
+
+impl ActionRepeat {
+    pub fn new(
+        action: NiaAction,
+        times: i32,
+        interval_ms: i32,
+    ) -> ActionRepeat {
+        ActionRepeat {
+            action: Box::new(action),
+            times,
+            interval_ms,
+        }
+    }
+
+    pub fn get_action(&self) -> &NiaAction {
+        &self.action
+    }
+
+    pub fn get_times(&self) -> i32 {
+        self.times
+    }
+
+    pub fn get_interval_ms(&self) -> i32 {
+        self.interval_ms
+    }
+
+    // A single execution is the action itself, so at least two are
+    // required.
+    pub fn check(&self) -> NiaServerResult<()> {
+        if self.times < 2 {
+            return NiaServerError::invalid_request(format!(
+                "Invalid repetition count: {}.",
+                self.times
+            ))
+            .into();
+        }
+
+        if self.interval_ms < 0 {
+            return NiaServerError::invalid_request(format!(
+                "Invalid repetition interval: {}.",
+                self.interval_ms
+            ))
+            .into();
+        }
+
+        self.action.check()
+    }
+
+    pub fn to_code(&self) -> NiaServerResult<String> {
+        repeat_to_code(
+            self.times,
+            self.interval_ms,
+            &self.action.to_interpreter_repr(),
+        )
+    }
+
+    pub fn from_code(code: &str) -> NiaServerResult<Option<ActionRepeat>> {
+        let (times, interval_ms, action) = match repeat_from_code(code) {
+            Some(repetition) => repetition,
+            None => return Ok(None),
+        };
+
+        let action = NiaAction::from_interpreter_repr(&action)?;
+        let action_repeat = ActionRepeat::new(action, times, interval_ms);
+
+        if action_repeat.check().is_err() {
+            return Ok(None);
+        }
+
+        Ok(Some(action_repeat))
+    }
+}
+
+impl Serializable<ActionRepeat, nia_protocol_rust::ActionRepeat>
+    for ActionRepeat
+{
+    fn to_pb(&self) -> nia_protocol_rust::ActionRepeat {
+        let mut action_repeat_pb = nia_protocol_rust::ActionRepeat::new();
+
+        action_repeat_pb.set_action(self.action.to_pb());
+        action_repeat_pb.set_times(self.times);
+        action_repeat_pb.set_interval_ms(self.interval_ms);
+
+        action_repeat_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ActionRepeat,
+    ) -> NiaServerResult<ActionRepeat> {
+        let mut object_pb = object_pb;
+
+        let action = NiaAction::from_pb(object_pb.take_action())?;
+        let action_repeat = ActionRepeat::new(
+            action,
+            object_pb.get_times(),
+            object_pb.get_interval_ms(),
+        );
+
+        action_repeat.check().map_err(|error| {
+            NiaServerError::deserialization_error(error.get_message())
+        })?;
+
+        Ok(action_repeat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::ActionMouseButtonClick;
+
+    fn construct_action_repeat() -> ActionRepeat {
+        ActionRepeat::new(
+            NiaAction::new(ActionMouseButtonClick::new(272).into()),
+            3,
+            40,
+        )
+    }
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = construct_action_repeat();
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = ActionRepeat::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn converts_to_code_and_back() {
+        let expected = construct_action_repeat();
+
+        let result =
+            ActionRepeat::from_code(&expected.to_code().unwrap()).unwrap();
+
+        assert_eq!(Some(expected), result);
+    }
+
+    #[test]
+    fn rejects_invalid_repetitions() {
+        let action = NiaAction::new(ActionMouseButtonClick::new(272).into());
+
+        assert!(ActionRepeat::new(action.clone(), 1, 40).check().is_err());
+        assert!(ActionRepeat::new(action.clone(), 2, -1).check().is_err());
+        assert!(ActionRepeat::new(action, 100000, 40).check().is_ok());
+    }
+}
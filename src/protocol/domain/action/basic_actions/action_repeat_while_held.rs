@@ -0,0 +1,139 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{
+    repeat_while_held_from_code, repeat_while_held_to_code, NiaAction,
+    NiaConvertable, Serializable,
+};
+use serde::{Deserialize, Serialize};
+
+// Executes the action every interval for as long as the key that fired the
+// mapping is held. The interpreter runs the loop and stops it once the key
+// is released. Suits auto-fire, scrolling keys and continuous mouse
+// movement.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionRepeatWhileHeld {
+    action: Box<NiaAction>,
+    interval_ms: i32,
+}
+
+impl ActionRepeatWhileHeld {
+    pub fn new(action: NiaAction, interval_ms: i32) -> ActionRepeatWhileHeld {
+        ActionRepeatWhileHeld {
+            action: Box::new(action),
+            interval_ms,
+        }
+    }
+
+    pub fn get_action(&self) -> &NiaAction {
+        &self.action
+    }
+
+    pub fn get_interval_ms(&self) -> i32 {
+        self.interval_ms
+    }
+
+    // Without an interval the action would be executed in a busy loop.
+    pub fn check(&self) -> NiaServerResult<()> {
+        if self.interval_ms <= 0 {
+            return NiaServerError::invalid_request(format!(
+                "Invalid repetition interval: {}.",
+                self.interval_ms
+            ))
+            .into();
+        }
+
+        self.action.check()
+    }
+
+    pub fn to_code(&self) -> NiaServerResult<String> {
+        repeat_while_held_to_code(
+            self.interval_ms,
+            &self.action.to_interpreter_repr(),
+        )
+    }
+
+    pub fn from_code(
+        code: &str,
+    ) -> NiaServerResult<Option<ActionRepeatWhileHeld>> {
+        let (interval_ms, action) = match repeat_while_held_from_code(code) {
+            Some(repetition) => repetition,
+            None => return Ok(None),
+        };
+
+        let action = NiaAction::from_interpreter_repr(&action)?;
+        let action_repeat_while_held =
+            ActionRepeatWhileHeld::new(action, interval_ms);
+
+        if action_repeat_while_held.check().is_err() {
+            return Ok(None);
+        }
+
+        Ok(Some(action_repeat_while_held))
+    }
+}
+
+impl
+    Serializable<
+        ActionRepeatWhileHeld,
+        nia_protocol_rust::ActionRepeatWhileHeld,
+    > for ActionRepeatWhileHeld
+{
+    fn to_pb(&self) -> nia_protocol_rust::ActionRepeatWhileHeld {
+        let mut action_repeat_while_held_pb =
+            nia_protocol_rust::ActionRepeatWhileHeld::new();
+
+        action_repeat_while_held_pb.set_action(self.action.to_pb());
+        action_repeat_while_held_pb.set_interval_ms(self.interval_ms);
+
+        action_repeat_while_held_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ActionRepeatWhileHeld,
+    ) -> NiaServerResult<ActionRepeatWhileHeld> {
+        let mut object_pb = object_pb;
+
+        let action = NiaAction::from_pb(object_pb.take_action())?;
+        let action_repeat_while_held =
+            ActionRepeatWhileHeld::new(action, object_pb.get_interval_ms());
+
+        action_repeat_while_held.check().map_err(|error| {
+            NiaServerError::deserialization_error(error.get_message())
+        })?;
+
+        Ok(action_repeat_while_held)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::ActionMouseRelativeMove;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = ActionRepeatWhileHeld::new(
+            NiaAction::new(ActionMouseRelativeMove::new(0, 10).into()),
+            20,
+        );
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = ActionRepeatWhileHeld::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn converts_to_code_and_back() {
+        let expected = ActionRepeatWhileHeld::new(
+            NiaAction::new(ActionMouseRelativeMove::new(0, 10).into()),
+            20,
+        );
+
+        let result =
+            ActionRepeatWhileHeld::from_code(&expected.to_code().unwrap())
+                .unwrap();
+
+        assert_eq!(Some(expected), result);
+    }
+}
@@ -2,6 +2,9 @@ mod action_key_click;
 mod action_key_press;
 mod action_key_release;
 
+mod action_emit_chord;
+mod action_emit_raw_event;
+
 mod action_mouse_button_click;
 mod action_mouse_button_press;
 mod action_mouse_button_release;
@@ -16,6 +19,7 @@ mod action_text_key_click;
 
 mod action_mouse_absolute_move;
 mod action_mouse_relative_move;
+mod action_mouse_scroll;
 
 mod action_execute_code;
 mod action_execute_function;
@@ -25,12 +29,20 @@ mod action_execute_os_command;
 mod action_text_type;
 mod action_wait;
 
+mod action_activate_profile;
+
+mod action_repeat;
+mod action_repeat_while_held;
+
 mod action_enum;
 
 pub use action_key_click::*;
 pub use action_key_press::*;
 pub use action_key_release::*;
 
+pub use action_emit_chord::*;
+pub use action_emit_raw_event::*;
+
 pub use action_mouse_button_click::*;
 pub use action_mouse_button_press::*;
 pub use action_mouse_button_release::*;
@@ -45,6 +57,7 @@ pub use action_text_key_click::*;
 
 pub use action_mouse_absolute_move::*;
 pub use action_mouse_relative_move::*;
+pub use action_mouse_scroll::*;
 
 pub use action_execute_code::*;
 pub use action_execute_function::*;
@@ -54,4 +67,9 @@ pub use action_execute_os_command::*;
 pub use action_text_type::*;
 pub use action_wait::*;
 
+pub use action_activate_profile::*;
+
+pub use action_repeat::*;
+pub use action_repeat_while_held::*;
+
 pub use action_enum::*;
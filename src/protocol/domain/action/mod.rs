@@ -1,9 +1,11 @@
 mod action;
+mod action_code;
 mod action_list;
 mod basic_actions;
 mod named_action;
 
 pub use action::*;
+pub use action_code::*;
 pub use action_list::*;
 pub use basic_actions::*;
 pub use named_action::*;
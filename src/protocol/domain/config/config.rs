@@ -0,0 +1,596 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{
+    format_key, format_key_chords, parse_key_chord, parse_key_chords,
+};
+use crate::protocol::{
+    NiaAction, NiaConfigDevice, NiaKey, NiaKeyChord, NiaMapping,
+    NiaMappingMetadata, NiaModifierDescription, NiaNamedAction,
+};
+
+// The file representation of a configuration. Keys and key chords are
+// written in the key chord notation, so that the file stays editable by hand.
+#[derive(Serialize, Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    devices: Vec<ConfigFileDevice>,
+    #[serde(default)]
+    modifiers: Vec<ConfigFileModifier>,
+    #[serde(default)]
+    actions: Vec<ConfigFileAction>,
+    #[serde(default)]
+    mappings: Vec<ConfigFileMapping>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ConfigFileDevice {
+    id: i32,
+    name: String,
+    path: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ConfigFileModifier {
+    key: String,
+    alias: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ConfigFileAction {
+    name: String,
+    action: NiaAction,
+}
+
+// Disabled mappings are written along with the enabled ones. Timestamps of
+// mapping metadata belong to the server and are not written.
+#[derive(Serialize, Deserialize)]
+struct ConfigFileMapping {
+    keys: String,
+    action: NiaAction,
+    #[serde(default = "is_enabled_by_default")]
+    enabled: bool,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+fn is_enabled_by_default() -> bool {
+    true
+}
+
+fn parse_key<F>(notation: &str, resolve_device: &F) -> NiaServerResult<NiaKey>
+where
+    F: Fn(&str) -> Option<i32>,
+{
+    let key_chord = parse_key_chord(notation, resolve_device)?;
+
+    if !key_chord.get_modifiers().is_empty() {
+        return NiaServerError::invalid_request(format!(
+            "Expected a single key: \"{}\".",
+            notation
+        ))
+        .into();
+    }
+
+    Ok(key_chord.get_key())
+}
+
+fn map_key_device_id<F>(
+    key: NiaKey,
+    map_device_id: &F,
+) -> NiaServerResult<NiaKey>
+where
+    F: Fn(i32) -> NiaServerResult<i32>,
+{
+    let key = match key {
+        NiaKey::Key1(key_code) => NiaKey::make_key_1(key_code),
+        NiaKey::Key2(device_id, key_code) => {
+            NiaKey::make_key_2(map_device_id(device_id)?, key_code)
+        }
+    };
+
+    Ok(key)
+}
+
+fn map_key_chord_device_ids<F>(
+    key_chord: &NiaKeyChord,
+    map_device_id: &F,
+) -> NiaServerResult<NiaKeyChord>
+where
+    F: Fn(i32) -> NiaServerResult<i32>,
+{
+    let modifiers = key_chord
+        .get_modifiers()
+        .iter()
+        .map(|modifier| map_key_device_id(*modifier, map_device_id))
+        .collect::<NiaServerResult<Vec<NiaKey>>>()?;
+    let key = map_key_device_id(key_chord.get_key(), map_device_id)?;
+
+    Ok(NiaKeyChord::new(modifiers, key))
+}
+
+fn map_key_chords_device_ids<F>(
+    key_chords: &[NiaKeyChord],
+    map_device_id: &F,
+) -> NiaServerResult<Vec<NiaKeyChord>>
+where
+    F: Fn(i32) -> NiaServerResult<i32>,
+{
+    key_chords
+        .iter()
+        .map(|key_chord| map_key_chord_device_ids(key_chord, map_device_id))
+        .collect()
+}
+
+fn map_mappings_device_ids<F>(
+    mappings: &[NiaMapping],
+    map_device_id: &F,
+) -> NiaServerResult<Vec<NiaMapping>>
+where
+    F: Fn(i32) -> NiaServerResult<i32>,
+{
+    mappings
+        .iter()
+        .map(|mapping| {
+            let key_chords = map_key_chords_device_ids(
+                mapping.get_key_chords(),
+                map_device_id,
+            )?;
+
+            Ok(NiaMapping::new(key_chords, mapping.get_action().clone()))
+        })
+        .collect()
+}
+
+fn make_config_file_mapping(
+    mapping: &NiaMapping,
+    mapping_metadata: Option<&NiaMappingMetadata>,
+    enabled: bool,
+) -> ConfigFileMapping {
+    ConfigFileMapping {
+        keys: format_key_chords(mapping.get_key_chords()),
+        action: mapping.get_action().clone(),
+        enabled,
+        description: mapping_metadata
+            .map(|mapping_metadata| mapping_metadata.get_description())
+            .unwrap_or("")
+            .to_string(),
+        tags: mapping_metadata
+            .map(|mapping_metadata| mapping_metadata.get_tags().clone())
+            .unwrap_or_default(),
+    }
+}
+
+// Besides definitions, a configuration keeps disabled mappings and metadata
+// of mappings, which the server keeps itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NiaConfig {
+    devices: Vec<NiaConfigDevice>,
+    modifiers: Vec<NiaModifierDescription>,
+    named_actions: Vec<NiaNamedAction>,
+    mappings: Vec<NiaMapping>,
+    disabled_mappings: Vec<NiaMapping>,
+    mappings_metadata: HashMap<Vec<NiaKeyChord>, NiaMappingMetadata>,
+}
+
+impl NiaConfig {
+    pub fn new(
+        devices: Vec<NiaConfigDevice>,
+        modifiers: Vec<NiaModifierDescription>,
+        named_actions: Vec<NiaNamedAction>,
+        mappings: Vec<NiaMapping>,
+    ) -> NiaConfig {
+        NiaConfig {
+            devices,
+            modifiers,
+            named_actions,
+            mappings,
+            disabled_mappings: Vec::new(),
+            mappings_metadata: HashMap::new(),
+        }
+    }
+
+    pub fn get_devices(&self) -> &Vec<NiaConfigDevice> {
+        &self.devices
+    }
+
+    pub fn get_modifiers(&self) -> &Vec<NiaModifierDescription> {
+        &self.modifiers
+    }
+
+    pub fn get_named_actions(&self) -> &Vec<NiaNamedAction> {
+        &self.named_actions
+    }
+
+    pub fn get_mappings(&self) -> &Vec<NiaMapping> {
+        &self.mappings
+    }
+
+    pub fn get_disabled_mappings(&self) -> &Vec<NiaMapping> {
+        &self.disabled_mappings
+    }
+
+    pub fn set_disabled_mappings(
+        &mut self,
+        disabled_mappings: Vec<NiaMapping>,
+    ) {
+        self.disabled_mappings = disabled_mappings;
+    }
+
+    pub fn get_mappings_metadata(
+        &self,
+    ) -> &HashMap<Vec<NiaKeyChord>, NiaMappingMetadata> {
+        &self.mappings_metadata
+    }
+
+    pub fn set_mappings_metadata(
+        &mut self,
+        mappings_metadata: HashMap<Vec<NiaKeyChord>, NiaMappingMetadata>,
+    ) {
+        self.mappings_metadata = mappings_metadata;
+    }
+
+    // Replaces device ids of every key, e.g. ids of a file with ids of the
+    // connected devices.
+    pub fn map_device_ids<F>(
+        &self,
+        map_device_id: F,
+    ) -> NiaServerResult<NiaConfig>
+    where
+        F: Fn(i32) -> NiaServerResult<i32>,
+    {
+        let devices = self
+            .devices
+            .iter()
+            .map(|device| {
+                Ok(NiaConfigDevice::new(
+                    map_device_id(device.get_device_id())?,
+                    device.get_device_name(),
+                    device.get_device_path(),
+                ))
+            })
+            .collect::<NiaServerResult<Vec<NiaConfigDevice>>>()?;
+
+        let modifiers = self
+            .modifiers
+            .iter()
+            .map(|modifier| {
+                Ok(NiaModifierDescription::new(
+                    map_key_device_id(modifier.get_key(), &map_device_id)?,
+                    modifier.get_alias().clone(),
+                ))
+            })
+            .collect::<NiaServerResult<Vec<NiaModifierDescription>>>()?;
+
+        let mappings = map_mappings_device_ids(&self.mappings, &map_device_id)?;
+        let disabled_mappings =
+            map_mappings_device_ids(&self.disabled_mappings, &map_device_id)?;
+
+        let mappings_metadata =
+            self.mappings_metadata
+                .iter()
+                .map(|(key_chords, mapping_metadata)| {
+                    Ok((
+                        map_key_chords_device_ids(key_chords, &map_device_id)?,
+                        mapping_metadata.clone(),
+                    ))
+                })
+                .collect::<NiaServerResult<
+                    HashMap<Vec<NiaKeyChord>, NiaMappingMetadata>,
+                >>()?;
+
+        let mut config = NiaConfig::new(
+            devices,
+            modifiers,
+            self.named_actions.clone(),
+            mappings,
+        );
+
+        config.set_disabled_mappings(disabled_mappings);
+        config.set_mappings_metadata(mappings_metadata);
+
+        Ok(config)
+    }
+
+    pub fn to_string(&self) -> NiaServerResult<String> {
+        let config_file = ConfigFile {
+            devices: self
+                .devices
+                .iter()
+                .map(|device| ConfigFileDevice {
+                    id: device.get_device_id(),
+                    name: device.get_device_name().to_string(),
+                    path: device.get_device_path().to_string(),
+                })
+                .collect(),
+            modifiers: self
+                .modifiers
+                .iter()
+                .map(|modifier| ConfigFileModifier {
+                    key: format_key(modifier.get_key()),
+                    alias: modifier.get_alias().clone(),
+                })
+                .collect(),
+            actions: self
+                .named_actions
+                .iter()
+                .map(|named_action| ConfigFileAction {
+                    name: named_action.get_action_name().clone(),
+                    action: named_action.get_action().clone(),
+                })
+                .collect(),
+            mappings: self
+                .mappings
+                .iter()
+                .map(|mapping| (mapping, true))
+                .chain(
+                    self.disabled_mappings
+                        .iter()
+                        .map(|mapping| (mapping, false)),
+                )
+                .map(|(mapping, enabled)| {
+                    make_config_file_mapping(
+                        mapping,
+                        self.mappings_metadata.get(mapping.get_key_chords()),
+                        enabled,
+                    )
+                })
+                .collect(),
+        };
+
+        serde_json::to_string_pretty(&config_file).map_err(|error| {
+            NiaServerError::unknown(format!(
+                "Cannot serialize configuration: {}",
+                error
+            ))
+        })
+    }
+
+    pub fn from_str(config: &str) -> NiaServerResult<NiaConfig> {
+        let config_file: ConfigFile =
+            serde_json::from_str(config).map_err(|error| {
+                NiaServerError::deserialization_error(format!(
+                    "Invalid configuration: {}",
+                    error
+                ))
+            })?;
+
+        let devices = config_file
+            .devices
+            .into_iter()
+            .map(|device| {
+                NiaConfigDevice::new(device.id, device.name, device.path)
+            })
+            .collect::<Vec<NiaConfigDevice>>();
+
+        // Keys may refer to devices of the file by name as well as by id.
+        let resolve_device = |device_name: &str| {
+            devices
+                .iter()
+                .find(|device| device.get_device_name() == device_name)
+                .map(|device| device.get_device_id())
+        };
+
+        let modifiers = config_file
+            .modifiers
+            .into_iter()
+            .map(|modifier| {
+                let key = parse_key(&modifier.key, &resolve_device)?;
+
+                Ok(NiaModifierDescription::new(key, modifier.alias))
+            })
+            .collect::<NiaServerResult<Vec<NiaModifierDescription>>>()?;
+
+        let named_actions = config_file
+            .actions
+            .into_iter()
+            .map(|action| NiaNamedAction::new(action.action, action.name))
+            .collect::<Vec<NiaNamedAction>>();
+
+        let mut mappings = Vec::new();
+        let mut disabled_mappings = Vec::new();
+        let mut mappings_metadata = HashMap::new();
+
+        for config_file_mapping in config_file.mappings {
+            let key_chords =
+                parse_key_chords(&config_file_mapping.keys, &resolve_device)?;
+
+            if !config_file_mapping.description.is_empty()
+                || !config_file_mapping.tags.is_empty()
+            {
+                let mut mapping_metadata = NiaMappingMetadata::new(0);
+
+                mapping_metadata.set_enabled(config_file_mapping.enabled, 0);
+                mapping_metadata
+                    .set_description(config_file_mapping.description, 0);
+                mapping_metadata.set_tags(config_file_mapping.tags, 0);
+
+                mappings_metadata.insert(key_chords.clone(), mapping_metadata);
+            }
+
+            let mapping =
+                NiaMapping::new(key_chords, config_file_mapping.action);
+
+            if config_file_mapping.enabled {
+                mappings.push(mapping);
+            } else {
+                disabled_mappings.push(mapping);
+            }
+        }
+
+        let mut config =
+            NiaConfig::new(devices, modifiers, named_actions, mappings);
+
+        config.set_disabled_mappings(disabled_mappings);
+        config.set_mappings_metadata(mappings_metadata);
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::{
+        ActionExecuteOSCommand, ActionKeyClick, NiaActionEnum,
+    };
+
+    fn construct_config() -> NiaConfig {
+        let mut config = NiaConfig::new(
+            vec![NiaConfigDevice::new(1, "Keyboard", "/dev/input/event3")],
+            vec![
+                NiaModifierDescription::new(NiaKey::make_key_2(1, 29), "ctrl"),
+                NiaModifierDescription::new(NiaKey::make_key_1(56), "alt"),
+            ],
+            vec![NiaNamedAction::new(
+                NiaAction::new(NiaActionEnum::KeyClick(ActionKeyClick::new(
+                    30,
+                ))),
+                "click-a",
+            )],
+            vec![NiaMapping::new(
+                vec![
+                    NiaKeyChord::new(
+                        vec![NiaKey::make_key_2(1, 29)],
+                        NiaKey::make_key_2(1, 45),
+                    ),
+                    NiaKeyChord::new(
+                        vec![NiaKey::make_key_2(1, 29)],
+                        NiaKey::make_key_2(1, 31),
+                    ),
+                ],
+                NiaAction::new(NiaActionEnum::ExecuteOSCommand(
+                    ActionExecuteOSCommand::new("echo catgirl"),
+                )),
+            )],
+        );
+
+        let disabled_key_chords = vec![NiaKeyChord::new(
+            vec![NiaKey::make_key_1(56)],
+            NiaKey::make_key_2(1, 30),
+        )];
+        let mut mapping_metadata = NiaMappingMetadata::new(0);
+
+        mapping_metadata.set_enabled(false, 0);
+        mapping_metadata.set_description("Clicks b", 0);
+        mapping_metadata.set_tags(vec![String::from("test")], 0);
+
+        config.set_disabled_mappings(vec![NiaMapping::new(
+            disabled_key_chords.clone(),
+            NiaAction::new(NiaActionEnum::KeyClick(ActionKeyClick::new(48))),
+        )]);
+        config.set_mappings_metadata(
+            vec![(disabled_key_chords, mapping_metadata)]
+                .into_iter()
+                .collect(),
+        );
+
+        config
+    }
+
+    #[test]
+    fn converts_to_string_and_back() {
+        let expected = construct_config();
+
+        let config = expected.to_string().unwrap();
+        let result = NiaConfig::from_str(&config).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn parses_keys_referring_devices_by_name() {
+        let config = r#"{
+            "devices": [{"id": 4, "name": "kbd", "path": "/dev/input/event4"}],
+            "modifiers": [{"key": "[kbd]LEFTCTRL", "alias": "ctrl"}],
+            "mappings": [
+                {"keys": "[kbd]C-a", "action": {"KeyClick": {"key_code": 30}}}
+            ]
+        }"#;
+
+        let result = NiaConfig::from_str(config).unwrap();
+
+        assert_eq!(
+            NiaKey::make_key_2(4, 29),
+            result.get_modifiers()[0].get_key()
+        );
+        assert_eq!(
+            &vec![NiaKeyChord::new(
+                vec![NiaKey::make_key_2(4, 29)],
+                NiaKey::make_key_2(4, 30)
+            )],
+            result.get_mappings()[0].get_key_chords()
+        );
+    }
+
+    #[test]
+    fn maps_device_ids() {
+        let config = construct_config()
+            .map_device_ids(|device_id| Ok(device_id + 1))
+            .unwrap();
+
+        assert_eq!(2, config.get_devices()[0].get_device_id());
+        assert_eq!(
+            NiaKey::make_key_2(2, 29),
+            config.get_modifiers()[0].get_key()
+        );
+        assert_eq!(NiaKey::make_key_1(56), config.get_modifiers()[1].get_key());
+        assert_eq!(
+            NiaKey::make_key_2(2, 45),
+            config.get_mappings()[0].get_key_chords()[0].get_key()
+        );
+        assert_eq!(
+            NiaKey::make_key_2(2, 30),
+            config.get_disabled_mappings()[0].get_key_chords()[0].get_key()
+        );
+        assert!(config.get_mappings_metadata().contains_key(&vec![
+            NiaKeyChord::new(
+                vec![NiaKey::make_key_1(56)],
+                NiaKey::make_key_2(2, 30),
+            )
+        ]));
+    }
+
+    #[test]
+    fn parses_disabled_mappings_and_metadata() {
+        let config = r#"{
+            "mappings": [
+                {"keys": "C-a", "action": {"KeyClick": {"key_code": 30}}},
+                {
+                    "keys": "C-b",
+                    "action": {"KeyClick": {"key_code": 48}},
+                    "enabled": false,
+                    "tags": ["wm"]
+                }
+            ]
+        }"#;
+
+        let result = NiaConfig::from_str(config).unwrap();
+
+        assert_eq!(1, result.get_mappings().len());
+        assert_eq!(1, result.get_disabled_mappings().len());
+        assert_eq!(1, result.get_mappings_metadata().len());
+
+        let mapping_metadata = result
+            .get_mappings_metadata()
+            .get(result.get_disabled_mappings()[0].get_key_chords())
+            .unwrap();
+
+        assert!(!mapping_metadata.is_enabled());
+        assert!(mapping_metadata.has_tag("wm"));
+    }
+
+    #[test]
+    fn returns_error_on_invalid_config() {
+        assert!(NiaConfig::from_str("{").is_err());
+        assert!(NiaConfig::from_str(
+            r#"{"modifiers": [{"key": "C-a", "alias": "ctrl"}]}"#
+        )
+        .is_err());
+    }
+}
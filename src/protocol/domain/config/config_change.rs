@@ -0,0 +1,124 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NiaConfigChangeKind {
+    Added,
+    Changed,
+    Removed,
+}
+
+// One entry of the report of a configuration import, e.g. "mapping C-x C-s".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NiaConfigChange {
+    kind: NiaConfigChangeKind,
+    description: String,
+}
+
+impl NiaConfigChange {
+    pub fn new<S>(kind: NiaConfigChangeKind, description: S) -> NiaConfigChange
+    where
+        S: Into<String>,
+    {
+        NiaConfigChange {
+            kind,
+            description: description.into(),
+        }
+    }
+
+    pub fn added<S>(description: S) -> NiaConfigChange
+    where
+        S: Into<String>,
+    {
+        NiaConfigChange::new(NiaConfigChangeKind::Added, description)
+    }
+
+    pub fn changed<S>(description: S) -> NiaConfigChange
+    where
+        S: Into<String>,
+    {
+        NiaConfigChange::new(NiaConfigChangeKind::Changed, description)
+    }
+
+    pub fn removed<S>(description: S) -> NiaConfigChange
+    where
+        S: Into<String>,
+    {
+        NiaConfigChange::new(NiaConfigChangeKind::Removed, description)
+    }
+
+    pub fn get_kind(&self) -> NiaConfigChangeKind {
+        self.kind
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl Serializable<NiaConfigChange, nia_protocol_rust::ConfigChange>
+    for NiaConfigChange
+{
+    fn to_pb(&self) -> nia_protocol_rust::ConfigChange {
+        let mut config_change_pb = nia_protocol_rust::ConfigChange::new();
+
+        config_change_pb
+            .set_description(protobuf::Chars::from(self.description.clone()));
+
+        match self.kind {
+            NiaConfigChangeKind::Added => config_change_pb
+                .set_added(nia_protocol_rust::ConfigItemAdded::new()),
+            NiaConfigChangeKind::Changed => config_change_pb
+                .set_changed(nia_protocol_rust::ConfigItemChanged::new()),
+            NiaConfigChangeKind::Removed => config_change_pb
+                .set_removed(nia_protocol_rust::ConfigItemRemoved::new()),
+        }
+
+        config_change_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ConfigChange,
+    ) -> NiaServerResult<NiaConfigChange> {
+        let mut object_pb = object_pb;
+
+        let kind = if object_pb.has_added() {
+            NiaConfigChangeKind::Added
+        } else if object_pb.has_changed() {
+            NiaConfigChangeKind::Changed
+        } else if object_pb.has_removed() {
+            NiaConfigChangeKind::Removed
+        } else {
+            return NiaServerError::deserialization_error(
+                "Unknown configuration change.",
+            )
+            .into();
+        };
+
+        let description = object_pb.take_description().to_string();
+
+        Ok(NiaConfigChange::new(kind, description))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = vec![
+            NiaConfigChange::added("mapping C-x C-s"),
+            NiaConfigChange::changed("action save"),
+            NiaConfigChange::removed("modifier [1]LEFTCTRL"),
+        ];
+
+        for expected in expected {
+            let bytes = expected.to_bytes().unwrap();
+            let result = NiaConfigChange::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, result);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+// A device as it is referred to in a configuration file. The id is local to
+// the file, devices are matched against the connected ones by name and path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NiaConfigDevice {
+    device_id: i32,
+    device_name: String,
+    device_path: String,
+}
+
+impl NiaConfigDevice {
+    pub fn new<S, T>(
+        device_id: i32,
+        device_name: S,
+        device_path: T,
+    ) -> NiaConfigDevice
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        NiaConfigDevice {
+            device_id,
+            device_name: device_name.into(),
+            device_path: device_path.into(),
+        }
+    }
+
+    pub fn get_device_id(&self) -> i32 {
+        self.device_id
+    }
+
+    pub fn get_device_name(&self) -> &str {
+        &self.device_name
+    }
+
+    pub fn get_device_path(&self) -> &str {
+        &self.device_path
+    }
+}
@@ -0,0 +1,596 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{format_key, format_key_chords};
+use crate::protocol::{
+    NiaAction, NiaConfig, NiaConfigChangeKind, NiaMapping,
+    NiaModifierDescription, NiaNamedAction,
+};
+
+// An item that differs between two configurations. Only the new item is
+// present when it was added, only the old one when it was removed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NiaConfigDiffEntry<T> {
+    old_item: Option<T>,
+    new_item: Option<T>,
+}
+
+impl<T> NiaConfigDiffEntry<T> {
+    pub fn new(
+        old_item: Option<T>,
+        new_item: Option<T>,
+    ) -> NiaConfigDiffEntry<T> {
+        NiaConfigDiffEntry { old_item, new_item }
+    }
+
+    pub fn get_kind(&self) -> NiaConfigChangeKind {
+        match (&self.old_item, &self.new_item) {
+            (None, _) => NiaConfigChangeKind::Added,
+            (_, None) => NiaConfigChangeKind::Removed,
+            _ => NiaConfigChangeKind::Changed,
+        }
+    }
+
+    pub fn get_old_item(&self) -> Option<&T> {
+        self.old_item.as_ref()
+    }
+
+    pub fn get_new_item(&self) -> Option<&T> {
+        self.new_item.as_ref()
+    }
+}
+
+fn make_diff_entry<T>(
+    old_item: Option<T>,
+    new_item: Option<T>,
+) -> NiaServerResult<NiaConfigDiffEntry<T>> {
+    if old_item.is_none() && new_item.is_none() {
+        return NiaServerError::deserialization_error(
+            "Configuration difference has neither old nor new item.",
+        )
+        .into();
+    }
+
+    Ok(NiaConfigDiffEntry::new(old_item, new_item))
+}
+
+fn diff_items<T, K, F>(
+    old_items: &[T],
+    new_items: &[T],
+    get_identity: F,
+) -> Vec<NiaConfigDiffEntry<T>>
+where
+    T: Clone + PartialEq,
+    K: PartialEq,
+    F: Fn(&T) -> K,
+{
+    let mut entries = Vec::new();
+
+    for old_item in old_items {
+        let new_item = new_items
+            .iter()
+            .find(|new_item| get_identity(new_item) == get_identity(old_item));
+
+        match new_item {
+            None => entries
+                .push(NiaConfigDiffEntry::new(Some(old_item.clone()), None)),
+            Some(new_item) if new_item != old_item => {
+                entries.push(NiaConfigDiffEntry::new(
+                    Some(old_item.clone()),
+                    Some(new_item.clone()),
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+
+    for new_item in new_items {
+        let is_added = !old_items
+            .iter()
+            .any(|old_item| get_identity(old_item) == get_identity(new_item));
+
+        if is_added {
+            entries.push(NiaConfigDiffEntry::new(None, Some(new_item.clone())));
+        }
+    }
+
+    entries
+}
+
+// Device ids are local to a configuration, so devices of both compared
+// configurations are renumbered by their names.
+fn canonicalize_device_ids(
+    config: &NiaConfig,
+    device_names: &[String],
+) -> NiaServerResult<NiaConfig> {
+    config.map_device_ids(|device_id| {
+        config
+            .get_devices()
+            .iter()
+            .find(|device| device.get_device_id() == device_id)
+            .and_then(|device| {
+                device_names
+                    .iter()
+                    .position(|device_name| {
+                        device_name == device.get_device_name()
+                    })
+                    .map(|index| index as i32 + 1)
+            })
+            .ok_or_else(|| {
+                NiaServerError::invalid_request(format!(
+                    "Configuration refers to undeclared device: {}.",
+                    device_id
+                ))
+            })
+    })
+}
+
+fn describe_action(action: &NiaAction) -> String {
+    serde_json::to_string(action).unwrap_or_else(|_| format!("{:?}", action))
+}
+
+fn write_entries<T, F, G>(
+    lines: &mut Vec<String>,
+    title: &str,
+    entries: &[NiaConfigDiffEntry<T>],
+    describe_identity: F,
+    describe_value: G,
+) where
+    F: Fn(&T) -> String,
+    G: Fn(&T) -> String,
+{
+    if entries.is_empty() {
+        return;
+    }
+
+    lines.push(format!("{}:", title));
+
+    for entry in entries {
+        let line = match (entry.get_old_item(), entry.get_new_item()) {
+            (Some(old_item), Some(new_item)) => format!(
+                "  ~ {}: {} -> {}",
+                describe_identity(new_item),
+                describe_value(old_item),
+                describe_value(new_item)
+            ),
+            (Some(old_item), None) => format!(
+                "  - {}: {}",
+                describe_identity(old_item),
+                describe_value(old_item)
+            ),
+            (None, Some(new_item)) => format!(
+                "  + {}: {}",
+                describe_identity(new_item),
+                describe_value(new_item)
+            ),
+            (None, None) => continue,
+        };
+
+        lines.push(line);
+    }
+}
+
+// Differences between two configurations. Modifiers are matched by their
+// keys, named actions by their names and mappings by their key chords, with
+// device ids replaced by indices of device names.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NiaConfigDiff {
+    device_names: Vec<String>,
+    modifiers: Vec<NiaConfigDiffEntry<NiaModifierDescription>>,
+    named_actions: Vec<NiaConfigDiffEntry<NiaNamedAction>>,
+    mappings: Vec<NiaConfigDiffEntry<NiaMapping>>,
+}
+
+impl NiaConfigDiff {
+    pub fn new(
+        old_config: &NiaConfig,
+        new_config: &NiaConfig,
+    ) -> NiaServerResult<NiaConfigDiff> {
+        let mut device_names = old_config
+            .get_devices()
+            .iter()
+            .chain(new_config.get_devices().iter())
+            .map(|device| device.get_device_name().to_string())
+            .collect::<Vec<String>>();
+
+        device_names.sort();
+        device_names.dedup();
+
+        let old_config = canonicalize_device_ids(old_config, &device_names)?;
+        let new_config = canonicalize_device_ids(new_config, &device_names)?;
+
+        let modifiers = diff_items(
+            old_config.get_modifiers(),
+            new_config.get_modifiers(),
+            |modifier| modifier.get_key(),
+        );
+        let named_actions = diff_items(
+            old_config.get_named_actions(),
+            new_config.get_named_actions(),
+            |named_action| named_action.get_action_name().clone(),
+        );
+        let mappings = diff_items(
+            old_config.get_mappings(),
+            new_config.get_mappings(),
+            |mapping| mapping.get_key_chords().clone(),
+        );
+
+        Ok(NiaConfigDiff {
+            device_names,
+            modifiers,
+            named_actions,
+            mappings,
+        })
+    }
+
+    // Names of the devices, the device with id N is the N-th one.
+    pub fn get_device_names(&self) -> &Vec<String> {
+        &self.device_names
+    }
+
+    pub fn get_modifiers(
+        &self,
+    ) -> &Vec<NiaConfigDiffEntry<NiaModifierDescription>> {
+        &self.modifiers
+    }
+
+    pub fn get_named_actions(
+        &self,
+    ) -> &Vec<NiaConfigDiffEntry<NiaNamedAction>> {
+        &self.named_actions
+    }
+
+    pub fn get_mappings(&self) -> &Vec<NiaConfigDiffEntry<NiaMapping>> {
+        &self.mappings
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modifiers.is_empty()
+            && self.named_actions.is_empty()
+            && self.mappings.is_empty()
+    }
+
+    pub fn to_text(&self) -> String {
+        if self.is_empty() {
+            return String::from("No differences.");
+        }
+
+        let mut lines = Vec::new();
+
+        if !self.device_names.is_empty() {
+            lines.push(String::from("Devices:"));
+
+            for (index, device_name) in self.device_names.iter().enumerate() {
+                lines.push(format!("  [{}] {}", index + 1, device_name));
+            }
+        }
+
+        write_entries(
+            &mut lines,
+            "Modifiers",
+            &self.modifiers,
+            |modifier| format_key(modifier.get_key()),
+            |modifier| modifier.get_alias().clone(),
+        );
+        write_entries(
+            &mut lines,
+            "Actions",
+            &self.named_actions,
+            |named_action| named_action.get_action_name().clone(),
+            |named_action| describe_action(named_action.get_action()),
+        );
+        write_entries(
+            &mut lines,
+            "Mappings",
+            &self.mappings,
+            |mapping| format_key_chords(mapping.get_key_chords()),
+            |mapping| describe_action(mapping.get_action()),
+        );
+
+        lines.join("\n")
+    }
+}
+
+impl Serializable<NiaConfigDiff, nia_protocol_rust::ConfigDiff>
+    for NiaConfigDiff
+{
+    fn to_pb(&self) -> nia_protocol_rust::ConfigDiff {
+        let mut config_diff_pb = nia_protocol_rust::ConfigDiff::new();
+
+        config_diff_pb.set_device_names(
+            self.device_names
+                .iter()
+                .map(|device_name| protobuf::Chars::from(device_name.clone()))
+                .collect(),
+        );
+
+        config_diff_pb.set_modifiers(
+            self.modifiers
+                .iter()
+                .map(|entry| {
+                    let mut modifier_diff_pb =
+                        nia_protocol_rust::ModifierDiff::new();
+
+                    if let Some(old_modifier) = entry.get_old_item() {
+                        modifier_diff_pb.set_old_modifier(old_modifier.to_pb());
+                    }
+
+                    if let Some(new_modifier) = entry.get_new_item() {
+                        modifier_diff_pb.set_new_modifier(new_modifier.to_pb());
+                    }
+
+                    modifier_diff_pb
+                })
+                .collect(),
+        );
+
+        config_diff_pb.set_named_actions(
+            self.named_actions
+                .iter()
+                .map(|entry| {
+                    let mut named_action_diff_pb =
+                        nia_protocol_rust::NamedActionDiff::new();
+
+                    if let Some(old_named_action) = entry.get_old_item() {
+                        named_action_diff_pb
+                            .set_old_named_action(old_named_action.to_pb());
+                    }
+
+                    if let Some(new_named_action) = entry.get_new_item() {
+                        named_action_diff_pb
+                            .set_new_named_action(new_named_action.to_pb());
+                    }
+
+                    named_action_diff_pb
+                })
+                .collect(),
+        );
+
+        config_diff_pb.set_mappings(
+            self.mappings
+                .iter()
+                .map(|entry| {
+                    let mut mapping_diff_pb =
+                        nia_protocol_rust::MappingDiff::new();
+
+                    if let Some(old_mapping) = entry.get_old_item() {
+                        mapping_diff_pb.set_old_mapping(old_mapping.to_pb());
+                    }
+
+                    if let Some(new_mapping) = entry.get_new_item() {
+                        mapping_diff_pb.set_new_mapping(new_mapping.to_pb());
+                    }
+
+                    mapping_diff_pb
+                })
+                .collect(),
+        );
+
+        config_diff_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ConfigDiff,
+    ) -> NiaServerResult<NiaConfigDiff> {
+        let mut object_pb = object_pb;
+
+        let device_names = object_pb
+            .take_device_names()
+            .into_iter()
+            .map(|device_name| device_name.to_string())
+            .collect();
+
+        let modifiers = object_pb
+            .take_modifiers()
+            .into_iter()
+            .map(|mut modifier_diff_pb| {
+                let old_modifier = if modifier_diff_pb.has_old_modifier() {
+                    Some(NiaModifierDescription::from_pb(
+                        modifier_diff_pb.take_old_modifier(),
+                    )?)
+                } else {
+                    None
+                };
+                let new_modifier = if modifier_diff_pb.has_new_modifier() {
+                    Some(NiaModifierDescription::from_pb(
+                        modifier_diff_pb.take_new_modifier(),
+                    )?)
+                } else {
+                    None
+                };
+
+                make_diff_entry(old_modifier, new_modifier)
+            })
+            .collect::<NiaServerResult<Vec<_>>>()?;
+
+        let named_actions = object_pb
+            .take_named_actions()
+            .into_iter()
+            .map(|mut named_action_diff_pb| {
+                let old_named_action =
+                    if named_action_diff_pb.has_old_named_action() {
+                        Some(NiaNamedAction::from_pb(
+                            named_action_diff_pb.take_old_named_action(),
+                        )?)
+                    } else {
+                        None
+                    };
+                let new_named_action =
+                    if named_action_diff_pb.has_new_named_action() {
+                        Some(NiaNamedAction::from_pb(
+                            named_action_diff_pb.take_new_named_action(),
+                        )?)
+                    } else {
+                        None
+                    };
+
+                make_diff_entry(old_named_action, new_named_action)
+            })
+            .collect::<NiaServerResult<Vec<_>>>()?;
+
+        let mappings = object_pb
+            .take_mappings()
+            .into_iter()
+            .map(|mut mapping_diff_pb| {
+                let old_mapping = if mapping_diff_pb.has_old_mapping() {
+                    Some(NiaMapping::from_pb(
+                        mapping_diff_pb.take_old_mapping(),
+                    )?)
+                } else {
+                    None
+                };
+                let new_mapping = if mapping_diff_pb.has_new_mapping() {
+                    Some(NiaMapping::from_pb(
+                        mapping_diff_pb.take_new_mapping(),
+                    )?)
+                } else {
+                    None
+                };
+
+                make_diff_entry(old_mapping, new_mapping)
+            })
+            .collect::<NiaServerResult<Vec<_>>>()?;
+
+        Ok(NiaConfigDiff {
+            device_names,
+            modifiers,
+            named_actions,
+            mappings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::{
+        ActionExecuteOSCommand, ActionKeyClick, NiaActionEnum, NiaConfigDevice,
+        NiaKey, NiaKeyChord,
+    };
+
+    fn make_mapping(
+        device_id: i32,
+        key_code: i32,
+        command: &str,
+    ) -> NiaMapping {
+        NiaMapping::new(
+            vec![NiaKeyChord::new(
+                vec![NiaKey::make_key_2(device_id, 29)],
+                NiaKey::make_key_2(device_id, key_code),
+            )],
+            NiaAction::new(NiaActionEnum::ExecuteOSCommand(
+                ActionExecuteOSCommand::new(command),
+            )),
+        )
+    }
+
+    fn make_named_action(action_name: &str, key_code: i32) -> NiaNamedAction {
+        NiaNamedAction::new(
+            NiaAction::new(NiaActionEnum::KeyClick(ActionKeyClick::new(
+                key_code,
+            ))),
+            action_name,
+        )
+    }
+
+    fn construct_configs() -> (NiaConfig, NiaConfig) {
+        let old_config = NiaConfig::new(
+            vec![NiaConfigDevice::new(1, "kbd", "/dev/input/event3")],
+            vec![NiaModifierDescription::new(
+                NiaKey::make_key_2(1, 29),
+                "ctrl",
+            )],
+            vec![
+                make_named_action("click-a", 30),
+                make_named_action("click-b", 48),
+            ],
+            vec![make_mapping(1, 30, "echo a"), make_mapping(1, 31, "echo s")],
+        );
+        let new_config = NiaConfig::new(
+            vec![NiaConfigDevice::new(5, "kbd", "/dev/input/event7")],
+            vec![
+                NiaModifierDescription::new(NiaKey::make_key_2(5, 29), "ctrl"),
+                NiaModifierDescription::new(NiaKey::make_key_1(56), "alt"),
+            ],
+            vec![make_named_action("click-a", 31)],
+            vec![make_mapping(5, 30, "echo a"), make_mapping(5, 31, "echo d")],
+        );
+
+        (old_config, new_config)
+    }
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let (old_config, new_config) = construct_configs();
+        let expected = NiaConfigDiff::new(&old_config, &new_config).unwrap();
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaConfigDiff::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn matches_items_of_devices_with_same_names() {
+        let (old_config, new_config) = construct_configs();
+        let config_diff = NiaConfigDiff::new(&old_config, &new_config).unwrap();
+
+        assert_eq!(&vec![String::from("kbd")], config_diff.get_device_names());
+
+        assert_eq!(1, config_diff.get_modifiers().len());
+        assert_eq!(
+            NiaConfigChangeKind::Added,
+            config_diff.get_modifiers()[0].get_kind()
+        );
+
+        let named_action_kinds = config_diff
+            .get_named_actions()
+            .iter()
+            .map(|entry| entry.get_kind())
+            .collect::<Vec<NiaConfigChangeKind>>();
+        assert_eq!(
+            vec![NiaConfigChangeKind::Changed, NiaConfigChangeKind::Removed],
+            named_action_kinds
+        );
+
+        assert_eq!(1, config_diff.get_mappings().len());
+        assert_eq!(
+            Some(&make_mapping(1, 31, "echo d")),
+            config_diff.get_mappings()[0].get_new_item()
+        );
+    }
+
+    #[test]
+    fn renders_text() {
+        let (old_config, _) = construct_configs();
+        let config_diff = NiaConfigDiff::new(&old_config, &old_config).unwrap();
+
+        assert_eq!("No differences.", config_diff.to_text());
+
+        let new_config = NiaConfig::new(
+            old_config.get_devices().clone(),
+            vec![],
+            old_config.get_named_actions().clone(),
+            old_config.get_mappings().clone(),
+        );
+        let config_diff = NiaConfigDiff::new(&old_config, &new_config).unwrap();
+
+        assert_eq!(
+            "Devices:\n  [1] kbd\nModifiers:\n  - [1]LEFTCTRL: ctrl",
+            config_diff.to_text()
+        );
+    }
+
+    #[test]
+    fn returns_error_on_undeclared_devices() {
+        let (old_config, new_config) = construct_configs();
+        let old_config = NiaConfig::new(
+            vec![],
+            vec![],
+            vec![],
+            old_config.get_mappings().clone(),
+        );
+
+        assert!(NiaConfigDiff::new(&old_config, &new_config).is_err());
+    }
+}
@@ -0,0 +1,8 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NiaConfigImportMode {
+    // Defines what the configuration has and keeps everything else.
+    Merge,
+    // Additionally removes modifiers, actions and mappings the configuration
+    // does not have.
+    Replace,
+}
@@ -0,0 +1,7 @@
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NiaConfigSource {
+    // What is defined in the server now.
+    Live,
+    // A configuration in the file format.
+    Text(String),
+}
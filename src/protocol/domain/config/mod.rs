@@ -0,0 +1,13 @@
+mod config;
+mod config_change;
+mod config_device;
+mod config_diff;
+mod config_import_mode;
+mod config_source;
+
+pub use config::*;
+pub use config_change::*;
+pub use config_device::*;
+pub use config_diff::*;
+pub use config_import_mode::*;
+pub use config_source::*;
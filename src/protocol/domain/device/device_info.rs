@@ -53,6 +53,10 @@ impl DeviceInfo {
     pub fn get_device_model(&self) -> &DeviceModel {
         &self.device_model
     }
+
+    pub fn set_device_model(&mut self, device_model: DeviceModel) {
+        self.device_model = device_model;
+    }
 }
 
 impl Serializable<DeviceInfo, nia_protocol_rust::DeviceInfo> for DeviceInfo {
@@ -30,8 +30,11 @@ impl DeviceModel {
         let integers: Vec<i32> = string
             .lines()
             .flat_map(|line| line.split_whitespace())
-            .map(|part| part.parse().expect("Cannot convert an integer"))
-            .collect();
+            .map(|part| part.parse::<i32>())
+            .collect::<Result<Vec<i32>, _>>()
+            .map_err(|_| {
+                NiaServerError::deserialization_error("Invalid kbm file")
+            })?;
 
         let mut iter = integers.into_iter().peekable();
 
@@ -155,4 +158,11 @@ mod tests {
 
         assert_eq!(expected_keyboard_model, actual_keyboard_model)
     }
+
+    #[test]
+    fn returns_error_when_kbm_file_contains_not_an_integer() {
+        let result = DeviceModel::from_string("100 200\n1 2 3 4 q");
+
+        assert!(result.is_err());
+    }
 }
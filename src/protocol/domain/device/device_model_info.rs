@@ -0,0 +1,155 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{DeviceModel, Serializable};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NiaDeviceModelSource {
+    Builtin,
+    User(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NiaDeviceModelInfo {
+    model_name: String,
+    source: NiaDeviceModelSource,
+    device_model: DeviceModel,
+}
+
+impl NiaDeviceModelInfo {
+    pub fn new<S>(
+        model_name: S,
+        source: NiaDeviceModelSource,
+        device_model: DeviceModel,
+    ) -> NiaDeviceModelInfo
+    where
+        S: Into<String>,
+    {
+        NiaDeviceModelInfo {
+            model_name: model_name.into(),
+            source,
+            device_model,
+        }
+    }
+
+    pub fn get_model_name(&self) -> &str {
+        &self.model_name
+    }
+
+    pub fn get_source(&self) -> &NiaDeviceModelSource {
+        &self.source
+    }
+
+    pub fn get_device_model(&self) -> &DeviceModel {
+        &self.device_model
+    }
+
+    pub fn is_model_of(&self, device_name: &str) -> bool {
+        device_name.contains(&self.model_name)
+    }
+}
+
+impl Serializable<NiaDeviceModelInfo, nia_protocol_rust::DeviceModelInfo>
+    for NiaDeviceModelInfo
+{
+    fn to_pb(&self) -> nia_protocol_rust::DeviceModelInfo {
+        let mut device_model_info_pb =
+            nia_protocol_rust::DeviceModelInfo::new();
+
+        device_model_info_pb
+            .set_model_name(protobuf::Chars::from(self.model_name.clone()));
+        device_model_info_pb.set_device_model(self.device_model.to_pb());
+
+        match &self.source {
+            NiaDeviceModelSource::Builtin => {
+                let builtin_source_pb =
+                    nia_protocol_rust::BuiltinDeviceModelSource::new();
+
+                device_model_info_pb.set_builtin_source(builtin_source_pb);
+            }
+            NiaDeviceModelSource::User(path) => {
+                let mut user_source_pb =
+                    nia_protocol_rust::UserDeviceModelSource::new();
+
+                user_source_pb.set_path(protobuf::Chars::from(path.clone()));
+
+                device_model_info_pb.set_user_source(user_source_pb);
+            }
+        }
+
+        device_model_info_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::DeviceModelInfo,
+    ) -> NiaServerResult<NiaDeviceModelInfo> {
+        let mut object_pb = object_pb;
+
+        let model_name = object_pb.take_model_name().to_string();
+        let device_model = DeviceModel::from_pb(object_pb.take_device_model())?;
+
+        let source = if object_pb.has_builtin_source() {
+            NiaDeviceModelSource::Builtin
+        } else if object_pb.has_user_source() {
+            let path = object_pb.take_user_source().take_path().to_string();
+
+            NiaDeviceModelSource::User(path)
+        } else {
+            return NiaServerError::deserialization_error(
+                "Provided neither a builtin nor user source.",
+            )
+            .into();
+        };
+
+        let device_model_info =
+            NiaDeviceModelInfo::new(model_name, source, device_model);
+
+        Ok(device_model_info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::KeyDescription;
+
+    fn construct_device_model() -> DeviceModel {
+        DeviceModel::new(
+            vec![
+                KeyDescription::new(1, 2, 3, 4, 5),
+                KeyDescription::new(3, 2, 4, 1, 2),
+            ],
+            100,
+            200,
+        )
+    }
+
+    #[test]
+    fn serializes_and_deserializes_builtin() {
+        let expected = NiaDeviceModelInfo::new(
+            "Logitech",
+            NiaDeviceModelSource::Builtin,
+            construct_device_model(),
+        );
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaDeviceModelInfo::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn serializes_and_deserializes_user() {
+        let expected = NiaDeviceModelInfo::new(
+            "Logitech",
+            NiaDeviceModelSource::User(String::from(
+                "/home/user/.config/nia/keyboard_models/Logitech.kbm",
+            )),
+            construct_device_model(),
+        );
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaDeviceModelInfo::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
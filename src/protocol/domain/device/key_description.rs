@@ -1,5 +1,6 @@
 use crate::error::NiaServerResult;
 use crate::protocol::Serializable;
+use crate::protocol::{get_key_name, resolve_key_code};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct KeyDescription {
@@ -60,6 +61,10 @@ impl Serializable<KeyDescription, nia_protocol_rust::KeyDescription>
         key_description_pb.set_height(self.height);
         key_description_pb.set_key_code(self.key_code);
 
+        if let Some(key_name) = get_key_name(self.key_code) {
+            key_description_pb.set_key_name(protobuf::Chars::from(key_name));
+        }
+
         key_description_pb
     }
 
@@ -72,7 +77,10 @@ impl Serializable<KeyDescription, nia_protocol_rust::KeyDescription>
         let y = key_description_pb.get_y();
         let width = key_description_pb.get_width();
         let height = key_description_pb.get_height();
-        let key_code = key_description_pb.get_key_code();
+        let key_code = resolve_key_code(
+            key_description_pb.get_key_code(),
+            key_description_pb.get_key_name(),
+        )?;
 
         let key_description =
             KeyDescription::new(x, y, width, height, key_code);
@@ -1,9 +1,11 @@
 mod default_device_model;
 mod device_info;
 mod device_model;
+mod device_model_info;
 mod key_description;
 
 pub use default_device_model::*;
 pub use device_info::*;
 pub use device_model::*;
+pub use device_model_info::*;
 pub use key_description::*;
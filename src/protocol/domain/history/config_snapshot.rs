@@ -0,0 +1,262 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::protocol::{
+    NiaDefinitionChange, NiaKey, NiaKeyChord, NiaMapping,
+    NiaModifierDescription, NiaNamedAction,
+};
+
+fn index_mappings(
+    mappings: &[NiaMapping],
+) -> BTreeMap<&[NiaKeyChord], &NiaMapping> {
+    mappings
+        .iter()
+        .map(|mapping| (mapping.get_key_chords().as_slice(), mapping))
+        .collect()
+}
+
+fn index_modifiers(
+    modifiers: &[NiaModifierDescription],
+) -> BTreeMap<NiaKey, &NiaModifierDescription> {
+    modifiers
+        .iter()
+        .map(|modifier| (modifier.get_key(), modifier))
+        .collect()
+}
+
+fn index_named_actions(
+    named_actions: &[NiaNamedAction],
+) -> BTreeMap<&str, &NiaNamedAction> {
+    named_actions
+        .iter()
+        .map(|named_action| {
+            (named_action.get_action_name().as_str(), named_action)
+        })
+        .collect()
+}
+
+// What is defined at some moment. Comparing the snapshots taken before and
+// after a request gives the changes the request made.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NiaConfigSnapshot {
+    device_ids: Vec<i32>,
+    modifiers: Vec<NiaModifierDescription>,
+    named_actions: Vec<NiaNamedAction>,
+    mappings: Vec<NiaMapping>,
+}
+
+impl NiaConfigSnapshot {
+    pub fn new(
+        device_ids: Vec<i32>,
+        modifiers: Vec<NiaModifierDescription>,
+        named_actions: Vec<NiaNamedAction>,
+        mappings: Vec<NiaMapping>,
+    ) -> NiaConfigSnapshot {
+        NiaConfigSnapshot {
+            device_ids,
+            modifiers,
+            named_actions,
+            mappings,
+        }
+    }
+
+    pub fn get_device_ids(&self) -> &Vec<i32> {
+        &self.device_ids
+    }
+
+    pub fn get_modifiers(&self) -> &Vec<NiaModifierDescription> {
+        &self.modifiers
+    }
+
+    pub fn get_named_actions(&self) -> &Vec<NiaNamedAction> {
+        &self.named_actions
+    }
+
+    pub fn get_mappings(&self) -> &Vec<NiaMapping> {
+        &self.mappings
+    }
+
+    // Changes that turn this snapshot into the `other` one, ordered so that
+    // nothing is removed while a definition still refers to it and nothing
+    // is defined before what it refers to: removed mappings go first, then
+    // redefined modifiers and actions, new devices, modifiers and actions,
+    // changed and new mappings, and at last removed actions, modifiers and
+    // devices.
+    pub fn diff(&self, other: &NiaConfigSnapshot) -> Vec<NiaDefinitionChange> {
+        let device_ids = self.device_ids.iter().collect::<BTreeSet<&i32>>();
+        let other_device_ids =
+            other.device_ids.iter().collect::<BTreeSet<&i32>>();
+        let modifiers = index_modifiers(&self.modifiers);
+        let other_modifiers = index_modifiers(&other.modifiers);
+        let named_actions = index_named_actions(&self.named_actions);
+        let other_named_actions = index_named_actions(&other.named_actions);
+        let mappings = index_mappings(&self.mappings);
+        let other_mappings = index_mappings(&other.mappings);
+
+        let mut changes = Vec::new();
+
+        for mapping in &self.mappings {
+            if !other_mappings.contains_key(mapping.get_key_chords().as_slice())
+            {
+                changes
+                    .push(NiaDefinitionChange::RemoveMapping(mapping.clone()));
+            }
+        }
+
+        for modifier in &self.modifiers {
+            match other_modifiers.get(&modifier.get_key()) {
+                Some(other_modifier) if *other_modifier != modifier => {
+                    changes.push(NiaDefinitionChange::RemoveModifier(
+                        modifier.clone(),
+                    ));
+                    changes.push(NiaDefinitionChange::DefineModifier(
+                        (*other_modifier).clone(),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        for named_action in &self.named_actions {
+            match other_named_actions
+                .get(named_action.get_action_name().as_str())
+            {
+                Some(other_action) if *other_action != named_action => {
+                    changes.push(NiaDefinitionChange::RemoveAction(
+                        named_action.clone(),
+                    ));
+                    changes.push(NiaDefinitionChange::DefineAction(
+                        (*other_action).clone(),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        for device_id in &other.device_ids {
+            if !device_ids.contains(device_id) {
+                changes.push(NiaDefinitionChange::DefineDevice(*device_id));
+            }
+        }
+
+        for modifier in &other.modifiers {
+            if !modifiers.contains_key(&modifier.get_key()) {
+                changes.push(NiaDefinitionChange::DefineModifier(
+                    modifier.clone(),
+                ));
+            }
+        }
+
+        for named_action in &other.named_actions {
+            if !named_actions
+                .contains_key(named_action.get_action_name().as_str())
+            {
+                changes.push(NiaDefinitionChange::DefineAction(
+                    named_action.clone(),
+                ));
+            }
+        }
+
+        for mapping in &self.mappings {
+            match other_mappings.get(mapping.get_key_chords().as_slice()) {
+                Some(other_mapping) if *other_mapping != mapping => changes
+                    .push(NiaDefinitionChange::ChangeMapping {
+                        old_mapping: mapping.clone(),
+                        new_mapping: (*other_mapping).clone(),
+                    }),
+                _ => {}
+            }
+        }
+
+        for mapping in &other.mappings {
+            if !mappings.contains_key(mapping.get_key_chords().as_slice()) {
+                changes
+                    .push(NiaDefinitionChange::DefineMapping(mapping.clone()));
+            }
+        }
+
+        for named_action in &self.named_actions {
+            if !other_named_actions
+                .contains_key(named_action.get_action_name().as_str())
+            {
+                changes.push(NiaDefinitionChange::RemoveAction(
+                    named_action.clone(),
+                ));
+            }
+        }
+
+        for modifier in &self.modifiers {
+            if !other_modifiers.contains_key(&modifier.get_key()) {
+                changes.push(NiaDefinitionChange::RemoveModifier(
+                    modifier.clone(),
+                ));
+            }
+        }
+
+        for device_id in &self.device_ids {
+            if !other_device_ids.contains(device_id) {
+                changes.push(NiaDefinitionChange::RemoveDevice(*device_id));
+            }
+        }
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::{ActionKeyClick, NiaAction};
+
+    fn make_mapping(key: i32, key_code: i32) -> NiaMapping {
+        NiaMapping::new(
+            vec![NiaKeyChord::new(
+                vec![NiaKey::make_key_1(29)],
+                NiaKey::make_key_1(key),
+            )],
+            NiaAction::new(ActionKeyClick::new(key_code).into()),
+        )
+    }
+
+    #[test]
+    fn finds_changes_between_snapshots() {
+        let ctrl = NiaModifierDescription::new(NiaKey::make_key_1(29), "ctrl");
+        let alt = NiaModifierDescription::new(NiaKey::make_key_1(56), "alt");
+        let meta = NiaModifierDescription::new(NiaKey::make_key_1(125), "meta");
+        let super_key =
+            NiaModifierDescription::new(NiaKey::make_key_1(125), "super");
+        let shift =
+            NiaModifierDescription::new(NiaKey::make_key_1(42), "shift");
+
+        let before = NiaConfigSnapshot::new(
+            vec![1, 3],
+            vec![ctrl.clone(), meta.clone(), shift.clone()],
+            vec![],
+            vec![make_mapping(30, 31), make_mapping(32, 33)],
+        );
+        let after = NiaConfigSnapshot::new(
+            vec![1, 2],
+            vec![ctrl, super_key.clone(), alt.clone()],
+            vec![],
+            vec![make_mapping(30, 34), make_mapping(35, 36)],
+        );
+
+        let expected = vec![
+            NiaDefinitionChange::RemoveMapping(make_mapping(32, 33)),
+            NiaDefinitionChange::RemoveModifier(meta),
+            NiaDefinitionChange::DefineModifier(super_key),
+            NiaDefinitionChange::DefineDevice(2),
+            NiaDefinitionChange::DefineModifier(alt),
+            NiaDefinitionChange::ChangeMapping {
+                old_mapping: make_mapping(30, 31),
+                new_mapping: make_mapping(30, 34),
+            },
+            NiaDefinitionChange::DefineMapping(make_mapping(35, 36)),
+            NiaDefinitionChange::RemoveModifier(shift),
+            NiaDefinitionChange::RemoveDevice(3),
+        ];
+
+        assert_eq!(expected, before.diff(&after));
+        assert!(before.diff(&before).is_empty());
+    }
+}
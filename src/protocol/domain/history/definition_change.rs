@@ -0,0 +1,232 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{NiaMapping, NiaModifierDescription, NiaNamedAction};
+
+// A single change of what is defined. Removals keep the removed definition,
+// so that every change can be inverted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NiaDefinitionChange {
+    DefineDevice(i32),
+    RemoveDevice(i32),
+    DefineModifier(NiaModifierDescription),
+    RemoveModifier(NiaModifierDescription),
+    DefineAction(NiaNamedAction),
+    RemoveAction(NiaNamedAction),
+    DefineMapping(NiaMapping),
+    RemoveMapping(NiaMapping),
+    ChangeMapping {
+        old_mapping: NiaMapping,
+        new_mapping: NiaMapping,
+    },
+}
+
+impl NiaDefinitionChange {
+    pub fn invert(&self) -> NiaDefinitionChange {
+        match self {
+            NiaDefinitionChange::DefineDevice(device_id) => {
+                NiaDefinitionChange::RemoveDevice(*device_id)
+            }
+            NiaDefinitionChange::RemoveDevice(device_id) => {
+                NiaDefinitionChange::DefineDevice(*device_id)
+            }
+            NiaDefinitionChange::DefineModifier(modifier) => {
+                NiaDefinitionChange::RemoveModifier(modifier.clone())
+            }
+            NiaDefinitionChange::RemoveModifier(modifier) => {
+                NiaDefinitionChange::DefineModifier(modifier.clone())
+            }
+            NiaDefinitionChange::DefineAction(named_action) => {
+                NiaDefinitionChange::RemoveAction(named_action.clone())
+            }
+            NiaDefinitionChange::RemoveAction(named_action) => {
+                NiaDefinitionChange::DefineAction(named_action.clone())
+            }
+            NiaDefinitionChange::DefineMapping(mapping) => {
+                NiaDefinitionChange::RemoveMapping(mapping.clone())
+            }
+            NiaDefinitionChange::RemoveMapping(mapping) => {
+                NiaDefinitionChange::DefineMapping(mapping.clone())
+            }
+            NiaDefinitionChange::ChangeMapping {
+                old_mapping,
+                new_mapping,
+            } => NiaDefinitionChange::ChangeMapping {
+                old_mapping: new_mapping.clone(),
+                new_mapping: old_mapping.clone(),
+            },
+        }
+    }
+}
+
+impl Serializable<NiaDefinitionChange, nia_protocol_rust::DefinitionChange>
+    for NiaDefinitionChange
+{
+    fn to_pb(&self) -> nia_protocol_rust::DefinitionChange {
+        let mut definition_change_pb =
+            nia_protocol_rust::DefinitionChange::new();
+
+        match self {
+            NiaDefinitionChange::DefineDevice(device_id) => {
+                definition_change_pb.set_define_device(*device_id)
+            }
+            NiaDefinitionChange::RemoveDevice(device_id) => {
+                definition_change_pb.set_remove_device(*device_id)
+            }
+            NiaDefinitionChange::DefineModifier(modifier) => {
+                definition_change_pb.set_define_modifier(modifier.to_pb())
+            }
+            NiaDefinitionChange::RemoveModifier(modifier) => {
+                definition_change_pb.set_remove_modifier(modifier.to_pb())
+            }
+            NiaDefinitionChange::DefineAction(named_action) => {
+                definition_change_pb.set_define_action(named_action.to_pb())
+            }
+            NiaDefinitionChange::RemoveAction(named_action) => {
+                definition_change_pb.set_remove_action(named_action.to_pb())
+            }
+            NiaDefinitionChange::DefineMapping(mapping) => {
+                definition_change_pb.set_define_mapping(mapping.to_pb())
+            }
+            NiaDefinitionChange::RemoveMapping(mapping) => {
+                definition_change_pb.set_remove_mapping(mapping.to_pb())
+            }
+            NiaDefinitionChange::ChangeMapping {
+                old_mapping,
+                new_mapping,
+            } => {
+                let mut mapping_change_pb =
+                    nia_protocol_rust::MappingChange::new();
+
+                mapping_change_pb.set_old_mapping(old_mapping.to_pb());
+                mapping_change_pb.set_new_mapping(new_mapping.to_pb());
+
+                definition_change_pb.set_change_mapping(mapping_change_pb)
+            }
+        }
+
+        definition_change_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::DefinitionChange,
+    ) -> NiaServerResult<NiaDefinitionChange> {
+        let mut object_pb = object_pb;
+
+        let definition_change = if object_pb.has_define_device() {
+            NiaDefinitionChange::DefineDevice(object_pb.get_define_device())
+        } else if object_pb.has_remove_device() {
+            NiaDefinitionChange::RemoveDevice(object_pb.get_remove_device())
+        } else if object_pb.has_define_modifier() {
+            NiaDefinitionChange::DefineModifier(
+                NiaModifierDescription::from_pb(
+                    object_pb.take_define_modifier(),
+                )?,
+            )
+        } else if object_pb.has_remove_modifier() {
+            NiaDefinitionChange::RemoveModifier(
+                NiaModifierDescription::from_pb(
+                    object_pb.take_remove_modifier(),
+                )?,
+            )
+        } else if object_pb.has_define_action() {
+            NiaDefinitionChange::DefineAction(NiaNamedAction::from_pb(
+                object_pb.take_define_action(),
+            )?)
+        } else if object_pb.has_remove_action() {
+            NiaDefinitionChange::RemoveAction(NiaNamedAction::from_pb(
+                object_pb.take_remove_action(),
+            )?)
+        } else if object_pb.has_define_mapping() {
+            NiaDefinitionChange::DefineMapping(NiaMapping::from_pb(
+                object_pb.take_define_mapping(),
+            )?)
+        } else if object_pb.has_remove_mapping() {
+            NiaDefinitionChange::RemoveMapping(NiaMapping::from_pb(
+                object_pb.take_remove_mapping(),
+            )?)
+        } else if object_pb.has_change_mapping() {
+            let mut mapping_change_pb = object_pb.take_change_mapping();
+
+            NiaDefinitionChange::ChangeMapping {
+                old_mapping: NiaMapping::from_pb(
+                    mapping_change_pb.take_old_mapping(),
+                )?,
+                new_mapping: NiaMapping::from_pb(
+                    mapping_change_pb.take_new_mapping(),
+                )?,
+            }
+        } else {
+            return NiaServerError::deserialization_error(
+                "Unknown definition change.",
+            )
+            .into();
+        };
+
+        Ok(definition_change)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::{ActionKeyClick, NiaAction, NiaKey, NiaKeyChord};
+
+    fn make_mapping(key_code: i32) -> NiaMapping {
+        NiaMapping::new(
+            vec![NiaKeyChord::new(
+                vec![NiaKey::make_key_2(1, 29)],
+                NiaKey::make_key_2(1, 30),
+            )],
+            NiaAction::new(ActionKeyClick::new(key_code).into()),
+        )
+    }
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let definition_changes = vec![
+            NiaDefinitionChange::DefineDevice(1),
+            NiaDefinitionChange::RemoveModifier(NiaModifierDescription::new(
+                NiaKey::make_key_1(29),
+                "ctrl",
+            )),
+            NiaDefinitionChange::DefineAction(NiaNamedAction::new(
+                NiaAction::new(ActionKeyClick::new(30).into()),
+                "click-a",
+            )),
+            NiaDefinitionChange::RemoveMapping(make_mapping(30)),
+            NiaDefinitionChange::ChangeMapping {
+                old_mapping: make_mapping(30),
+                new_mapping: make_mapping(31),
+            },
+        ];
+
+        for expected in definition_changes {
+            let bytes = expected.to_bytes().unwrap();
+            let result = NiaDefinitionChange::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, result);
+        }
+    }
+
+    #[test]
+    fn inverts_changes() {
+        let definition_change = NiaDefinitionChange::ChangeMapping {
+            old_mapping: make_mapping(30),
+            new_mapping: make_mapping(31),
+        };
+
+        assert_eq!(
+            NiaDefinitionChange::ChangeMapping {
+                old_mapping: make_mapping(31),
+                new_mapping: make_mapping(30),
+            },
+            definition_change.invert()
+        );
+        assert_eq!(definition_change, definition_change.invert().invert());
+        assert_eq!(
+            NiaDefinitionChange::RemoveDevice(2),
+            NiaDefinitionChange::DefineDevice(2).invert()
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::VecDeque;
+
+use crate::protocol::NiaHistoryEntry;
+
+pub const DEFAULT_HISTORY_CAPACITY: usize = 100;
+
+// Entries that can be undone and the undone ones that can be redone. Only
+// the `capacity` latest entries are kept, and recording a new entry drops
+// everything that could be redone.
+#[derive(Clone, Debug)]
+pub struct NiaHistory {
+    capacity: usize,
+    undo_entries: VecDeque<NiaHistoryEntry>,
+    redo_entries: Vec<NiaHistoryEntry>,
+}
+
+impl NiaHistory {
+    pub fn new(capacity: usize) -> NiaHistory {
+        NiaHistory {
+            capacity,
+            undo_entries: VecDeque::new(),
+            redo_entries: Vec::new(),
+        }
+    }
+
+    // Oldest entries go first.
+    pub fn get_undo_entries(&self) -> Vec<&NiaHistoryEntry> {
+        self.undo_entries.iter().collect()
+    }
+
+    // The entry to be redone next goes first.
+    pub fn get_redo_entries(&self) -> Vec<&NiaHistoryEntry> {
+        self.redo_entries.iter().rev().collect()
+    }
+
+    fn push_undo_entry(&mut self, history_entry: NiaHistoryEntry) {
+        self.undo_entries.push_back(history_entry);
+
+        while self.undo_entries.len() > self.capacity {
+            self.undo_entries.pop_front();
+        }
+    }
+
+    pub fn record(&mut self, history_entry: NiaHistoryEntry) {
+        self.redo_entries.clear();
+        self.push_undo_entry(history_entry);
+    }
+
+    pub fn clear(&mut self) {
+        self.undo_entries.clear();
+        self.redo_entries.clear();
+    }
+
+    pub fn take_undo_entry(&mut self) -> Option<NiaHistoryEntry> {
+        self.undo_entries.pop_back()
+    }
+
+    pub fn take_redo_entry(&mut self) -> Option<NiaHistoryEntry> {
+        self.redo_entries.pop()
+    }
+
+    pub fn mark_undone(&mut self, history_entry: NiaHistoryEntry) {
+        self.redo_entries.push(history_entry);
+    }
+
+    pub fn mark_redone(&mut self, history_entry: NiaHistoryEntry) {
+        self.push_undo_entry(history_entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn make_entry(timestamp: u64) -> NiaHistoryEntry {
+        NiaHistoryEntry::new("DefineDevice", timestamp, vec![])
+    }
+
+    #[test]
+    fn keeps_latest_entries() {
+        let mut history = NiaHistory::new(2);
+
+        history.record(make_entry(1));
+        history.record(make_entry(2));
+        history.record(make_entry(3));
+
+        assert_eq!(
+            vec![&make_entry(2), &make_entry(3)],
+            history.get_undo_entries()
+        );
+    }
+
+    #[test]
+    fn recording_drops_redo_entries() {
+        let mut history = NiaHistory::new(10);
+
+        history.record(make_entry(1));
+        history.record(make_entry(2));
+
+        let history_entry = history.take_undo_entry().unwrap();
+        history.mark_undone(history_entry);
+
+        assert_eq!(vec![&make_entry(2)], history.get_redo_entries());
+
+        history.record(make_entry(3));
+
+        assert!(history.get_redo_entries().is_empty());
+        assert_eq!(
+            vec![&make_entry(1), &make_entry(3)],
+            history.get_undo_entries()
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use crate::error::NiaServerResult;
+use crate::protocol::{NiaDefinitionChange, Serializable};
+
+// Changes made by a single request. Timestamps are milliseconds since the
+// Unix epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NiaHistoryEntry {
+    description: String,
+    timestamp: u64,
+    changes: Vec<NiaDefinitionChange>,
+}
+
+impl NiaHistoryEntry {
+    pub fn new<S>(
+        description: S,
+        timestamp: u64,
+        changes: Vec<NiaDefinitionChange>,
+    ) -> NiaHistoryEntry
+    where
+        S: Into<String>,
+    {
+        NiaHistoryEntry {
+            description: description.into(),
+            timestamp,
+            changes,
+        }
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn get_timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn get_changes(&self) -> &Vec<NiaDefinitionChange> {
+        &self.changes
+    }
+
+    // Changes that undo the entry, in the order they are to be applied.
+    pub fn get_inverse_changes(&self) -> Vec<NiaDefinitionChange> {
+        self.changes
+            .iter()
+            .rev()
+            .map(|change| change.invert())
+            .collect()
+    }
+}
+
+impl Serializable<NiaHistoryEntry, nia_protocol_rust::HistoryEntry>
+    for NiaHistoryEntry
+{
+    fn to_pb(&self) -> nia_protocol_rust::HistoryEntry {
+        let mut history_entry_pb = nia_protocol_rust::HistoryEntry::new();
+
+        history_entry_pb
+            .set_description(protobuf::Chars::from(self.description.clone()));
+        history_entry_pb.set_timestamp(self.timestamp);
+        history_entry_pb.set_changes(
+            self.changes.iter().map(|change| change.to_pb()).collect(),
+        );
+
+        history_entry_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::HistoryEntry,
+    ) -> NiaServerResult<NiaHistoryEntry> {
+        let mut object_pb = object_pb;
+
+        let changes = object_pb
+            .take_changes()
+            .into_iter()
+            .map(|change_pb| NiaDefinitionChange::from_pb(change_pb))
+            .collect::<NiaServerResult<Vec<NiaDefinitionChange>>>()?;
+
+        let history_entry = NiaHistoryEntry::new(
+            object_pb.take_description().to_string(),
+            object_pb.get_timestamp(),
+            changes,
+        );
+
+        Ok(history_entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::{NiaKey, NiaModifierDescription};
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaHistoryEntry::new(
+            "RemoveModifier",
+            100,
+            vec![NiaDefinitionChange::RemoveModifier(
+                NiaModifierDescription::new(NiaKey::make_key_1(29), "ctrl"),
+            )],
+        );
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaHistoryEntry::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn inverts_changes_in_reverse_order() {
+        let history_entry = NiaHistoryEntry::new(
+            "DefineDevice",
+            100,
+            vec![
+                NiaDefinitionChange::DefineDevice(1),
+                NiaDefinitionChange::RemoveDevice(2),
+            ],
+        );
+
+        assert_eq!(
+            vec![
+                NiaDefinitionChange::DefineDevice(2),
+                NiaDefinitionChange::RemoveDevice(1),
+            ],
+            history_entry.get_inverse_changes()
+        );
+    }
+}
@@ -0,0 +1,9 @@
+mod config_snapshot;
+mod definition_change;
+mod history;
+mod history_entry;
+
+pub use config_snapshot::*;
+pub use definition_change::*;
+pub use history::*;
+pub use history_entry::*;
@@ -0,0 +1,77 @@
+use crate::error::NiaServerResult;
+use crate::protocol::{NiaMapping, Serializable};
+
+// A mapping that fired at the given time of a key event simulation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NiaFiredMapping {
+    time: u64,
+    mapping: NiaMapping,
+}
+
+impl NiaFiredMapping {
+    pub fn new(time: u64, mapping: NiaMapping) -> NiaFiredMapping {
+        NiaFiredMapping { time, mapping }
+    }
+
+    pub fn get_time(&self) -> u64 {
+        self.time
+    }
+
+    pub fn get_mapping(&self) -> &NiaMapping {
+        &self.mapping
+    }
+}
+
+impl Serializable<NiaFiredMapping, nia_protocol_rust::FiredMapping>
+    for NiaFiredMapping
+{
+    fn to_pb(&self) -> nia_protocol_rust::FiredMapping {
+        let mut fired_mapping_pb = nia_protocol_rust::FiredMapping::new();
+
+        fired_mapping_pb.set_time(self.time);
+        fired_mapping_pb.set_mapping(self.mapping.to_pb());
+
+        fired_mapping_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::FiredMapping,
+    ) -> NiaServerResult<NiaFiredMapping> {
+        let mut object_pb = object_pb;
+
+        let time = object_pb.get_time();
+        let mapping = NiaMapping::from_pb(object_pb.take_mapping())?;
+
+        Ok(NiaFiredMapping::new(time, mapping))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::{
+        ActionExecuteOSCommand, NiaAction, NiaActionEnum, NiaKey, NiaKeyChord,
+    };
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaFiredMapping::new(
+            150,
+            NiaMapping::new(
+                vec![NiaKeyChord::new(
+                    vec![NiaKey::make_key_2(1, 29)],
+                    NiaKey::make_key_2(1, 30),
+                )],
+                NiaAction::new(NiaActionEnum::ExecuteOSCommand(
+                    ActionExecuteOSCommand::new("echo catgirl"),
+                )),
+            ),
+        );
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaFiredMapping::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -1,10 +1,11 @@
 use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{get_key_name, resolve_key_code};
 use crate::protocol::{NiaConvertable, Serializable};
 use nia_interpreter_core::Key;
 use nia_protocol_rust::Key_oneof_key::key_2;
 use std::fs::read;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum NiaKey {
     Key1(i32),
     Key2(i32, i32),
@@ -32,6 +33,17 @@ impl NiaKey {
             NiaKey::Key2(_, key_code) => *key_code,
         }
     }
+
+    // A key without a device stands for the key of any device.
+    pub fn matches(&self, other: NiaKey) -> bool {
+        match (self, other) {
+            (
+                NiaKey::Key2(device_id, key_code),
+                NiaKey::Key2(other_device_id, other_key_code),
+            ) => *device_id == other_device_id && *key_code == other_key_code,
+            _ => self.get_key_code() == other.get_key_code(),
+        }
+    }
 }
 
 impl NiaConvertable<NiaKey, nia_interpreter_core::Key> for NiaKey {
@@ -71,6 +83,10 @@ impl Serializable<NiaKey, nia_protocol_rust::Key> for NiaKey {
 
                 key_1_pb.set_key_code(*key_code);
 
+                if let Some(key_name) = get_key_name(*key_code) {
+                    key_1_pb.set_key_name(protobuf::Chars::from(key_name));
+                }
+
                 key_pb.set_key_1(key_1_pb);
             }
             NiaKey::Key2(device_id, key_code) => {
@@ -79,6 +95,10 @@ impl Serializable<NiaKey, nia_protocol_rust::Key> for NiaKey {
                 key_2_pb.set_device_id(*device_id);
                 key_2_pb.set_key_code(*key_code);
 
+                if let Some(key_name) = get_key_name(*key_code) {
+                    key_2_pb.set_key_name(protobuf::Chars::from(key_name));
+                }
+
                 key_pb.set_key_2(key_2_pb);
             }
         }
@@ -92,14 +112,20 @@ impl Serializable<NiaKey, nia_protocol_rust::Key> for NiaKey {
         let key = if object_pb.has_key_1() {
             let key_1_pb = object_pb.take_key_1();
 
-            let key_code = key_1_pb.get_key_code();
+            let key_code = resolve_key_code(
+                key_1_pb.get_key_code(),
+                key_1_pb.get_key_name(),
+            )?;
 
             NiaKey::Key1(key_code)
         } else if object_pb.has_key_2() {
             let key_2_pb = object_pb.take_key_2();
 
             let device_id = key_2_pb.get_device_id();
-            let key_code = key_2_pb.get_key_code();
+            let key_code = resolve_key_code(
+                key_2_pb.get_key_code(),
+                key_2_pb.get_key_name(),
+            )?;
 
             NiaKey::Key2(device_id, key_code)
         } else {
@@ -147,6 +173,31 @@ mod tests {
         assert_eq!(key_code_expected, key_code_actual);
     }
 
+    #[test]
+    fn deserializes_key_names() {
+        let mut key_2_pb = nia_protocol_rust::Key2::new();
+        key_2_pb.set_device_id(1);
+        key_2_pb.set_key_name(protobuf::Chars::from("KEY_LEFTCTRL"));
+
+        let mut key_pb = nia_protocol_rust::Key::new();
+        key_pb.set_key_2(key_2_pb);
+
+        let key = NiaKey::from_pb(key_pb).unwrap();
+
+        assert_eq!(NiaKey::make_key_2(1, 29), key);
+    }
+
+    #[test]
+    fn fails_to_deserialize_unknown_key_names() {
+        let mut key_1_pb = nia_protocol_rust::Key1::new();
+        key_1_pb.set_key_name(protobuf::Chars::from("KEY_NYA"));
+
+        let mut key_pb = nia_protocol_rust::Key::new();
+        key_pb.set_key_1(key_1_pb);
+
+        assert!(NiaKey::from_pb(key_pb).is_err());
+    }
+
     #[test]
     fn convertable_between_server_and_interpreter_representations_key_1() {
         let key_code_expected = 123;
@@ -171,4 +222,13 @@ mod tests {
         assert_eq!(Some(device_id_expected), result.get_device_id());
         assert_eq!(key_code_expected, result.get_key_code());
     }
+
+    #[test]
+    fn keys_without_device_match_keys_of_any_device() {
+        assert!(NiaKey::make_key_1(30).matches(NiaKey::make_key_2(1, 30)));
+        assert!(NiaKey::make_key_2(1, 30).matches(NiaKey::make_key_1(30)));
+        assert!(NiaKey::make_key_2(1, 30).matches(NiaKey::make_key_2(1, 30)));
+        assert!(!NiaKey::make_key_2(1, 30).matches(NiaKey::make_key_2(2, 30)));
+        assert!(!NiaKey::make_key_1(30).matches(NiaKey::make_key_1(31)));
+    }
 }
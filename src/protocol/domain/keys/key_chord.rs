@@ -1,40 +1,21 @@
 use crate::error::NiaServerResult;
 use crate::protocol::{NiaConvertable, NiaKey, Serializable};
 
-#[derive(Clone, Debug, Eq)]
+// Modifiers are kept sorted and deduplicated, so that chords that differ only
+// in the order of modifiers are equal, hash equally and are ordered the same.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct NiaKeyChord {
     modifiers: Vec<NiaKey>,
     ordinary_key: NiaKey,
 }
 
-impl PartialEq for NiaKeyChord {
-    fn eq(&self, other: &Self) -> bool {
-        if self.ordinary_key != other.ordinary_key {
-            return false;
-        }
-
-        if self.modifiers.len() != other.modifiers.len() {
-            return false;
-        }
-
-        for modifier in &self.modifiers {
-            if !other.modifiers.contains(modifier) {
-                return false;
-            }
-        }
-
-        for other_modifier in &other.modifiers {
-            if !self.modifiers.contains(other_modifier) {
-                return false;
-            }
-        }
-
-        return true;
-    }
-}
-
 impl NiaKeyChord {
     pub fn new(modifiers: Vec<NiaKey>, ordinary_key: NiaKey) -> NiaKeyChord {
+        let mut modifiers = modifiers;
+
+        modifiers.sort();
+        modifiers.dedup();
+
         NiaKeyChord {
             modifiers,
             ordinary_key,
@@ -48,6 +29,29 @@ impl NiaKeyChord {
     pub fn get_key(&self) -> NiaKey {
         self.ordinary_key
     }
+
+    // Whether pressing `key` while `pressed_modifiers` are held fires the
+    // chord. Keys of the chord without a device match the keys of any device.
+    pub fn matches(&self, pressed_modifiers: &[NiaKey], key: NiaKey) -> bool {
+        self.ordinary_key.matches(key)
+            && self.modifiers.len() == pressed_modifiers.len()
+            && self.modifiers.iter().all(|modifier| {
+                pressed_modifiers
+                    .iter()
+                    .any(|pressed_modifier| modifier.matches(*pressed_modifier))
+            })
+            && pressed_modifiers.iter().all(|pressed_modifier| {
+                self.modifiers
+                    .iter()
+                    .any(|modifier| modifier.matches(*pressed_modifier))
+            })
+    }
+
+    // Whether some key press fires both chords, e.g. a chord of any device
+    // and the same chord of a specific device.
+    pub fn overlaps(&self, other: &NiaKeyChord) -> bool {
+        self.matches(&other.modifiers, other.ordinary_key)
+    }
 }
 
 impl NiaConvertable<NiaKeyChord, nia_interpreter_core::KeyChord>
@@ -130,6 +134,50 @@ mod tests {
     #[allow(unused_imports)]
     use super::*;
 
+    #[cfg(test)]
+    mod matching {
+        #[allow(unused_imports)]
+        use super::*;
+
+        #[test]
+        fn keys_without_devices_match_keys_of_any_device() {
+            let key_chord = NiaKeyChord::new(
+                vec![NiaKey::make_key_1(29)],
+                NiaKey::make_key_2(1, 30),
+            );
+
+            assert!(key_chord.matches(
+                &[NiaKey::make_key_2(2, 29)],
+                NiaKey::make_key_2(1, 30)
+            ));
+            assert!(!key_chord.matches(
+                &[NiaKey::make_key_2(2, 29)],
+                NiaKey::make_key_2(2, 30)
+            ));
+            assert!(!key_chord.matches(&[], NiaKey::make_key_2(1, 30)));
+        }
+
+        #[test]
+        fn chords_overlap_when_some_press_fires_both() {
+            let any_device = NiaKeyChord::new(
+                vec![NiaKey::make_key_1(29)],
+                NiaKey::make_key_1(30),
+            );
+            let first_device = NiaKeyChord::new(
+                vec![NiaKey::make_key_2(1, 29)],
+                NiaKey::make_key_2(1, 30),
+            );
+            let second_device = NiaKeyChord::new(
+                vec![NiaKey::make_key_2(2, 29)],
+                NiaKey::make_key_2(2, 30),
+            );
+
+            assert!(any_device.overlaps(&first_device));
+            assert!(first_device.overlaps(&any_device));
+            assert!(!first_device.overlaps(&second_device));
+        }
+    }
+
     #[cfg(test)]
     mod serialization {
         #[allow(unused_imports)]
@@ -170,4 +218,31 @@ mod tests {
             assert_eq!(expected_key_chord, key_chord)
         }
     }
+
+    #[cfg(test)]
+    mod canonical_form {
+        #[allow(unused_imports)]
+        use super::*;
+
+        #[test]
+        fn ignores_order_and_duplicates_of_modifiers() {
+            let expected = NiaKeyChord::new(
+                vec![NiaKey::make_key_1(1), NiaKey::make_key_2(1, 2)],
+                NiaKey::make_key_2(2, 3),
+            );
+
+            let result = NiaKeyChord::new(
+                vec![
+                    NiaKey::make_key_2(1, 2),
+                    NiaKey::make_key_1(1),
+                    NiaKey::make_key_2(1, 2),
+                ],
+                NiaKey::make_key_2(2, 3),
+            );
+
+            assert_eq!(expected, result);
+            assert_eq!(expected.get_modifiers(), result.get_modifiers());
+            assert_eq!(expected.cmp(&result), std::cmp::Ordering::Equal);
+        }
+    }
 }
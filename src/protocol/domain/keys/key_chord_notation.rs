@@ -0,0 +1,586 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{get_key_code, get_key_name, NiaKey, NiaKeyChord};
+
+// Textual notation of key chords, e.g. "C-M-a", "[kbd]C-x", "2:LeftCtrl+2:q".
+//
+// A chord is a list of keys separated with '-' or '+', the last key is the
+// ordinary key and the others are modifiers. A key may be prefixed with
+// "<device>:", a whole chord may be prefixed with "[<device>]", where device
+// is either a device id or a device name. Device names that contain
+// separators, colons, brackets or whitespace are written in double quotes,
+// e.g. ["My keyboard"]C-a. Raw key codes are written as "#<code>", e.g. "#-5".
+// Chords of a sequence are separated with whitespace: "C-x C-s".
+
+const MODIFIER_SHORTCUTS: &'static [(&'static str, &'static str)] = &[
+    ("C", "KEY_LEFTCTRL"),
+    ("M", "KEY_LEFTALT"),
+    ("S", "KEY_LEFTSHIFT"),
+    ("s", "KEY_LEFTMETA"),
+];
+
+const KEY_SEPARATORS: &'static [char] = &['-', '+'];
+const RAW_KEY_CODE_PREFIX: char = '#';
+const QUOTE: char = '"';
+const ESCAPE: char = '\\';
+
+fn invalid_notation<S>(notation: &str, reason: S) -> NiaServerError
+where
+    S: Into<String>,
+{
+    NiaServerError::invalid_request(format!(
+        "Invalid key chord notation \"{}\": {}",
+        notation,
+        reason.into()
+    ))
+}
+
+// Tracks whether a character of a notation is inside a quoted device name.
+#[derive(Default)]
+struct QuoteState {
+    quoted: bool,
+    escaped: bool,
+}
+
+impl QuoteState {
+    // Returns true when the character is outside of quotes.
+    fn feed(&mut self, c: char) -> bool {
+        if self.quoted {
+            if self.escaped {
+                self.escaped = false;
+            } else if c == ESCAPE {
+                self.escaped = true;
+            } else if c == QUOTE {
+                self.quoted = false;
+            }
+
+            false
+        } else {
+            if c == QUOTE {
+                self.quoted = true;
+            }
+
+            true
+        }
+    }
+}
+
+fn find_unquoted(text: &str, target: char) -> Option<usize> {
+    let mut state = QuoteState::default();
+
+    text.char_indices()
+        .find(|(_, c)| state.feed(*c) && *c == target)
+        .map(|(index, _)| index)
+}
+
+// Splits `text` on the unquoted characters accepted by `is_separator`, which
+// also gets the part collected so far.
+fn split_unquoted<F>(
+    notation: &str,
+    text: &str,
+    is_separator: F,
+) -> NiaServerResult<Vec<String>>
+where
+    F: Fn(&str, char) -> bool,
+{
+    let mut state = QuoteState::default();
+    let mut parts = Vec::new();
+    let mut part = String::new();
+
+    for c in text.chars() {
+        if state.feed(c) && is_separator(&part, c) {
+            parts.push(std::mem::take(&mut part));
+        } else {
+            part.push(c);
+        }
+    }
+
+    if state.quoted {
+        return invalid_notation(notation, "unclosed quote.").into();
+    }
+
+    parts.push(part);
+
+    Ok(parts)
+}
+
+fn unquote(notation: &str, text: &str) -> NiaServerResult<String> {
+    let mut result = String::new();
+    let mut chars = text.chars().skip(1);
+
+    while let Some(c) = chars.next() {
+        match c {
+            ESCAPE => match chars.next() {
+                Some(c) => result.push(c),
+                None => break,
+            },
+            QUOTE => {
+                if chars.next().is_some() {
+                    return invalid_notation(
+                        notation,
+                        "unexpected characters after a quoted device name.",
+                    )
+                    .into();
+                }
+
+                return Ok(result);
+            }
+            c => result.push(c),
+        }
+    }
+
+    invalid_notation(notation, "unclosed quote.").into()
+}
+
+fn parse_device<F>(
+    notation: &str,
+    device: &str,
+    resolve_device: &F,
+) -> NiaServerResult<i32>
+where
+    F: Fn(&str) -> Option<i32>,
+{
+    let device_name = if device.starts_with(QUOTE) {
+        unquote(notation, device)?
+    } else if let Ok(device_id) = device.parse::<i32>() {
+        return Ok(device_id);
+    } else {
+        device.to_string()
+    };
+
+    resolve_device(&device_name).ok_or_else(|| {
+        NiaServerError::invalid_request(format!(
+            "Unknown device: {}.",
+            device_name
+        ))
+    })
+}
+
+fn parse_key_code(key: &str, is_modifier: bool) -> Option<i32> {
+    if key.starts_with(RAW_KEY_CODE_PREFIX) {
+        return key[1..].parse::<i32>().ok();
+    }
+
+    if is_modifier {
+        for (shortcut, key_name) in MODIFIER_SHORTCUTS {
+            if *shortcut == key {
+                return get_key_code(key_name);
+            }
+        }
+    }
+
+    get_key_code(key).or_else(|| get_key_code(&format!("KEY_{}", key)))
+}
+
+fn parse_key<F>(
+    notation: &str,
+    key: &str,
+    chord_device_id: Option<i32>,
+    is_modifier: bool,
+    resolve_device: &F,
+) -> NiaServerResult<NiaKey>
+where
+    F: Fn(&str) -> Option<i32>,
+{
+    let (device_id, key) = match find_unquoted(key, ':') {
+        Some(index) => {
+            let device_id =
+                parse_device(notation, &key[..index], resolve_device)?;
+
+            (Some(device_id), &key[index + 1..])
+        }
+        None => (chord_device_id, key),
+    };
+
+    let key_code = parse_key_code(key, is_modifier).ok_or_else(|| {
+        invalid_notation(notation, format!("unknown key \"{}\".", key))
+    })?;
+
+    let key = match device_id {
+        Some(device_id) => NiaKey::make_key_2(device_id, key_code),
+        None => NiaKey::make_key_1(key_code),
+    };
+
+    Ok(key)
+}
+
+pub fn parse_key_chord<F>(
+    notation: &str,
+    resolve_device: &F,
+) -> NiaServerResult<NiaKeyChord>
+where
+    F: Fn(&str) -> Option<i32>,
+{
+    let (chord_device_id, keys) = if notation.starts_with('[') {
+        let index = find_unquoted(notation, ']').ok_or_else(|| {
+            invalid_notation(notation, "unclosed device prefix.")
+        })?;
+        let device_id =
+            parse_device(notation, &notation[1..index], resolve_device)?;
+
+        (Some(device_id), &notation[index + 1..])
+    } else {
+        (None, notation)
+    };
+
+    // A '-' right after the raw key code prefix is the sign of the code.
+    let parts = split_unquoted(notation, keys, |part, c| {
+        KEY_SEPARATORS.contains(&c)
+            && !(c == '-' && part.ends_with(RAW_KEY_CODE_PREFIX))
+    })?;
+
+    if parts.iter().any(|part| part.is_empty()) {
+        return invalid_notation(notation, "empty key.").into();
+    }
+
+    let (ordinary_key, modifiers) = match parts.split_last() {
+        Some(split) => split,
+        None => return invalid_notation(notation, "empty key chord.").into(),
+    };
+
+    let modifiers = modifiers
+        .iter()
+        .map(|modifier| {
+            parse_key(notation, modifier, chord_device_id, true, resolve_device)
+        })
+        .collect::<NiaServerResult<Vec<NiaKey>>>()?;
+
+    let ordinary_key = parse_key(
+        notation,
+        ordinary_key,
+        chord_device_id,
+        false,
+        resolve_device,
+    )?;
+
+    Ok(NiaKeyChord::new(modifiers, ordinary_key))
+}
+
+pub fn parse_key_chords<F>(
+    notation: &str,
+    resolve_device: &F,
+) -> NiaServerResult<Vec<NiaKeyChord>>
+where
+    F: Fn(&str) -> Option<i32>,
+{
+    let key_chords =
+        split_unquoted(notation, notation, |_, c| c.is_whitespace())?
+            .iter()
+            .filter(|key_chord| !key_chord.is_empty())
+            .map(|key_chord| parse_key_chord(key_chord, resolve_device))
+            .collect::<NiaServerResult<Vec<NiaKeyChord>>>()?;
+
+    if key_chords.is_empty() {
+        return invalid_notation(notation, "no key chords.").into();
+    }
+
+    Ok(key_chords)
+}
+
+// Picks the shortest spelling of the key that is parsed back into the same
+// key code, so that e.g. KEY_S as a modifier isn't written as the "s"
+// shortcut of KEY_LEFTMETA.
+fn format_key_code(key_code: i32, is_modifier: bool) -> String {
+    let raw_key_code = format!("{}{}", RAW_KEY_CODE_PREFIX, key_code);
+
+    let key_name = match get_key_name(key_code) {
+        Some(key_name) => key_name,
+        None => return raw_key_code,
+    };
+
+    let mut spellings = Vec::new();
+
+    if is_modifier {
+        for (shortcut, shortcut_key_name) in MODIFIER_SHORTCUTS {
+            if *shortcut_key_name == key_name {
+                spellings.push(shortcut.to_string());
+            }
+        }
+    }
+
+    if key_name.starts_with("KEY_") {
+        let short_name = &key_name[4..];
+
+        if short_name.len() == 1 {
+            spellings.push(short_name.to_lowercase());
+        } else {
+            spellings.push(short_name.to_string());
+        }
+    }
+
+    spellings.push(key_name.to_string());
+
+    spellings
+        .into_iter()
+        .find(|spelling| {
+            parse_key_code(spelling, is_modifier) == Some(key_code)
+        })
+        .unwrap_or(raw_key_code)
+}
+
+pub fn format_key_chord(key_chord: &NiaKeyChord) -> String {
+    let keys = key_chord
+        .get_modifiers()
+        .iter()
+        .map(|modifier| (*modifier, true))
+        .chain(std::iter::once((key_chord.get_key(), false)))
+        .collect::<Vec<(NiaKey, bool)>>();
+
+    let first_device_id = keys[0].0.get_device_id();
+    let same_device = keys
+        .iter()
+        .all(|(key, _)| key.get_device_id() == first_device_id);
+
+    if same_device {
+        let formatted_keys = keys
+            .iter()
+            .map(|(key, is_modifier)| {
+                format_key_code(key.get_key_code(), *is_modifier)
+            })
+            .collect::<Vec<String>>()
+            .join("-");
+
+        match first_device_id {
+            Some(device_id) => format!("[{}]{}", device_id, formatted_keys),
+            None => formatted_keys,
+        }
+    } else {
+        keys.iter()
+            .map(|(key, is_modifier)| {
+                let formatted_key =
+                    format_key_code(key.get_key_code(), *is_modifier);
+
+                match key.get_device_id() {
+                    Some(device_id) => {
+                        format!("{}:{}", device_id, formatted_key)
+                    }
+                    None => formatted_key,
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("+")
+    }
+}
+
+pub fn format_key(key: NiaKey) -> String {
+    format_key_chord(&NiaKeyChord::new(vec![], key))
+}
+
+pub fn format_key_chords(key_chords: &[NiaKeyChord]) -> String {
+    key_chords
+        .iter()
+        .map(format_key_chord)
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::protocol::get_key_names;
+
+    fn resolve_device(device_name: &str) -> Option<i32> {
+        match device_name {
+            "kbd" => Some(3),
+            "My keyboard" => Some(4),
+            "usb-kbd:1 \"left\"" => Some(5),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn parses_emacs_like_notation() {
+        let expected = vec![NiaKeyChord::new(
+            vec![NiaKey::make_key_1(29), NiaKey::make_key_1(56)],
+            NiaKey::make_key_1(30),
+        )];
+
+        let result = parse_key_chords("C-M-a", &resolve_device).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn parses_chord_device_prefix() {
+        let expected = vec![NiaKeyChord::new(
+            vec![NiaKey::make_key_2(3, 29), NiaKey::make_key_2(3, 56)],
+            NiaKey::make_key_2(3, 30),
+        )];
+
+        let result = parse_key_chords("[kbd]C-M-a", &resolve_device).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn parses_key_device_prefixes() {
+        let expected = vec![NiaKeyChord::new(
+            vec![NiaKey::make_key_2(2, 29)],
+            NiaKey::make_key_2(2, 16),
+        )];
+
+        let result =
+            parse_key_chords("2:LeftCtrl+2:q", &resolve_device).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn parses_sequences() {
+        let expected = vec![
+            NiaKeyChord::new(
+                vec![NiaKey::make_key_1(29)],
+                NiaKey::make_key_1(45),
+            ),
+            NiaKeyChord::new(
+                vec![NiaKey::make_key_1(29)],
+                NiaKey::make_key_1(31),
+            ),
+        ];
+
+        let result = parse_key_chords("C-x C-s", &resolve_device).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn parses_raw_key_codes() {
+        let expected = vec![NiaKeyChord::new(vec![], NiaKey::make_key_1(999))];
+
+        let result = parse_key_chords("#999", &resolve_device).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn parses_negative_raw_key_codes() {
+        let expected = vec![
+            NiaKeyChord::new(vec![], NiaKey::make_key_1(-5)),
+            NiaKeyChord::new(
+                vec![NiaKey::make_key_1(-7)],
+                NiaKey::make_key_2(2, -5),
+            ),
+        ];
+
+        let result =
+            parse_key_chords("#-5 #-7-2:#-5", &resolve_device).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn parses_quoted_device_names() {
+        let expected = vec![
+            NiaKeyChord::new(
+                vec![NiaKey::make_key_2(4, 29)],
+                NiaKey::make_key_2(4, 30),
+            ),
+            NiaKeyChord::new(
+                vec![NiaKey::make_key_2(5, 29)],
+                NiaKey::make_key_2(4, 30),
+            ),
+        ];
+
+        let result = parse_key_chords(
+            r#"["My keyboard"]C-a "usb-kbd:1 \"left\"":C+"My keyboard":a"#,
+            &resolve_device,
+        )
+        .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn returns_error_on_invalid_notation() {
+        assert!(parse_key_chords("", &resolve_device).is_err());
+        assert!(parse_key_chords("C-", &resolve_device).is_err());
+        assert!(parse_key_chords("C-nya", &resolve_device).is_err());
+        assert!(parse_key_chords("[unknown]C-a", &resolve_device).is_err());
+        assert!(parse_key_chords("[kbd C-a", &resolve_device).is_err());
+        assert!(parse_key_chords("[\"kbd]C-a", &resolve_device).is_err());
+        assert!(parse_key_chords("\"kbd\"x:a", &resolve_device).is_err());
+    }
+
+    #[test]
+    fn formats_key_chords() {
+        let key_chords = vec![
+            NiaKeyChord::new(
+                vec![NiaKey::make_key_2(3, 29), NiaKey::make_key_2(3, 56)],
+                NiaKey::make_key_2(3, 30),
+            ),
+            NiaKeyChord::new(
+                vec![NiaKey::make_key_2(2, 29)],
+                NiaKey::make_key_2(1, 28),
+            ),
+            NiaKeyChord::new(vec![], NiaKey::make_key_1(999)),
+        ];
+
+        let result = format_key_chords(&key_chords);
+
+        assert_eq!("[3]C-M-a 2:C+1:ENTER #999", result);
+    }
+
+    #[test]
+    fn formatted_key_chords_are_parsed_back() {
+        let expected = vec![
+            NiaKeyChord::new(
+                vec![NiaKey::make_key_1(42), NiaKey::make_key_1(125)],
+                NiaKey::make_key_1(59),
+            ),
+            NiaKeyChord::new(
+                vec![NiaKey::make_key_2(1, 97)],
+                NiaKey::make_key_2(2, 272),
+            ),
+        ];
+
+        let notation = format_key_chords(&expected);
+        let result = parse_key_chords(&notation, &resolve_device).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn modifiers_are_formatted_unambiguously() {
+        let key_chord = NiaKeyChord::new(
+            vec![NiaKey::make_key_1(31)],
+            NiaKey::make_key_1(30),
+        );
+
+        let notation = format_key_chord(&key_chord);
+        let result = parse_key_chord(&notation, &resolve_device).unwrap();
+
+        assert_eq!("KEY_S-a", notation);
+        assert_eq!(key_chord, result);
+    }
+
+    #[test]
+    fn every_key_is_parsed_back_after_formatting() {
+        let key_codes = get_key_names()
+            .iter()
+            .map(|(key_code, _)| *key_code)
+            .chain(vec![-5, 999]);
+
+        for key_code in key_codes {
+            let key_chords = vec![
+                NiaKeyChord::new(
+                    vec![NiaKey::make_key_1(key_code)],
+                    NiaKey::make_key_1(key_code),
+                ),
+                NiaKeyChord::new(
+                    vec![NiaKey::make_key_2(1, key_code)],
+                    NiaKey::make_key_2(2, key_code),
+                ),
+                NiaKeyChord::new(
+                    vec![NiaKey::make_key_2(3, key_code)],
+                    NiaKey::make_key_2(3, key_code),
+                ),
+            ];
+
+            let notation = format_key_chords(&key_chords);
+            let result = parse_key_chords(&notation, &resolve_device).unwrap();
+
+            assert_eq!(key_chords, result, "{}", notation);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{get_key_name, resolve_key_code, NiaKey};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NiaKeyEventType {
+    Press,
+    Release,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NiaKeyEvent {
+    time: u64,
+    device_id: i32,
+    key_code: i32,
+    event_type: NiaKeyEventType,
+}
+
+impl NiaKeyEvent {
+    pub fn new(
+        time: u64,
+        device_id: i32,
+        key_code: i32,
+        event_type: NiaKeyEventType,
+    ) -> NiaKeyEvent {
+        NiaKeyEvent {
+            time,
+            device_id,
+            key_code,
+            event_type,
+        }
+    }
+
+    pub fn press(time: u64, device_id: i32, key_code: i32) -> NiaKeyEvent {
+        NiaKeyEvent::new(time, device_id, key_code, NiaKeyEventType::Press)
+    }
+
+    pub fn release(time: u64, device_id: i32, key_code: i32) -> NiaKeyEvent {
+        NiaKeyEvent::new(time, device_id, key_code, NiaKeyEventType::Release)
+    }
+
+    pub fn get_time(&self) -> u64 {
+        self.time
+    }
+
+    pub fn get_device_id(&self) -> i32 {
+        self.device_id
+    }
+
+    pub fn get_key_code(&self) -> i32 {
+        self.key_code
+    }
+
+    pub fn get_event_type(&self) -> NiaKeyEventType {
+        self.event_type
+    }
+
+    pub fn get_key(&self) -> NiaKey {
+        NiaKey::make_key_2(self.device_id, self.key_code)
+    }
+}
+
+impl Serializable<NiaKeyEvent, nia_protocol_rust::KeyEvent> for NiaKeyEvent {
+    fn to_pb(&self) -> nia_protocol_rust::KeyEvent {
+        let mut key_event_pb = nia_protocol_rust::KeyEvent::new();
+
+        key_event_pb.set_time(self.time);
+        key_event_pb.set_device_id(self.device_id);
+        key_event_pb.set_key_code(self.key_code);
+
+        if let Some(key_name) = get_key_name(self.key_code) {
+            key_event_pb.set_key_name(protobuf::Chars::from(key_name));
+        }
+
+        match self.event_type {
+            NiaKeyEventType::Press => key_event_pb
+                .set_press_event(nia_protocol_rust::KeyPressEvent::new()),
+            NiaKeyEventType::Release => key_event_pb
+                .set_release_event(nia_protocol_rust::KeyReleaseEvent::new()),
+        }
+
+        key_event_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::KeyEvent,
+    ) -> NiaServerResult<NiaKeyEvent> {
+        let key_code = resolve_key_code(
+            object_pb.get_key_code(),
+            object_pb.get_key_name(),
+        )?;
+
+        let event_type = if object_pb.has_press_event() {
+            NiaKeyEventType::Press
+        } else if object_pb.has_release_event() {
+            NiaKeyEventType::Release
+        } else {
+            return NiaServerError::deserialization_error(
+                "Provided neither a press nor release event.",
+            )
+            .into();
+        };
+
+        let key_event = NiaKeyEvent::new(
+            object_pb.get_time(),
+            object_pb.get_device_id(),
+            key_code,
+            event_type,
+        );
+
+        Ok(key_event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = vec![
+            NiaKeyEvent::press(100, 1, 29),
+            NiaKeyEvent::release(250, 2, 999),
+        ];
+
+        for expected in expected {
+            let bytes = expected.to_bytes().unwrap();
+            let result = NiaKeyEvent::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, result);
+        }
+    }
+}
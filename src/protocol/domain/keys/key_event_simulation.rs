@@ -0,0 +1,408 @@
+use crate::protocol::{
+    expand_key_chord, is_modifier_defined, NiaActionEnum, NiaFiredMapping,
+    NiaKey, NiaKeyChord, NiaKeyEvent, NiaKeyEventType, NiaMapping,
+    NiaMappingIndex, NiaModifierDescription, NiaModifierGroup,
+};
+
+// A fired mapping whose action repeats until the key that fired it is
+// released.
+struct HeldRepeat {
+    key: NiaKey,
+    time: u64,
+    interval_ms: u64,
+    mapping: NiaMapping,
+}
+
+impl HeldRepeat {
+    fn from_fired_mapping(
+        key: NiaKey,
+        time: u64,
+        mapping: &NiaMapping,
+    ) -> Option<HeldRepeat> {
+        match mapping.get_action().get_action() {
+            NiaActionEnum::RepeatWhileHeld(action_repeat_while_held) => {
+                Some(HeldRepeat {
+                    key,
+                    time,
+                    interval_ms: action_repeat_while_held.get_interval_ms()
+                        as u64,
+                    mapping: mapping.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    // Intervals are checked to be positive when actions are defined.
+    fn fire_until(&self, release_time: u64) -> Vec<NiaFiredMapping> {
+        let mut fired_mappings = Vec::new();
+        let mut time = self.time + self.interval_ms;
+
+        while time < release_time {
+            fired_mappings
+                .push(NiaFiredMapping::new(time, self.mapping.clone()));
+            time += self.interval_ms;
+        }
+
+        fired_mappings
+    }
+}
+
+// Mappings are reported with the keys that represent modifier groups, such a
+// chord is fired by any member of the group. Held modifiers are compared as
+// they are, so that their devices are not lost.
+fn find_next_key_chord(
+    mapping_index: &NiaMappingIndex,
+    modifier_groups: &[NiaModifierGroup],
+    key_chords: &[NiaKeyChord],
+    pressed_modifiers: &[NiaKey],
+    key: NiaKey,
+) -> Option<NiaKeyChord> {
+    mapping_index
+        .get_next_key_chords(key_chords)
+        .into_iter()
+        .find(|key_chord| {
+            expand_key_chord(modifier_groups, key_chord)
+                .iter()
+                .any(|key_chord| key_chord.matches(pressed_modifiers, key))
+        })
+        .cloned()
+}
+
+// Replays key events through the same matching the interpreter does while
+// listening: defined modifiers are tracked while held, and every press of
+// another key makes a chord of it and the held modifiers. Chords are matched
+// with NiaKeyChord::matches, the same way conflicts and key resolution treat
+// keys of any device and keys of specific devices. The chord either
+// continues the current sequence, completes a mapping, or starts over.
+// Nothing is grabbed or emitted, the fired mappings are returned in order.
+// Mappings that repeat while held are reported again every interval until
+// the key that fired them is released.
+pub fn simulate_key_events(
+    key_events: &[NiaKeyEvent],
+    mapping_index: &NiaMappingIndex,
+    defined_modifiers: &[NiaModifierDescription],
+    modifier_groups: &[NiaModifierGroup],
+) -> Vec<NiaFiredMapping> {
+    let mut key_events = key_events.to_vec();
+
+    key_events.sort_by_key(|key_event| key_event.get_time());
+
+    let mut pressed_modifiers = Vec::new();
+    let mut key_chords = Vec::new();
+    let mut fired_mappings = Vec::new();
+    let mut held_repeats: Vec<HeldRepeat> = Vec::new();
+
+    for key_event in key_events {
+        let key = key_event.get_key();
+        let is_modifier = is_modifier_defined(key, defined_modifiers);
+
+        match key_event.get_event_type() {
+            NiaKeyEventType::Release => {
+                for held_repeat in &held_repeats {
+                    if held_repeat.key == key {
+                        fired_mappings.extend(
+                            held_repeat.fire_until(key_event.get_time()),
+                        );
+                    }
+                }
+
+                held_repeats.retain(|held_repeat| held_repeat.key != key);
+                pressed_modifiers
+                    .retain(|pressed_modifier| *pressed_modifier != key);
+                continue;
+            }
+            NiaKeyEventType::Press if is_modifier => {
+                if !pressed_modifiers.contains(&key) {
+                    pressed_modifiers.push(key);
+                }
+                continue;
+            }
+            NiaKeyEventType::Press => {}
+        }
+
+        let mut next_key_chord = find_next_key_chord(
+            mapping_index,
+            &modifier_groups,
+            &key_chords,
+            &pressed_modifiers,
+            key,
+        );
+
+        if next_key_chord.is_none() && !key_chords.is_empty() {
+            key_chords.clear();
+
+            next_key_chord = find_next_key_chord(
+                mapping_index,
+                &modifier_groups,
+                &key_chords,
+                &pressed_modifiers,
+                key,
+            );
+        }
+
+        match next_key_chord {
+            Some(next_key_chord) => {
+                key_chords.push(next_key_chord);
+
+                if let Some(mapping) = mapping_index.get(&key_chords) {
+                    held_repeats.extend(HeldRepeat::from_fired_mapping(
+                        key,
+                        key_event.get_time(),
+                        mapping,
+                    ));
+                    fired_mappings.push(NiaFiredMapping::new(
+                        key_event.get_time(),
+                        mapping.clone(),
+                    ));
+
+                    key_chords.clear();
+                }
+            }
+            None => key_chords.clear(),
+        }
+    }
+
+    fired_mappings.sort_by_key(|fired_mapping| fired_mapping.get_time());
+
+    fired_mappings
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::{
+        ActionExecuteOSCommand, ActionMouseRelativeMove, ActionRepeatWhileHeld,
+        NiaAction,
+    };
+
+    fn construct_mapping(key_chords: Vec<NiaKeyChord>) -> NiaMapping {
+        NiaMapping::new(
+            key_chords,
+            NiaAction::new(NiaActionEnum::ExecuteOSCommand(
+                ActionExecuteOSCommand::new("echo catgirl"),
+            )),
+        )
+    }
+
+    fn construct_mapping_index() -> NiaMappingIndex {
+        NiaMappingIndex::from_mappings(vec![
+            construct_mapping(vec![
+                NiaKeyChord::new(
+                    vec![NiaKey::make_key_1(29)],
+                    NiaKey::make_key_1(45),
+                ),
+                NiaKeyChord::new(
+                    vec![NiaKey::make_key_1(29)],
+                    NiaKey::make_key_1(31),
+                ),
+            ]),
+            construct_mapping(vec![NiaKeyChord::new(
+                vec![],
+                NiaKey::make_key_2(2, 30),
+            )]),
+        ])
+    }
+
+    fn construct_modifiers() -> Vec<NiaModifierDescription> {
+        vec![NiaModifierDescription::new(NiaKey::make_key_1(29), "ctrl")]
+    }
+
+    #[test]
+    fn fires_key_chord_sequences() {
+        let key_events = vec![
+            NiaKeyEvent::press(0, 1, 29),
+            NiaKeyEvent::press(10, 1, 45),
+            NiaKeyEvent::release(20, 1, 45),
+            NiaKeyEvent::press(30, 1, 31),
+            NiaKeyEvent::release(40, 1, 31),
+            NiaKeyEvent::release(50, 1, 29),
+        ];
+
+        let result = simulate_key_events(
+            &key_events,
+            &construct_mapping_index(),
+            &construct_modifiers(),
+            &[],
+        );
+
+        assert_eq!(1, result.len());
+        assert_eq!(30, result[0].get_time());
+    }
+
+    #[test]
+    fn orders_events_by_time() {
+        let key_events = vec![
+            NiaKeyEvent::press(30, 1, 31),
+            NiaKeyEvent::press(10, 1, 45),
+            NiaKeyEvent::press(0, 1, 29),
+        ];
+
+        let result = simulate_key_events(
+            &key_events,
+            &construct_mapping_index(),
+            &construct_modifiers(),
+            &[],
+        );
+
+        assert_eq!(1, result.len());
+    }
+
+    #[test]
+    fn resets_sequences_on_unmapped_chords() {
+        let key_events = vec![
+            NiaKeyEvent::press(0, 1, 29),
+            NiaKeyEvent::press(10, 1, 45),
+            NiaKeyEvent::release(20, 1, 29),
+            NiaKeyEvent::press(30, 1, 31),
+            NiaKeyEvent::press(40, 2, 30),
+        ];
+
+        let result = simulate_key_events(
+            &key_events,
+            &construct_mapping_index(),
+            &construct_modifiers(),
+            &[],
+        );
+
+        assert_eq!(1, result.len());
+        assert_eq!(40, result[0].get_time());
+    }
+
+    #[test]
+    fn respects_devices_of_keys() {
+        let key_events = vec![NiaKeyEvent::press(0, 1, 30)];
+
+        let result = simulate_key_events(
+            &key_events,
+            &construct_mapping_index(),
+            &construct_modifiers(),
+            &[],
+        );
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn matches_members_of_modifier_groups() {
+        let modifiers = vec![
+            NiaModifierDescription::new(NiaKey::make_key_1(29), "ctrl"),
+            NiaModifierDescription::new(NiaKey::make_key_1(97), "ctrl"),
+        ];
+        let key_events = vec![
+            NiaKeyEvent::press(0, 1, 97),
+            NiaKeyEvent::press(10, 1, 45),
+            NiaKeyEvent::release(20, 1, 97),
+            NiaKeyEvent::press(30, 1, 29),
+            NiaKeyEvent::press(40, 1, 31),
+        ];
+
+        let modifier_groups = vec![NiaModifierGroup::new(
+            "ctrl",
+            vec![NiaKey::make_key_1(29), NiaKey::make_key_1(97)],
+        )];
+
+        let result = simulate_key_events(
+            &key_events,
+            &construct_mapping_index(),
+            &modifiers,
+            &modifier_groups,
+        );
+
+        assert_eq!(1, result.len());
+        assert_eq!(40, result[0].get_time());
+
+        let result = simulate_key_events(
+            &key_events,
+            &construct_mapping_index(),
+            &modifiers,
+            &[],
+        );
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn repeats_mappings_while_keys_are_held() {
+        let mapping_index =
+            NiaMappingIndex::from_mappings(vec![NiaMapping::new(
+                vec![NiaKeyChord::new(vec![], NiaKey::make_key_1(30))],
+                NiaAction::new(
+                    ActionRepeatWhileHeld::new(
+                        NiaAction::new(
+                            ActionMouseRelativeMove::new(0, 10).into(),
+                        ),
+                        20,
+                    )
+                    .into(),
+                ),
+            )]);
+        let key_events = vec![
+            NiaKeyEvent::press(0, 1, 30),
+            NiaKeyEvent::press(10, 1, 45),
+            NiaKeyEvent::release(50, 1, 30),
+        ];
+
+        let result = simulate_key_events(
+            &key_events,
+            &mapping_index,
+            &construct_modifiers(),
+            &[],
+        );
+
+        let times = result
+            .iter()
+            .map(|fired_mapping| fired_mapping.get_time())
+            .collect::<Vec<u64>>();
+
+        assert_eq!(vec![0, 20, 40], times);
+    }
+
+    #[test]
+    fn matches_keys_of_any_device_with_keys_of_every_device() {
+        let key_events = vec![
+            NiaKeyEvent::press(0, 3, 29),
+            NiaKeyEvent::press(10, 3, 45),
+            NiaKeyEvent::release(20, 3, 45),
+            NiaKeyEvent::press(30, 4, 31),
+        ];
+
+        let result = simulate_key_events(
+            &key_events,
+            &construct_mapping_index(),
+            &construct_modifiers(),
+            &[],
+        );
+
+        assert_eq!(1, result.len());
+        assert_eq!(30, result[0].get_time());
+    }
+
+    #[test]
+    fn matches_modifiers_of_specific_devices_only_on_these_devices() {
+        let mapping_index = NiaMappingIndex::from_mappings(vec![
+            construct_mapping(vec![NiaKeyChord::new(
+                vec![NiaKey::make_key_2(1, 29)],
+                NiaKey::make_key_1(30),
+            )]),
+        ]);
+        let key_events = vec![
+            NiaKeyEvent::press(0, 2, 29),
+            NiaKeyEvent::press(10, 1, 30),
+            NiaKeyEvent::release(20, 2, 29),
+            NiaKeyEvent::press(30, 1, 29),
+            NiaKeyEvent::press(40, 2, 30),
+        ];
+
+        let result = simulate_key_events(
+            &key_events,
+            &mapping_index,
+            &construct_modifiers(),
+            &[],
+        );
+
+        assert_eq!(1, result.len());
+        assert_eq!(40, result[0].get_time());
+    }
+}
@@ -0,0 +1,314 @@
+use crate::error::NiaServerResult;
+use crate::protocol::Serializable;
+use crate::protocol::{
+    NiaKey, NiaKeyChord, NiaMapping, NiaMappingIndex, NiaModifierDescription,
+};
+
+// What a key chord sequence does: the mapping it triggers, the mappings it
+// is a prefix of, the defined modifiers among its keys and every mapping that
+// uses any of its keys.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NiaKeyResolution {
+    mapping: Option<NiaMapping>,
+    continuations: Vec<NiaMapping>,
+    modifiers: Vec<NiaModifierDescription>,
+    related_mappings: Vec<NiaMapping>,
+}
+
+impl NiaKeyResolution {
+    pub fn new(
+        mapping: Option<NiaMapping>,
+        continuations: Vec<NiaMapping>,
+        modifiers: Vec<NiaModifierDescription>,
+        related_mappings: Vec<NiaMapping>,
+    ) -> NiaKeyResolution {
+        NiaKeyResolution {
+            mapping,
+            continuations,
+            modifiers,
+            related_mappings,
+        }
+    }
+
+    pub fn get_mapping(&self) -> Option<&NiaMapping> {
+        self.mapping.as_ref()
+    }
+
+    pub fn get_continuations(&self) -> &Vec<NiaMapping> {
+        &self.continuations
+    }
+
+    pub fn get_modifiers(&self) -> &Vec<NiaModifierDescription> {
+        &self.modifiers
+    }
+
+    pub fn get_related_mappings(&self) -> &Vec<NiaMapping> {
+        &self.related_mappings
+    }
+
+    pub fn is_prefix(&self) -> bool {
+        !self.continuations.is_empty()
+    }
+}
+
+fn get_keys(key_chords: &[NiaKeyChord]) -> Vec<NiaKey> {
+    let mut keys = Vec::new();
+
+    for key_chord in key_chords {
+        for key in key_chord
+            .get_modifiers()
+            .iter()
+            .cloned()
+            .chain(std::iter::once(key_chord.get_key()))
+        {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+
+    keys
+}
+
+fn uses_key(mapping: &NiaMapping, key: NiaKey) -> bool {
+    mapping.get_key_chords().iter().any(|key_chord| {
+        key_chord.get_key().matches(key)
+            || key_chord
+                .get_modifiers()
+                .iter()
+                .any(|modifier| modifier.matches(key))
+    })
+}
+
+pub fn resolve_keys(
+    key_chords: &[NiaKeyChord],
+    mapping_index: &NiaMappingIndex,
+    defined_modifiers: &[NiaModifierDescription],
+) -> NiaKeyResolution {
+    let keys = get_keys(key_chords);
+
+    // The mapping bound to the very sequence goes first, otherwise the one
+    // that the same key presses fire, e.g. a mapping of any device.
+    let mapping = mapping_index
+        .get(key_chords)
+        .or_else(|| mapping_index.get_overlapping(key_chords).first().cloned())
+        .cloned();
+
+    let continuations = mapping_index
+        .get_continuations(key_chords)
+        .into_iter()
+        .cloned()
+        .collect();
+
+    let modifiers = defined_modifiers
+        .iter()
+        .filter(|modifier| {
+            keys.iter().any(|key| modifier.get_key().matches(*key))
+        })
+        .cloned()
+        .collect();
+
+    let related_mappings = mapping_index
+        .get_mappings()
+        .into_iter()
+        .filter(|mapping| keys.iter().any(|key| uses_key(mapping, *key)))
+        .cloned()
+        .collect();
+
+    NiaKeyResolution::new(mapping, continuations, modifiers, related_mappings)
+}
+
+impl Serializable<NiaKeyResolution, nia_protocol_rust::KeyResolution>
+    for NiaKeyResolution
+{
+    fn to_pb(&self) -> nia_protocol_rust::KeyResolution {
+        let mut key_resolution_pb = nia_protocol_rust::KeyResolution::new();
+
+        if let Some(mapping) = &self.mapping {
+            key_resolution_pb.set_mapping(mapping.to_pb());
+        }
+
+        key_resolution_pb.set_continuations(
+            self.continuations
+                .iter()
+                .map(|mapping| mapping.to_pb())
+                .collect(),
+        );
+        key_resolution_pb.set_modifiers(
+            self.modifiers
+                .iter()
+                .map(|modifier| modifier.to_pb())
+                .collect(),
+        );
+        key_resolution_pb.set_related_mappings(
+            self.related_mappings
+                .iter()
+                .map(|mapping| mapping.to_pb())
+                .collect(),
+        );
+
+        key_resolution_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::KeyResolution,
+    ) -> NiaServerResult<NiaKeyResolution> {
+        let mut object_pb = object_pb;
+
+        let mapping = if object_pb.has_mapping() {
+            Some(NiaMapping::from_pb(object_pb.take_mapping())?)
+        } else {
+            None
+        };
+
+        let continuations = object_pb
+            .take_continuations()
+            .into_iter()
+            .map(|mapping_pb| NiaMapping::from_pb(mapping_pb))
+            .collect::<NiaServerResult<Vec<NiaMapping>>>()?;
+
+        let modifiers = object_pb
+            .take_modifiers()
+            .into_iter()
+            .map(|modifier_pb| NiaModifierDescription::from_pb(modifier_pb))
+            .collect::<NiaServerResult<Vec<NiaModifierDescription>>>()?;
+
+        let related_mappings = object_pb
+            .take_related_mappings()
+            .into_iter()
+            .map(|mapping_pb| NiaMapping::from_pb(mapping_pb))
+            .collect::<NiaServerResult<Vec<NiaMapping>>>()?;
+
+        let key_resolution = NiaKeyResolution::new(
+            mapping,
+            continuations,
+            modifiers,
+            related_mappings,
+        );
+
+        Ok(key_resolution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::{ActionExecuteOSCommand, NiaAction, NiaActionEnum};
+
+    fn construct_key_chords(key_codes: &[i32]) -> Vec<NiaKeyChord> {
+        key_codes
+            .iter()
+            .map(|key_code| {
+                NiaKeyChord::new(
+                    vec![NiaKey::make_key_1(29)],
+                    NiaKey::make_key_1(*key_code),
+                )
+            })
+            .collect()
+    }
+
+    fn construct_mapping(key_codes: &[i32]) -> NiaMapping {
+        NiaMapping::new(
+            construct_key_chords(key_codes),
+            NiaAction::new(NiaActionEnum::ExecuteOSCommand(
+                ActionExecuteOSCommand::new("echo catgirl"),
+            )),
+        )
+    }
+
+    fn construct_modifiers() -> Vec<NiaModifierDescription> {
+        vec![NiaModifierDescription::new(NiaKey::make_key_1(29), "ctrl")]
+    }
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaKeyResolution::new(
+            Some(construct_mapping(&[45])),
+            vec![construct_mapping(&[45, 31])],
+            construct_modifiers(),
+            vec![construct_mapping(&[45]), construct_mapping(&[45, 31])],
+        );
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaKeyResolution::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn resolves_prefixes_to_continuations() {
+        let mapping_index = NiaMappingIndex::from_mappings(vec![
+            construct_mapping(&[45, 31]),
+            construct_mapping(&[45, 30]),
+            construct_mapping(&[46]),
+        ]);
+
+        let result = resolve_keys(
+            &construct_key_chords(&[45]),
+            &mapping_index,
+            &construct_modifiers(),
+        );
+
+        assert_eq!(None, result.get_mapping());
+        assert!(result.is_prefix());
+        assert_eq!(2, result.get_continuations().len());
+        assert_eq!(&construct_modifiers(), result.get_modifiers());
+        assert_eq!(3, result.get_related_mappings().len());
+    }
+
+    #[test]
+    fn resolves_mappings_involving_a_key() {
+        let mapping_index = NiaMappingIndex::from_mappings(vec![
+            construct_mapping(&[45, 31]),
+            construct_mapping(&[46]),
+        ]);
+
+        let key_chords =
+            vec![NiaKeyChord::new(vec![], NiaKey::make_key_2(1, 31))];
+
+        let result =
+            resolve_keys(&key_chords, &mapping_index, &construct_modifiers());
+
+        assert_eq!(None, result.get_mapping());
+        assert!(!result.is_prefix());
+        assert!(result.get_modifiers().is_empty());
+        assert_eq!(
+            &vec![construct_mapping(&[45, 31])],
+            result.get_related_mappings()
+        );
+    }
+
+    #[test]
+    fn resolves_keys_of_devices_to_mappings_of_any_device() {
+        let mapping_index = NiaMappingIndex::from_mappings(vec![
+            construct_mapping(&[45]),
+            construct_mapping(&[46, 31]),
+        ]);
+
+        let key_chords = vec![NiaKeyChord::new(
+            vec![NiaKey::make_key_2(1, 29)],
+            NiaKey::make_key_2(1, 45),
+        )];
+
+        let result =
+            resolve_keys(&key_chords, &mapping_index, &construct_modifiers());
+
+        assert_eq!(Some(&construct_mapping(&[45])), result.get_mapping());
+        assert_eq!(&construct_modifiers(), result.get_modifiers());
+
+        let key_chords = vec![NiaKeyChord::new(
+            vec![NiaKey::make_key_2(1, 29)],
+            NiaKey::make_key_2(1, 46),
+        )];
+
+        let result =
+            resolve_keys(&key_chords, &mapping_index, &construct_modifiers());
+
+        assert_eq!(None, result.get_mapping());
+        assert_eq!(
+            &vec![construct_mapping(&[46, 31])],
+            result.get_continuations()
+        );
+    }
+}
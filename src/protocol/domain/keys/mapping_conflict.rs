@@ -0,0 +1,507 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{
+    NiaKey, NiaKeyChord, NiaMapping, NiaMappingIndex, NiaModifierDescription,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NiaMappingConflict {
+    // Two mappings are bound to sequences that the same key presses fire,
+    // e.g. to the same sequence, or to a sequence of any device and to the
+    // same sequence of a specific device.
+    Duplicate(Vec<NiaKeyChord>),
+    // The prefix mapping fires before the shadowed one can ever be reached.
+    PrefixShadowing {
+        prefix_key_chords: Vec<NiaKeyChord>,
+        shadowed_key_chords: Vec<NiaKeyChord>,
+    },
+    // A chord uses a key as a modifier that is not defined as a modifier.
+    UndefinedModifier {
+        key_chords: Vec<NiaKeyChord>,
+        modifier: NiaKey,
+    },
+}
+
+// A modifier defined without a device matches the key of any device.
+pub fn is_modifier_defined(
+    modifier: NiaKey,
+    defined_modifiers: &[NiaModifierDescription],
+) -> bool {
+    defined_modifiers.iter().any(|defined_modifier| {
+        let defined_key = defined_modifier.get_key();
+
+        match defined_key {
+            NiaKey::Key1(key_code) => key_code == modifier.get_key_code(),
+            NiaKey::Key2(_, _) => defined_key == modifier,
+        }
+    })
+}
+
+fn find_undefined_modifiers(
+    key_chords: &[NiaKeyChord],
+    defined_modifiers: &[NiaModifierDescription],
+) -> Vec<NiaMappingConflict> {
+    let mut conflicts = Vec::new();
+
+    for key_chord in key_chords {
+        for modifier in key_chord.get_modifiers() {
+            if is_modifier_defined(*modifier, defined_modifiers) {
+                continue;
+            }
+
+            let conflict = NiaMappingConflict::UndefinedModifier {
+                key_chords: key_chords.to_vec(),
+                modifier: *modifier,
+            };
+
+            if !conflicts.contains(&conflict) {
+                conflicts.push(conflict);
+            }
+        }
+    }
+
+    conflicts
+}
+
+// Conflicts a mapping bound to `key_chords` would have with already defined
+// mappings.
+pub fn find_mapping_conflicts(
+    key_chords: &[NiaKeyChord],
+    mapping_index: &NiaMappingIndex,
+    defined_modifiers: &[NiaModifierDescription],
+) -> Vec<NiaMappingConflict> {
+    find_mapping_conflicts_excluding(
+        key_chords,
+        &[],
+        mapping_index,
+        defined_modifiers,
+    )
+}
+
+// Same as find_mapping_conflicts, but the mapping bound to
+// `excluded_key_chords` is ignored, e.g. the mapping being changed or
+// rebound is not a conflict of itself.
+pub fn find_mapping_conflicts_excluding(
+    key_chords: &[NiaKeyChord],
+    excluded_key_chords: &[NiaKeyChord],
+    mapping_index: &NiaMappingIndex,
+    defined_modifiers: &[NiaModifierDescription],
+) -> Vec<NiaMappingConflict> {
+    let is_included = |mapping: &&NiaMapping| {
+        mapping.get_key_chords().as_slice() != excluded_key_chords
+    };
+
+    let mut conflicts = find_undefined_modifiers(key_chords, defined_modifiers);
+
+    if mapping_index
+        .get_overlapping(key_chords)
+        .into_iter()
+        .any(|mapping| is_included(&mapping))
+    {
+        conflicts.push(NiaMappingConflict::Duplicate(key_chords.to_vec()));
+    }
+
+    for prefix_mapping in mapping_index
+        .get_prefix_mappings(key_chords)
+        .into_iter()
+        .filter(is_included)
+    {
+        conflicts.push(NiaMappingConflict::PrefixShadowing {
+            prefix_key_chords: prefix_mapping.get_key_chords().clone(),
+            shadowed_key_chords: key_chords.to_vec(),
+        });
+    }
+
+    for continuation in mapping_index
+        .get_continuations(key_chords)
+        .into_iter()
+        .filter(is_included)
+    {
+        conflicts.push(NiaMappingConflict::PrefixShadowing {
+            prefix_key_chords: key_chords.to_vec(),
+            shadowed_key_chords: continuation.get_key_chords().clone(),
+        });
+    }
+
+    conflicts
+}
+
+// Conflicts between all the defined mappings, each reported once.
+pub fn find_all_mapping_conflicts(
+    defined_mappings: &[NiaMapping],
+    defined_modifiers: &[NiaModifierDescription],
+) -> Vec<NiaMappingConflict> {
+    let mut conflicts = Vec::new();
+    let mut mapping_index = NiaMappingIndex::new();
+
+    for mapping in defined_mappings {
+        let key_chords = mapping.get_key_chords();
+
+        conflicts
+            .extend(find_undefined_modifiers(key_chords, defined_modifiers));
+
+        if mapping_index.insert(mapping.clone()).is_some() {
+            conflicts.push(NiaMappingConflict::Duplicate(key_chords.clone()));
+        }
+    }
+
+    for mapping in mapping_index.get_mappings() {
+        let key_chords = mapping.get_key_chords();
+
+        let has_duplicate = mapping_index
+            .get_overlapping(key_chords)
+            .into_iter()
+            .any(|other_mapping| other_mapping.get_key_chords() != key_chords);
+        let conflict = NiaMappingConflict::Duplicate(key_chords.clone());
+
+        if has_duplicate && !conflicts.contains(&conflict) {
+            conflicts.push(conflict);
+        }
+
+        for continuation in mapping_index.get_continuations(key_chords) {
+            conflicts.push(NiaMappingConflict::PrefixShadowing {
+                prefix_key_chords: key_chords.clone(),
+                shadowed_key_chords: continuation.get_key_chords().clone(),
+            });
+        }
+    }
+
+    conflicts
+}
+
+fn key_chords_to_pb(
+    key_chords: &[NiaKeyChord],
+) -> protobuf::RepeatedField<nia_protocol_rust::KeyChord> {
+    key_chords
+        .iter()
+        .map(|key_chord| key_chord.to_pb())
+        .collect()
+}
+
+fn key_chords_from_pb(
+    key_chords_pb: protobuf::RepeatedField<nia_protocol_rust::KeyChord>,
+) -> NiaServerResult<Vec<NiaKeyChord>> {
+    key_chords_pb
+        .into_iter()
+        .map(|key_chord_pb| NiaKeyChord::from_pb(key_chord_pb))
+        .collect()
+}
+
+impl Serializable<NiaMappingConflict, nia_protocol_rust::MappingConflict>
+    for NiaMappingConflict
+{
+    fn to_pb(&self) -> nia_protocol_rust::MappingConflict {
+        let mut mapping_conflict_pb = nia_protocol_rust::MappingConflict::new();
+
+        match self {
+            NiaMappingConflict::Duplicate(key_chords) => {
+                let mut duplicate_pb =
+                    nia_protocol_rust::DuplicateMappingConflict::new();
+
+                duplicate_pb.set_key_chords(key_chords_to_pb(key_chords));
+
+                mapping_conflict_pb.set_duplicate_conflict(duplicate_pb);
+            }
+            NiaMappingConflict::PrefixShadowing {
+                prefix_key_chords,
+                shadowed_key_chords,
+            } => {
+                let mut prefix_shadowing_pb =
+                    nia_protocol_rust::PrefixShadowingMappingConflict::new();
+
+                prefix_shadowing_pb
+                    .set_prefix_key_chords(key_chords_to_pb(prefix_key_chords));
+                prefix_shadowing_pb.set_shadowed_key_chords(key_chords_to_pb(
+                    shadowed_key_chords,
+                ));
+
+                mapping_conflict_pb
+                    .set_prefix_shadowing_conflict(prefix_shadowing_pb);
+            }
+            NiaMappingConflict::UndefinedModifier {
+                key_chords,
+                modifier,
+            } => {
+                let mut undefined_modifier_pb =
+                    nia_protocol_rust::UndefinedModifierMappingConflict::new();
+
+                undefined_modifier_pb
+                    .set_key_chords(key_chords_to_pb(key_chords));
+                undefined_modifier_pb.set_modifier(modifier.to_pb());
+
+                mapping_conflict_pb
+                    .set_undefined_modifier_conflict(undefined_modifier_pb);
+            }
+        }
+
+        mapping_conflict_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::MappingConflict,
+    ) -> NiaServerResult<NiaMappingConflict> {
+        let mut object_pb = object_pb;
+
+        let mapping_conflict = if object_pb.has_duplicate_conflict() {
+            let mut duplicate_pb = object_pb.take_duplicate_conflict();
+
+            NiaMappingConflict::Duplicate(key_chords_from_pb(
+                duplicate_pb.take_key_chords(),
+            )?)
+        } else if object_pb.has_prefix_shadowing_conflict() {
+            let mut prefix_shadowing_pb =
+                object_pb.take_prefix_shadowing_conflict();
+
+            NiaMappingConflict::PrefixShadowing {
+                prefix_key_chords: key_chords_from_pb(
+                    prefix_shadowing_pb.take_prefix_key_chords(),
+                )?,
+                shadowed_key_chords: key_chords_from_pb(
+                    prefix_shadowing_pb.take_shadowed_key_chords(),
+                )?,
+            }
+        } else if object_pb.has_undefined_modifier_conflict() {
+            let mut undefined_modifier_pb =
+                object_pb.take_undefined_modifier_conflict();
+
+            NiaMappingConflict::UndefinedModifier {
+                key_chords: key_chords_from_pb(
+                    undefined_modifier_pb.take_key_chords(),
+                )?,
+                modifier: NiaKey::from_pb(
+                    undefined_modifier_pb.take_modifier(),
+                )?,
+            }
+        } else {
+            return NiaServerError::deserialization_error(
+                "Unknown mapping conflict.",
+            )
+            .into();
+        };
+
+        Ok(mapping_conflict)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::{ActionExecuteOSCommand, NiaAction, NiaActionEnum};
+
+    fn construct_key_chord(key_code: i32) -> NiaKeyChord {
+        NiaKeyChord::new(
+            vec![NiaKey::make_key_1(29)],
+            NiaKey::make_key_1(key_code),
+        )
+    }
+
+    fn construct_mapping(key_codes: Vec<i32>) -> NiaMapping {
+        NiaMapping::new(
+            key_codes.into_iter().map(construct_key_chord).collect(),
+            NiaAction::new(NiaActionEnum::ExecuteOSCommand(
+                ActionExecuteOSCommand::new("echo catgirl"),
+            )),
+        )
+    }
+
+    fn construct_modifiers() -> Vec<NiaModifierDescription> {
+        vec![NiaModifierDescription::new(NiaKey::make_key_1(29), "ctrl")]
+    }
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = vec![
+            NiaMappingConflict::Duplicate(vec![construct_key_chord(45)]),
+            NiaMappingConflict::PrefixShadowing {
+                prefix_key_chords: vec![construct_key_chord(45)],
+                shadowed_key_chords: vec![
+                    construct_key_chord(45),
+                    construct_key_chord(31),
+                ],
+            },
+            NiaMappingConflict::UndefinedModifier {
+                key_chords: vec![construct_key_chord(45)],
+                modifier: NiaKey::make_key_2(1, 56),
+            },
+        ];
+
+        for expected in expected {
+            let bytes = expected.to_bytes().unwrap();
+            let result = NiaMappingConflict::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, result);
+        }
+    }
+
+    #[test]
+    fn finds_duplicates_and_prefix_shadowing() {
+        let mapping_index = NiaMappingIndex::from_mappings(vec![
+            construct_mapping(vec![45, 31]),
+            construct_mapping(vec![46]),
+        ]);
+
+        let expected = vec![NiaMappingConflict::PrefixShadowing {
+            prefix_key_chords: vec![construct_key_chord(45)],
+            shadowed_key_chords: vec![
+                construct_key_chord(45),
+                construct_key_chord(31),
+            ],
+        }];
+        let result = find_mapping_conflicts(
+            &[construct_key_chord(45)],
+            &mapping_index,
+            &construct_modifiers(),
+        );
+
+        assert_eq!(expected, result);
+
+        let expected =
+            vec![NiaMappingConflict::Duplicate(vec![construct_key_chord(46)])];
+        let result = find_mapping_conflicts(
+            &[construct_key_chord(46)],
+            &mapping_index,
+            &construct_modifiers(),
+        );
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn ignores_excluded_mapping() {
+        let mapping_index = NiaMappingIndex::from_mappings(vec![
+            construct_mapping(vec![45, 31]),
+            construct_mapping(vec![46]),
+        ]);
+
+        let result = find_mapping_conflicts_excluding(
+            &[construct_key_chord(46)],
+            &[construct_key_chord(46)],
+            &mapping_index,
+            &construct_modifiers(),
+        );
+
+        assert!(result.is_empty());
+
+        let expected = vec![NiaMappingConflict::PrefixShadowing {
+            prefix_key_chords: vec![construct_key_chord(45)],
+            shadowed_key_chords: vec![
+                construct_key_chord(45),
+                construct_key_chord(31),
+            ],
+        }];
+        let result = find_mapping_conflicts_excluding(
+            &[construct_key_chord(45)],
+            &[construct_key_chord(46)],
+            &mapping_index,
+            &construct_modifiers(),
+        );
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn finds_undefined_modifiers() {
+        let key_chords = vec![NiaKeyChord::new(
+            vec![NiaKey::make_key_2(1, 29), NiaKey::make_key_1(56)],
+            NiaKey::make_key_1(30),
+        )];
+
+        let expected = vec![NiaMappingConflict::UndefinedModifier {
+            key_chords: key_chords.clone(),
+            modifier: NiaKey::make_key_1(56),
+        }];
+        let result = find_mapping_conflicts(
+            &key_chords,
+            &NiaMappingIndex::new(),
+            &construct_modifiers(),
+        );
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn reports_each_conflict_of_defined_mappings_once() {
+        let defined_mappings = vec![
+            construct_mapping(vec![45]),
+            construct_mapping(vec![45, 31]),
+            construct_mapping(vec![46]),
+        ];
+
+        let result = find_all_mapping_conflicts(
+            &defined_mappings,
+            &construct_modifiers(),
+        );
+
+        assert_eq!(1, result.len());
+    }
+
+    #[test]
+    fn finds_conflicts_between_devices() {
+        let first_device_key_chord = NiaKeyChord::new(
+            vec![NiaKey::make_key_2(1, 29)],
+            NiaKey::make_key_2(1, 45),
+        );
+        let mapping_index = NiaMappingIndex::from_mappings(vec![
+            construct_mapping(vec![45]),
+            construct_mapping(vec![46, 31]),
+        ]);
+
+        let expected = vec![NiaMappingConflict::Duplicate(vec![
+            first_device_key_chord.clone(),
+        ])];
+        let result = find_mapping_conflicts(
+            &[first_device_key_chord],
+            &mapping_index,
+            &construct_modifiers(),
+        );
+
+        assert_eq!(expected, result);
+
+        let key_chords = vec![NiaKeyChord::new(
+            vec![NiaKey::make_key_2(2, 29)],
+            NiaKey::make_key_2(2, 46),
+        )];
+
+        let expected = vec![NiaMappingConflict::PrefixShadowing {
+            prefix_key_chords: key_chords.clone(),
+            shadowed_key_chords: vec![
+                construct_key_chord(46),
+                construct_key_chord(31),
+            ],
+        }];
+        let result = find_mapping_conflicts(
+            &key_chords,
+            &mapping_index,
+            &construct_modifiers(),
+        );
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn reports_duplicates_between_devices_of_defined_mappings() {
+        let defined_mappings = vec![
+            construct_mapping(vec![45]),
+            NiaMapping::new(
+                vec![NiaKeyChord::new(
+                    vec![NiaKey::make_key_2(1, 29)],
+                    NiaKey::make_key_2(1, 45),
+                )],
+                construct_mapping(vec![45]).get_action().clone(),
+            ),
+            construct_mapping(vec![46]),
+        ];
+
+        let result = find_all_mapping_conflicts(
+            &defined_mappings,
+            &construct_modifiers(),
+        );
+
+        assert_eq!(2, result.len());
+        assert!(result.iter().all(|conflict| match conflict {
+            NiaMappingConflict::Duplicate(_) => true,
+            _ => false,
+        }));
+    }
+}
@@ -0,0 +1,333 @@
+use std::collections::BTreeMap;
+
+use crate::protocol::{NiaKeyChord, NiaMapping};
+
+#[derive(Clone, Debug, Default)]
+struct NiaMappingIndexNode {
+    mapping: Option<NiaMapping>,
+    children: BTreeMap<NiaKeyChord, NiaMappingIndexNode>,
+}
+
+impl NiaMappingIndexNode {
+    fn collect_mappings<'a>(&'a self, mappings: &mut Vec<&'a NiaMapping>) {
+        if let Some(mapping) = &self.mapping {
+            mappings.push(mapping);
+        }
+
+        for child in self.children.values() {
+            child.collect_mappings(mappings);
+        }
+    }
+
+    fn remove(&mut self, key_chords: &[NiaKeyChord]) -> Option<NiaMapping> {
+        let (key_chord, rest) = match key_chords.split_first() {
+            Some(split) => split,
+            None => return self.mapping.take(),
+        };
+
+        let child = self.children.get_mut(key_chord)?;
+        let removed_mapping = child.remove(rest);
+
+        if child.mapping.is_none() && child.children.is_empty() {
+            self.children.remove(key_chord);
+        }
+
+        removed_mapping
+    }
+}
+
+// Trie of mappings keyed by their key chord sequences, so that lookups of a
+// sequence, of its prefixes and of its continuations do not depend on the
+// number of defined mappings. Exact lookups find the mapping bound to the
+// very sequence, the others are device aware: a chord of any device is met
+// by the same chord of a specific device and the other way around.
+#[derive(Clone, Debug, Default)]
+pub struct NiaMappingIndex {
+    root: NiaMappingIndexNode,
+    mappings_count: usize,
+}
+
+impl NiaMappingIndex {
+    pub fn new() -> NiaMappingIndex {
+        NiaMappingIndex::default()
+    }
+
+    pub fn from_mappings(mappings: Vec<NiaMapping>) -> NiaMappingIndex {
+        let mut mapping_index = NiaMappingIndex::new();
+
+        for mapping in mappings {
+            mapping_index.insert(mapping);
+        }
+
+        mapping_index
+    }
+
+    pub fn len(&self) -> usize {
+        self.mappings_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mappings_count == 0
+    }
+
+    fn get_node(
+        &self,
+        key_chords: &[NiaKeyChord],
+    ) -> Option<&NiaMappingIndexNode> {
+        let mut node = &self.root;
+
+        for key_chord in key_chords {
+            node = node.children.get(key_chord)?;
+        }
+
+        Some(node)
+    }
+
+    // Nodes of the sequences which chords overlap the given ones.
+    fn get_overlapping_nodes(
+        &self,
+        key_chords: &[NiaKeyChord],
+    ) -> Vec<&NiaMappingIndexNode> {
+        let mut nodes = vec![&self.root];
+
+        for key_chord in key_chords {
+            nodes = nodes
+                .into_iter()
+                .flat_map(|node| {
+                    node.children
+                        .iter()
+                        .filter(move |(child_key_chord, _)| {
+                            child_key_chord.overlaps(key_chord)
+                        })
+                        .map(|(_, child)| child)
+                })
+                .collect();
+
+            if nodes.is_empty() {
+                break;
+            }
+        }
+
+        nodes
+    }
+
+    // Returns the mapping that was previously bound to the same sequence.
+    pub fn insert(&mut self, mapping: NiaMapping) -> Option<NiaMapping> {
+        let mut node = &mut self.root;
+
+        for key_chord in mapping.get_key_chords() {
+            node = node
+                .children
+                .entry(key_chord.clone())
+                .or_insert_with(NiaMappingIndexNode::default);
+        }
+
+        let previous_mapping = node.mapping.replace(mapping);
+
+        if previous_mapping.is_none() {
+            self.mappings_count += 1;
+        }
+
+        previous_mapping
+    }
+
+    pub fn remove(&mut self, key_chords: &[NiaKeyChord]) -> Option<NiaMapping> {
+        let removed_mapping = self.root.remove(key_chords);
+
+        if removed_mapping.is_some() {
+            self.mappings_count -= 1;
+        }
+
+        removed_mapping
+    }
+
+    pub fn get(&self, key_chords: &[NiaKeyChord]) -> Option<&NiaMapping> {
+        self.get_node(key_chords)
+            .and_then(|node| node.mapping.as_ref())
+    }
+
+    // Mappings bound to sequences that are fired by the same key presses as
+    // the given one, including the mapping bound to the very sequence.
+    pub fn get_overlapping(
+        &self,
+        key_chords: &[NiaKeyChord],
+    ) -> Vec<&NiaMapping> {
+        self.get_overlapping_nodes(key_chords)
+            .into_iter()
+            .filter_map(|node| node.mapping.as_ref())
+            .collect()
+    }
+
+    // Mappings bound to proper prefixes of the sequence, shortest first.
+    pub fn get_prefix_mappings(
+        &self,
+        key_chords: &[NiaKeyChord],
+    ) -> Vec<&NiaMapping> {
+        let mut prefix_mappings = Vec::new();
+
+        for length in 1..key_chords.len() {
+            prefix_mappings.extend(self.get_overlapping(&key_chords[..length]));
+        }
+
+        prefix_mappings
+    }
+
+    // Mappings which sequences start with, but are longer than the sequence.
+    pub fn get_continuations(
+        &self,
+        key_chords: &[NiaKeyChord],
+    ) -> Vec<&NiaMapping> {
+        let mut continuations = Vec::new();
+
+        for node in self.get_overlapping_nodes(key_chords) {
+            for child in node.children.values() {
+                child.collect_mappings(&mut continuations);
+            }
+        }
+
+        continuations
+    }
+
+    // Key chords that may follow the sequence.
+    pub fn get_next_key_chords(
+        &self,
+        key_chords: &[NiaKeyChord],
+    ) -> Vec<&NiaKeyChord> {
+        match self.get_node(key_chords) {
+            Some(node) => node.children.keys().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn get_mappings(&self) -> Vec<&NiaMapping> {
+        let mut mappings = Vec::new();
+
+        self.root.collect_mappings(&mut mappings);
+
+        mappings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::{
+        ActionExecuteOSCommand, NiaAction, NiaActionEnum, NiaKey,
+    };
+
+    fn construct_key_chords(key_codes: &[i32]) -> Vec<NiaKeyChord> {
+        key_codes
+            .iter()
+            .map(|key_code| {
+                NiaKeyChord::new(
+                    vec![NiaKey::make_key_1(29)],
+                    NiaKey::make_key_1(*key_code),
+                )
+            })
+            .collect()
+    }
+
+    fn construct_mapping(key_codes: &[i32]) -> NiaMapping {
+        NiaMapping::new(
+            construct_key_chords(key_codes),
+            NiaAction::new(NiaActionEnum::ExecuteOSCommand(
+                ActionExecuteOSCommand::new("echo catgirl"),
+            )),
+        )
+    }
+
+    fn construct_mapping_index() -> NiaMappingIndex {
+        NiaMappingIndex::from_mappings(vec![
+            construct_mapping(&[45]),
+            construct_mapping(&[45, 31]),
+            construct_mapping(&[45, 31, 30]),
+            construct_mapping(&[46]),
+        ])
+    }
+
+    #[test]
+    fn finds_mappings_by_key_chords() {
+        let mapping_index = construct_mapping_index();
+
+        assert_eq!(4, mapping_index.len());
+        assert_eq!(
+            Some(&construct_mapping(&[45, 31])),
+            mapping_index.get(&construct_key_chords(&[45, 31]))
+        );
+        assert_eq!(None, mapping_index.get(&construct_key_chords(&[31])));
+    }
+
+    #[test]
+    fn finds_prefix_mappings_and_continuations() {
+        let mapping_index = construct_mapping_index();
+
+        assert_eq!(
+            vec![&construct_mapping(&[45])],
+            mapping_index.get_prefix_mappings(&construct_key_chords(&[45, 31]))
+        );
+        assert_eq!(
+            vec![
+                &construct_mapping(&[45, 31]),
+                &construct_mapping(&[45, 31, 30])
+            ],
+            mapping_index.get_continuations(&construct_key_chords(&[45]))
+        );
+    }
+
+    #[test]
+    fn removes_mappings() {
+        let mut mapping_index = construct_mapping_index();
+
+        let removed =
+            mapping_index.remove(&construct_key_chords(&[45, 31, 30]));
+
+        assert_eq!(Some(construct_mapping(&[45, 31, 30])), removed);
+        assert_eq!(3, mapping_index.len());
+        assert!(mapping_index
+            .get_continuations(&construct_key_chords(&[45, 31]))
+            .is_empty());
+        assert_eq!(None, mapping_index.remove(&construct_key_chords(&[31])));
+    }
+
+    #[test]
+    fn finds_mappings_of_any_device_and_of_specific_devices() {
+        let any_device = construct_mapping(&[45]);
+        let first_device = NiaMapping::new(
+            vec![
+                NiaKeyChord::new(
+                    vec![NiaKey::make_key_2(1, 29)],
+                    NiaKey::make_key_2(1, 45),
+                ),
+                NiaKeyChord::new(
+                    vec![NiaKey::make_key_2(1, 29)],
+                    NiaKey::make_key_2(1, 31),
+                ),
+            ],
+            any_device.get_action().clone(),
+        );
+        let mapping_index = NiaMappingIndex::from_mappings(vec![
+            any_device.clone(),
+            first_device.clone(),
+        ]);
+
+        assert_eq!(
+            vec![&any_device],
+            mapping_index.get_overlapping(&first_device.get_key_chords()[..1])
+        );
+        assert_eq!(
+            vec![&any_device],
+            mapping_index.get_prefix_mappings(first_device.get_key_chords())
+        );
+        assert_eq!(
+            vec![&first_device],
+            mapping_index.get_continuations(any_device.get_key_chords())
+        );
+        assert!(mapping_index
+            .get_continuations(&[NiaKeyChord::new(
+                vec![NiaKey::make_key_2(2, 29)],
+                NiaKey::make_key_2(2, 45),
+            )])
+            .is_empty());
+    }
+}
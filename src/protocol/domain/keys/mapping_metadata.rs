@@ -0,0 +1,163 @@
+use crate::error::NiaServerResult;
+use crate::protocol::Serializable;
+
+// What the server knows about a mapping besides its key chords and action.
+// Timestamps are milliseconds since the Unix epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NiaMappingMetadata {
+    enabled: bool,
+    description: String,
+    tags: Vec<String>,
+    created_at: u64,
+    modified_at: u64,
+}
+
+impl NiaMappingMetadata {
+    pub fn new(timestamp: u64) -> NiaMappingMetadata {
+        NiaMappingMetadata {
+            enabled: true,
+            description: String::new(),
+            tags: Vec::new(),
+            created_at: timestamp,
+            modified_at: timestamp,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn get_tags(&self) -> &Vec<String> {
+        &self.tags
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|existing_tag| existing_tag == tag)
+    }
+
+    pub fn get_created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    pub fn get_modified_at(&self) -> u64 {
+        self.modified_at
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool, timestamp: u64) {
+        self.enabled = enabled;
+        self.modified_at = timestamp;
+    }
+
+    pub fn set_description<S>(&mut self, description: S, timestamp: u64)
+    where
+        S: Into<String>,
+    {
+        self.description = description.into();
+        self.modified_at = timestamp;
+    }
+
+    pub fn set_tags(&mut self, tags: Vec<String>, timestamp: u64) {
+        let mut tags = tags;
+
+        tags.sort();
+        tags.dedup();
+
+        self.tags = tags;
+        self.modified_at = timestamp;
+    }
+
+    pub fn touch(&mut self, timestamp: u64) {
+        self.modified_at = timestamp;
+    }
+}
+
+impl Serializable<NiaMappingMetadata, nia_protocol_rust::MappingMetadata>
+    for NiaMappingMetadata
+{
+    fn to_pb(&self) -> nia_protocol_rust::MappingMetadata {
+        let mut mapping_metadata_pb = nia_protocol_rust::MappingMetadata::new();
+
+        mapping_metadata_pb.set_enabled(self.enabled);
+        mapping_metadata_pb
+            .set_description(protobuf::Chars::from(self.description.clone()));
+        mapping_metadata_pb.set_tags(
+            self.tags
+                .iter()
+                .map(|tag| protobuf::Chars::from(tag.clone()))
+                .collect(),
+        );
+        mapping_metadata_pb.set_created_at(self.created_at);
+        mapping_metadata_pb.set_modified_at(self.modified_at);
+
+        mapping_metadata_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::MappingMetadata,
+    ) -> NiaServerResult<NiaMappingMetadata> {
+        let mut object_pb = object_pb;
+
+        let tags = object_pb
+            .take_tags()
+            .into_iter()
+            .map(|tag| tag.to_string())
+            .collect();
+
+        let mapping_metadata = NiaMappingMetadata {
+            enabled: object_pb.get_enabled(),
+            description: object_pb.take_description().to_string(),
+            tags,
+            created_at: object_pb.get_created_at(),
+            modified_at: object_pb.get_modified_at(),
+        };
+
+        Ok(mapping_metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let mut expected = NiaMappingMetadata::new(100);
+
+        expected.set_enabled(false, 200);
+        expected.set_description("Switches to the terminal", 300);
+        expected
+            .set_tags(vec![String::from("wm"), String::from("gaming")], 400);
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaMappingMetadata::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn updates_modification_time() {
+        let mut mapping_metadata = NiaMappingMetadata::new(100);
+
+        mapping_metadata.set_tags(
+            vec![
+                String::from("wm"),
+                String::from("gaming"),
+                String::from("wm"),
+            ],
+            200,
+        );
+
+        assert_eq!(100, mapping_metadata.get_created_at());
+        assert_eq!(200, mapping_metadata.get_modified_at());
+        assert_eq!(
+            &vec![String::from("gaming"), String::from("wm")],
+            mapping_metadata.get_tags()
+        );
+        assert!(mapping_metadata.has_tag("wm"));
+    }
+}
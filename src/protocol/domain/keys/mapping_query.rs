@@ -0,0 +1,494 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{
+    NiaActionEnum, NiaKey, NiaMapping, NiaMappingMetadata, ACTION_TYPE_NAMES,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NiaMappingSortKey {
+    KeyChords,
+    CreatedAt,
+    ModifiedAt,
+    Description,
+}
+
+// Filters, order and page of defined mappings. Every filter that is set must
+// match, an empty query selects everything.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NiaMappingQuery {
+    device_ids: Vec<i32>,
+    key: Option<NiaKey>,
+    modifier: Option<NiaKey>,
+    action_type: Option<String>,
+    action_name: Option<String>,
+    tag: Option<String>,
+    sort_key: NiaMappingSortKey,
+    descending: bool,
+    offset: usize,
+    limit: Option<usize>,
+}
+
+impl NiaMappingQuery {
+    pub fn new() -> NiaMappingQuery {
+        NiaMappingQuery {
+            device_ids: Vec::new(),
+            key: None,
+            modifier: None,
+            action_type: None,
+            action_name: None,
+            tag: None,
+            sort_key: NiaMappingSortKey::KeyChords,
+            descending: false,
+            offset: 0,
+            limit: None,
+        }
+    }
+
+    pub fn get_device_ids(&self) -> &Vec<i32> {
+        &self.device_ids
+    }
+
+    pub fn get_key(&self) -> Option<NiaKey> {
+        self.key
+    }
+
+    pub fn get_modifier(&self) -> Option<NiaKey> {
+        self.modifier
+    }
+
+    pub fn get_action_type(&self) -> Option<&str> {
+        self.action_type.as_deref()
+    }
+
+    pub fn get_action_name(&self) -> Option<&str> {
+        self.action_name.as_deref()
+    }
+
+    pub fn get_tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    pub fn get_sort_key(&self) -> NiaMappingSortKey {
+        self.sort_key
+    }
+
+    pub fn is_descending(&self) -> bool {
+        self.descending
+    }
+
+    pub fn get_offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn get_limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    pub fn set_device_ids(&mut self, device_ids: Vec<i32>) {
+        self.device_ids = device_ids;
+    }
+
+    pub fn set_key(&mut self, key: Option<NiaKey>) {
+        self.key = key;
+    }
+
+    pub fn set_modifier(&mut self, modifier: Option<NiaKey>) {
+        self.modifier = modifier;
+    }
+
+    pub fn set_action_type(&mut self, action_type: Option<String>) {
+        self.action_type = action_type;
+    }
+
+    pub fn set_action_name(&mut self, action_name: Option<String>) {
+        self.action_name = action_name;
+    }
+
+    pub fn set_tag(&mut self, tag: Option<String>) {
+        self.tag = tag;
+    }
+
+    pub fn set_sort_key(&mut self, sort_key: NiaMappingSortKey) {
+        self.sort_key = sort_key;
+    }
+
+    pub fn set_descending(&mut self, descending: bool) {
+        self.descending = descending;
+    }
+
+    pub fn set_offset(&mut self, offset: usize) {
+        self.offset = offset;
+    }
+
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+    }
+
+    pub fn check(&self) -> NiaServerResult<()> {
+        if let Some(action_type) = &self.action_type {
+            if !ACTION_TYPE_NAMES.contains(&action_type.as_str()) {
+                return NiaServerError::invalid_request(format!(
+                    "Unknown action type: {}.",
+                    action_type
+                ))
+                .into();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn matches(
+        &self,
+        mapping: &NiaMapping,
+        mapping_metadata: &NiaMappingMetadata,
+    ) -> bool {
+        let key_chords = mapping.get_key_chords();
+        let action = mapping.get_action().get_action();
+
+        let device_matches = self.device_ids.is_empty()
+            || key_chords.iter().any(|key_chord| {
+                key_chord
+                    .get_modifiers()
+                    .iter()
+                    .chain(std::iter::once(&key_chord.get_key()))
+                    .filter_map(|key| key.get_device_id())
+                    .any(|device_id| self.device_ids.contains(&device_id))
+            });
+
+        let key_matches = match self.key {
+            Some(key) => key_chords.iter().any(|key_chord| {
+                key_chord.get_key().matches(key)
+                    || key_chord
+                        .get_modifiers()
+                        .iter()
+                        .any(|modifier| modifier.matches(key))
+            }),
+            None => true,
+        };
+
+        let modifier_matches = match self.modifier {
+            Some(modifier) => key_chords.iter().any(|key_chord| {
+                key_chord
+                    .get_modifiers()
+                    .iter()
+                    .any(|chord_modifier| chord_modifier.matches(modifier))
+            }),
+            None => true,
+        };
+
+        let action_type_matches = match &self.action_type {
+            Some(action_type) => action.get_type_name() == action_type,
+            None => true,
+        };
+
+        let action_name_matches = match &self.action_name {
+            Some(action_name) => match action {
+                NiaActionEnum::ExecuteNamedAction(
+                    action_execute_named_action,
+                ) => {
+                    action_execute_named_action.get_action_name() == action_name
+                }
+                _ => false,
+            },
+            None => true,
+        };
+
+        let tag_matches = match &self.tag {
+            Some(tag) => mapping_metadata.has_tag(tag),
+            None => true,
+        };
+
+        device_matches
+            && key_matches
+            && modifier_matches
+            && action_type_matches
+            && action_name_matches
+            && tag_matches
+    }
+
+    // Returns the requested page of the matching mappings and the number of
+    // all matching mappings.
+    pub fn apply(
+        &self,
+        mappings: Vec<(NiaMapping, NiaMappingMetadata)>,
+    ) -> (Vec<(NiaMapping, NiaMappingMetadata)>, usize) {
+        let mut mappings = mappings
+            .into_iter()
+            .filter(|(mapping, mapping_metadata)| {
+                self.matches(mapping, mapping_metadata)
+            })
+            .collect::<Vec<(NiaMapping, NiaMappingMetadata)>>();
+
+        match self.sort_key {
+            NiaMappingSortKey::KeyChords => {
+                mappings.sort_by(|(a, _), (b, _)| {
+                    a.get_key_chords().cmp(b.get_key_chords())
+                })
+            }
+            NiaMappingSortKey::CreatedAt => {
+                mappings.sort_by_key(|(_, mapping_metadata)| {
+                    mapping_metadata.get_created_at()
+                })
+            }
+            NiaMappingSortKey::ModifiedAt => {
+                mappings.sort_by_key(|(_, mapping_metadata)| {
+                    mapping_metadata.get_modified_at()
+                })
+            }
+            NiaMappingSortKey::Description => {
+                mappings.sort_by(|(_, a), (_, b)| {
+                    a.get_description().cmp(b.get_description())
+                })
+            }
+        }
+
+        if self.descending {
+            mappings.reverse();
+        }
+
+        let total_count = mappings.len();
+
+        let page = mappings
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(total_count))
+            .collect();
+
+        (page, total_count)
+    }
+}
+
+impl Serializable<NiaMappingQuery, nia_protocol_rust::MappingQuery>
+    for NiaMappingQuery
+{
+    fn to_pb(&self) -> nia_protocol_rust::MappingQuery {
+        let mut mapping_query_pb = nia_protocol_rust::MappingQuery::new();
+
+        mapping_query_pb.set_device_ids(self.device_ids.clone());
+
+        if let Some(key) = self.key {
+            mapping_query_pb.set_key(key.to_pb());
+        }
+
+        if let Some(modifier) = self.modifier {
+            mapping_query_pb.set_modifier(modifier.to_pb());
+        }
+
+        if let Some(action_type) = &self.action_type {
+            mapping_query_pb
+                .set_action_type(protobuf::Chars::from(action_type.clone()));
+        }
+
+        if let Some(action_name) = &self.action_name {
+            mapping_query_pb
+                .set_action_name(protobuf::Chars::from(action_name.clone()));
+        }
+
+        if let Some(tag) = &self.tag {
+            mapping_query_pb.set_tag(protobuf::Chars::from(tag.clone()));
+        }
+
+        match self.sort_key {
+            NiaMappingSortKey::KeyChords => mapping_query_pb
+                .set_sort_by_key_chords(
+                    nia_protocol_rust::SortByKeyChords::new(),
+                ),
+            NiaMappingSortKey::CreatedAt => mapping_query_pb
+                .set_sort_by_created_at(
+                    nia_protocol_rust::SortByCreatedAt::new(),
+                ),
+            NiaMappingSortKey::ModifiedAt => mapping_query_pb
+                .set_sort_by_modified_at(
+                    nia_protocol_rust::SortByModifiedAt::new(),
+                ),
+            NiaMappingSortKey::Description => mapping_query_pb
+                .set_sort_by_description(
+                    nia_protocol_rust::SortByDescription::new(),
+                ),
+        }
+
+        mapping_query_pb.set_descending(self.descending);
+        mapping_query_pb.set_offset(self.offset as u32);
+        mapping_query_pb.set_limit(self.limit.unwrap_or(0) as u32);
+
+        mapping_query_pb
+    }
+
+    // Empty strings and a zero limit mean that the field is not set.
+    fn from_pb(
+        object_pb: nia_protocol_rust::MappingQuery,
+    ) -> NiaServerResult<NiaMappingQuery> {
+        let mut object_pb = object_pb;
+        let mut mapping_query = NiaMappingQuery::new();
+
+        let non_empty = |string: protobuf::Chars| {
+            if string.is_empty() {
+                None
+            } else {
+                Some(string.to_string())
+            }
+        };
+
+        mapping_query.set_device_ids(object_pb.take_device_ids());
+
+        if object_pb.has_key() {
+            mapping_query.set_key(Some(NiaKey::from_pb(object_pb.take_key())?));
+        }
+
+        if object_pb.has_modifier() {
+            mapping_query.set_modifier(Some(NiaKey::from_pb(
+                object_pb.take_modifier(),
+            )?));
+        }
+
+        mapping_query.set_action_type(non_empty(object_pb.take_action_type()));
+        mapping_query.set_action_name(non_empty(object_pb.take_action_name()));
+        mapping_query.set_tag(non_empty(object_pb.take_tag()));
+
+        let sort_key = if object_pb.has_sort_by_created_at() {
+            NiaMappingSortKey::CreatedAt
+        } else if object_pb.has_sort_by_modified_at() {
+            NiaMappingSortKey::ModifiedAt
+        } else if object_pb.has_sort_by_description() {
+            NiaMappingSortKey::Description
+        } else {
+            NiaMappingSortKey::KeyChords
+        };
+
+        mapping_query.set_sort_key(sort_key);
+        mapping_query.set_descending(object_pb.get_descending());
+        mapping_query.set_offset(object_pb.get_offset() as usize);
+
+        let limit = match object_pb.get_limit() {
+            0 => None,
+            limit => Some(limit as usize),
+        };
+
+        mapping_query.set_limit(limit);
+
+        Ok(mapping_query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::{
+        ActionExecuteNamedAction, ActionKeyClick, NiaAction, NiaKeyChord,
+    };
+
+    fn construct_mappings() -> Vec<(NiaMapping, NiaMappingMetadata)> {
+        let mut tagged_metadata = NiaMappingMetadata::new(300);
+
+        tagged_metadata.set_tags(vec![String::from("gaming")], 300);
+
+        vec![
+            (
+                NiaMapping::new(
+                    vec![NiaKeyChord::new(
+                        vec![NiaKey::make_key_2(1, 29)],
+                        NiaKey::make_key_2(1, 30),
+                    )],
+                    NiaAction::new(ActionKeyClick::new(31).into()),
+                ),
+                NiaMappingMetadata::new(200),
+            ),
+            (
+                NiaMapping::new(
+                    vec![NiaKeyChord::new(vec![], NiaKey::make_key_2(2, 30))],
+                    NiaAction::new(
+                        ActionExecuteNamedAction::new("terminal").into(),
+                    ),
+                ),
+                tagged_metadata,
+            ),
+            (
+                NiaMapping::new(
+                    vec![NiaKeyChord::new(
+                        vec![NiaKey::make_key_1(56)],
+                        NiaKey::make_key_1(45),
+                    )],
+                    NiaAction::new(ActionKeyClick::new(32).into()),
+                ),
+                NiaMappingMetadata::new(100),
+            ),
+        ]
+    }
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let mut expected = NiaMappingQuery::new();
+
+        expected.set_device_ids(vec![1, 2]);
+        expected.set_key(Some(NiaKey::make_key_2(1, 30)));
+        expected.set_modifier(Some(NiaKey::make_key_1(29)));
+        expected.set_action_type(Some(String::from("KeyClick")));
+        expected.set_action_name(Some(String::from("terminal")));
+        expected.set_tag(Some(String::from("gaming")));
+        expected.set_sort_key(NiaMappingSortKey::ModifiedAt);
+        expected.set_descending(true);
+        expected.set_offset(20);
+        expected.set_limit(Some(10));
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaMappingQuery::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn filters_mappings() {
+        let mut mapping_query = NiaMappingQuery::new();
+
+        mapping_query.set_device_ids(vec![1]);
+        assert_eq!(1, mapping_query.apply(construct_mappings()).1);
+
+        let mut mapping_query = NiaMappingQuery::new();
+
+        mapping_query.set_key(Some(NiaKey::make_key_1(30)));
+        assert_eq!(2, mapping_query.apply(construct_mappings()).1);
+
+        let mut mapping_query = NiaMappingQuery::new();
+
+        mapping_query.set_modifier(Some(NiaKey::make_key_1(29)));
+        assert_eq!(1, mapping_query.apply(construct_mappings()).1);
+
+        let mut mapping_query = NiaMappingQuery::new();
+
+        mapping_query.set_action_type(Some(String::from("KeyClick")));
+        assert_eq!(2, mapping_query.apply(construct_mappings()).1);
+
+        let mut mapping_query = NiaMappingQuery::new();
+
+        mapping_query.set_action_name(Some(String::from("terminal")));
+        mapping_query.set_tag(Some(String::from("gaming")));
+        assert_eq!(1, mapping_query.apply(construct_mappings()).1);
+    }
+
+    #[test]
+    fn sorts_and_paginates_mappings() {
+        let mut mapping_query = NiaMappingQuery::new();
+
+        mapping_query.set_sort_key(NiaMappingSortKey::CreatedAt);
+        mapping_query.set_descending(true);
+        mapping_query.set_offset(1);
+        mapping_query.set_limit(Some(1));
+
+        let (page, total_count) = mapping_query.apply(construct_mappings());
+
+        assert_eq!(3, total_count);
+        assert_eq!(1, page.len());
+        assert_eq!(200, page[0].1.get_created_at());
+    }
+
+    #[test]
+    fn rejects_unknown_action_types() {
+        let mut mapping_query = NiaMappingQuery::new();
+
+        mapping_query.set_action_type(Some(String::from("Teleport")));
+
+        assert!(mapping_query.check().is_err());
+    }
+}
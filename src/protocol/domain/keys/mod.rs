@@ -1,9 +1,31 @@
+mod fired_mapping;
 mod key;
 mod key_chord;
+mod key_chord_notation;
+mod key_event;
+mod key_event_simulation;
+mod key_names;
+mod key_resolution;
 mod mapping;
+mod mapping_conflict;
+mod mapping_index;
+mod mapping_metadata;
+mod mapping_query;
 mod modifier_description;
+mod modifier_group;
 
+pub use fired_mapping::*;
 pub use key::*;
 pub use key_chord::*;
+pub use key_chord_notation::*;
+pub use key_event::*;
+pub use key_event_simulation::*;
+pub use key_names::*;
+pub use key_resolution::*;
 pub use mapping::*;
+pub use mapping_conflict::*;
+pub use mapping_index::*;
+pub use mapping_metadata::*;
+pub use mapping_query::*;
 pub use modifier_description::*;
+pub use modifier_group::*;
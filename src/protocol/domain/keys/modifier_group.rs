@@ -0,0 +1,394 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{
+    NiaKey, NiaKeyChord, NiaMapping, NiaModifierDescription,
+};
+
+// Modifier keys defined together under one alias, e.g. left and right
+// control keys of every keyboard. A chord with one of them stands for the
+// chords with any of them. Groups are defined explicitly, keys that merely
+// share an alias are not grouped. Keys are kept sorted, the first one
+// represents the group in the chords the server reports.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NiaModifierGroup {
+    alias: String,
+    keys: Vec<NiaKey>,
+}
+
+// A mapping stands for a key chord sequence per every combination of group
+// members, the interpreter gets every one of them. Mappings that would need
+// more of them are refused.
+pub const MAX_EXPANDED_KEY_CHORDS: usize = 64;
+
+impl NiaModifierGroup {
+    pub fn new<S>(alias: S, keys: Vec<NiaKey>) -> NiaModifierGroup
+    where
+        S: Into<String>,
+    {
+        let mut keys = keys;
+
+        keys.sort();
+        keys.dedup();
+
+        NiaModifierGroup {
+            alias: alias.into(),
+            keys,
+        }
+    }
+
+    pub fn get_alias(&self) -> &String {
+        &self.alias
+    }
+
+    pub fn get_keys(&self) -> &Vec<NiaKey> {
+        &self.keys
+    }
+
+    pub fn contains(&self, key: NiaKey) -> bool {
+        self.keys.contains(&key)
+    }
+
+    pub fn get_representative_key(&self) -> Option<NiaKey> {
+        self.keys.first().cloned()
+    }
+
+    pub fn get_modifier_descriptions(&self) -> Vec<NiaModifierDescription> {
+        self.keys
+            .iter()
+            .map(|key| NiaModifierDescription::new(*key, self.alias.clone()))
+            .collect()
+    }
+}
+
+pub fn find_modifier_group(
+    modifier_groups: &[NiaModifierGroup],
+    key: NiaKey,
+) -> Option<&NiaModifierGroup> {
+    modifier_groups
+        .iter()
+        .find(|modifier_group| modifier_group.contains(key))
+}
+
+// Replaces every modifier of a group with the key that represents it.
+pub fn canonicalize_key_chords(
+    modifier_groups: &[NiaModifierGroup],
+    key_chords: &[NiaKeyChord],
+) -> Vec<NiaKeyChord> {
+    key_chords
+        .iter()
+        .map(|key_chord| {
+            let modifiers = key_chord
+                .get_modifiers()
+                .iter()
+                .map(|modifier| {
+                    find_modifier_group(modifier_groups, *modifier)
+                        .and_then(|modifier_group| {
+                            modifier_group.get_representative_key()
+                        })
+                        .unwrap_or(*modifier)
+                })
+                .collect();
+
+            NiaKeyChord::new(modifiers, key_chord.get_key())
+        })
+        .collect()
+}
+
+// Every key chord that the given one stands for.
+pub fn expand_key_chord(
+    modifier_groups: &[NiaModifierGroup],
+    key_chord: &NiaKeyChord,
+) -> Vec<NiaKeyChord> {
+    let mut modifier_lists: Vec<Vec<NiaKey>> = vec![Vec::new()];
+
+    for modifier in key_chord.get_modifiers() {
+        let alternatives = match find_modifier_group(modifier_groups, *modifier)
+        {
+            Some(modifier_group) => modifier_group.get_keys().clone(),
+            None => vec![*modifier],
+        };
+
+        modifier_lists = modifier_lists
+            .iter()
+            .flat_map(|modifiers| {
+                alternatives.iter().map(move |alternative| {
+                    let mut modifiers = modifiers.clone();
+
+                    modifiers.push(*alternative);
+                    modifiers
+                })
+            })
+            .collect();
+    }
+
+    modifier_lists
+        .into_iter()
+        .map(|modifiers| NiaKeyChord::new(modifiers, key_chord.get_key()))
+        .collect()
+}
+
+fn count_expanded_key_chords(
+    modifier_groups: &[NiaModifierGroup],
+    key_chords: &[NiaKeyChord],
+) -> usize {
+    key_chords
+        .iter()
+        .flat_map(|key_chord| key_chord.get_modifiers().iter())
+        .map(|modifier| {
+            find_modifier_group(modifier_groups, *modifier)
+                .map(|modifier_group| modifier_group.get_keys().len())
+                .unwrap_or(1)
+        })
+        .fold(1, |count, alternatives_count| {
+            count.saturating_mul(alternatives_count)
+        })
+}
+
+// Every key chord sequence that the given one stands for, the canonical one
+// goes first.
+pub fn expand_key_chords(
+    modifier_groups: &[NiaModifierGroup],
+    key_chords: &[NiaKeyChord],
+) -> NiaServerResult<Vec<Vec<NiaKeyChord>>> {
+    let count = count_expanded_key_chords(modifier_groups, key_chords);
+
+    if count > MAX_EXPANDED_KEY_CHORDS {
+        return NiaServerError::invalid_request(format!(
+            "Key chords stand for {} combinations of modifier group keys, \
+             at most {} are allowed.",
+            count, MAX_EXPANDED_KEY_CHORDS
+        ))
+        .into();
+    }
+
+    let canonical_key_chords =
+        canonicalize_key_chords(modifier_groups, key_chords);
+    let mut expanded_key_chords: Vec<Vec<NiaKeyChord>> = vec![Vec::new()];
+
+    for key_chord in key_chords {
+        let alternatives = expand_key_chord(modifier_groups, key_chord);
+
+        expanded_key_chords = expanded_key_chords
+            .iter()
+            .flat_map(|key_chords| {
+                alternatives.iter().map(move |alternative| {
+                    let mut key_chords = key_chords.clone();
+
+                    key_chords.push(alternative.clone());
+                    key_chords
+                })
+            })
+            .collect();
+    }
+
+    expanded_key_chords
+        .retain(|key_chords| key_chords != &canonical_key_chords);
+    expanded_key_chords.insert(0, canonical_key_chords);
+
+    Ok(expanded_key_chords)
+}
+
+// Mappings defined with the same action for every key chord sequence that a
+// canonical one stands for are reported once, as the canonical one. The rest
+// are reported as they are, e.g. the ones defined bypassing the server, so
+// that different mappings of different group members are never merged.
+pub fn collapse_mappings(
+    modifier_groups: &[NiaModifierGroup],
+    mappings: &[NiaMapping],
+) -> Vec<NiaMapping> {
+    let mut collapsed_mappings = Vec::new();
+    let mut collapsed_key_chords_list: Vec<Vec<NiaKeyChord>> = Vec::new();
+
+    for mapping in mappings {
+        if collapsed_key_chords_list.contains(mapping.get_key_chords()) {
+            continue;
+        }
+
+        let key_chords_list = match expand_key_chords(
+            modifier_groups,
+            mapping.get_key_chords(),
+        ) {
+            Ok(key_chords_list) if key_chords_list.len() > 1 => key_chords_list,
+            _ => {
+                collapsed_mappings.push(mapping.clone());
+                continue;
+            }
+        };
+
+        let is_consistent = key_chords_list.iter().all(|key_chords| {
+            mappings.iter().any(|other_mapping| {
+                other_mapping.get_key_chords() == key_chords
+                    && other_mapping.get_action() == mapping.get_action()
+            })
+        });
+
+        if is_consistent {
+            collapsed_mappings.push(NiaMapping::new(
+                key_chords_list[0].clone(),
+                mapping.get_action().clone(),
+            ));
+            collapsed_key_chords_list.extend(key_chords_list);
+        } else {
+            collapsed_mappings.push(mapping.clone());
+        }
+    }
+
+    collapsed_mappings
+}
+
+impl Serializable<NiaModifierGroup, nia_protocol_rust::ModifierGroup>
+    for NiaModifierGroup
+{
+    fn to_pb(&self) -> nia_protocol_rust::ModifierGroup {
+        let mut modifier_group_pb = nia_protocol_rust::ModifierGroup::new();
+
+        modifier_group_pb.set_alias(protobuf::Chars::from(self.alias.clone()));
+        modifier_group_pb
+            .set_keys(self.keys.iter().map(|key| key.to_pb()).collect());
+
+        modifier_group_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ModifierGroup,
+    ) -> NiaServerResult<NiaModifierGroup> {
+        let mut object_pb = object_pb;
+
+        let alias = object_pb.take_alias().to_string();
+        let keys = object_pb
+            .take_keys()
+            .into_iter()
+            .map(|key_pb| NiaKey::from_pb(key_pb))
+            .collect::<NiaServerResult<Vec<NiaKey>>>()?;
+
+        if keys.is_empty() {
+            return NiaServerError::deserialization_error(
+                "Modifier group has no keys.",
+            )
+            .into();
+        }
+
+        Ok(NiaModifierGroup::new(alias, keys))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::protocol::{ActionExecuteOSCommand, NiaAction, NiaActionEnum};
+
+    fn construct_modifier_groups() -> Vec<NiaModifierGroup> {
+        vec![NiaModifierGroup::new(
+            "Control",
+            vec![NiaKey::make_key_1(97), NiaKey::make_key_1(29)],
+        )]
+    }
+
+    fn make_mapping(modifier_key_code: i32, os_command: &str) -> NiaMapping {
+        NiaMapping::new(
+            vec![NiaKeyChord::new(
+                vec![NiaKey::make_key_1(modifier_key_code)],
+                NiaKey::make_key_1(30),
+            )],
+            NiaAction::new(NiaActionEnum::ExecuteOSCommand(
+                ActionExecuteOSCommand::new(os_command),
+            )),
+        )
+    }
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaModifierGroup::new(
+            "Control",
+            vec![NiaKey::make_key_2(1, 97), NiaKey::make_key_1(29)],
+        );
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaModifierGroup::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn canonicalizes_key_chords() {
+        let modifier_groups = construct_modifier_groups();
+
+        let key_chords = vec![NiaKeyChord::new(
+            vec![NiaKey::make_key_1(97), NiaKey::make_key_1(56)],
+            NiaKey::make_key_1(30),
+        )];
+        let expected = vec![NiaKeyChord::new(
+            vec![NiaKey::make_key_1(29), NiaKey::make_key_1(56)],
+            NiaKey::make_key_1(30),
+        )];
+
+        assert_eq!(
+            expected,
+            canonicalize_key_chords(&modifier_groups, &key_chords)
+        );
+    }
+
+    #[test]
+    fn expands_key_chords() {
+        let modifier_groups = construct_modifier_groups();
+
+        let key_chords = vec![
+            NiaKeyChord::new(
+                vec![NiaKey::make_key_1(97)],
+                NiaKey::make_key_1(45),
+            ),
+            NiaKeyChord::new(
+                vec![NiaKey::make_key_1(29)],
+                NiaKey::make_key_1(31),
+            ),
+        ];
+
+        let result = expand_key_chords(&modifier_groups, &key_chords).unwrap();
+
+        assert_eq!(4, result.len());
+        assert_eq!(
+            canonicalize_key_chords(&modifier_groups, &key_chords),
+            result[0]
+        );
+        assert!(result.contains(&key_chords));
+    }
+
+    #[test]
+    fn refuses_too_many_expanded_key_chords() {
+        let modifier_groups = construct_modifier_groups();
+
+        let key_chords = (0..7)
+            .map(|key_code| {
+                NiaKeyChord::new(
+                    vec![NiaKey::make_key_1(29)],
+                    NiaKey::make_key_1(key_code),
+                )
+            })
+            .collect::<Vec<NiaKeyChord>>();
+
+        assert!(expand_key_chords(&modifier_groups, &key_chords).is_err());
+        assert!(expand_key_chords(&modifier_groups, &key_chords[..6]).is_ok());
+    }
+
+    #[test]
+    fn collapses_only_consistent_mappings() {
+        let modifier_groups = construct_modifier_groups();
+
+        let mappings =
+            vec![make_mapping(97, "echo a"), make_mapping(29, "echo a")];
+        let expected = vec![make_mapping(29, "echo a")];
+
+        assert_eq!(expected, collapse_mappings(&modifier_groups, &mappings));
+
+        let mappings =
+            vec![make_mapping(97, "echo a"), make_mapping(29, "echo b")];
+
+        assert_eq!(mappings, collapse_mappings(&modifier_groups, &mappings));
+
+        let mappings = vec![make_mapping(97, "echo a")];
+
+        assert_eq!(mappings, collapse_mappings(&modifier_groups, &mappings));
+    }
+}
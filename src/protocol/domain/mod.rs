@@ -1,11 +1,19 @@
 mod action;
+mod config;
 mod convertable;
 mod device;
+mod history;
 mod keys;
+mod profile;
+mod references;
 mod serializable;
 
 pub use action::*;
+pub use config::*;
 pub use convertable::*;
 pub use device::*;
+pub use history::*;
 pub use keys::*;
+pub use profile::*;
+pub use references::*;
 pub use serializable::*;
@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{
+    NiaKeyChord, NiaMapping, NiaMappingMetadata, NiaModifierDescription,
+    NiaNamedAction,
+};
+
+// Profile names end up in code of profile switching actions, so they are
+// kept to letters, digits, dashes and underscores.
+pub fn check_profile_name(profile_name: &str) -> NiaServerResult<()> {
+    let is_valid = !profile_name.is_empty()
+        && profile_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if !is_valid {
+        return NiaServerError::invalid_request(format!(
+            "Invalid profile name: \"{}\". Only letters, digits, dashes and \
+             underscores are allowed.",
+            profile_name
+        ))
+        .into();
+    }
+
+    Ok(())
+}
+
+fn mappings_to_pb(
+    mappings: &[NiaMapping],
+    mappings_metadata: &HashMap<Vec<NiaKeyChord>, NiaMappingMetadata>,
+) -> Vec<nia_protocol_rust::Mapping> {
+    mappings
+        .iter()
+        .map(|mapping| {
+            let mut mapping_pb = mapping.to_pb();
+
+            if let Some(mapping_metadata) =
+                mappings_metadata.get(mapping.get_key_chords())
+            {
+                mapping_pb.set_metadata(mapping_metadata.to_pb());
+            }
+
+            mapping_pb
+        })
+        .collect()
+}
+
+fn mappings_from_pb(
+    mappings_pb: Vec<nia_protocol_rust::Mapping>,
+    mappings_metadata: &mut HashMap<Vec<NiaKeyChord>, NiaMappingMetadata>,
+) -> NiaServerResult<Vec<NiaMapping>> {
+    mappings_pb
+        .into_iter()
+        .map(|mapping_pb| {
+            let mut mapping_pb = mapping_pb;
+
+            let mapping_metadata = if mapping_pb.has_metadata() {
+                Some(NiaMappingMetadata::from_pb(mapping_pb.take_metadata())?)
+            } else {
+                None
+            };
+
+            let mapping = NiaMapping::from_pb(mapping_pb)?;
+
+            if let Some(mapping_metadata) = mapping_metadata {
+                mappings_metadata
+                    .insert(mapping.get_key_chords().clone(), mapping_metadata);
+            }
+
+            Ok(mapping)
+        })
+        .collect()
+}
+
+// A named set of modifiers, actions and mappings that replaces the defined
+// ones when activated. Disabled mappings and metadata of mappings belong to
+// the profile as well.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NiaProfile {
+    profile_name: String,
+    modifiers: Vec<NiaModifierDescription>,
+    named_actions: Vec<NiaNamedAction>,
+    mappings: Vec<NiaMapping>,
+    disabled_mappings: Vec<NiaMapping>,
+    mappings_metadata: HashMap<Vec<NiaKeyChord>, NiaMappingMetadata>,
+}
+
+impl NiaProfile {
+    pub fn new<S>(
+        profile_name: S,
+        modifiers: Vec<NiaModifierDescription>,
+        named_actions: Vec<NiaNamedAction>,
+        mappings: Vec<NiaMapping>,
+    ) -> NiaProfile
+    where
+        S: Into<String>,
+    {
+        NiaProfile {
+            profile_name: profile_name.into(),
+            modifiers,
+            named_actions,
+            mappings,
+            disabled_mappings: Vec::new(),
+            mappings_metadata: HashMap::new(),
+        }
+    }
+
+    pub fn get_profile_name(&self) -> &str {
+        &self.profile_name
+    }
+
+    pub fn get_modifiers(&self) -> &Vec<NiaModifierDescription> {
+        &self.modifiers
+    }
+
+    pub fn get_named_actions(&self) -> &Vec<NiaNamedAction> {
+        &self.named_actions
+    }
+
+    pub fn get_mappings(&self) -> &Vec<NiaMapping> {
+        &self.mappings
+    }
+
+    pub fn get_disabled_mappings(&self) -> &Vec<NiaMapping> {
+        &self.disabled_mappings
+    }
+
+    pub fn set_disabled_mappings(
+        &mut self,
+        disabled_mappings: Vec<NiaMapping>,
+    ) {
+        self.disabled_mappings = disabled_mappings;
+    }
+
+    pub fn get_mappings_metadata(
+        &self,
+    ) -> &HashMap<Vec<NiaKeyChord>, NiaMappingMetadata> {
+        &self.mappings_metadata
+    }
+
+    pub fn set_mappings_metadata(
+        &mut self,
+        mappings_metadata: HashMap<Vec<NiaKeyChord>, NiaMappingMetadata>,
+    ) {
+        self.mappings_metadata = mappings_metadata;
+    }
+}
+
+impl Serializable<NiaProfile, nia_protocol_rust::Profile> for NiaProfile {
+    fn to_pb(&self) -> nia_protocol_rust::Profile {
+        let mut profile_pb = nia_protocol_rust::Profile::new();
+
+        profile_pb
+            .set_profile_name(protobuf::Chars::from(self.profile_name.clone()));
+        profile_pb.set_modifiers(
+            self.modifiers
+                .iter()
+                .map(|modifier| modifier.to_pb())
+                .collect(),
+        );
+        profile_pb.set_named_actions(
+            self.named_actions
+                .iter()
+                .map(|named_action| named_action.to_pb())
+                .collect(),
+        );
+        profile_pb.set_mappings(
+            mappings_to_pb(&self.mappings, &self.mappings_metadata).into(),
+        );
+        profile_pb.set_disabled_mappings(
+            mappings_to_pb(&self.disabled_mappings, &self.mappings_metadata)
+                .into(),
+        );
+
+        profile_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::Profile,
+    ) -> NiaServerResult<NiaProfile> {
+        let mut object_pb = object_pb;
+
+        let profile_name = object_pb.take_profile_name().to_string();
+
+        let modifiers = object_pb
+            .take_modifiers()
+            .into_iter()
+            .map(|modifier_pb| NiaModifierDescription::from_pb(modifier_pb))
+            .collect::<NiaServerResult<Vec<NiaModifierDescription>>>()?;
+
+        let named_actions = object_pb
+            .take_named_actions()
+            .into_iter()
+            .map(|named_action_pb| NiaNamedAction::from_pb(named_action_pb))
+            .collect::<NiaServerResult<Vec<NiaNamedAction>>>()?;
+
+        let mut mappings_metadata = HashMap::new();
+
+        let mappings = mappings_from_pb(
+            object_pb.take_mappings().into_iter().collect(),
+            &mut mappings_metadata,
+        )?;
+        let disabled_mappings = mappings_from_pb(
+            object_pb.take_disabled_mappings().into_iter().collect(),
+            &mut mappings_metadata,
+        )?;
+
+        let mut profile =
+            NiaProfile::new(profile_name, modifiers, named_actions, mappings);
+
+        profile.set_disabled_mappings(disabled_mappings);
+        profile.set_mappings_metadata(mappings_metadata);
+
+        Ok(profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::{
+        ActionKeyClick, NiaAction, NiaActionEnum, NiaKey, NiaKeyChord,
+    };
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let action =
+            NiaAction::new(NiaActionEnum::KeyClick(ActionKeyClick::new(30)));
+
+        let disabled_key_chords = vec![NiaKeyChord::new(
+            vec![NiaKey::make_key_1(29)],
+            NiaKey::make_key_2(1, 46),
+        )];
+        let mut mapping_metadata = NiaMappingMetadata::new(100);
+
+        mapping_metadata.set_enabled(false, 200);
+        mapping_metadata.set_description("Clicks a", 300);
+
+        let mut expected = NiaProfile::new(
+            "gaming",
+            vec![NiaModifierDescription::new(NiaKey::make_key_1(29), "ctrl")],
+            vec![NiaNamedAction::new(action.clone(), "click-a")],
+            vec![NiaMapping::new(
+                vec![NiaKeyChord::new(
+                    vec![NiaKey::make_key_1(29)],
+                    NiaKey::make_key_2(1, 45),
+                )],
+                action.clone(),
+            )],
+        );
+
+        expected.set_disabled_mappings(vec![NiaMapping::new(
+            disabled_key_chords.clone(),
+            action,
+        )]);
+        expected.set_mappings_metadata(
+            vec![(disabled_key_chords, mapping_metadata)]
+                .into_iter()
+                .collect(),
+        );
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaProfile::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn checks_profile_names() {
+        assert!(check_profile_name("gaming").is_ok());
+        assert!(check_profile_name("coding_2-dvorak").is_ok());
+        assert!(check_profile_name("").is_err());
+        assert!(check_profile_name("two words").is_err());
+        assert!(check_profile_name("x'; rm -rf ~").is_err());
+    }
+}
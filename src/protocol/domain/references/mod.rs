@@ -0,0 +1,7 @@
+mod reference_target;
+mod references;
+mod removal_mode;
+
+pub use reference_target::*;
+pub use references::*;
+pub use removal_mode::*;
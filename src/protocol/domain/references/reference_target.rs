@@ -0,0 +1,8 @@
+use crate::protocol::NiaKey;
+
+// A definition that mappings and named actions may refer to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NiaReferenceTarget {
+    Modifier(NiaKey),
+    Action(String),
+}
@@ -0,0 +1,255 @@
+use crate::error::NiaServerResult;
+use crate::protocol::format_key_chords;
+use crate::protocol::Serializable;
+use crate::protocol::{NiaKey, NiaMapping, NiaNamedAction};
+
+// Definitions that refer to a modifier or to a named action, and would be
+// left dangling by its removal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NiaReferences {
+    mappings: Vec<NiaMapping>,
+    named_actions: Vec<NiaNamedAction>,
+}
+
+impl NiaReferences {
+    pub fn new(
+        mappings: Vec<NiaMapping>,
+        named_actions: Vec<NiaNamedAction>,
+    ) -> NiaReferences {
+        NiaReferences {
+            mappings,
+            named_actions,
+        }
+    }
+
+    pub fn get_mappings(&self) -> &Vec<NiaMapping> {
+        &self.mappings
+    }
+
+    pub fn get_named_actions(&self) -> &Vec<NiaNamedAction> {
+        &self.named_actions
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty() && self.named_actions.is_empty()
+    }
+
+    // E.g. "mappings C-x C-s, C-a; actions save".
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+
+        if !self.mappings.is_empty() {
+            let mappings = self
+                .mappings
+                .iter()
+                .map(|mapping| format_key_chords(mapping.get_key_chords()))
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            parts.push(format!("mappings {}", mappings));
+        }
+
+        if !self.named_actions.is_empty() {
+            let named_actions = self
+                .named_actions
+                .iter()
+                .map(|named_action| named_action.get_action_name().clone())
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            parts.push(format!("actions {}", named_actions));
+        }
+
+        parts.join("; ")
+    }
+}
+
+// Mappings that hold the key as a modifier of a chord.
+pub fn find_modifier_references(
+    modifier_key: NiaKey,
+    mappings: &[NiaMapping],
+) -> NiaReferences {
+    let mappings = mappings
+        .iter()
+        .filter(|mapping| {
+            mapping.get_key_chords().iter().any(|key_chord| {
+                key_chord
+                    .get_modifiers()
+                    .iter()
+                    .any(|modifier| modifier.matches(modifier_key))
+            })
+        })
+        .cloned()
+        .collect();
+
+    NiaReferences::new(mappings, Vec::new())
+}
+
+// Named actions that execute the action, the ones that execute those and so
+// on, and every mapping that executes any of them.
+pub fn find_action_references(
+    action_name: &str,
+    named_actions: &[NiaNamedAction],
+    mappings: &[NiaMapping],
+) -> NiaReferences {
+    let mut action_names = vec![action_name.to_string()];
+    let mut referring_actions: Vec<NiaNamedAction> = Vec::new();
+    let mut index = 0;
+
+    while index < action_names.len() {
+        for named_action in named_actions {
+            let is_known = named_action.get_action_name() == action_name
+                || referring_actions.contains(named_action);
+
+            if !is_known
+                && named_action
+                    .get_action()
+                    .refers_to_named_action(&action_names[index])
+            {
+                action_names.push(named_action.get_action_name().clone());
+                referring_actions.push(named_action.clone());
+            }
+        }
+
+        index += 1;
+    }
+
+    let mappings = mappings
+        .iter()
+        .filter(|mapping| {
+            action_names.iter().any(|action_name| {
+                mapping.get_action().refers_to_named_action(action_name)
+            })
+        })
+        .cloned()
+        .collect();
+
+    NiaReferences::new(mappings, referring_actions)
+}
+
+impl Serializable<NiaReferences, nia_protocol_rust::References>
+    for NiaReferences
+{
+    fn to_pb(&self) -> nia_protocol_rust::References {
+        let mut references_pb = nia_protocol_rust::References::new();
+
+        references_pb.set_mappings(
+            self.mappings
+                .iter()
+                .map(|mapping| mapping.to_pb())
+                .collect(),
+        );
+        references_pb.set_named_actions(
+            self.named_actions
+                .iter()
+                .map(|named_action| named_action.to_pb())
+                .collect(),
+        );
+
+        references_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::References,
+    ) -> NiaServerResult<NiaReferences> {
+        let mut object_pb = object_pb;
+
+        let mappings = object_pb
+            .take_mappings()
+            .into_iter()
+            .map(|mapping_pb| NiaMapping::from_pb(mapping_pb))
+            .collect::<NiaServerResult<Vec<NiaMapping>>>()?;
+        let named_actions = object_pb
+            .take_named_actions()
+            .into_iter()
+            .map(|named_action_pb| NiaNamedAction::from_pb(named_action_pb))
+            .collect::<NiaServerResult<Vec<NiaNamedAction>>>()?;
+
+        Ok(NiaReferences::new(mappings, named_actions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::{
+        ActionExecuteNamedAction, ActionKeyClick, NiaAction, NiaActionEnum,
+        NiaKeyChord,
+    };
+
+    fn execute_named_action(action_name: &str) -> NiaAction {
+        NiaAction::new(NiaActionEnum::ExecuteNamedAction(
+            ActionExecuteNamedAction::new(action_name),
+        ))
+    }
+
+    fn construct_named_actions() -> Vec<NiaNamedAction> {
+        vec![
+            NiaNamedAction::new(
+                NiaAction::new(NiaActionEnum::KeyClick(ActionKeyClick::new(
+                    30,
+                ))),
+                "click-a",
+            ),
+            NiaNamedAction::new(execute_named_action("click-a"), "alias-a"),
+            NiaNamedAction::new(execute_named_action("alias-a"), "alias-b"),
+        ]
+    }
+
+    fn construct_mappings() -> Vec<NiaMapping> {
+        vec![
+            NiaMapping::new(
+                vec![NiaKeyChord::new(
+                    vec![NiaKey::make_key_1(29)],
+                    NiaKey::make_key_1(30),
+                )],
+                execute_named_action("alias-b"),
+            ),
+            NiaMapping::new(
+                vec![NiaKeyChord::new(vec![], NiaKey::make_key_1(31))],
+                execute_named_action("click-a"),
+            ),
+        ]
+    }
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected =
+            NiaReferences::new(construct_mappings(), construct_named_actions());
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaReferences::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn finds_action_references_transitively() {
+        let named_actions = construct_named_actions();
+        let mappings = construct_mappings();
+
+        let result =
+            find_action_references("click-a", &named_actions, &mappings);
+
+        assert_eq!(&mappings, result.get_mappings());
+        assert_eq!(&named_actions[1..].to_vec(), result.get_named_actions());
+
+        let result =
+            find_action_references("alias-b", &named_actions, &mappings);
+
+        assert_eq!(&mappings[..1].to_vec(), result.get_mappings());
+        assert!(result.get_named_actions().is_empty());
+    }
+
+    #[test]
+    fn finds_modifier_references() {
+        let mappings = construct_mappings();
+
+        let result =
+            find_modifier_references(NiaKey::make_key_1(29), &mappings);
+
+        assert_eq!(&mappings[..1].to_vec(), result.get_mappings());
+        assert_eq!("mappings C-a", result.describe());
+    }
+}
@@ -0,0 +1,36 @@
+use crate::error::{NiaServerError, NiaServerResult};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NiaRemovalMode {
+    // Refuses to remove a definition that something refers to.
+    Strict,
+    // Removes whatever refers to the definition along with it.
+    Cascade,
+    // Removes the definition and leaves the references dangling.
+    Force,
+}
+
+impl NiaRemovalMode {
+    pub fn from_flags(
+        cascade: bool,
+        force: bool,
+    ) -> NiaServerResult<NiaRemovalMode> {
+        match (cascade, force) {
+            (false, false) => Ok(NiaRemovalMode::Strict),
+            (true, false) => Ok(NiaRemovalMode::Cascade),
+            (false, true) => Ok(NiaRemovalMode::Force),
+            (true, true) => NiaServerError::deserialization_error(
+                "Provided both cascade and force flags.",
+            )
+            .into(),
+        }
+    }
+
+    pub fn is_cascade(&self) -> bool {
+        *self == NiaRemovalMode::Cascade
+    }
+
+    pub fn is_force(&self) -> bool {
+        *self == NiaRemovalMode::Force
+    }
+}
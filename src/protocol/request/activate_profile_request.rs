@@ -0,0 +1,67 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaActivateProfileRequest {
+    profile_name: String,
+}
+
+impl NiaActivateProfileRequest {
+    pub fn new<S>(profile_name: S) -> NiaActivateProfileRequest
+    where
+        S: Into<String>,
+    {
+        NiaActivateProfileRequest {
+            profile_name: profile_name.into(),
+        }
+    }
+
+    pub fn take_profile_name(self) -> String {
+        self.profile_name
+    }
+}
+
+impl
+    Serializable<
+        NiaActivateProfileRequest,
+        nia_protocol_rust::ActivateProfileRequest,
+    > for NiaActivateProfileRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::ActivateProfileRequest {
+        let mut activate_profile_request_pb =
+            nia_protocol_rust::ActivateProfileRequest::new();
+
+        activate_profile_request_pb
+            .set_profile_name(protobuf::Chars::from(self.profile_name.clone()));
+
+        activate_profile_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ActivateProfileRequest,
+    ) -> NiaServerResult<NiaActivateProfileRequest> {
+        let mut object_pb = object_pb;
+
+        let profile_name = object_pb.take_profile_name().to_string();
+
+        Ok(NiaActivateProfileRequest::new(profile_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaActivateProfileRequest::new("gaming");
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaActivateProfileRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+use nia_protocol_rust::BeginTransactionRequest;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaBeginTransactionRequest {}
+
+impl NiaBeginTransactionRequest {
+    pub fn new() -> NiaBeginTransactionRequest {
+        NiaBeginTransactionRequest {}
+    }
+}
+
+impl
+    Serializable<
+        NiaBeginTransactionRequest,
+        nia_protocol_rust::BeginTransactionRequest,
+    > for NiaBeginTransactionRequest
+{
+    fn to_pb(&self) -> BeginTransactionRequest {
+        nia_protocol_rust::BeginTransactionRequest::new()
+    }
+
+    fn from_pb(
+        object_pb: BeginTransactionRequest,
+    ) -> NiaServerResult<NiaBeginTransactionRequest> {
+        Ok(NiaBeginTransactionRequest::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaBeginTransactionRequest::new();
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaBeginTransactionRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+use nia_protocol_rust::CheckMappingsRequest;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaCheckMappingsRequest {}
+
+impl NiaCheckMappingsRequest {
+    pub fn new() -> NiaCheckMappingsRequest {
+        NiaCheckMappingsRequest {}
+    }
+}
+
+impl
+    Serializable<
+        NiaCheckMappingsRequest,
+        nia_protocol_rust::CheckMappingsRequest,
+    > for NiaCheckMappingsRequest
+{
+    fn to_pb(&self) -> CheckMappingsRequest {
+        nia_protocol_rust::CheckMappingsRequest::new()
+    }
+
+    fn from_pb(
+        object_pb: CheckMappingsRequest,
+    ) -> NiaServerResult<NiaCheckMappingsRequest> {
+        Ok(NiaCheckMappingsRequest::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaCheckMappingsRequest::new();
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaCheckMappingsRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
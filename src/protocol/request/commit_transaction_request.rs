@@ -0,0 +1,47 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+use nia_protocol_rust::CommitTransactionRequest;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaCommitTransactionRequest {}
+
+impl NiaCommitTransactionRequest {
+    pub fn new() -> NiaCommitTransactionRequest {
+        NiaCommitTransactionRequest {}
+    }
+}
+
+impl
+    Serializable<
+        NiaCommitTransactionRequest,
+        nia_protocol_rust::CommitTransactionRequest,
+    > for NiaCommitTransactionRequest
+{
+    fn to_pb(&self) -> CommitTransactionRequest {
+        nia_protocol_rust::CommitTransactionRequest::new()
+    }
+
+    fn from_pb(
+        object_pb: CommitTransactionRequest,
+    ) -> NiaServerResult<NiaCommitTransactionRequest> {
+        Ok(NiaCommitTransactionRequest::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaCommitTransactionRequest::new();
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaCommitTransactionRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaCreateProfileRequest {
+    profile_name: String,
+}
+
+impl NiaCreateProfileRequest {
+    pub fn new<S>(profile_name: S) -> NiaCreateProfileRequest
+    where
+        S: Into<String>,
+    {
+        NiaCreateProfileRequest {
+            profile_name: profile_name.into(),
+        }
+    }
+
+    pub fn take_profile_name(self) -> String {
+        self.profile_name
+    }
+}
+
+impl
+    Serializable<
+        NiaCreateProfileRequest,
+        nia_protocol_rust::CreateProfileRequest,
+    > for NiaCreateProfileRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::CreateProfileRequest {
+        let mut create_profile_request_pb =
+            nia_protocol_rust::CreateProfileRequest::new();
+
+        create_profile_request_pb
+            .set_profile_name(protobuf::Chars::from(self.profile_name.clone()));
+
+        create_profile_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::CreateProfileRequest,
+    ) -> NiaServerResult<NiaCreateProfileRequest> {
+        let mut object_pb = object_pb;
+
+        let profile_name = object_pb.take_profile_name().to_string();
+
+        Ok(NiaCreateProfileRequest::new(profile_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaCreateProfileRequest::new("gaming");
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaCreateProfileRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::error::NiaServerError;
 use crate::error::NiaServerResult;
 
+use crate::protocol::NiaKeyChord;
 use crate::protocol::NiaMapping;
 use crate::protocol::Serializable;
 
@@ -17,6 +18,10 @@ impl NiaDefineMappingRequest {
     pub fn get_mapping(self) -> NiaMapping {
         self.mapping
     }
+
+    pub fn get_key_chords(&self) -> &Vec<NiaKeyChord> {
+        self.mapping.get_key_chords()
+    }
 }
 
 impl
@@ -0,0 +1,73 @@
+use crate::error::NiaServerResult;
+
+use crate::protocol::{NiaModifierGroup, Serializable};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaDefineModifierGroupRequest {
+    modifier_group: NiaModifierGroup,
+}
+
+impl NiaDefineModifierGroupRequest {
+    pub fn new(
+        modifier_group: NiaModifierGroup,
+    ) -> NiaDefineModifierGroupRequest {
+        NiaDefineModifierGroupRequest { modifier_group }
+    }
+
+    pub fn get_modifier_group(&self) -> &NiaModifierGroup {
+        &self.modifier_group
+    }
+
+    pub fn take_modifier_group(self) -> NiaModifierGroup {
+        self.modifier_group
+    }
+}
+
+impl
+    Serializable<
+        NiaDefineModifierGroupRequest,
+        nia_protocol_rust::DefineModifierGroupRequest,
+    > for NiaDefineModifierGroupRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::DefineModifierGroupRequest {
+        let mut define_modifier_group_request_pb =
+            nia_protocol_rust::DefineModifierGroupRequest::new();
+
+        define_modifier_group_request_pb
+            .set_modifier_group(self.modifier_group.to_pb());
+
+        define_modifier_group_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::DefineModifierGroupRequest,
+    ) -> NiaServerResult<NiaDefineModifierGroupRequest> {
+        let mut object_pb = object_pb;
+
+        let modifier_group =
+            NiaModifierGroup::from_pb(object_pb.take_modifier_group())?;
+
+        Ok(NiaDefineModifierGroupRequest::new(modifier_group))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::NiaKey;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected =
+            NiaDefineModifierGroupRequest::new(NiaModifierGroup::new(
+                "Control",
+                vec![NiaKey::make_key_1(29), NiaKey::make_key_2(2, 97)],
+            ));
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaDefineModifierGroupRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::NiaAction;
+use crate::protocol::Serializable;
+
+// Same as `NiaDefineMappingRequest`, but key chords are written in the
+// textual notation, e.g. "C-x C-s".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaDefineTextMappingRequest {
+    key_chords: String,
+    action: NiaAction,
+}
+
+impl NiaDefineTextMappingRequest {
+    pub fn new<S>(
+        key_chords: S,
+        action: NiaAction,
+    ) -> NiaDefineTextMappingRequest
+    where
+        S: Into<String>,
+    {
+        NiaDefineTextMappingRequest {
+            key_chords: key_chords.into(),
+            action,
+        }
+    }
+
+    pub fn get_key_chords(&self) -> &String {
+        &self.key_chords
+    }
+
+    pub fn get_action(&self) -> &NiaAction {
+        &self.action
+    }
+
+    pub fn into_tuple(self) -> (String, NiaAction) {
+        (self.key_chords, self.action)
+    }
+}
+
+impl
+    Serializable<
+        NiaDefineTextMappingRequest,
+        nia_protocol_rust::DefineTextMappingRequest,
+    > for NiaDefineTextMappingRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::DefineTextMappingRequest {
+        let mut define_text_mapping_request_pb =
+            nia_protocol_rust::DefineTextMappingRequest::new();
+
+        define_text_mapping_request_pb
+            .set_key_chords(protobuf::Chars::from(self.key_chords.clone()));
+        define_text_mapping_request_pb.set_action(self.action.to_pb());
+
+        define_text_mapping_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::DefineTextMappingRequest,
+    ) -> NiaServerResult<NiaDefineTextMappingRequest> {
+        let mut object_pb = object_pb;
+
+        let key_chords = object_pb.take_key_chords().to_string();
+        let action = NiaAction::from_pb(object_pb.take_action())?;
+
+        Ok(NiaDefineTextMappingRequest::new(key_chords, action))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::{ActionMouseRelativeMove, NiaActionEnum};
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaDefineTextMappingRequest::new(
+            "[kbd]C-x C-s",
+            NiaAction::new(NiaActionEnum::MouseRelativeMove(
+                ActionMouseRelativeMove::new(100, 100),
+            )),
+        );
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaDefineTextMappingRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaDeleteProfileRequest {
+    profile_name: String,
+}
+
+impl NiaDeleteProfileRequest {
+    pub fn new<S>(profile_name: S) -> NiaDeleteProfileRequest
+    where
+        S: Into<String>,
+    {
+        NiaDeleteProfileRequest {
+            profile_name: profile_name.into(),
+        }
+    }
+
+    pub fn take_profile_name(self) -> String {
+        self.profile_name
+    }
+}
+
+impl
+    Serializable<
+        NiaDeleteProfileRequest,
+        nia_protocol_rust::DeleteProfileRequest,
+    > for NiaDeleteProfileRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::DeleteProfileRequest {
+        let mut delete_profile_request_pb =
+            nia_protocol_rust::DeleteProfileRequest::new();
+
+        delete_profile_request_pb
+            .set_profile_name(protobuf::Chars::from(self.profile_name.clone()));
+
+        delete_profile_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::DeleteProfileRequest,
+    ) -> NiaServerResult<NiaDeleteProfileRequest> {
+        let mut object_pb = object_pb;
+
+        let profile_name = object_pb.take_profile_name().to_string();
+
+        Ok(NiaDeleteProfileRequest::new(profile_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaDeleteProfileRequest::new("gaming");
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaDeleteProfileRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
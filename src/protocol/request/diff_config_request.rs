@@ -0,0 +1,116 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::{NiaConfigSource, Serializable};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaDiffConfigRequest {
+    old_source: NiaConfigSource,
+    new_source: NiaConfigSource,
+}
+
+impl NiaDiffConfigRequest {
+    pub fn new(
+        old_source: NiaConfigSource,
+        new_source: NiaConfigSource,
+    ) -> NiaDiffConfigRequest {
+        NiaDiffConfigRequest {
+            old_source,
+            new_source,
+        }
+    }
+
+    pub fn get_old_source(&self) -> &NiaConfigSource {
+        &self.old_source
+    }
+
+    pub fn get_new_source(&self) -> &NiaConfigSource {
+        &self.new_source
+    }
+
+    pub fn into_tuple(self) -> (NiaConfigSource, NiaConfigSource) {
+        (self.old_source, self.new_source)
+    }
+}
+
+impl Serializable<NiaDiffConfigRequest, nia_protocol_rust::DiffConfigRequest>
+    for NiaDiffConfigRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::DiffConfigRequest {
+        let mut diff_config_request_pb =
+            nia_protocol_rust::DiffConfigRequest::new();
+
+        match &self.old_source {
+            NiaConfigSource::Live => diff_config_request_pb
+                .set_old_live_config(nia_protocol_rust::LiveConfig::new()),
+            NiaConfigSource::Text(config) => diff_config_request_pb
+                .set_old_config(protobuf::Chars::from(config.clone())),
+        }
+
+        match &self.new_source {
+            NiaConfigSource::Live => diff_config_request_pb
+                .set_new_live_config(nia_protocol_rust::LiveConfig::new()),
+            NiaConfigSource::Text(config) => diff_config_request_pb
+                .set_new_config(protobuf::Chars::from(config.clone())),
+        }
+
+        diff_config_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::DiffConfigRequest,
+    ) -> NiaServerResult<NiaDiffConfigRequest> {
+        let mut object_pb = object_pb;
+
+        let old_source = if object_pb.has_old_live_config() {
+            NiaConfigSource::Live
+        } else if object_pb.has_old_config() {
+            NiaConfigSource::Text(object_pb.take_old_config().to_string())
+        } else {
+            return NiaServerError::deserialization_error(
+                "Provided no old configuration.",
+            )
+            .into();
+        };
+
+        let new_source = if object_pb.has_new_live_config() {
+            NiaConfigSource::Live
+        } else if object_pb.has_new_config() {
+            NiaConfigSource::Text(object_pb.take_new_config().to_string())
+        } else {
+            return NiaServerError::deserialization_error(
+                "Provided no new configuration.",
+            )
+            .into();
+        };
+
+        Ok(NiaDiffConfigRequest::new(old_source, new_source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = vec![
+            NiaDiffConfigRequest::new(
+                NiaConfigSource::Live,
+                NiaConfigSource::Text(String::from("{}")),
+            ),
+            NiaDiffConfigRequest::new(
+                NiaConfigSource::Text(String::from("{\"mappings\": []}")),
+                NiaConfigSource::Live,
+            ),
+        ];
+
+        for expected in expected {
+            let bytes = expected.to_bytes().unwrap();
+            let result = NiaDiffConfigRequest::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, result);
+        }
+    }
+}
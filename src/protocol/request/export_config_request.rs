@@ -0,0 +1,45 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+use nia_protocol_rust::ExportConfigRequest;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaExportConfigRequest {}
+
+impl NiaExportConfigRequest {
+    pub fn new() -> NiaExportConfigRequest {
+        NiaExportConfigRequest {}
+    }
+}
+
+impl
+    Serializable<NiaExportConfigRequest, nia_protocol_rust::ExportConfigRequest>
+    for NiaExportConfigRequest
+{
+    fn to_pb(&self) -> ExportConfigRequest {
+        nia_protocol_rust::ExportConfigRequest::new()
+    }
+
+    fn from_pb(
+        object_pb: ExportConfigRequest,
+    ) -> NiaServerResult<NiaExportConfigRequest> {
+        Ok(NiaExportConfigRequest::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaExportConfigRequest::new();
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaExportConfigRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -1,6 +1,6 @@
 use std::convert::TryFrom;
 
-use crate::protocol::Serializable;
+use crate::protocol::{NiaMappingQuery, Serializable};
 
 use nia_protocol_rust::GetDefinedMappingsRequest;
 
@@ -8,11 +8,27 @@ use crate::error::NiaServerError;
 use crate::error::NiaServerResult;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct NiaGetDefinedMappingsRequest {}
+pub struct NiaGetDefinedMappingsRequest {
+    query: NiaMappingQuery,
+}
 
 impl NiaGetDefinedMappingsRequest {
     pub fn new() -> NiaGetDefinedMappingsRequest {
-        NiaGetDefinedMappingsRequest {}
+        NiaGetDefinedMappingsRequest {
+            query: NiaMappingQuery::new(),
+        }
+    }
+
+    pub fn with_query(query: NiaMappingQuery) -> NiaGetDefinedMappingsRequest {
+        NiaGetDefinedMappingsRequest { query }
+    }
+
+    pub fn get_query(&self) -> &NiaMappingQuery {
+        &self.query
+    }
+
+    pub fn take_query(self) -> NiaMappingQuery {
+        self.query
     }
 }
 
@@ -22,9 +38,9 @@ impl TryFrom<nia_protocol_rust::GetDefinedMappingsRequest>
     type Error = NiaServerError;
 
     fn try_from(
-        _get_devices_request: nia_protocol_rust::GetDefinedMappingsRequest,
+        request_pb: nia_protocol_rust::GetDefinedMappingsRequest,
     ) -> Result<Self, Self::Error> {
-        Ok(NiaGetDefinedMappingsRequest::new())
+        NiaGetDefinedMappingsRequest::from_pb(request_pb)
     }
 }
 
@@ -38,13 +54,23 @@ impl
         let mut get_defined_mappings_request_pb =
             nia_protocol_rust::GetDefinedMappingsRequest::new();
 
+        get_defined_mappings_request_pb.set_query(self.query.to_pb());
+
         get_defined_mappings_request_pb
     }
 
     fn from_pb(
         object_pb: nia_protocol_rust::GetDefinedMappingsRequest,
     ) -> NiaServerResult<NiaGetDefinedMappingsRequest> {
-        Ok(NiaGetDefinedMappingsRequest::new())
+        let mut object_pb = object_pb;
+
+        if !object_pb.has_query() {
+            return Ok(NiaGetDefinedMappingsRequest::new());
+        }
+
+        let query = NiaMappingQuery::from_pb(object_pb.take_query())?;
+
+        Ok(NiaGetDefinedMappingsRequest::with_query(query))
     }
 }
 
@@ -53,9 +79,18 @@ mod tests {
     #[allow(unused_imports)]
     use super::*;
 
+    use crate::protocol::{NiaKey, NiaMappingSortKey};
+
     #[test]
     fn serializes_and_deserializes() {
-        let expected = NiaGetDefinedMappingsRequest::new();
+        let mut query = NiaMappingQuery::new();
+
+        query.set_key(Some(NiaKey::make_key_2(1, 30)));
+        query.set_tag(Some(String::from("gaming")));
+        query.set_sort_key(NiaMappingSortKey::CreatedAt);
+        query.set_limit(Some(10));
+
+        let expected = NiaGetDefinedMappingsRequest::with_query(query);
 
         let bytes = expected.to_bytes().unwrap();
         let result = NiaGetDefinedMappingsRequest::from_bytes(bytes).unwrap();
@@ -0,0 +1,47 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+use nia_protocol_rust::GetDeviceModelsRequest;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaGetDeviceModelsRequest {}
+
+impl NiaGetDeviceModelsRequest {
+    pub fn new() -> NiaGetDeviceModelsRequest {
+        NiaGetDeviceModelsRequest {}
+    }
+}
+
+impl
+    Serializable<
+        NiaGetDeviceModelsRequest,
+        nia_protocol_rust::GetDeviceModelsRequest,
+    > for NiaGetDeviceModelsRequest
+{
+    fn to_pb(&self) -> GetDeviceModelsRequest {
+        nia_protocol_rust::GetDeviceModelsRequest::new()
+    }
+
+    fn from_pb(
+        object_pb: GetDeviceModelsRequest,
+    ) -> NiaServerResult<NiaGetDeviceModelsRequest> {
+        Ok(NiaGetDeviceModelsRequest::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaGetDeviceModelsRequest::new();
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaGetDeviceModelsRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+use nia_protocol_rust::GetHistoryRequest;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaGetHistoryRequest {}
+
+impl NiaGetHistoryRequest {
+    pub fn new() -> NiaGetHistoryRequest {
+        NiaGetHistoryRequest {}
+    }
+}
+
+impl Serializable<NiaGetHistoryRequest, nia_protocol_rust::GetHistoryRequest>
+    for NiaGetHistoryRequest
+{
+    fn to_pb(&self) -> GetHistoryRequest {
+        nia_protocol_rust::GetHistoryRequest::new()
+    }
+
+    fn from_pb(
+        object_pb: GetHistoryRequest,
+    ) -> NiaServerResult<NiaGetHistoryRequest> {
+        Ok(NiaGetHistoryRequest::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaGetHistoryRequest::new();
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaGetHistoryRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
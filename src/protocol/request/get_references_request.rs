@@ -0,0 +1,95 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::{NiaKey, NiaReferenceTarget, Serializable};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaGetReferencesRequest {
+    reference_target: NiaReferenceTarget,
+}
+
+impl NiaGetReferencesRequest {
+    pub fn new(
+        reference_target: NiaReferenceTarget,
+    ) -> NiaGetReferencesRequest {
+        NiaGetReferencesRequest { reference_target }
+    }
+
+    pub fn get_reference_target(&self) -> &NiaReferenceTarget {
+        &self.reference_target
+    }
+
+    pub fn take_reference_target(self) -> NiaReferenceTarget {
+        self.reference_target
+    }
+}
+
+impl
+    Serializable<
+        NiaGetReferencesRequest,
+        nia_protocol_rust::GetReferencesRequest,
+    > for NiaGetReferencesRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::GetReferencesRequest {
+        let mut get_references_request_pb =
+            nia_protocol_rust::GetReferencesRequest::new();
+
+        match &self.reference_target {
+            NiaReferenceTarget::Modifier(modifier_key) => {
+                get_references_request_pb.set_modifier_key(modifier_key.to_pb())
+            }
+            NiaReferenceTarget::Action(action_name) => {
+                get_references_request_pb
+                    .set_action_name(protobuf::Chars::from(action_name.clone()))
+            }
+        }
+
+        get_references_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::GetReferencesRequest,
+    ) -> NiaServerResult<NiaGetReferencesRequest> {
+        let mut object_pb = object_pb;
+
+        let reference_target = if object_pb.has_modifier_key() {
+            NiaReferenceTarget::Modifier(NiaKey::from_pb(
+                object_pb.take_modifier_key(),
+            )?)
+        } else if object_pb.has_action_name() {
+            NiaReferenceTarget::Action(object_pb.take_action_name().to_string())
+        } else {
+            return NiaServerError::deserialization_error(
+                "Provided neither modifier key nor action name.",
+            )
+            .into();
+        };
+
+        Ok(NiaGetReferencesRequest::new(reference_target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = vec![
+            NiaGetReferencesRequest::new(NiaReferenceTarget::Modifier(
+                NiaKey::make_key_2(1, 29),
+            )),
+            NiaGetReferencesRequest::new(NiaReferenceTarget::Action(
+                String::from("click-a"),
+            )),
+        ];
+
+        for expected in expected {
+            let bytes = expected.to_bytes().unwrap();
+            let result = NiaGetReferencesRequest::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, result);
+        }
+    }
+}
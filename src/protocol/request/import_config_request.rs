@@ -0,0 +1,101 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::{NiaConfigImportMode, Serializable};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaImportConfigRequest {
+    config: String,
+    import_mode: NiaConfigImportMode,
+}
+
+impl NiaImportConfigRequest {
+    pub fn new<S>(
+        config: S,
+        import_mode: NiaConfigImportMode,
+    ) -> NiaImportConfigRequest
+    where
+        S: Into<String>,
+    {
+        NiaImportConfigRequest {
+            config: config.into(),
+            import_mode,
+        }
+    }
+
+    pub fn get_config(&self) -> &str {
+        &self.config
+    }
+
+    pub fn get_import_mode(&self) -> NiaConfigImportMode {
+        self.import_mode
+    }
+
+    pub fn into_tuple(self) -> (String, NiaConfigImportMode) {
+        (self.config, self.import_mode)
+    }
+}
+
+impl
+    Serializable<NiaImportConfigRequest, nia_protocol_rust::ImportConfigRequest>
+    for NiaImportConfigRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::ImportConfigRequest {
+        let mut import_config_request_pb =
+            nia_protocol_rust::ImportConfigRequest::new();
+
+        import_config_request_pb
+            .set_config(protobuf::Chars::from(self.config.clone()));
+
+        match self.import_mode {
+            NiaConfigImportMode::Merge => import_config_request_pb
+                .set_merge_mode(nia_protocol_rust::ConfigMergeMode::new()),
+            NiaConfigImportMode::Replace => import_config_request_pb
+                .set_replace_mode(nia_protocol_rust::ConfigReplaceMode::new()),
+        }
+
+        import_config_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ImportConfigRequest,
+    ) -> NiaServerResult<NiaImportConfigRequest> {
+        let mut object_pb = object_pb;
+
+        let import_mode = if object_pb.has_merge_mode() {
+            NiaConfigImportMode::Merge
+        } else if object_pb.has_replace_mode() {
+            NiaConfigImportMode::Replace
+        } else {
+            return NiaServerError::deserialization_error(
+                "Provided neither a merge nor replace mode.",
+            )
+            .into();
+        };
+
+        let config = object_pb.take_config().to_string();
+
+        Ok(NiaImportConfigRequest::new(config, import_mode))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = vec![
+            NiaImportConfigRequest::new("{}", NiaConfigImportMode::Merge),
+            NiaImportConfigRequest::new("{}", NiaConfigImportMode::Replace),
+        ];
+
+        for expected in expected {
+            let bytes = expected.to_bytes().unwrap();
+            let result = NiaImportConfigRequest::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, result);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+use nia_protocol_rust::ListProfilesRequest;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaListProfilesRequest {}
+
+impl NiaListProfilesRequest {
+    pub fn new() -> NiaListProfilesRequest {
+        NiaListProfilesRequest {}
+    }
+}
+
+impl
+    Serializable<NiaListProfilesRequest, nia_protocol_rust::ListProfilesRequest>
+    for NiaListProfilesRequest
+{
+    fn to_pb(&self) -> ListProfilesRequest {
+        nia_protocol_rust::ListProfilesRequest::new()
+    }
+
+    fn from_pb(
+        object_pb: ListProfilesRequest,
+    ) -> NiaServerResult<NiaListProfilesRequest> {
+        Ok(NiaListProfilesRequest::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaListProfilesRequest::new();
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaListProfilesRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -1,43 +1,99 @@
+mod activate_profile_request;
+mod begin_transaction_request;
 mod change_mapping_request;
+mod check_mappings_request;
+mod commit_transaction_request;
+mod create_profile_request;
 mod define_action_request;
 mod define_device_request;
 mod define_mapping_request;
+mod define_modifier_group_request;
 mod define_modifier_request;
+mod define_text_mapping_request;
+mod delete_profile_request;
+mod diff_config_request;
 mod execute_code_request;
+mod export_config_request;
 mod get_defined_actions_request;
 mod get_defined_mappings_request;
 mod get_defined_modifiers_request;
+mod get_device_models_request;
 mod get_devices_request;
+mod get_history_request;
+mod get_references_request;
 mod handshake_request;
+mod import_config_request;
 mod is_listening_request;
+mod list_profiles_request;
+mod rebind_mapping_request;
+mod redo_request;
 mod remove_action_request;
 mod remove_device_by_id_request;
 mod remove_device_by_name_request;
 mod remove_device_by_path_request;
 mod remove_mapping_request;
+mod remove_modifier_group_request;
 mod remove_modifier_request;
+mod remove_text_mapping_request;
+mod rename_action_request;
+mod rename_modifier_alias_request;
 mod request;
+mod resolve_keys_request;
+mod rollback_transaction_request;
+mod set_mapping_enabled_request;
+mod set_mapping_metadata_request;
+mod set_mappings_enabled_by_tag_request;
+mod simulate_key_events_request;
 mod start_listening_request;
 mod stop_listening_request;
+mod undo_request;
 
+pub use activate_profile_request::*;
+pub use begin_transaction_request::*;
 pub use change_mapping_request::*;
+pub use check_mappings_request::*;
+pub use commit_transaction_request::*;
+pub use create_profile_request::*;
 pub use define_action_request::*;
 pub use define_device_request::*;
 pub use define_mapping_request::*;
+pub use define_modifier_group_request::*;
 pub use define_modifier_request::*;
+pub use define_text_mapping_request::*;
+pub use delete_profile_request::*;
+pub use diff_config_request::*;
 pub use execute_code_request::*;
+pub use export_config_request::*;
 pub use get_defined_actions_request::*;
 pub use get_defined_mappings_request::*;
 pub use get_defined_modifiers_request::*;
+pub use get_device_models_request::*;
 pub use get_devices_request::*;
+pub use get_history_request::*;
+pub use get_references_request::*;
 pub use handshake_request::*;
+pub use import_config_request::*;
 pub use is_listening_request::*;
+pub use list_profiles_request::*;
+pub use rebind_mapping_request::*;
+pub use redo_request::*;
 pub use remove_action_request::*;
 pub use remove_device_by_id_request::*;
 pub use remove_device_by_name_request::*;
 pub use remove_device_by_path_request::*;
 pub use remove_mapping_request::*;
+pub use remove_modifier_group_request::*;
 pub use remove_modifier_request::*;
+pub use remove_text_mapping_request::*;
+pub use rename_action_request::*;
+pub use rename_modifier_alias_request::*;
 pub use request::*;
+pub use resolve_keys_request::*;
+pub use rollback_transaction_request::*;
+pub use set_mapping_enabled_request::*;
+pub use set_mapping_metadata_request::*;
+pub use set_mappings_enabled_by_tag_request::*;
+pub use simulate_key_events_request::*;
 pub use start_listening_request::*;
 pub use stop_listening_request::*;
+pub use undo_request::*;
@@ -0,0 +1,119 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::NiaKeyChord;
+use crate::protocol::Serializable;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaRebindMappingRequest {
+    old_key_chords: Vec<NiaKeyChord>,
+    new_key_chords: Vec<NiaKeyChord>,
+}
+
+impl NiaRebindMappingRequest {
+    pub fn new(
+        old_key_chords: Vec<NiaKeyChord>,
+        new_key_chords: Vec<NiaKeyChord>,
+    ) -> NiaRebindMappingRequest {
+        NiaRebindMappingRequest {
+            old_key_chords,
+            new_key_chords,
+        }
+    }
+
+    pub fn get_old_key_chords(&self) -> &Vec<NiaKeyChord> {
+        &self.old_key_chords
+    }
+
+    pub fn get_new_key_chords(&self) -> &Vec<NiaKeyChord> {
+        &self.new_key_chords
+    }
+
+    pub fn into_tuple(self) -> (Vec<NiaKeyChord>, Vec<NiaKeyChord>) {
+        (self.old_key_chords, self.new_key_chords)
+    }
+}
+
+impl
+    Serializable<
+        NiaRebindMappingRequest,
+        nia_protocol_rust::RebindMappingRequest,
+    > for NiaRebindMappingRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::RebindMappingRequest {
+        let old_key_chords_pb = self
+            .old_key_chords
+            .iter()
+            .map(|key_chord| key_chord.to_pb())
+            .collect();
+        let new_key_chords_pb = self
+            .new_key_chords
+            .iter()
+            .map(|key_chord| key_chord.to_pb())
+            .collect();
+
+        let mut rebind_mapping_request_pb =
+            nia_protocol_rust::RebindMappingRequest::new();
+
+        rebind_mapping_request_pb.set_old_key_chords(old_key_chords_pb);
+        rebind_mapping_request_pb.set_new_key_chords(new_key_chords_pb);
+
+        rebind_mapping_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::RebindMappingRequest,
+    ) -> NiaServerResult<NiaRebindMappingRequest> {
+        let mut object_pb = object_pb;
+
+        let old_key_chords = object_pb
+            .take_old_key_chords()
+            .into_iter()
+            .map(|key_chord_pb| NiaKeyChord::from_pb(key_chord_pb))
+            .collect::<NiaServerResult<Vec<NiaKeyChord>>>()?;
+
+        let new_key_chords = object_pb
+            .take_new_key_chords()
+            .into_iter()
+            .map(|key_chord_pb| NiaKeyChord::from_pb(key_chord_pb))
+            .collect::<NiaServerResult<Vec<NiaKeyChord>>>()?;
+
+        if new_key_chords.is_empty() {
+            return NiaServerError::deserialization_error(
+                "New key chords of a rebound mapping cannot be empty.",
+            )
+            .into();
+        }
+
+        Ok(NiaRebindMappingRequest::new(old_key_chords, new_key_chords))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::NiaKey;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaRebindMappingRequest::new(
+            vec![NiaKeyChord::new(
+                vec![NiaKey::make_key_2(1, 29)],
+                NiaKey::make_key_2(1, 30),
+            )],
+            vec![
+                NiaKeyChord::new(
+                    vec![NiaKey::make_key_1(56)],
+                    NiaKey::make_key_1(30),
+                ),
+                NiaKeyChord::new(vec![], NiaKey::make_key_1(31)),
+            ],
+        );
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaRebindMappingRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
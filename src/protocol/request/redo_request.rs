@@ -0,0 +1,42 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+use nia_protocol_rust::RedoRequest;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaRedoRequest {}
+
+impl NiaRedoRequest {
+    pub fn new() -> NiaRedoRequest {
+        NiaRedoRequest {}
+    }
+}
+
+impl Serializable<NiaRedoRequest, nia_protocol_rust::RedoRequest>
+    for NiaRedoRequest
+{
+    fn to_pb(&self) -> RedoRequest {
+        nia_protocol_rust::RedoRequest::new()
+    }
+
+    fn from_pb(object_pb: RedoRequest) -> NiaServerResult<NiaRedoRequest> {
+        Ok(NiaRedoRequest::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaRedoRequest::new();
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaRedoRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
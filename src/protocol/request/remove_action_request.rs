@@ -2,11 +2,13 @@ use crate::error::NiaServerError;
 use crate::error::NiaServerResult;
 
 use crate::protocol::NiaAction;
+use crate::protocol::NiaRemovalMode;
 use crate::protocol::Serializable;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NiaRemoveActionRequest {
     action_name: String,
+    removal_mode: NiaRemovalMode,
 }
 
 impl NiaRemoveActionRequest {
@@ -16,9 +18,27 @@ impl NiaRemoveActionRequest {
     {
         NiaRemoveActionRequest {
             action_name: action_name.into(),
+            removal_mode: NiaRemovalMode::Strict,
         }
     }
 
+    pub fn with_removal_mode<S>(
+        action_name: S,
+        removal_mode: NiaRemovalMode,
+    ) -> NiaRemoveActionRequest
+    where
+        S: Into<String>,
+    {
+        NiaRemoveActionRequest {
+            action_name: action_name.into(),
+            removal_mode,
+        }
+    }
+
+    pub fn get_removal_mode(&self) -> NiaRemovalMode {
+        self.removal_mode
+    }
+
     pub fn take_action_name(self) -> String {
         self.action_name
     }
@@ -34,6 +54,8 @@ impl
 
         remove_action_request_pb
             .set_action_name(protobuf::Chars::from(self.action_name.clone()));
+        remove_action_request_pb.set_cascade(self.removal_mode.is_cascade());
+        remove_action_request_pb.set_force(self.removal_mode.is_force());
 
         remove_action_request_pb
     }
@@ -44,8 +66,15 @@ impl
         let mut object_pb = object_pb;
 
         let action_name = object_pb.take_action_name().to_string();
+        let removal_mode = NiaRemovalMode::from_flags(
+            object_pb.get_cascade(),
+            object_pb.get_force(),
+        )?;
 
-        Ok(NiaRemoveActionRequest::new(action_name))
+        Ok(NiaRemoveActionRequest::with_removal_mode(
+            action_name,
+            removal_mode,
+        ))
     }
 }
 
@@ -64,4 +93,20 @@ mod tests {
 
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn serializes_and_deserializes_removal_modes() {
+        let removal_modes =
+            vec![NiaRemovalMode::Cascade, NiaRemovalMode::Force];
+
+        for removal_mode in removal_modes {
+            let expected =
+                NiaRemoveActionRequest::with_removal_mode("dev", removal_mode);
+
+            let bytes = expected.to_bytes().unwrap();
+            let result = NiaRemoveActionRequest::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, result);
+        }
+    }
 }
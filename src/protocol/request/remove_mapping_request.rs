@@ -18,6 +18,10 @@ impl NiaRemoveMappingRequest {
     pub fn take_key_chords(self) -> Vec<NiaKeyChord> {
         self.key_chords
     }
+
+    pub fn get_key_chords(&self) -> &Vec<NiaKeyChord> {
+        &self.key_chords
+    }
 }
 
 impl
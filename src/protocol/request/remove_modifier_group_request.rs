@@ -0,0 +1,70 @@
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaRemoveModifierGroupRequest {
+    alias: String,
+}
+
+impl NiaRemoveModifierGroupRequest {
+    pub fn new<S>(alias: S) -> NiaRemoveModifierGroupRequest
+    where
+        S: Into<String>,
+    {
+        NiaRemoveModifierGroupRequest {
+            alias: alias.into(),
+        }
+    }
+
+    pub fn get_alias(&self) -> &str {
+        &self.alias
+    }
+
+    pub fn take_alias(self) -> String {
+        self.alias
+    }
+}
+
+impl
+    Serializable<
+        NiaRemoveModifierGroupRequest,
+        nia_protocol_rust::RemoveModifierGroupRequest,
+    > for NiaRemoveModifierGroupRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::RemoveModifierGroupRequest {
+        let mut remove_modifier_group_request_pb =
+            nia_protocol_rust::RemoveModifierGroupRequest::new();
+
+        remove_modifier_group_request_pb
+            .set_alias(protobuf::Chars::from(self.alias.clone()));
+
+        remove_modifier_group_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::RemoveModifierGroupRequest,
+    ) -> NiaServerResult<NiaRemoveModifierGroupRequest> {
+        let mut object_pb = object_pb;
+
+        let alias = object_pb.take_alias().to_string();
+
+        Ok(NiaRemoveModifierGroupRequest::new(alias))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaRemoveModifierGroupRequest::new("Control");
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaRemoveModifierGroupRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
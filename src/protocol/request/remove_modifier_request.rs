@@ -2,22 +2,37 @@ use nia_protocol_rust::RemoveModifierRequest;
 
 use crate::error::{NiaServerError, NiaServerResult};
 
-use crate::protocol::NiaKey;
 use crate::protocol::Serializable;
+use crate::protocol::{NiaKey, NiaRemovalMode};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NiaRemoveModifierRequest {
     key: NiaKey,
+    removal_mode: NiaRemovalMode,
 }
 
 impl NiaRemoveModifierRequest {
     pub fn new(key: NiaKey) -> NiaRemoveModifierRequest {
-        NiaRemoveModifierRequest { key }
+        NiaRemoveModifierRequest {
+            key,
+            removal_mode: NiaRemovalMode::Strict,
+        }
+    }
+
+    pub fn with_removal_mode(
+        key: NiaKey,
+        removal_mode: NiaRemovalMode,
+    ) -> NiaRemoveModifierRequest {
+        NiaRemoveModifierRequest { key, removal_mode }
     }
 
     pub fn take_key(&self) -> NiaKey {
         self.key
     }
+
+    pub fn get_removal_mode(&self) -> NiaRemovalMode {
+        self.removal_mode
+    }
 }
 
 impl
@@ -32,6 +47,8 @@ impl
             nia_protocol_rust::RemoveModifierRequest::new();
 
         remove_modifier_request_pb.set_modifier_key(modifier_key_pb);
+        remove_modifier_request_pb.set_cascade(self.removal_mode.is_cascade());
+        remove_modifier_request_pb.set_force(self.removal_mode.is_force());
 
         remove_modifier_request_pb
     }
@@ -42,8 +59,13 @@ impl
         let mut object_pb = object_pb;
 
         let key = NiaKey::from_pb(object_pb.take_modifier_key())?;
+        let removal_mode = NiaRemovalMode::from_flags(
+            object_pb.get_cascade(),
+            object_pb.get_force(),
+        )?;
 
-        let remove_modifier_request = NiaRemoveModifierRequest::new(key);
+        let remove_modifier_request =
+            NiaRemoveModifierRequest::with_removal_mode(key, removal_mode);
 
         Ok(remove_modifier_request)
     }
@@ -73,4 +95,39 @@ mod tests {
 
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn serializes_and_deserializes_removal_modes() {
+        let removal_modes = vec![
+            NiaRemovalMode::Strict,
+            NiaRemovalMode::Cascade,
+            NiaRemovalMode::Force,
+        ];
+
+        for removal_mode in removal_modes {
+            let expected = NiaRemoveModifierRequest::with_removal_mode(
+                NiaKey::Key1(29),
+                removal_mode,
+            );
+
+            let bytes = expected.to_bytes().unwrap();
+            let result = NiaRemoveModifierRequest::from_bytes(bytes).unwrap();
+
+            assert_eq!(expected, result);
+        }
+    }
+
+    #[test]
+    fn rejects_both_cascade_and_force() {
+        let mut remove_modifier_request_pb =
+            NiaRemoveModifierRequest::new(NiaKey::Key1(29)).to_pb();
+
+        remove_modifier_request_pb.set_cascade(true);
+        remove_modifier_request_pb.set_force(true);
+
+        assert!(
+            NiaRemoveModifierRequest::from_pb(remove_modifier_request_pb)
+                .is_err()
+        );
+    }
 }
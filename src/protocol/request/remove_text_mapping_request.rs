@@ -0,0 +1,69 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+
+// Same as `NiaRemoveMappingRequest`, but key chords are written in the
+// textual notation, e.g. "C-x C-s".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaRemoveTextMappingRequest {
+    key_chords: String,
+}
+
+impl NiaRemoveTextMappingRequest {
+    pub fn new<S>(key_chords: S) -> NiaRemoveTextMappingRequest
+    where
+        S: Into<String>,
+    {
+        NiaRemoveTextMappingRequest {
+            key_chords: key_chords.into(),
+        }
+    }
+
+    pub fn take_key_chords(self) -> String {
+        self.key_chords
+    }
+}
+
+impl
+    Serializable<
+        NiaRemoveTextMappingRequest,
+        nia_protocol_rust::RemoveTextMappingRequest,
+    > for NiaRemoveTextMappingRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::RemoveTextMappingRequest {
+        let mut remove_text_mapping_request_pb =
+            nia_protocol_rust::RemoveTextMappingRequest::new();
+
+        remove_text_mapping_request_pb
+            .set_key_chords(protobuf::Chars::from(self.key_chords.clone()));
+
+        remove_text_mapping_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::RemoveTextMappingRequest,
+    ) -> NiaServerResult<NiaRemoveTextMappingRequest> {
+        let mut object_pb = object_pb;
+
+        let key_chords = object_pb.take_key_chords().to_string();
+
+        Ok(NiaRemoveTextMappingRequest::new(key_chords))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaRemoveTextMappingRequest::new("2:LeftCtrl+2:q");
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaRemoveTextMappingRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
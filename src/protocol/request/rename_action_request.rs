@@ -0,0 +1,85 @@
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaRenameActionRequest {
+    old_action_name: String,
+    new_action_name: String,
+}
+
+impl NiaRenameActionRequest {
+    pub fn new<S>(
+        old_action_name: S,
+        new_action_name: S,
+    ) -> NiaRenameActionRequest
+    where
+        S: Into<String>,
+    {
+        NiaRenameActionRequest {
+            old_action_name: old_action_name.into(),
+            new_action_name: new_action_name.into(),
+        }
+    }
+
+    pub fn get_old_action_name(&self) -> &str {
+        &self.old_action_name
+    }
+
+    pub fn get_new_action_name(&self) -> &str {
+        &self.new_action_name
+    }
+
+    pub fn into_tuple(self) -> (String, String) {
+        (self.old_action_name, self.new_action_name)
+    }
+}
+
+impl
+    Serializable<NiaRenameActionRequest, nia_protocol_rust::RenameActionRequest>
+    for NiaRenameActionRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::RenameActionRequest {
+        let mut rename_action_request_pb =
+            nia_protocol_rust::RenameActionRequest::new();
+
+        rename_action_request_pb.set_old_action_name(protobuf::Chars::from(
+            self.old_action_name.clone(),
+        ));
+        rename_action_request_pb.set_new_action_name(protobuf::Chars::from(
+            self.new_action_name.clone(),
+        ));
+
+        rename_action_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::RenameActionRequest,
+    ) -> NiaServerResult<NiaRenameActionRequest> {
+        let mut object_pb = object_pb;
+
+        let old_action_name = object_pb.take_old_action_name().to_string();
+        let new_action_name = object_pb.take_new_action_name().to_string();
+
+        Ok(NiaRenameActionRequest::new(
+            old_action_name,
+            new_action_name,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaRenameActionRequest::new("click-a", "press-a");
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaRenameActionRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaRenameModifierAliasRequest {
+    old_alias: String,
+    new_alias: String,
+}
+
+impl NiaRenameModifierAliasRequest {
+    pub fn new<S>(old_alias: S, new_alias: S) -> NiaRenameModifierAliasRequest
+    where
+        S: Into<String>,
+    {
+        NiaRenameModifierAliasRequest {
+            old_alias: old_alias.into(),
+            new_alias: new_alias.into(),
+        }
+    }
+
+    pub fn get_old_alias(&self) -> &str {
+        &self.old_alias
+    }
+
+    pub fn get_new_alias(&self) -> &str {
+        &self.new_alias
+    }
+
+    pub fn into_tuple(self) -> (String, String) {
+        (self.old_alias, self.new_alias)
+    }
+}
+
+impl
+    Serializable<
+        NiaRenameModifierAliasRequest,
+        nia_protocol_rust::RenameModifierAliasRequest,
+    > for NiaRenameModifierAliasRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::RenameModifierAliasRequest {
+        let mut rename_modifier_alias_request_pb =
+            nia_protocol_rust::RenameModifierAliasRequest::new();
+
+        rename_modifier_alias_request_pb
+            .set_old_alias(protobuf::Chars::from(self.old_alias.clone()));
+        rename_modifier_alias_request_pb
+            .set_new_alias(protobuf::Chars::from(self.new_alias.clone()));
+
+        rename_modifier_alias_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::RenameModifierAliasRequest,
+    ) -> NiaServerResult<NiaRenameModifierAliasRequest> {
+        let mut object_pb = object_pb;
+
+        let old_alias = object_pb.take_old_alias().to_string();
+        let new_alias = object_pb.take_new_alias().to_string();
+
+        Ok(NiaRenameModifierAliasRequest::new(old_alias, new_alias))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaRenameModifierAliasRequest::new("ctrl", "Control");
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaRenameModifierAliasRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -26,6 +26,34 @@ pub enum NiaRequest {
     IsListening(NiaIsListeningRequest),
     StartListening(NiaStartListeningRequest),
     StopListening(NiaStopListeningRequest),
+    GetDeviceModels(NiaGetDeviceModelsRequest),
+    DefineTextMapping(NiaDefineTextMappingRequest),
+    RemoveTextMapping(NiaRemoveTextMappingRequest),
+    CheckMappings(NiaCheckMappingsRequest),
+    ResolveKeys(NiaResolveKeysRequest),
+    SimulateKeyEvents(NiaSimulateKeyEventsRequest),
+    ExportConfig(NiaExportConfigRequest),
+    ImportConfig(NiaImportConfigRequest),
+    CreateProfile(NiaCreateProfileRequest),
+    ListProfiles(NiaListProfilesRequest),
+    ActivateProfile(NiaActivateProfileRequest),
+    DeleteProfile(NiaDeleteProfileRequest),
+    SetMappingEnabled(NiaSetMappingEnabledRequest),
+    SetMappingsEnabledByTag(NiaSetMappingsEnabledByTagRequest),
+    SetMappingMetadata(NiaSetMappingMetadataRequest),
+    RebindMapping(NiaRebindMappingRequest),
+    BeginTransaction(NiaBeginTransactionRequest),
+    CommitTransaction(NiaCommitTransactionRequest),
+    RollbackTransaction(NiaRollbackTransactionRequest),
+    Undo(NiaUndoRequest),
+    Redo(NiaRedoRequest),
+    GetHistory(NiaGetHistoryRequest),
+    DiffConfig(NiaDiffConfigRequest),
+    DefineModifierGroup(NiaDefineModifierGroupRequest),
+    RemoveModifierGroup(NiaRemoveModifierGroupRequest),
+    GetReferences(NiaGetReferencesRequest),
+    RenameAction(NiaRenameActionRequest),
+    RenameModifierAlias(NiaRenameModifierAliasRequest),
 }
 
 macro_rules! make_from_implementation {
@@ -76,6 +104,113 @@ make_from_implementation!(NiaRemoveMappingRequest, NiaRequest::RemoveMapping);
 make_from_implementation!(NiaIsListeningRequest, NiaRequest::IsListening);
 make_from_implementation!(NiaStartListeningRequest, NiaRequest::StartListening);
 make_from_implementation!(NiaStopListeningRequest, NiaRequest::StopListening);
+make_from_implementation!(
+    NiaGetDeviceModelsRequest,
+    NiaRequest::GetDeviceModels
+);
+make_from_implementation!(
+    NiaDefineTextMappingRequest,
+    NiaRequest::DefineTextMapping
+);
+make_from_implementation!(
+    NiaRemoveTextMappingRequest,
+    NiaRequest::RemoveTextMapping
+);
+make_from_implementation!(NiaCheckMappingsRequest, NiaRequest::CheckMappings);
+make_from_implementation!(NiaResolveKeysRequest, NiaRequest::ResolveKeys);
+make_from_implementation!(
+    NiaSimulateKeyEventsRequest,
+    NiaRequest::SimulateKeyEvents
+);
+make_from_implementation!(NiaExportConfigRequest, NiaRequest::ExportConfig);
+make_from_implementation!(NiaImportConfigRequest, NiaRequest::ImportConfig);
+make_from_implementation!(NiaCreateProfileRequest, NiaRequest::CreateProfile);
+make_from_implementation!(NiaListProfilesRequest, NiaRequest::ListProfiles);
+make_from_implementation!(
+    NiaActivateProfileRequest,
+    NiaRequest::ActivateProfile
+);
+make_from_implementation!(NiaDeleteProfileRequest, NiaRequest::DeleteProfile);
+make_from_implementation!(
+    NiaSetMappingEnabledRequest,
+    NiaRequest::SetMappingEnabled
+);
+make_from_implementation!(
+    NiaSetMappingsEnabledByTagRequest,
+    NiaRequest::SetMappingsEnabledByTag
+);
+make_from_implementation!(
+    NiaSetMappingMetadataRequest,
+    NiaRequest::SetMappingMetadata
+);
+make_from_implementation!(NiaRebindMappingRequest, NiaRequest::RebindMapping);
+make_from_implementation!(
+    NiaBeginTransactionRequest,
+    NiaRequest::BeginTransaction
+);
+make_from_implementation!(
+    NiaCommitTransactionRequest,
+    NiaRequest::CommitTransaction
+);
+make_from_implementation!(
+    NiaRollbackTransactionRequest,
+    NiaRequest::RollbackTransaction
+);
+make_from_implementation!(NiaUndoRequest, NiaRequest::Undo);
+make_from_implementation!(NiaRedoRequest, NiaRequest::Redo);
+make_from_implementation!(NiaGetHistoryRequest, NiaRequest::GetHistory);
+make_from_implementation!(NiaDiffConfigRequest, NiaRequest::DiffConfig);
+make_from_implementation!(
+    NiaDefineModifierGroupRequest,
+    NiaRequest::DefineModifierGroup
+);
+make_from_implementation!(
+    NiaRemoveModifierGroupRequest,
+    NiaRequest::RemoveModifierGroup
+);
+make_from_implementation!(NiaGetReferencesRequest, NiaRequest::GetReferences);
+make_from_implementation!(NiaRenameActionRequest, NiaRequest::RenameAction);
+make_from_implementation!(
+    NiaRenameModifierAliasRequest,
+    NiaRequest::RenameModifierAlias
+);
+
+impl NiaRequest {
+    // Used in logs: key chords are written in the key chord notation, other
+    // requests are printed as they are.
+    pub fn describe(&self) -> String {
+        let (request_name, key_chords) = match self {
+            NiaRequest::DefineMapping(request) => {
+                ("DefineMapping", request.get_key_chords())
+            }
+            NiaRequest::ChangeMapping(request) => {
+                ("ChangeMapping", request.get_key_chords())
+            }
+            NiaRequest::RemoveMapping(request) => {
+                ("RemoveMapping", request.get_key_chords())
+            }
+            NiaRequest::ResolveKeys(request) => {
+                ("ResolveKeys", request.get_key_chords())
+            }
+            NiaRequest::SetMappingEnabled(request) => {
+                ("SetMappingEnabled", request.get_key_chords())
+            }
+            NiaRequest::SetMappingMetadata(request) => {
+                ("SetMappingMetadata", request.get_key_chords())
+            }
+            NiaRequest::RebindMapping(request) => {
+                return format!(
+                    "RebindMapping {} -> {}",
+                    format_key_chords(request.get_old_key_chords()),
+                    format_key_chords(request.get_new_key_chords())
+                );
+            }
+            _ => return format!("{:?}", self),
+        };
+
+        format!("{} {}", request_name, format_key_chords(key_chords))
+    }
+}
 
 impl Serializable<NiaRequest, nia_protocol_rust::Request> for NiaRequest {
     fn to_pb(&self) -> Request {
@@ -142,6 +277,105 @@ impl Serializable<NiaRequest, nia_protocol_rust::Request> for NiaRequest {
                 .set_start_listening_request(start_listening_request.to_pb()),
             NiaRequest::StopListening(stop_listening_request) => request_pb
                 .set_stop_listening_request(stop_listening_request.to_pb()),
+            NiaRequest::GetDeviceModels(get_device_models_request) => {
+                request_pb.set_get_device_models_request(
+                    get_device_models_request.to_pb(),
+                )
+            }
+            NiaRequest::DefineTextMapping(define_text_mapping_request) => {
+                request_pb.set_define_text_mapping_request(
+                    define_text_mapping_request.to_pb(),
+                )
+            }
+            NiaRequest::RemoveTextMapping(remove_text_mapping_request) => {
+                request_pb.set_remove_text_mapping_request(
+                    remove_text_mapping_request.to_pb(),
+                )
+            }
+            NiaRequest::CheckMappings(check_mappings_request) => request_pb
+                .set_check_mappings_request(check_mappings_request.to_pb()),
+            NiaRequest::ResolveKeys(resolve_keys_request) => request_pb
+                .set_resolve_keys_request(resolve_keys_request.to_pb()),
+            NiaRequest::SimulateKeyEvents(simulate_key_events_request) => {
+                request_pb.set_simulate_key_events_request(
+                    simulate_key_events_request.to_pb(),
+                )
+            }
+            NiaRequest::ExportConfig(export_config_request) => request_pb
+                .set_export_config_request(export_config_request.to_pb()),
+            NiaRequest::ImportConfig(import_config_request) => request_pb
+                .set_import_config_request(import_config_request.to_pb()),
+            NiaRequest::CreateProfile(create_profile_request) => request_pb
+                .set_create_profile_request(create_profile_request.to_pb()),
+            NiaRequest::ListProfiles(list_profiles_request) => request_pb
+                .set_list_profiles_request(list_profiles_request.to_pb()),
+            NiaRequest::ActivateProfile(activate_profile_request) => request_pb
+                .set_activate_profile_request(activate_profile_request.to_pb()),
+            NiaRequest::DeleteProfile(delete_profile_request) => request_pb
+                .set_delete_profile_request(delete_profile_request.to_pb()),
+            NiaRequest::SetMappingEnabled(set_mapping_enabled_request) => {
+                request_pb.set_set_mapping_enabled_request(
+                    set_mapping_enabled_request.to_pb(),
+                )
+            }
+            NiaRequest::SetMappingsEnabledByTag(
+                set_mappings_enabled_by_tag_request,
+            ) => request_pb.set_set_mappings_enabled_by_tag_request(
+                set_mappings_enabled_by_tag_request.to_pb(),
+            ),
+            NiaRequest::SetMappingMetadata(set_mapping_metadata_request) => {
+                request_pb.set_set_mapping_metadata_request(
+                    set_mapping_metadata_request.to_pb(),
+                )
+            }
+            NiaRequest::RebindMapping(rebind_mapping_request) => request_pb
+                .set_rebind_mapping_request(rebind_mapping_request.to_pb()),
+            NiaRequest::BeginTransaction(begin_transaction_request) => {
+                request_pb.set_begin_transaction_request(
+                    begin_transaction_request.to_pb(),
+                )
+            }
+            NiaRequest::CommitTransaction(commit_transaction_request) => {
+                request_pb.set_commit_transaction_request(
+                    commit_transaction_request.to_pb(),
+                )
+            }
+            NiaRequest::RollbackTransaction(rollback_transaction_request) => {
+                request_pb.set_rollback_transaction_request(
+                    rollback_transaction_request.to_pb(),
+                )
+            }
+            NiaRequest::Undo(undo_request) => {
+                request_pb.set_undo_request(undo_request.to_pb())
+            }
+            NiaRequest::Redo(redo_request) => {
+                request_pb.set_redo_request(redo_request.to_pb())
+            }
+            NiaRequest::GetHistory(get_history_request) => {
+                request_pb.set_get_history_request(get_history_request.to_pb())
+            }
+            NiaRequest::DiffConfig(diff_config_request) => {
+                request_pb.set_diff_config_request(diff_config_request.to_pb())
+            }
+            NiaRequest::DefineModifierGroup(define_modifier_group_request) => {
+                request_pb.set_define_modifier_group_request(
+                    define_modifier_group_request.to_pb(),
+                )
+            }
+            NiaRequest::RemoveModifierGroup(remove_modifier_group_request) => {
+                request_pb.set_remove_modifier_group_request(
+                    remove_modifier_group_request.to_pb(),
+                )
+            }
+            NiaRequest::GetReferences(get_references_request) => request_pb
+                .set_get_references_request(get_references_request.to_pb()),
+            NiaRequest::RenameAction(rename_action_request) => request_pb
+                .set_rename_action_request(rename_action_request.to_pb()),
+            NiaRequest::RenameModifierAlias(rename_modifier_alias_request) => {
+                request_pb.set_rename_modifier_alias_request(
+                    rename_modifier_alias_request.to_pb(),
+                )
+            }
         }
 
         request_pb
@@ -256,6 +490,158 @@ impl Serializable<NiaRequest, nia_protocol_rust::Request> for NiaRequest {
                 request_pb.take_stop_listening_request(),
             )?;
             NiaRequest::StopListening(stop_listening_request)
+        } else if request_pb.has_get_device_models_request() {
+            let get_device_models_request = NiaGetDeviceModelsRequest::from_pb(
+                request_pb.take_get_device_models_request(),
+            )?;
+            NiaRequest::GetDeviceModels(get_device_models_request)
+        } else if request_pb.has_define_text_mapping_request() {
+            let define_text_mapping_request =
+                NiaDefineTextMappingRequest::from_pb(
+                    request_pb.take_define_text_mapping_request(),
+                )?;
+            NiaRequest::DefineTextMapping(define_text_mapping_request)
+        } else if request_pb.has_remove_text_mapping_request() {
+            let remove_text_mapping_request =
+                NiaRemoveTextMappingRequest::from_pb(
+                    request_pb.take_remove_text_mapping_request(),
+                )?;
+            NiaRequest::RemoveTextMapping(remove_text_mapping_request)
+        } else if request_pb.has_check_mappings_request() {
+            let check_mappings_request = NiaCheckMappingsRequest::from_pb(
+                request_pb.take_check_mappings_request(),
+            )?;
+            NiaRequest::CheckMappings(check_mappings_request)
+        } else if request_pb.has_resolve_keys_request() {
+            let resolve_keys_request = NiaResolveKeysRequest::from_pb(
+                request_pb.take_resolve_keys_request(),
+            )?;
+            NiaRequest::ResolveKeys(resolve_keys_request)
+        } else if request_pb.has_simulate_key_events_request() {
+            let simulate_key_events_request =
+                NiaSimulateKeyEventsRequest::from_pb(
+                    request_pb.take_simulate_key_events_request(),
+                )?;
+            NiaRequest::SimulateKeyEvents(simulate_key_events_request)
+        } else if request_pb.has_export_config_request() {
+            let export_config_request = NiaExportConfigRequest::from_pb(
+                request_pb.take_export_config_request(),
+            )?;
+            NiaRequest::ExportConfig(export_config_request)
+        } else if request_pb.has_import_config_request() {
+            let import_config_request = NiaImportConfigRequest::from_pb(
+                request_pb.take_import_config_request(),
+            )?;
+            NiaRequest::ImportConfig(import_config_request)
+        } else if request_pb.has_create_profile_request() {
+            let create_profile_request = NiaCreateProfileRequest::from_pb(
+                request_pb.take_create_profile_request(),
+            )?;
+            NiaRequest::CreateProfile(create_profile_request)
+        } else if request_pb.has_list_profiles_request() {
+            let list_profiles_request = NiaListProfilesRequest::from_pb(
+                request_pb.take_list_profiles_request(),
+            )?;
+            NiaRequest::ListProfiles(list_profiles_request)
+        } else if request_pb.has_activate_profile_request() {
+            let activate_profile_request = NiaActivateProfileRequest::from_pb(
+                request_pb.take_activate_profile_request(),
+            )?;
+            NiaRequest::ActivateProfile(activate_profile_request)
+        } else if request_pb.has_delete_profile_request() {
+            let delete_profile_request = NiaDeleteProfileRequest::from_pb(
+                request_pb.take_delete_profile_request(),
+            )?;
+            NiaRequest::DeleteProfile(delete_profile_request)
+        } else if request_pb.has_set_mapping_enabled_request() {
+            let set_mapping_enabled_request =
+                NiaSetMappingEnabledRequest::from_pb(
+                    request_pb.take_set_mapping_enabled_request(),
+                )?;
+            NiaRequest::SetMappingEnabled(set_mapping_enabled_request)
+        } else if request_pb.has_set_mappings_enabled_by_tag_request() {
+            let set_mappings_enabled_by_tag_request =
+                NiaSetMappingsEnabledByTagRequest::from_pb(
+                    request_pb.take_set_mappings_enabled_by_tag_request(),
+                )?;
+            NiaRequest::SetMappingsEnabledByTag(
+                set_mappings_enabled_by_tag_request,
+            )
+        } else if request_pb.has_set_mapping_metadata_request() {
+            let set_mapping_metadata_request =
+                NiaSetMappingMetadataRequest::from_pb(
+                    request_pb.take_set_mapping_metadata_request(),
+                )?;
+            NiaRequest::SetMappingMetadata(set_mapping_metadata_request)
+        } else if request_pb.has_rebind_mapping_request() {
+            let rebind_mapping_request = NiaRebindMappingRequest::from_pb(
+                request_pb.take_rebind_mapping_request(),
+            )?;
+            NiaRequest::RebindMapping(rebind_mapping_request)
+        } else if request_pb.has_begin_transaction_request() {
+            let begin_transaction_request =
+                NiaBeginTransactionRequest::from_pb(
+                    request_pb.take_begin_transaction_request(),
+                )?;
+            NiaRequest::BeginTransaction(begin_transaction_request)
+        } else if request_pb.has_commit_transaction_request() {
+            let commit_transaction_request =
+                NiaCommitTransactionRequest::from_pb(
+                    request_pb.take_commit_transaction_request(),
+                )?;
+            NiaRequest::CommitTransaction(commit_transaction_request)
+        } else if request_pb.has_rollback_transaction_request() {
+            let rollback_transaction_request =
+                NiaRollbackTransactionRequest::from_pb(
+                    request_pb.take_rollback_transaction_request(),
+                )?;
+            NiaRequest::RollbackTransaction(rollback_transaction_request)
+        } else if request_pb.has_undo_request() {
+            let undo_request =
+                NiaUndoRequest::from_pb(request_pb.take_undo_request())?;
+            NiaRequest::Undo(undo_request)
+        } else if request_pb.has_redo_request() {
+            let redo_request =
+                NiaRedoRequest::from_pb(request_pb.take_redo_request())?;
+            NiaRequest::Redo(redo_request)
+        } else if request_pb.has_get_history_request() {
+            let get_history_request = NiaGetHistoryRequest::from_pb(
+                request_pb.take_get_history_request(),
+            )?;
+            NiaRequest::GetHistory(get_history_request)
+        } else if request_pb.has_diff_config_request() {
+            let diff_config_request = NiaDiffConfigRequest::from_pb(
+                request_pb.take_diff_config_request(),
+            )?;
+            NiaRequest::DiffConfig(diff_config_request)
+        } else if request_pb.has_define_modifier_group_request() {
+            let define_modifier_group_request =
+                NiaDefineModifierGroupRequest::from_pb(
+                    request_pb.take_define_modifier_group_request(),
+                )?;
+            NiaRequest::DefineModifierGroup(define_modifier_group_request)
+        } else if request_pb.has_remove_modifier_group_request() {
+            let remove_modifier_group_request =
+                NiaRemoveModifierGroupRequest::from_pb(
+                    request_pb.take_remove_modifier_group_request(),
+                )?;
+            NiaRequest::RemoveModifierGroup(remove_modifier_group_request)
+        } else if request_pb.has_get_references_request() {
+            let get_references_request = NiaGetReferencesRequest::from_pb(
+                request_pb.take_get_references_request(),
+            )?;
+            NiaRequest::GetReferences(get_references_request)
+        } else if request_pb.has_rename_action_request() {
+            let rename_action_request = NiaRenameActionRequest::from_pb(
+                request_pb.take_rename_action_request(),
+            )?;
+            NiaRequest::RenameAction(rename_action_request)
+        } else if request_pb.has_rename_modifier_alias_request() {
+            let rename_modifier_alias_request =
+                NiaRenameModifierAliasRequest::from_pb(
+                    request_pb.take_rename_modifier_alias_request(),
+                )?;
+            NiaRequest::RenameModifierAlias(rename_modifier_alias_request)
         } else {
             return NiaServerError::deserialization_error("Unknown request.")
                 .into();
@@ -0,0 +1,95 @@
+use crate::error::NiaServerResult;
+
+use crate::protocol::NiaKeyChord;
+use crate::protocol::Serializable;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaResolveKeysRequest {
+    key_chords: Vec<NiaKeyChord>,
+}
+
+impl NiaResolveKeysRequest {
+    pub fn new(key_chords: Vec<NiaKeyChord>) -> NiaResolveKeysRequest {
+        NiaResolveKeysRequest { key_chords }
+    }
+
+    pub fn get_key_chords(&self) -> &Vec<NiaKeyChord> {
+        &self.key_chords
+    }
+
+    pub fn take_key_chords(self) -> Vec<NiaKeyChord> {
+        self.key_chords
+    }
+}
+
+impl Serializable<NiaResolveKeysRequest, nia_protocol_rust::ResolveKeysRequest>
+    for NiaResolveKeysRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::ResolveKeysRequest {
+        let mut key_chords_pb_vector = Vec::new();
+
+        for key_chord in &self.key_chords {
+            key_chords_pb_vector.push(key_chord.to_pb());
+        }
+
+        let mut resolve_keys_request_pb =
+            nia_protocol_rust::ResolveKeysRequest::new();
+
+        resolve_keys_request_pb.set_key_chords(
+            protobuf::RepeatedField::from_vec(key_chords_pb_vector),
+        );
+
+        resolve_keys_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ResolveKeysRequest,
+    ) -> NiaServerResult<NiaResolveKeysRequest> {
+        let mut object_pb = object_pb;
+        let mut key_chords = Vec::new();
+
+        for key_chord_pb in object_pb.take_key_chords().into_iter() {
+            let key_chord = NiaKeyChord::from_pb(key_chord_pb)?;
+
+            key_chords.push(key_chord);
+        }
+
+        let request = NiaResolveKeysRequest::new(key_chords);
+
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::NiaKey;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaResolveKeysRequest::new(vec![
+            NiaKeyChord::new(
+                vec![
+                    NiaKey::make_key_2(1, 2),
+                    NiaKey::make_key_2(1, 3),
+                    NiaKey::make_key_2(1, 4),
+                ],
+                NiaKey::make_key_2(1, 5),
+            ),
+            NiaKeyChord::new(
+                vec![
+                    NiaKey::make_key_2(2, 2),
+                    NiaKey::make_key_2(2, 3),
+                    NiaKey::make_key_2(2, 4),
+                ],
+                NiaKey::make_key_2(2, 5),
+            ),
+        ]);
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaResolveKeysRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+use nia_protocol_rust::RollbackTransactionRequest;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaRollbackTransactionRequest {}
+
+impl NiaRollbackTransactionRequest {
+    pub fn new() -> NiaRollbackTransactionRequest {
+        NiaRollbackTransactionRequest {}
+    }
+}
+
+impl
+    Serializable<
+        NiaRollbackTransactionRequest,
+        nia_protocol_rust::RollbackTransactionRequest,
+    > for NiaRollbackTransactionRequest
+{
+    fn to_pb(&self) -> RollbackTransactionRequest {
+        nia_protocol_rust::RollbackTransactionRequest::new()
+    }
+
+    fn from_pb(
+        object_pb: RollbackTransactionRequest,
+    ) -> NiaServerResult<NiaRollbackTransactionRequest> {
+        Ok(NiaRollbackTransactionRequest::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaRollbackTransactionRequest::new();
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaRollbackTransactionRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
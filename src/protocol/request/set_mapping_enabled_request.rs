@@ -0,0 +1,96 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::NiaKeyChord;
+use crate::protocol::Serializable;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaSetMappingEnabledRequest {
+    key_chords: Vec<NiaKeyChord>,
+    enabled: bool,
+}
+
+impl NiaSetMappingEnabledRequest {
+    pub fn new(
+        key_chords: Vec<NiaKeyChord>,
+        enabled: bool,
+    ) -> NiaSetMappingEnabledRequest {
+        NiaSetMappingEnabledRequest {
+            key_chords,
+            enabled,
+        }
+    }
+
+    pub fn into_tuple(self) -> (Vec<NiaKeyChord>, bool) {
+        (self.key_chords, self.enabled)
+    }
+
+    pub fn get_key_chords(&self) -> &Vec<NiaKeyChord> {
+        &self.key_chords
+    }
+}
+
+impl
+    Serializable<
+        NiaSetMappingEnabledRequest,
+        nia_protocol_rust::SetMappingEnabledRequest,
+    > for NiaSetMappingEnabledRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::SetMappingEnabledRequest {
+        let key_chords_pb = self
+            .key_chords
+            .iter()
+            .map(|key_chord| key_chord.to_pb())
+            .collect();
+
+        let mut set_mapping_enabled_request_pb =
+            nia_protocol_rust::SetMappingEnabledRequest::new();
+
+        set_mapping_enabled_request_pb.set_key_chords(key_chords_pb);
+        set_mapping_enabled_request_pb.set_enabled(self.enabled);
+
+        set_mapping_enabled_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::SetMappingEnabledRequest,
+    ) -> NiaServerResult<NiaSetMappingEnabledRequest> {
+        let mut object_pb = object_pb;
+
+        let key_chords = object_pb
+            .take_key_chords()
+            .into_iter()
+            .map(|key_chord_pb| NiaKeyChord::from_pb(key_chord_pb))
+            .collect::<NiaServerResult<Vec<NiaKeyChord>>>()?;
+
+        let request = NiaSetMappingEnabledRequest::new(
+            key_chords,
+            object_pb.get_enabled(),
+        );
+
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::NiaKey;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaSetMappingEnabledRequest::new(
+            vec![NiaKeyChord::new(
+                vec![NiaKey::make_key_2(1, 29)],
+                NiaKey::make_key_2(1, 30),
+            )],
+            false,
+        );
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaSetMappingEnabledRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::NiaKeyChord;
+use crate::protocol::Serializable;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaSetMappingMetadataRequest {
+    key_chords: Vec<NiaKeyChord>,
+    description: String,
+    tags: Vec<String>,
+}
+
+impl NiaSetMappingMetadataRequest {
+    pub fn new<S>(
+        key_chords: Vec<NiaKeyChord>,
+        description: S,
+        tags: Vec<String>,
+    ) -> NiaSetMappingMetadataRequest
+    where
+        S: Into<String>,
+    {
+        NiaSetMappingMetadataRequest {
+            key_chords,
+            description: description.into(),
+            tags,
+        }
+    }
+
+    pub fn into_tuple(self) -> (Vec<NiaKeyChord>, String, Vec<String>) {
+        (self.key_chords, self.description, self.tags)
+    }
+
+    pub fn get_key_chords(&self) -> &Vec<NiaKeyChord> {
+        &self.key_chords
+    }
+}
+
+impl
+    Serializable<
+        NiaSetMappingMetadataRequest,
+        nia_protocol_rust::SetMappingMetadataRequest,
+    > for NiaSetMappingMetadataRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::SetMappingMetadataRequest {
+        let key_chords_pb = self
+            .key_chords
+            .iter()
+            .map(|key_chord| key_chord.to_pb())
+            .collect();
+        let tags_pb = self
+            .tags
+            .iter()
+            .map(|tag| protobuf::Chars::from(tag.clone()))
+            .collect();
+
+        let mut set_mapping_metadata_request_pb =
+            nia_protocol_rust::SetMappingMetadataRequest::new();
+
+        set_mapping_metadata_request_pb.set_key_chords(key_chords_pb);
+        set_mapping_metadata_request_pb
+            .set_description(protobuf::Chars::from(self.description.clone()));
+        set_mapping_metadata_request_pb.set_tags(tags_pb);
+
+        set_mapping_metadata_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::SetMappingMetadataRequest,
+    ) -> NiaServerResult<NiaSetMappingMetadataRequest> {
+        let mut object_pb = object_pb;
+
+        let key_chords = object_pb
+            .take_key_chords()
+            .into_iter()
+            .map(|key_chord_pb| NiaKeyChord::from_pb(key_chord_pb))
+            .collect::<NiaServerResult<Vec<NiaKeyChord>>>()?;
+        let description = object_pb.take_description().to_string();
+        let tags = object_pb
+            .take_tags()
+            .into_iter()
+            .map(|tag| tag.to_string())
+            .collect();
+
+        let request =
+            NiaSetMappingMetadataRequest::new(key_chords, description, tags);
+
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::NiaKey;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaSetMappingMetadataRequest::new(
+            vec![NiaKeyChord::new(
+                vec![NiaKey::make_key_2(1, 29)],
+                NiaKey::make_key_2(1, 30),
+            )],
+            "Opens a terminal",
+            vec![String::from("wm"), String::from("coding")],
+        );
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaSetMappingMetadataRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaSetMappingsEnabledByTagRequest {
+    tag: String,
+    enabled: bool,
+}
+
+impl NiaSetMappingsEnabledByTagRequest {
+    pub fn new<S>(tag: S, enabled: bool) -> NiaSetMappingsEnabledByTagRequest
+    where
+        S: Into<String>,
+    {
+        NiaSetMappingsEnabledByTagRequest {
+            tag: tag.into(),
+            enabled,
+        }
+    }
+
+    pub fn into_tuple(self) -> (String, bool) {
+        (self.tag, self.enabled)
+    }
+}
+
+impl
+    Serializable<
+        NiaSetMappingsEnabledByTagRequest,
+        nia_protocol_rust::SetMappingsEnabledByTagRequest,
+    > for NiaSetMappingsEnabledByTagRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::SetMappingsEnabledByTagRequest {
+        let mut set_mappings_enabled_by_tag_request_pb =
+            nia_protocol_rust::SetMappingsEnabledByTagRequest::new();
+
+        set_mappings_enabled_by_tag_request_pb
+            .set_tag(protobuf::Chars::from(self.tag.clone()));
+        set_mappings_enabled_by_tag_request_pb.set_enabled(self.enabled);
+
+        set_mappings_enabled_by_tag_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::SetMappingsEnabledByTagRequest,
+    ) -> NiaServerResult<NiaSetMappingsEnabledByTagRequest> {
+        let mut object_pb = object_pb;
+
+        let request = NiaSetMappingsEnabledByTagRequest::new(
+            object_pb.take_tag().to_string(),
+            object_pb.get_enabled(),
+        );
+
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaSetMappingsEnabledByTagRequest::new("gaming", true);
+
+        let bytes = expected.to_bytes().unwrap();
+        let result =
+            NiaSetMappingsEnabledByTagRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
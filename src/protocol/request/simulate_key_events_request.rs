@@ -0,0 +1,85 @@
+use crate::error::NiaServerResult;
+
+use crate::protocol::NiaKeyEvent;
+use crate::protocol::Serializable;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaSimulateKeyEventsRequest {
+    key_events: Vec<NiaKeyEvent>,
+}
+
+impl NiaSimulateKeyEventsRequest {
+    pub fn new(key_events: Vec<NiaKeyEvent>) -> NiaSimulateKeyEventsRequest {
+        NiaSimulateKeyEventsRequest { key_events }
+    }
+
+    pub fn get_key_events(&self) -> &Vec<NiaKeyEvent> {
+        &self.key_events
+    }
+
+    pub fn take_key_events(self) -> Vec<NiaKeyEvent> {
+        self.key_events
+    }
+}
+
+impl
+    Serializable<
+        NiaSimulateKeyEventsRequest,
+        nia_protocol_rust::SimulateKeyEventsRequest,
+    > for NiaSimulateKeyEventsRequest
+{
+    fn to_pb(&self) -> nia_protocol_rust::SimulateKeyEventsRequest {
+        let mut key_events_pb_vector = Vec::new();
+
+        for key_event in &self.key_events {
+            key_events_pb_vector.push(key_event.to_pb());
+        }
+
+        let mut simulate_key_events_request_pb =
+            nia_protocol_rust::SimulateKeyEventsRequest::new();
+
+        simulate_key_events_request_pb.set_key_events(
+            protobuf::RepeatedField::from_vec(key_events_pb_vector),
+        );
+
+        simulate_key_events_request_pb
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::SimulateKeyEventsRequest,
+    ) -> NiaServerResult<NiaSimulateKeyEventsRequest> {
+        let mut object_pb = object_pb;
+        let mut key_events = Vec::new();
+
+        for key_event_pb in object_pb.take_key_events().into_iter() {
+            let key_event = NiaKeyEvent::from_pb(key_event_pb)?;
+
+            key_events.push(key_event);
+        }
+
+        let request = NiaSimulateKeyEventsRequest::new(key_events);
+
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaSimulateKeyEventsRequest::new(vec![
+            NiaKeyEvent::press(0, 1, 29),
+            NiaKeyEvent::press(10, 1, 30),
+            NiaKeyEvent::release(20, 1, 30),
+            NiaKeyEvent::release(30, 1, 29),
+        ]);
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaSimulateKeyEventsRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
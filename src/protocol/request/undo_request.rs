@@ -0,0 +1,42 @@
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+use nia_protocol_rust::UndoRequest;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NiaUndoRequest {}
+
+impl NiaUndoRequest {
+    pub fn new() -> NiaUndoRequest {
+        NiaUndoRequest {}
+    }
+}
+
+impl Serializable<NiaUndoRequest, nia_protocol_rust::UndoRequest>
+    for NiaUndoRequest
+{
+    fn to_pb(&self) -> UndoRequest {
+        nia_protocol_rust::UndoRequest::new()
+    }
+
+    fn from_pb(object_pb: UndoRequest) -> NiaServerResult<NiaUndoRequest> {
+        Ok(NiaUndoRequest::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn serializes_and_deserializes() {
+        let expected = NiaUndoRequest::new();
+
+        let bytes = expected.to_bytes().unwrap();
+        let result = NiaUndoRequest::from_bytes(bytes).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
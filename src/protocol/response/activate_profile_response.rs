@@ -0,0 +1,91 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::NiaActivateProfileRequest;
+use crate::protocol::Serializable;
+use crate::server::Server;
+use crate::utils::activate_profile;
+
+#[derive(Debug, Clone)]
+pub struct NiaActivateProfileResponse {
+    result: Result<(), NiaServerError>,
+}
+
+impl NiaActivateProfileResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_activate_profile_request: NiaActivateProfileRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<()> {
+        let profile_name = nia_activate_profile_request.take_profile_name();
+
+        activate_profile(server, &event_loop_handle, &profile_name)
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_activate_profile_request: NiaActivateProfileRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaActivateProfileResponse {
+        let result = NiaActivateProfileResponse::try_from(
+            server,
+            nia_activate_profile_request,
+            event_loop_handle,
+        );
+
+        NiaActivateProfileResponse { result }
+    }
+}
+
+impl
+    Serializable<
+        NiaActivateProfileResponse,
+        nia_protocol_rust::ActivateProfileResponse,
+    > for NiaActivateProfileResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::ActivateProfileResponse {
+        let mut activate_profile_response =
+            nia_protocol_rust::ActivateProfileResponse::new();
+
+        match &self.result {
+            Ok(()) => {
+                let mut success_result =
+                    nia_protocol_rust::ActivateProfileResponse_SuccessResult::new();
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                activate_profile_response.set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::ActivateProfileResponse_ErrorResult::new(
+                    );
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                activate_profile_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::ActivateProfileResponse_FailureResult::new();
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                activate_profile_response.set_failure_result(failure_result);
+            }
+        }
+
+        activate_profile_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ActivateProfileResponse,
+    ) -> NiaServerResult<NiaActivateProfileResponse> {
+        unreachable!()
+    }
+}
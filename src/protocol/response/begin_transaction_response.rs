@@ -0,0 +1,91 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::NiaBeginTransactionRequest;
+use crate::protocol::Serializable;
+use crate::utils::NiaTransaction;
+
+#[derive(Debug, Clone)]
+pub struct NiaBeginTransactionResponse {
+    result: Result<(), NiaServerError>,
+}
+
+impl NiaBeginTransactionResponse {
+    fn try_from(
+        transaction: &mut Option<NiaTransaction>,
+        _nia_begin_transaction_request: NiaBeginTransactionRequest,
+    ) -> NiaServerResult<()> {
+        if transaction.is_some() {
+            return NiaServerError::invalid_request(
+                "A transaction is already in progress.",
+            )
+            .into();
+        }
+
+        *transaction = Some(NiaTransaction::new());
+
+        Ok(())
+    }
+
+    pub fn from(
+        transaction: &mut Option<NiaTransaction>,
+        nia_begin_transaction_request: NiaBeginTransactionRequest,
+    ) -> NiaBeginTransactionResponse {
+        let result = NiaBeginTransactionResponse::try_from(
+            transaction,
+            nia_begin_transaction_request,
+        );
+
+        NiaBeginTransactionResponse { result }
+    }
+}
+
+impl
+    Serializable<
+        NiaBeginTransactionResponse,
+        nia_protocol_rust::BeginTransactionResponse,
+    > for NiaBeginTransactionResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::BeginTransactionResponse {
+        let mut begin_transaction_response =
+            nia_protocol_rust::BeginTransactionResponse::new();
+
+        match &self.result {
+            Ok(()) => {
+                let mut success_result =
+                    nia_protocol_rust::BeginTransactionResponse_SuccessResult::new(
+                    );
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                begin_transaction_response.set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::BeginTransactionResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                begin_transaction_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::BeginTransactionResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                begin_transaction_response.set_failure_result(failure_result);
+            }
+        }
+
+        begin_transaction_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::BeginTransactionResponse,
+    ) -> NiaServerResult<NiaBeginTransactionResponse> {
+        unreachable!()
+    }
+}
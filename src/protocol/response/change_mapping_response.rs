@@ -1,8 +1,5 @@
 use nia_interpreter_core::EventLoopHandle;
-use nia_interpreter_core::Interpreter;
 use nia_interpreter_core::NiaChangeMappingCommandResult;
-use nia_interpreter_core::NiaInterpreterCommand;
-use nia_interpreter_core::NiaInterpreterCommandResult;
 use std::sync::MutexGuard;
 
 use nia_protocol_rust::ChangeMappingResponse;
@@ -11,69 +8,88 @@ use crate::error::NiaServerError;
 use crate::error::NiaServerResult;
 
 use crate::protocol::Serializable;
-use crate::protocol::{NiaChangeMappingRequest, NiaConvertable};
+use crate::protocol::{find_mapping_conflicts_excluding, format_key_chords};
+use crate::protocol::{
+    NiaChangeMappingRequest, NiaMapping, NiaMappingConflict,
+};
 use crate::server::Server;
+use crate::utils::{
+    change_mapping, get_current_timestamp, get_defined_modifiers,
+};
 
 #[derive(Debug, Clone)]
 pub struct NiaChangeMappingResponse {
     command_result: NiaChangeMappingCommandResult,
+    conflicts: Vec<NiaMappingConflict>,
 }
 
 impl NiaChangeMappingResponse {
     fn try_from(
+        server: &mut Server,
         nia_change_mapping_request: NiaChangeMappingRequest,
         event_loop_handle: MutexGuard<EventLoopHandle>,
     ) -> Result<NiaChangeMappingResponse, NiaServerError> {
         let (key_chords, action) = nia_change_mapping_request.into_tuple();
 
-        let mut interpreter_key_chords = key_chords
-            .into_iter()
-            .map(|key_chord| key_chord.to_interpreter_repr())
-            .collect();
-        let interpreter_action = action.to_interpreter_repr();
-
-        let interpreter_command =
-            NiaInterpreterCommand::make_change_mapping_command(
-                interpreter_key_chords,
-                interpreter_action,
+        // Disabled mappings are changed in place and stay disabled.
+        if server.get_disabled_mappings().get(&key_chords).is_some() {
+            server.change_disabled_mapping(
+                NiaMapping::new(key_chords, action),
+                get_current_timestamp(),
             );
 
-        event_loop_handle
-            .send_command(interpreter_command)
-            .map_err(|_| {
-                NiaServerError::interpreter_error(
-                    "Error sending command to the interpreter.",
-                )
-            })?;
-
-        let execution_result =
-            event_loop_handle.receive_result().map_err(|_| {
-                NiaServerError::interpreter_error(
-                    "Error reading command from the interpreter.",
-                )
-            })?;
-
-        let response = match execution_result {
-            NiaInterpreterCommandResult::ChangeMapping(command_result) => {
-                NiaChangeMappingResponse { command_result }
+            return Ok(NiaChangeMappingResponse {
+                command_result: NiaChangeMappingCommandResult::Success(),
+                conflicts: Vec::new(),
+            });
+        }
+
+        let defined_modifiers = get_defined_modifiers(&event_loop_handle)?;
+        let conflicts = find_mapping_conflicts_excluding(
+            &key_chords,
+            &key_chords,
+            server.get_mapping_index(&event_loop_handle)?,
+            &defined_modifiers,
+        );
+
+        let command_result = match change_mapping(
+            &event_loop_handle,
+            server.get_modifier_groups(),
+            &key_chords,
+            &action,
+        ) {
+            Ok(()) => {
+                server.mark_mapping_changed(
+                    &NiaMapping::new(key_chords.clone(), action.clone()),
+                    get_current_timestamp(),
+                );
+
+                NiaChangeMappingCommandResult::Success()
             }
-            _ => {
-                return NiaServerError::interpreter_error(
-                    "Unexpected command result.",
-                )
-                .into();
+            Err(NiaServerError::InvalidRequestError(message))
+            | Err(NiaServerError::InterpreterError(message)) => {
+                NiaChangeMappingCommandResult::Error(message)
             }
+            Err(error) => return Err(error),
         };
 
-        Ok(response)
+        Ok(NiaChangeMappingResponse {
+            command_result,
+            conflicts,
+        })
     }
 
     pub fn from(
+        server: &mut Server,
         nia_change_mapping_request: NiaChangeMappingRequest,
         event_loop_handle: MutexGuard<EventLoopHandle>,
     ) -> NiaChangeMappingResponse {
-        println!("{:?}", nia_change_mapping_request);
+        println!(
+            "Change mapping: {}",
+            format_key_chords(nia_change_mapping_request.get_key_chords())
+        );
         let try_result = NiaChangeMappingResponse::try_from(
+            server,
             nia_change_mapping_request,
             event_loop_handle,
         );
@@ -87,7 +103,10 @@ impl NiaChangeMappingResponse {
                 let command_result =
                     NiaChangeMappingCommandResult::Failure(message);
 
-                NiaChangeMappingResponse { command_result }
+                NiaChangeMappingResponse {
+                    command_result,
+                    conflicts: Vec::new(),
+                }
             }
         }
     }
@@ -136,6 +155,13 @@ impl
             }
         }
 
+        change_mapping_response.set_conflicts(
+            self.conflicts
+                .iter()
+                .map(|conflict| conflict.to_pb())
+                .collect(),
+        );
+
         change_mapping_response
     }
 
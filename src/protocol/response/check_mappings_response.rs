@@ -0,0 +1,96 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{
+    find_all_mapping_conflicts, NiaCheckMappingsRequest, NiaMappingConflict,
+};
+use crate::server::Server;
+use crate::utils::{get_defined_mappings, get_defined_modifiers};
+
+#[derive(Debug, Clone)]
+pub struct NiaCheckMappingsResponse {
+    conflicts_result: Result<Vec<NiaMappingConflict>, NiaServerError>,
+}
+
+impl NiaCheckMappingsResponse {
+    fn try_from(
+        server: &Server,
+        _nia_check_mappings_request: NiaCheckMappingsRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<Vec<NiaMappingConflict>> {
+        let defined_mappings = get_defined_mappings(
+            &event_loop_handle,
+            server.get_modifier_groups(),
+        )?;
+        let defined_modifiers = get_defined_modifiers(&event_loop_handle)?;
+
+        Ok(find_all_mapping_conflicts(
+            &defined_mappings,
+            &defined_modifiers,
+        ))
+    }
+
+    pub fn from(
+        server: &Server,
+        nia_check_mappings_request: NiaCheckMappingsRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaCheckMappingsResponse {
+        let conflicts_result = NiaCheckMappingsResponse::try_from(
+            server,
+            nia_check_mappings_request,
+            event_loop_handle,
+        );
+
+        NiaCheckMappingsResponse { conflicts_result }
+    }
+}
+
+impl
+    Serializable<
+        NiaCheckMappingsResponse,
+        nia_protocol_rust::CheckMappingsResponse,
+    > for NiaCheckMappingsResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::CheckMappingsResponse {
+        let mut check_mappings_response =
+            nia_protocol_rust::CheckMappingsResponse::new();
+
+        match &self.conflicts_result {
+            Ok(conflicts) => {
+                let conflicts =
+                    conflicts.iter().map(|conflict| conflict.to_pb()).collect();
+
+                let mut success_result =
+                    nia_protocol_rust::CheckMappingsResponse_SuccessResult::new(
+                    );
+
+                success_result.set_conflicts(conflicts);
+
+                check_mappings_response.set_success_result(success_result);
+            }
+            Err(error) => {
+                let message = error.get_message();
+                let mut failure_result =
+                    nia_protocol_rust::CheckMappingsResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    message
+                )));
+                check_mappings_response.set_failure_result(failure_result);
+            }
+        }
+
+        check_mappings_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::CheckMappingsResponse,
+    ) -> NiaServerResult<NiaCheckMappingsResponse> {
+        unreachable!()
+    }
+}
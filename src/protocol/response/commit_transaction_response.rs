@@ -0,0 +1,107 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::NiaCommitTransactionRequest;
+use crate::protocol::Serializable;
+use crate::server::Server;
+use crate::utils::{commit_transaction, NiaTransaction};
+
+#[derive(Debug, Clone)]
+pub struct NiaCommitTransactionResponse {
+    applied_count_result: Result<usize, NiaServerError>,
+}
+
+impl NiaCommitTransactionResponse {
+    fn try_from(
+        server: &mut Server,
+        transaction: &mut Option<NiaTransaction>,
+        _nia_commit_transaction_request: NiaCommitTransactionRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<usize> {
+        // The transaction ends with the commit whether it succeeds or not.
+        let transaction = match transaction.take() {
+            Some(transaction) => transaction,
+            None => {
+                return NiaServerError::invalid_request(
+                    "There is no transaction in progress.",
+                )
+                .into()
+            }
+        };
+
+        commit_transaction(server, &event_loop_handle, transaction)
+    }
+
+    pub fn from(
+        server: &mut Server,
+        transaction: &mut Option<NiaTransaction>,
+        nia_commit_transaction_request: NiaCommitTransactionRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaCommitTransactionResponse {
+        let applied_count_result = NiaCommitTransactionResponse::try_from(
+            server,
+            transaction,
+            nia_commit_transaction_request,
+            event_loop_handle,
+        );
+
+        NiaCommitTransactionResponse {
+            applied_count_result,
+        }
+    }
+}
+
+impl
+    Serializable<
+        NiaCommitTransactionResponse,
+        nia_protocol_rust::CommitTransactionResponse,
+    > for NiaCommitTransactionResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::CommitTransactionResponse {
+        let mut commit_transaction_response =
+            nia_protocol_rust::CommitTransactionResponse::new();
+
+        match &self.applied_count_result {
+            Ok(applied_count) => {
+                let mut success_result =
+                    nia_protocol_rust::CommitTransactionResponse_SuccessResult::new(
+                    );
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                success_result.set_applied_count(*applied_count as u32);
+                commit_transaction_response.set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::CommitTransactionResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                commit_transaction_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::CommitTransactionResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                commit_transaction_response.set_failure_result(failure_result);
+            }
+        }
+
+        commit_transaction_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::CommitTransactionResponse,
+    ) -> NiaServerResult<NiaCommitTransactionResponse> {
+        unreachable!()
+    }
+}
@@ -0,0 +1,112 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{check_profile_name, NiaCreateProfileRequest};
+use crate::server::Server;
+use crate::utils::make_profile_of_definitions;
+
+#[derive(Debug, Clone)]
+pub struct NiaCreateProfileResponse {
+    result: Result<(), NiaServerError>,
+}
+
+impl NiaCreateProfileResponse {
+    // A new profile is made of the current definitions, so that a setup is
+    // saved by defining it and creating a profile afterwards.
+    fn try_from(
+        server: &mut Server,
+        nia_create_profile_request: NiaCreateProfileRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<()> {
+        let profile_name = nia_create_profile_request.take_profile_name();
+
+        check_profile_name(&profile_name)?;
+
+        if server.get_profile(&profile_name).is_some() {
+            return NiaServerError::invalid_request(format!(
+                "Profile already exists: {}.",
+                profile_name
+            ))
+            .into();
+        }
+
+        let profile = make_profile_of_definitions(
+            server,
+            &event_loop_handle,
+            &profile_name,
+        )?;
+
+        server.set_profile(profile);
+
+        Ok(())
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_create_profile_request: NiaCreateProfileRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaCreateProfileResponse {
+        let result = NiaCreateProfileResponse::try_from(
+            server,
+            nia_create_profile_request,
+            event_loop_handle,
+        );
+
+        NiaCreateProfileResponse { result }
+    }
+}
+
+impl
+    Serializable<
+        NiaCreateProfileResponse,
+        nia_protocol_rust::CreateProfileResponse,
+    > for NiaCreateProfileResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::CreateProfileResponse {
+        let mut create_profile_response =
+            nia_protocol_rust::CreateProfileResponse::new();
+
+        match &self.result {
+            Ok(()) => {
+                let mut success_result =
+                    nia_protocol_rust::CreateProfileResponse_SuccessResult::new(
+                    );
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                create_profile_response.set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::CreateProfileResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                create_profile_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::CreateProfileResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                create_profile_response.set_failure_result(failure_result);
+            }
+        }
+
+        create_profile_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::CreateProfileResponse,
+    ) -> NiaServerResult<NiaCreateProfileResponse> {
+        unreachable!()
+    }
+}
@@ -30,6 +30,8 @@ impl NiaDefineActionResponse {
     ) -> Result<NiaDefineActionResponse, NiaServerError> {
         let action = nia_define_action_request.take_action();
 
+        action.get_action().check()?;
+
         let interpreter_command = make_define_action_command(action);
 
         event_loop_handle
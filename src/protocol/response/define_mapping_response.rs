@@ -1,8 +1,5 @@
 use nia_interpreter_core::EventLoopHandle;
-use nia_interpreter_core::Interpreter;
 use nia_interpreter_core::NiaDefineMappingCommandResult;
-use nia_interpreter_core::NiaInterpreterCommand;
-use nia_interpreter_core::NiaInterpreterCommandResult;
 use std::sync::MutexGuard;
 
 use nia_protocol_rust::DefineMappingResponse;
@@ -11,62 +8,75 @@ use crate::error::NiaServerError;
 use crate::error::NiaServerResult;
 
 use crate::protocol::Serializable;
-use crate::protocol::{NiaConvertable, NiaDefineMappingRequest};
+use crate::protocol::{canonicalize_key_chords, NiaMapping};
+use crate::protocol::{find_mapping_conflicts, NiaMappingConflict};
+use crate::protocol::{format_key_chords, NiaDefineMappingRequest};
 use crate::server::Server;
+use crate::utils::{
+    define_mapping, get_current_timestamp, get_defined_modifiers,
+};
 
 #[derive(Debug, Clone)]
 pub struct NiaDefineMappingResponse {
     command_result: NiaDefineMappingCommandResult,
+    conflicts: Vec<NiaMappingConflict>,
 }
 
 impl NiaDefineMappingResponse {
     fn try_from(
+        server: &mut Server,
         nia_define_mapping_request: NiaDefineMappingRequest,
         event_loop_handle: MutexGuard<EventLoopHandle>,
     ) -> Result<NiaDefineMappingResponse, NiaServerError> {
-        let mapping = nia_define_mapping_request
-            .get_mapping()
-            .to_interpreter_repr();
-
-        let interpreter_command =
-            NiaInterpreterCommand::make_define_mapping_command(mapping);
-
-        event_loop_handle
-            .send_command(interpreter_command)
-            .map_err(|_| {
-                NiaServerError::interpreter_error(
-                    "Error sending command to the interpreter.",
-                )
-            })?;
-
-        let execution_result =
-            event_loop_handle.receive_result().map_err(|_| {
-                NiaServerError::interpreter_error(
-                    "Error reading command from the interpreter.",
-                )
-            })?;
-
-        let response = match execution_result {
-            NiaInterpreterCommandResult::DefineMapping(command_result) => {
-                NiaDefineMappingResponse { command_result }
+        // Key chords are stored the way they are reported, with the keys
+        // that represent modifier groups.
+        let mapping = nia_define_mapping_request.get_mapping();
+        let key_chords = canonicalize_key_chords(
+            server.get_modifier_groups(),
+            mapping.get_key_chords(),
+        );
+        let mapping = NiaMapping::new(key_chords, mapping.get_action().clone());
+
+        let conflicts = find_mapping_conflicts(
+            mapping.get_key_chords(),
+            server.get_mapping_index(&event_loop_handle)?,
+            &get_defined_modifiers(&event_loop_handle)?,
+        );
+
+        let command_result = match define_mapping(
+            &event_loop_handle,
+            server.get_modifier_groups(),
+            &mapping,
+        ) {
+            Ok(()) => {
+                server.mark_mapping_defined(&mapping, get_current_timestamp());
+
+                NiaDefineMappingCommandResult::Success()
             }
-            _ => {
-                return NiaServerError::interpreter_error(
-                    "Unexpected command result.",
-                )
-                .into();
+            Err(NiaServerError::InvalidRequestError(message))
+            | Err(NiaServerError::InterpreterError(message)) => {
+                NiaDefineMappingCommandResult::Error(message)
             }
+            Err(error) => return Err(error),
         };
 
-        Ok(response)
+        Ok(NiaDefineMappingResponse {
+            command_result,
+            conflicts,
+        })
     }
 
     pub fn from(
+        server: &mut Server,
         nia_define_mapping_request: NiaDefineMappingRequest,
         event_loop_handle: MutexGuard<EventLoopHandle>,
     ) -> NiaDefineMappingResponse {
-        println!("{:?}", nia_define_mapping_request);
+        println!(
+            "Define mapping: {}",
+            format_key_chords(nia_define_mapping_request.get_key_chords())
+        );
         let try_result = NiaDefineMappingResponse::try_from(
+            server,
             nia_define_mapping_request,
             event_loop_handle,
         );
@@ -80,7 +90,10 @@ impl NiaDefineMappingResponse {
                 let command_result =
                     NiaDefineMappingCommandResult::Failure(message);
 
-                NiaDefineMappingResponse { command_result }
+                NiaDefineMappingResponse {
+                    command_result,
+                    conflicts: Vec::new(),
+                }
             }
         }
     }
@@ -129,6 +142,13 @@ impl
             }
         }
 
+        define_mapping_response.set_conflicts(
+            self.conflicts
+                .iter()
+                .map(|conflict| conflict.to_pb())
+                .collect(),
+        );
+
         define_mapping_response
     }
 
@@ -0,0 +1,95 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::NiaDefineModifierGroupRequest;
+use crate::protocol::Serializable;
+use crate::server::Server;
+use crate::utils::define_modifier_group;
+
+#[derive(Debug, Clone)]
+pub struct NiaDefineModifierGroupResponse {
+    result: Result<(), NiaServerError>,
+}
+
+impl NiaDefineModifierGroupResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_define_modifier_group_request: NiaDefineModifierGroupRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<()> {
+        let modifier_group =
+            nia_define_modifier_group_request.take_modifier_group();
+
+        define_modifier_group(server, &event_loop_handle, &modifier_group)?;
+
+        Ok(())
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_define_modifier_group_request: NiaDefineModifierGroupRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaDefineModifierGroupResponse {
+        let result = NiaDefineModifierGroupResponse::try_from(
+            server,
+            nia_define_modifier_group_request,
+            event_loop_handle,
+        );
+
+        NiaDefineModifierGroupResponse { result }
+    }
+}
+
+impl
+    Serializable<
+        NiaDefineModifierGroupResponse,
+        nia_protocol_rust::DefineModifierGroupResponse,
+    > for NiaDefineModifierGroupResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::DefineModifierGroupResponse {
+        let mut define_modifier_group_response =
+            nia_protocol_rust::DefineModifierGroupResponse::new();
+
+        match &self.result {
+            Ok(()) => {
+                let mut success_result =
+                    nia_protocol_rust::DefineModifierGroupResponse_SuccessResult::new();
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                define_modifier_group_response
+                    .set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::DefineModifierGroupResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                define_modifier_group_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::DefineModifierGroupResponse_FailureResult::new();
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                define_modifier_group_response
+                    .set_failure_result(failure_result);
+            }
+        }
+
+        define_modifier_group_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::DefineModifierGroupResponse,
+    ) -> NiaServerResult<NiaDefineModifierGroupResponse> {
+        unreachable!()
+    }
+}
@@ -1,13 +1,11 @@
 use std::sync::MutexGuard;
 
-use nia_interpreter_core::Interpreter;
-use nia_interpreter_core::NiaInterpreterCommand;
-use nia_interpreter_core::NiaInterpreterCommandResult;
 use nia_interpreter_core::{EventLoopHandle, NiaDefineModifierCommandResult};
 
 use crate::error::{NiaServerError, NiaServerResult};
 
-use crate::protocol::{NiaConvertable, NiaDefineModifierRequest, Serializable};
+use crate::protocol::{NiaDefineModifierRequest, Serializable};
+use crate::utils::define_modifier;
 use nia_protocol_rust::DefineModifierResponse;
 
 #[derive(Debug, Clone)]
@@ -21,41 +19,17 @@ impl NiaDefineModifierResponse {
         event_loop_handle: MutexGuard<EventLoopHandle>,
     ) -> Result<NiaDefineModifierResponse, NiaServerError> {
         let modifier = nia_define_modifier_request.take_modifier();
-        let interpreter_modifier = modifier.to_interpreter_repr();
-
-        let interpreter_command =
-            NiaInterpreterCommand::make_define_modifier_command(
-                interpreter_modifier,
-            );
-
-        event_loop_handle
-            .send_command(interpreter_command)
-            .map_err(|_| {
-                NiaServerError::interpreter_error(
-                    "Error sending command to the interpreter.",
-                )
-            })?;
-
-        let execution_result =
-            event_loop_handle.receive_result().map_err(|_| {
-                NiaServerError::interpreter_error(
-                    "Error reading command from the interpreter.",
-                )
-            })?;
-
-        let response = match execution_result {
-            NiaInterpreterCommandResult::DefineModifier(command_result) => {
-                NiaDefineModifierResponse { command_result }
-            }
-            _ => {
-                return NiaServerError::interpreter_error(
-                    "Unexpected command result.",
-                )
-                .into()
-            }
-        };
 
-        Ok(response)
+        let command_result =
+            match define_modifier(&event_loop_handle, &modifier) {
+                Ok(()) => NiaDefineModifierCommandResult::Success(),
+                Err(NiaServerError::InterpreterError(message)) => {
+                    NiaDefineModifierCommandResult::Error(message)
+                }
+                Err(error) => return Err(error),
+            };
+
+        Ok(NiaDefineModifierResponse { command_result })
     }
 
     pub fn from(
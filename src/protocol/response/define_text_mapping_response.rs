@@ -0,0 +1,160 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+use nia_interpreter_core::NiaDefineMappingCommandResult;
+
+use nia_protocol_rust::DefineTextMappingResponse;
+
+use crate::error::NiaServerError;
+use crate::error::NiaServerResult;
+
+use crate::protocol::Serializable;
+use crate::protocol::{canonicalize_key_chords, NiaDefineTextMappingRequest};
+use crate::protocol::{find_mapping_conflicts, NiaMappingConflict};
+use crate::protocol::{format_key_chords, parse_key_chords, NiaMapping};
+use crate::server::Server;
+use crate::utils::{
+    define_mapping, get_current_timestamp, get_defined_modifiers,
+};
+
+#[derive(Debug, Clone)]
+pub struct NiaDefineTextMappingResponse {
+    command_result: NiaDefineMappingCommandResult,
+    conflicts: Vec<NiaMappingConflict>,
+}
+
+impl NiaDefineTextMappingResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_define_text_mapping_request: NiaDefineTextMappingRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> Result<NiaDefineTextMappingResponse, NiaServerError> {
+        let (key_chords, action) = nia_define_text_mapping_request.into_tuple();
+
+        let key_chords = parse_key_chords(&key_chords, &|device_name| {
+            server.get_device_id_by_name(device_name)
+        })?;
+        let key_chords =
+            canonicalize_key_chords(server.get_modifier_groups(), &key_chords);
+
+        let conflicts = find_mapping_conflicts(
+            &key_chords,
+            server.get_mapping_index(&event_loop_handle)?,
+            &get_defined_modifiers(&event_loop_handle)?,
+        );
+
+        println!("Defining mapping: {}", format_key_chords(&key_chords));
+
+        let mapping = NiaMapping::new(key_chords, action);
+
+        let command_result = match define_mapping(
+            &event_loop_handle,
+            server.get_modifier_groups(),
+            &mapping,
+        ) {
+            Ok(()) => {
+                server.mark_mapping_defined(&mapping, get_current_timestamp());
+
+                NiaDefineMappingCommandResult::Success()
+            }
+            Err(NiaServerError::InvalidRequestError(message))
+            | Err(NiaServerError::InterpreterError(message)) => {
+                NiaDefineMappingCommandResult::Error(message)
+            }
+            Err(error) => return Err(error),
+        };
+
+        Ok(NiaDefineTextMappingResponse {
+            command_result,
+            conflicts,
+        })
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_define_text_mapping_request: NiaDefineTextMappingRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaDefineTextMappingResponse {
+        let try_result = NiaDefineTextMappingResponse::try_from(
+            server,
+            nia_define_text_mapping_request,
+            event_loop_handle,
+        );
+
+        match try_result {
+            Ok(result) => result,
+            Err(error) => {
+                let message =
+                    format!("Execution failure: {}", error.get_message());
+
+                let command_result =
+                    NiaDefineMappingCommandResult::Failure(message);
+
+                NiaDefineTextMappingResponse {
+                    command_result,
+                    conflicts: Vec::new(),
+                }
+            }
+        }
+    }
+}
+
+impl
+    Serializable<
+        NiaDefineTextMappingResponse,
+        nia_protocol_rust::DefineTextMappingResponse,
+    > for NiaDefineTextMappingResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::DefineTextMappingResponse {
+        let result = &self.command_result;
+
+        let mut define_text_mapping_response =
+            nia_protocol_rust::DefineTextMappingResponse::new();
+
+        match result {
+            NiaDefineMappingCommandResult::Success() => {
+                let mut success_result =
+                    nia_protocol_rust::DefineTextMappingResponse_SuccessResult::new(
+                    );
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                define_text_mapping_response.set_success_result(success_result);
+            }
+            NiaDefineMappingCommandResult::Error(error_message) => {
+                let mut error_result =
+                    nia_protocol_rust::DefineTextMappingResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(error_message.clone()));
+                define_text_mapping_response.set_error_result(error_result);
+            }
+            NiaDefineMappingCommandResult::Failure(failure_message) => {
+                let mut failure_result =
+                    nia_protocol_rust::DefineTextMappingResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(
+                    failure_message.clone(),
+                ));
+                define_text_mapping_response.set_failure_result(failure_result);
+            }
+        }
+
+        define_text_mapping_response.set_conflicts(
+            self.conflicts
+                .iter()
+                .map(|conflict| conflict.to_pb())
+                .collect(),
+        );
+
+        define_text_mapping_response
+    }
+
+    fn from_pb(
+        object_pb: DefineTextMappingResponse,
+    ) -> NiaServerResult<NiaDefineTextMappingResponse> {
+        unreachable!()
+    }
+}
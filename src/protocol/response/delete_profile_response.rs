@@ -0,0 +1,91 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::NiaDeleteProfileRequest;
+use crate::protocol::Serializable;
+use crate::server::Server;
+
+#[derive(Debug, Clone)]
+pub struct NiaDeleteProfileResponse {
+    result: Result<(), NiaServerError>,
+}
+
+impl NiaDeleteProfileResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_delete_profile_request: NiaDeleteProfileRequest,
+    ) -> NiaServerResult<()> {
+        let profile_name = nia_delete_profile_request.take_profile_name();
+
+        match server.remove_profile(&profile_name) {
+            Some(_) => Ok(()),
+            None => NiaServerError::invalid_request(format!(
+                "Unknown profile: {}.",
+                profile_name
+            ))
+            .into(),
+        }
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_delete_profile_request: NiaDeleteProfileRequest,
+    ) -> NiaDeleteProfileResponse {
+        let result = NiaDeleteProfileResponse::try_from(
+            server,
+            nia_delete_profile_request,
+        );
+
+        NiaDeleteProfileResponse { result }
+    }
+}
+
+impl
+    Serializable<
+        NiaDeleteProfileResponse,
+        nia_protocol_rust::DeleteProfileResponse,
+    > for NiaDeleteProfileResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::DeleteProfileResponse {
+        let mut delete_profile_response =
+            nia_protocol_rust::DeleteProfileResponse::new();
+
+        match &self.result {
+            Ok(()) => {
+                let mut success_result =
+                    nia_protocol_rust::DeleteProfileResponse_SuccessResult::new(
+                    );
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                delete_profile_response.set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::DeleteProfileResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                delete_profile_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::DeleteProfileResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                delete_profile_response.set_failure_result(failure_result);
+            }
+        }
+
+        delete_profile_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::DeleteProfileResponse,
+    ) -> NiaServerResult<NiaDeleteProfileResponse> {
+        unreachable!()
+    }
+}
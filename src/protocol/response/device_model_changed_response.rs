@@ -0,0 +1,56 @@
+use nia_protocol_rust::DeviceModelChangedResponse;
+
+use crate::error::NiaServerResult;
+use crate::protocol::{DeviceModel, Serializable};
+
+// Not an answer to a request: broadcasted to every client when a keyboard
+// model file of a device changes on disk.
+#[derive(Debug, Clone)]
+pub struct NiaDeviceModelChangedResponse {
+    device_id: i32,
+    device_model: DeviceModel,
+}
+
+impl NiaDeviceModelChangedResponse {
+    pub fn new(
+        device_id: i32,
+        device_model: DeviceModel,
+    ) -> NiaDeviceModelChangedResponse {
+        NiaDeviceModelChangedResponse {
+            device_id,
+            device_model,
+        }
+    }
+
+    pub fn get_device_id(&self) -> i32 {
+        self.device_id
+    }
+
+    pub fn get_device_model(&self) -> &DeviceModel {
+        &self.device_model
+    }
+}
+
+impl
+    Serializable<
+        NiaDeviceModelChangedResponse,
+        nia_protocol_rust::DeviceModelChangedResponse,
+    > for NiaDeviceModelChangedResponse
+{
+    fn to_pb(&self) -> DeviceModelChangedResponse {
+        let mut device_model_changed_response =
+            nia_protocol_rust::DeviceModelChangedResponse::new();
+
+        device_model_changed_response.set_device_id(self.device_id);
+        device_model_changed_response
+            .set_device_model(self.device_model.to_pb());
+
+        device_model_changed_response
+    }
+
+    fn from_pb(
+        object_pb: DeviceModelChangedResponse,
+    ) -> NiaServerResult<NiaDeviceModelChangedResponse> {
+        unreachable!()
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{
+    NiaConfig, NiaConfigDiff, NiaConfigSource, NiaDiffConfigRequest,
+};
+use crate::server::Server;
+use crate::utils::make_config_of_definitions;
+
+fn get_config(
+    server: &Server,
+    event_loop_handle: &EventLoopHandle,
+    config_source: NiaConfigSource,
+) -> NiaServerResult<NiaConfig> {
+    match config_source {
+        NiaConfigSource::Live => {
+            make_config_of_definitions(server, event_loop_handle)
+        }
+        NiaConfigSource::Text(config) => NiaConfig::from_str(&config),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NiaDiffConfigResponse {
+    config_diff_result: Result<NiaConfigDiff, NiaServerError>,
+}
+
+impl NiaDiffConfigResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_diff_config_request: NiaDiffConfigRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<NiaConfigDiff> {
+        let (old_source, new_source) = nia_diff_config_request.into_tuple();
+
+        let old_config = get_config(server, &event_loop_handle, old_source)?;
+        let new_config = get_config(server, &event_loop_handle, new_source)?;
+
+        NiaConfigDiff::new(&old_config, &new_config)
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_diff_config_request: NiaDiffConfigRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaDiffConfigResponse {
+        let config_diff_result = NiaDiffConfigResponse::try_from(
+            server,
+            nia_diff_config_request,
+            event_loop_handle,
+        );
+
+        NiaDiffConfigResponse { config_diff_result }
+    }
+}
+
+impl Serializable<NiaDiffConfigResponse, nia_protocol_rust::DiffConfigResponse>
+    for NiaDiffConfigResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::DiffConfigResponse {
+        let mut diff_config_response =
+            nia_protocol_rust::DiffConfigResponse::new();
+
+        match &self.config_diff_result {
+            Ok(config_diff) => {
+                let mut success_result =
+                    nia_protocol_rust::DiffConfigResponse_SuccessResult::new();
+
+                success_result.set_config_diff(config_diff.to_pb());
+                success_result
+                    .set_text(protobuf::Chars::from(config_diff.to_text()));
+
+                diff_config_response.set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message))
+            | Err(NiaServerError::DeserializationError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::DiffConfigResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                diff_config_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::DiffConfigResponse_FailureResult::new();
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                diff_config_response.set_failure_result(failure_result);
+            }
+        }
+
+        diff_config_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::DiffConfigResponse,
+    ) -> NiaServerResult<NiaDiffConfigResponse> {
+        unreachable!()
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::NiaExportConfigRequest;
+use crate::protocol::Serializable;
+use crate::server::Server;
+use crate::utils::make_config_of_definitions;
+
+#[derive(Debug, Clone)]
+pub struct NiaExportConfigResponse {
+    config_result: Result<String, NiaServerError>,
+}
+
+impl NiaExportConfigResponse {
+    fn try_from(
+        server: &mut Server,
+        _nia_export_config_request: NiaExportConfigRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<String> {
+        let config = make_config_of_definitions(server, &event_loop_handle)?;
+
+        config.to_string()
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_export_config_request: NiaExportConfigRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaExportConfigResponse {
+        let config_result = NiaExportConfigResponse::try_from(
+            server,
+            nia_export_config_request,
+            event_loop_handle,
+        );
+
+        NiaExportConfigResponse { config_result }
+    }
+}
+
+impl
+    Serializable<
+        NiaExportConfigResponse,
+        nia_protocol_rust::ExportConfigResponse,
+    > for NiaExportConfigResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::ExportConfigResponse {
+        let mut export_config_response =
+            nia_protocol_rust::ExportConfigResponse::new();
+
+        match &self.config_result {
+            Ok(config) => {
+                let mut success_result =
+                    nia_protocol_rust::ExportConfigResponse_SuccessResult::new(
+                    );
+
+                success_result
+                    .set_config(protobuf::Chars::from(config.clone()));
+
+                export_config_response.set_success_result(success_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::ExportConfigResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                export_config_response.set_failure_result(failure_result);
+            }
+        }
+
+        export_config_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ExportConfigResponse,
+    ) -> NiaServerResult<NiaExportConfigResponse> {
+        unreachable!()
+    }
+}
@@ -1,80 +1,51 @@
 use std::sync::MutexGuard;
 
 use nia_interpreter_core::EventLoopHandle;
-use nia_interpreter_core::NiaGetDefinedMappingsCommandResult;
-use nia_interpreter_core::NiaInterpreterCommand;
-use nia_interpreter_core::NiaInterpreterCommandResult;
 
 use nia_protocol_rust::GetDefinedMappingsResponse;
 
 use crate::error::NiaServerError;
 use crate::error::NiaServerResult;
-use crate::protocol::{NiaConvertable, Serializable};
-use crate::protocol::{NiaGetDefinedMappingsRequest, NiaMapping};
+use crate::protocol::Serializable;
+use crate::protocol::{
+    NiaGetDefinedMappingsRequest, NiaMapping, NiaMappingMetadata,
+};
+use crate::server::Server;
+use crate::utils::get_mappings_with_metadata;
 
 #[derive(Debug, Clone)]
 pub struct NiaGetDefinedMappingsResponse {
-    command_result: NiaGetDefinedMappingsCommandResult,
+    mappings_result:
+        Result<(Vec<(NiaMapping, NiaMappingMetadata)>, usize), NiaServerError>,
 }
 
 impl NiaGetDefinedMappingsResponse {
     fn try_from(
-        _nia_define_mapping_request: NiaGetDefinedMappingsRequest,
+        server: &mut Server,
+        nia_get_defined_mappings_request: NiaGetDefinedMappingsRequest,
         event_loop_handle: MutexGuard<EventLoopHandle>,
-    ) -> Result<NiaGetDefinedMappingsResponse, NiaServerError> {
-        let interpreter_command =
-            NiaInterpreterCommand::make_get_defined_mappings_command();
-
-        event_loop_handle
-            .send_command(interpreter_command)
-            .map_err(|_| {
-                NiaServerError::interpreter_error(
-                    "Error sending command to the interpreter.",
-                )
-            })?;
-
-        let execution_result =
-            event_loop_handle.receive_result().map_err(|_| {
-                NiaServerError::interpreter_error(
-                    "Error reading command from the interpreter.",
-                )
-            })?;
-
-        let response = match execution_result {
-            NiaInterpreterCommandResult::GetDefinedMappings(command_result) => {
-                NiaGetDefinedMappingsResponse { command_result }
-            }
-            _ => {
-                return NiaServerError::interpreter_error(
-                    "Unexpected command result.",
-                )
-                .into();
-            }
-        };
+    ) -> NiaServerResult<(Vec<(NiaMapping, NiaMappingMetadata)>, usize)> {
+        let query = nia_get_defined_mappings_request.take_query();
+
+        query.check()?;
+
+        let mappings = get_mappings_with_metadata(server, &event_loop_handle)?;
 
-        Ok(response)
+        Ok(query.apply(mappings))
     }
 
     pub fn from(
-        nia_define_mapping_request: NiaGetDefinedMappingsRequest,
+        server: &mut Server,
+        nia_get_defined_mappings_request: NiaGetDefinedMappingsRequest,
         event_loop_handle: MutexGuard<EventLoopHandle>,
     ) -> NiaGetDefinedMappingsResponse {
-        let try_result = NiaGetDefinedMappingsResponse::try_from(
-            nia_define_mapping_request,
+        let mappings_result = NiaGetDefinedMappingsResponse::try_from(
+            server,
+            nia_get_defined_mappings_request,
             event_loop_handle,
         );
 
-        match try_result {
-            Ok(result) => result,
-            Err(error) => {
-                let message =
-                    format!("Execution failure: {}", error.get_message());
-                let command_result =
-                    NiaGetDefinedMappingsCommandResult::Failure(message);
-
-                NiaGetDefinedMappingsResponse { command_result }
-            }
-        }
+        NiaGetDefinedMappingsResponse { mappings_result }
     }
 }
 
@@ -85,45 +56,30 @@ impl
     > for NiaGetDefinedMappingsResponse
 {
     fn to_pb(&self) -> GetDefinedMappingsResponse {
-        let command_result = &self.command_result;
-
         let mut get_defined_mappings_response =
             nia_protocol_rust::GetDefinedMappingsResponse::new();
 
-        match command_result {
-            NiaGetDefinedMappingsCommandResult::Success(defined_mappings) => {
-                let mut mappings = defined_mappings
+        match &self.mappings_result {
+            Ok((mappings, total_count)) => {
+                let mappings = mappings
                     .iter()
-                    .map(|interpreter_mapping| {
-                        NiaMapping::from_interpreter_repr(interpreter_mapping)
-                            .map(|mapping_result| mapping_result.to_pb())
+                    .map(|(mapping, mapping_metadata)| {
+                        let mut mapping_pb = mapping.to_pb();
+
+                        mapping_pb.set_metadata(mapping_metadata.to_pb());
+
+                        mapping_pb
                     })
-                    .collect::<NiaServerResult<Vec<nia_protocol_rust::Mapping>>>();
-
-                match mappings {
-                    Ok(mappings) => {
-                        let repeated =
-                            protobuf::RepeatedField::from_vec(mappings);
-
-                        let mut success_result = nia_protocol_rust::GetDefinedMappingsResponse_SuccessResult::new();
-                        success_result.set_mappings(repeated);
-
-                        get_defined_mappings_response
-                            .set_success_result(success_result);
-                    }
-                    Err(error) => {
-                        let message = error.get_message();
-                        let mut error_result =
-                            nia_protocol_rust::GetDefinedMappingsResponse_ErrorResult::new();
-
-                        error_result
-                            .set_message(protobuf::Chars::from(message));
-                        get_defined_mappings_response
-                            .set_error_result(error_result);
-                    }
-                }
+                    .collect();
+
+                let mut success_result = nia_protocol_rust::GetDefinedMappingsResponse_SuccessResult::new();
+                success_result.set_mappings(mappings);
+                success_result.set_total_count(*total_count as u32);
+
+                get_defined_mappings_response
+                    .set_success_result(success_result);
             }
-            NiaGetDefinedMappingsCommandResult::Error(error_message) => {
+            Err(NiaServerError::InvalidRequestError(error_message)) => {
                 let mut error_result =
                     nia_protocol_rust::GetDefinedMappingsResponse_ErrorResult::new();
 
@@ -131,13 +87,14 @@ impl
                     .set_message(protobuf::Chars::from(error_message.clone()));
                 get_defined_mappings_response.set_error_result(error_result);
             }
-            NiaGetDefinedMappingsCommandResult::Failure(failure_message) => {
+            Err(error) => {
                 let mut failure_result =
                     nia_protocol_rust::GetDefinedMappingsResponse_FailureResult::new();
 
-                failure_result.set_message(protobuf::Chars::from(
-                    failure_message.clone(),
-                ));
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
                 get_defined_mappings_response
                     .set_failure_result(failure_result);
             }
@@ -1,8 +1,9 @@
 use crate::error::{NiaServerError, NiaServerResult};
 use crate::protocol::{
     NiaConvertable, NiaGetDefinedModifiersRequest, NiaModifierDescription,
-    Serializable,
+    NiaModifierGroup, Serializable,
 };
+use crate::server::Server;
 use std::sync::MutexGuard;
 
 use nia_interpreter_core::NiaInterpreterCommand;
@@ -10,15 +11,17 @@ use nia_interpreter_core::NiaInterpreterCommandResult;
 use nia_interpreter_core::{
     EventLoopHandle, NiaGetDefinedModifiersCommandResult,
 };
-use nia_protocol_rust::{GetDefinedModifiersResponse, ModifierDescription};
+use nia_protocol_rust::GetDefinedModifiersResponse;
 
 #[derive(Debug, Clone)]
 pub struct NiaGetDefinedModifiersResponse {
     command_result: NiaGetDefinedModifiersCommandResult,
+    modifier_groups: Vec<NiaModifierGroup>,
 }
 
 impl NiaGetDefinedModifiersResponse {
     fn try_from(
+        server: &Server,
         nia_define_modifier_request: NiaGetDefinedModifiersRequest,
         event_loop_handle: MutexGuard<EventLoopHandle>,
     ) -> Result<NiaGetDefinedModifiersResponse, NiaServerError> {
@@ -43,7 +46,10 @@ impl NiaGetDefinedModifiersResponse {
         let response = match execution_result {
             NiaInterpreterCommandResult::GetDefinedModifiers(
                 command_result,
-            ) => NiaGetDefinedModifiersResponse { command_result },
+            ) => NiaGetDefinedModifiersResponse {
+                command_result,
+                modifier_groups: server.get_modifier_groups().clone(),
+            },
             _ => {
                 return NiaServerError::interpreter_error(
                     "Unexpected command result.",
@@ -56,11 +62,13 @@ impl NiaGetDefinedModifiersResponse {
     }
 
     pub fn from(
+        server: &Server,
         nia_define_modifier_request: NiaGetDefinedModifiersRequest,
         event_loop_handle: MutexGuard<EventLoopHandle>,
     ) -> NiaGetDefinedModifiersResponse {
         println!("{:?}", nia_define_modifier_request);
         let try_result = NiaGetDefinedModifiersResponse::try_from(
+            server,
             nia_define_modifier_request,
             event_loop_handle,
         );
@@ -73,7 +81,10 @@ impl NiaGetDefinedModifiersResponse {
                 let command_result =
                     NiaGetDefinedModifiersCommandResult::Failure(message);
 
-                NiaGetDefinedModifiersResponse { command_result }
+                NiaGetDefinedModifiersResponse {
+                    command_result,
+                    modifier_groups: Vec::new(),
+                }
             }
         }
     }
@@ -99,19 +110,26 @@ impl
                         NiaModifierDescription::from_interpreter_repr(
                             interpreter_modifier,
                         )
-                        .map(|modifier| modifier.to_pb())
                     })
-                    .collect::<NiaServerResult<Vec<ModifierDescription>>>();
+                    .collect::<NiaServerResult<Vec<NiaModifierDescription>>>();
 
                 match modifiers {
                     Ok(modifiers) => {
-                        let modifiers =
-                            protobuf::RepeatedField::from(modifiers);
+                        let modifier_groups = self
+                            .modifier_groups
+                            .iter()
+                            .map(|modifier_group| modifier_group.to_pb())
+                            .collect();
+                        let modifiers = modifiers
+                            .iter()
+                            .map(|modifier| modifier.to_pb())
+                            .collect();
 
                         let mut success_result =
                             nia_protocol_rust::GetDefinedModifiersResponse_SuccessResult::new();
 
                         success_result.set_modifier_descriptions(modifiers);
+                        success_result.set_modifier_groups(modifier_groups);
                         get_defined_modifiers_response
                             .set_success_result(success_result);
                     }
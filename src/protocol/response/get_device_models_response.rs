@@ -0,0 +1,67 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{NiaDeviceModelInfo, NiaGetDeviceModelsRequest};
+
+#[derive(Debug, Clone)]
+pub struct NiaGetDeviceModelsResponse {
+    device_models_result: Result<Vec<NiaDeviceModelInfo>, NiaServerError>,
+}
+
+impl NiaGetDeviceModelsResponse {
+    pub fn from(
+        _nia_get_device_models_request: NiaGetDeviceModelsRequest,
+    ) -> NiaGetDeviceModelsResponse {
+        let device_models_result = crate::utils::get_device_model_infos();
+
+        NiaGetDeviceModelsResponse {
+            device_models_result: Ok(device_models_result),
+        }
+    }
+}
+
+impl
+    Serializable<
+        NiaGetDeviceModelsResponse,
+        nia_protocol_rust::GetDeviceModelsResponse,
+    > for NiaGetDeviceModelsResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::GetDeviceModelsResponse {
+        let device_models_result = &self.device_models_result;
+        let mut get_device_models_response =
+            nia_protocol_rust::GetDeviceModelsResponse::new();
+
+        match device_models_result {
+            Ok(device_model_infos) => {
+                let device_model_infos = device_model_infos
+                    .iter()
+                    .map(|device_model_info| device_model_info.to_pb())
+                    .collect();
+
+                let mut success_result =
+                    nia_protocol_rust::GetDeviceModelsResponse_SuccessResult::new();
+
+                success_result.set_device_model_infos(device_model_infos);
+
+                get_device_models_response.set_success_result(success_result);
+            }
+            Err(error) => {
+                let message = error.get_message();
+                let mut error_result =
+                    nia_protocol_rust::GetDeviceModelsResponse_ErrorResult::new(
+                    );
+
+                error_result
+                    .set_message(protobuf::Chars::from(String::from(message)));
+                get_device_models_response.set_error_result(error_result);
+            }
+        }
+
+        get_device_models_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::GetDeviceModelsResponse,
+    ) -> NiaServerResult<NiaGetDeviceModelsResponse> {
+        unreachable!()
+    }
+}
@@ -0,0 +1,66 @@
+use crate::error::NiaServerResult;
+use crate::protocol::Serializable;
+use crate::protocol::{NiaGetHistoryRequest, NiaHistoryEntry};
+use crate::server::Server;
+
+#[derive(Debug, Clone)]
+pub struct NiaGetHistoryResponse {
+    undo_entries: Vec<NiaHistoryEntry>,
+    redo_entries: Vec<NiaHistoryEntry>,
+}
+
+impl NiaGetHistoryResponse {
+    pub fn from(
+        server: &mut Server,
+        _nia_get_history_request: NiaGetHistoryRequest,
+    ) -> NiaGetHistoryResponse {
+        let history = server.get_history();
+
+        NiaGetHistoryResponse {
+            undo_entries: history
+                .get_undo_entries()
+                .into_iter()
+                .cloned()
+                .collect(),
+            redo_entries: history
+                .get_redo_entries()
+                .into_iter()
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl Serializable<NiaGetHistoryResponse, nia_protocol_rust::GetHistoryResponse>
+    for NiaGetHistoryResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::GetHistoryResponse {
+        let mut get_history_response =
+            nia_protocol_rust::GetHistoryResponse::new();
+
+        let mut success_result =
+            nia_protocol_rust::GetHistoryResponse_SuccessResult::new();
+
+        success_result.set_undo_entries(
+            self.undo_entries
+                .iter()
+                .map(|history_entry| history_entry.to_pb())
+                .collect(),
+        );
+        success_result.set_redo_entries(
+            self.redo_entries
+                .iter()
+                .map(|history_entry| history_entry.to_pb())
+                .collect(),
+        );
+        get_history_response.set_success_result(success_result);
+
+        get_history_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::GetHistoryResponse,
+    ) -> NiaServerResult<NiaGetHistoryResponse> {
+        unreachable!()
+    }
+}
@@ -0,0 +1,91 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{NiaGetReferencesRequest, NiaReferences};
+use crate::server::Server;
+use crate::utils::get_references;
+
+#[derive(Debug, Clone)]
+pub struct NiaGetReferencesResponse {
+    references_result: Result<NiaReferences, NiaServerError>,
+}
+
+impl NiaGetReferencesResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_get_references_request: NiaGetReferencesRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<NiaReferences> {
+        let reference_target =
+            nia_get_references_request.take_reference_target();
+
+        get_references(server, &event_loop_handle, &reference_target)
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_get_references_request: NiaGetReferencesRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaGetReferencesResponse {
+        let references_result = NiaGetReferencesResponse::try_from(
+            server,
+            nia_get_references_request,
+            event_loop_handle,
+        );
+
+        NiaGetReferencesResponse { references_result }
+    }
+}
+
+impl
+    Serializable<
+        NiaGetReferencesResponse,
+        nia_protocol_rust::GetReferencesResponse,
+    > for NiaGetReferencesResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::GetReferencesResponse {
+        let mut get_references_response =
+            nia_protocol_rust::GetReferencesResponse::new();
+
+        match &self.references_result {
+            Ok(references) => {
+                let mut success_result =
+                    nia_protocol_rust::GetReferencesResponse_SuccessResult::new(
+                    );
+
+                success_result.set_references(references.to_pb());
+                get_references_response.set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::GetReferencesResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                get_references_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::GetReferencesResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                get_references_response.set_failure_result(failure_result);
+            }
+        }
+
+        get_references_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::GetReferencesResponse,
+    ) -> NiaServerResult<NiaGetReferencesResponse> {
+        unreachable!()
+    }
+}
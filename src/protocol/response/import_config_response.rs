@@ -0,0 +1,430 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{format_key, format_key_chords};
+use crate::protocol::{
+    NiaConfig, NiaConfigChange, NiaConfigImportMode, NiaConfigSnapshot,
+    NiaDefinitionChange, NiaImportConfigRequest, NiaKeyChord, NiaMapping,
+    NiaModifierDescription, NiaNamedAction,
+};
+use crate::server::Server;
+use crate::utils::{
+    apply_definition_changes, get_current_timestamp, make_config_snapshot,
+};
+
+fn describe_modifier(modifier: &NiaModifierDescription) -> String {
+    format!(
+        "modifier {} ({})",
+        format_key(modifier.get_key()),
+        modifier.get_alias()
+    )
+}
+
+fn describe_named_action(named_action: &NiaNamedAction) -> String {
+    format!("action {}", named_action.get_action_name())
+}
+
+fn describe_mapping(mapping: &NiaMapping) -> String {
+    format!("mapping {}", format_key_chords(mapping.get_key_chords()))
+}
+
+// Device ids of a configuration file are local to it, devices are looked up
+// by name first and by path then.
+fn map_device_id(
+    server: &Server,
+    config: &NiaConfig,
+    device_id: i32,
+) -> NiaServerResult<i32> {
+    let config_device = config
+        .get_devices()
+        .iter()
+        .find(|device| device.get_device_id() == device_id)
+        .ok_or_else(|| {
+            NiaServerError::invalid_request(format!(
+                "Configuration refers to undeclared device: {}.",
+                device_id
+            ))
+        })?;
+
+    let devices = server.get_devices();
+
+    devices
+        .iter()
+        .find(|device_info| {
+            device_info.get_device_name() == config_device.get_device_name()
+        })
+        .or_else(|| {
+            devices.iter().find(|device_info| {
+                device_info.get_device_path() == config_device.get_device_path()
+            })
+        })
+        .map(|device_info| device_info.get_device_id())
+        .ok_or_else(|| {
+            NiaServerError::invalid_request(format!(
+                "Unknown device: {}.",
+                config_device.get_device_name()
+            ))
+        })
+}
+
+// What is defined after the import. Items of the configuration replace the
+// defined ones with the same key, name or key chords, the defined ones that
+// are not in the configuration stay only when the configuration is merged.
+// Devices are never undefined.
+fn make_imported_snapshot(
+    server: &Server,
+    config_snapshot: &NiaConfigSnapshot,
+    config: &NiaConfig,
+    import_mode: NiaConfigImportMode,
+) -> NiaConfigSnapshot {
+    let is_merged = import_mode == NiaConfigImportMode::Merge;
+
+    let mut device_ids = config_snapshot.get_device_ids().clone();
+
+    for config_device in config.get_devices() {
+        let device_id = config_device.get_device_id();
+
+        if server.get_device_info_by_id(device_id).is_some()
+            && !device_ids.contains(&device_id)
+        {
+            device_ids.push(device_id);
+        }
+    }
+
+    let modifiers = config_snapshot
+        .get_modifiers()
+        .iter()
+        .filter(|defined_modifier| {
+            is_merged
+                && !config.get_modifiers().iter().any(|modifier| {
+                    modifier.get_key() == defined_modifier.get_key()
+                })
+        })
+        .chain(config.get_modifiers())
+        .cloned()
+        .collect();
+
+    let named_actions = config_snapshot
+        .get_named_actions()
+        .iter()
+        .filter(|defined_action| {
+            is_merged
+                && !config.get_named_actions().iter().any(|named_action| {
+                    named_action.get_action_name()
+                        == defined_action.get_action_name()
+                })
+        })
+        .chain(config.get_named_actions())
+        .cloned()
+        .collect();
+
+    let mappings = config_snapshot
+        .get_mappings()
+        .iter()
+        .filter(|defined_mapping| {
+            is_merged
+                && !is_mapping_in_config(
+                    config,
+                    defined_mapping.get_key_chords(),
+                )
+        })
+        .chain(config.get_mappings())
+        .cloned()
+        .collect();
+
+    NiaConfigSnapshot::new(device_ids, modifiers, named_actions, mappings)
+}
+
+fn is_mapping_in_config(
+    config: &NiaConfig,
+    key_chords: &[NiaKeyChord],
+) -> bool {
+    config
+        .get_mappings()
+        .iter()
+        .chain(config.get_disabled_mappings())
+        .any(|mapping| mapping.get_key_chords() == key_chords)
+}
+
+// What a definition change is about, and how it is reported.
+fn describe_definition_change(
+    server: &Server,
+    definition_change: &NiaDefinitionChange,
+) -> (String, String) {
+    match definition_change {
+        NiaDefinitionChange::DefineDevice(device_id)
+        | NiaDefinitionChange::RemoveDevice(device_id) => {
+            let device_name = server
+                .get_device_info_by_id(*device_id)
+                .map(|device_info| device_info.get_device_name().to_string())
+                .unwrap_or_else(|| device_id.to_string());
+
+            (
+                format!("device {}", device_id),
+                format!("device {}", device_name),
+            )
+        }
+        NiaDefinitionChange::DefineModifier(modifier)
+        | NiaDefinitionChange::RemoveModifier(modifier) => (
+            format!("modifier {}", format_key(modifier.get_key())),
+            describe_modifier(modifier),
+        ),
+        NiaDefinitionChange::DefineAction(named_action)
+        | NiaDefinitionChange::RemoveAction(named_action) => (
+            describe_named_action(named_action),
+            describe_named_action(named_action),
+        ),
+        NiaDefinitionChange::DefineMapping(mapping)
+        | NiaDefinitionChange::RemoveMapping(mapping)
+        | NiaDefinitionChange::ChangeMapping {
+            new_mapping: mapping,
+            ..
+        } => (describe_mapping(mapping), describe_mapping(mapping)),
+    }
+}
+
+// A definition that is removed and defined again is reported as changed.
+fn make_config_changes(
+    server: &Server,
+    definition_changes: &[NiaDefinitionChange],
+) -> Vec<NiaConfigChange> {
+    let descriptions = definition_changes
+        .iter()
+        .map(|definition_change| {
+            describe_definition_change(server, definition_change)
+        })
+        .collect::<Vec<(String, String)>>();
+
+    let is_redefined = |index: usize| {
+        descriptions.iter().enumerate().any(|(other_index, other)| {
+            other_index != index && other.0 == descriptions[index].0
+        })
+    };
+
+    let mut changes = Vec::new();
+
+    for (index, definition_change) in definition_changes.iter().enumerate() {
+        let description = descriptions[index].1.clone();
+
+        let change = match definition_change {
+            NiaDefinitionChange::ChangeMapping { .. } => {
+                NiaConfigChange::changed(description)
+            }
+            NiaDefinitionChange::DefineDevice(_)
+            | NiaDefinitionChange::DefineModifier(_)
+            | NiaDefinitionChange::DefineAction(_)
+            | NiaDefinitionChange::DefineMapping(_) => {
+                if is_redefined(index) {
+                    NiaConfigChange::changed(description)
+                } else {
+                    NiaConfigChange::added(description)
+                }
+            }
+            _ => {
+                if is_redefined(index) {
+                    continue;
+                }
+
+                NiaConfigChange::removed(description)
+            }
+        };
+
+        changes.push(change);
+    }
+
+    changes
+}
+
+// Disabled mappings and metadata are kept by the server, they are imported
+// once the definitions are.
+fn import_mapping_state(
+    server: &mut Server,
+    config: &NiaConfig,
+    import_mode: NiaConfigImportMode,
+    changes: &mut Vec<NiaConfigChange>,
+) {
+    let timestamp = get_current_timestamp();
+
+    let disabled_mappings = server
+        .get_disabled_mappings()
+        .get_mappings()
+        .into_iter()
+        .cloned()
+        .collect::<Vec<NiaMapping>>();
+
+    // Disabled mappings of the configuration replace the ones with the same
+    // key chords below, and enabled ones are already defined.
+    for disabled_mapping in disabled_mappings {
+        let key_chords = disabled_mapping.get_key_chords();
+
+        if import_mode == NiaConfigImportMode::Replace
+            && !is_mapping_in_config(config, key_chords)
+        {
+            server.mark_mapping_removed(key_chords);
+            changes.push(NiaConfigChange::removed(format!(
+                "disabled {}",
+                describe_mapping(&disabled_mapping)
+            )));
+        }
+    }
+
+    for mapping in config.get_disabled_mappings() {
+        let key_chords = mapping.get_key_chords();
+
+        if server.get_disabled_mappings().get(key_chords) == Some(mapping) {
+            continue;
+        }
+
+        server.disable_mapping(mapping.clone(), timestamp);
+        changes.push(NiaConfigChange::added(format!(
+            "disabled {}",
+            describe_mapping(mapping)
+        )));
+    }
+
+    for mapping in config
+        .get_mappings()
+        .iter()
+        .chain(config.get_disabled_mappings())
+    {
+        let key_chords = mapping.get_key_chords();
+        let (description, tags) =
+            match config.get_mappings_metadata().get(key_chords) {
+                Some(config_metadata) => (
+                    config_metadata.get_description().to_string(),
+                    config_metadata.get_tags().clone(),
+                ),
+                None => (String::new(), Vec::new()),
+            };
+
+        let mapping_metadata =
+            server.get_mapping_metadata(key_chords, timestamp);
+        let is_changed = mapping_metadata.get_description() != description
+            || mapping_metadata.get_tags() != &tags;
+
+        if is_changed {
+            mapping_metadata.set_description(description, timestamp);
+            mapping_metadata.set_tags(tags, timestamp);
+
+            changes.push(NiaConfigChange::changed(format!(
+                "metadata of {}",
+                describe_mapping(mapping)
+            )));
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NiaImportConfigResponse {
+    changes_result: Result<Vec<NiaConfigChange>, NiaServerError>,
+}
+
+impl NiaImportConfigResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_import_config_request: NiaImportConfigRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<Vec<NiaConfigChange>> {
+        let (config, import_mode) = nia_import_config_request.into_tuple();
+
+        let config = NiaConfig::from_str(&config)?;
+        let config = config.map_device_ids(|device_id| {
+            map_device_id(server, &config, device_id)
+        })?;
+
+        // Definitions are applied at once, so that a failed import changes
+        // nothing.
+        let config_snapshot = make_config_snapshot(server, &event_loop_handle)?;
+        let imported_snapshot = make_imported_snapshot(
+            server,
+            &config_snapshot,
+            &config,
+            import_mode,
+        );
+        let definition_changes = config_snapshot.diff(&imported_snapshot);
+
+        apply_definition_changes(
+            server,
+            &event_loop_handle,
+            &definition_changes,
+        )?;
+
+        let mut changes = make_config_changes(server, &definition_changes);
+
+        import_mapping_state(server, &config, import_mode, &mut changes);
+
+        Ok(changes)
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_import_config_request: NiaImportConfigRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaImportConfigResponse {
+        let changes_result = NiaImportConfigResponse::try_from(
+            server,
+            nia_import_config_request,
+            event_loop_handle,
+        );
+
+        NiaImportConfigResponse { changes_result }
+    }
+}
+
+impl
+    Serializable<
+        NiaImportConfigResponse,
+        nia_protocol_rust::ImportConfigResponse,
+    > for NiaImportConfigResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::ImportConfigResponse {
+        let mut import_config_response =
+            nia_protocol_rust::ImportConfigResponse::new();
+
+        match &self.changes_result {
+            Ok(changes) => {
+                let changes =
+                    changes.iter().map(|change| change.to_pb()).collect();
+
+                let mut success_result =
+                    nia_protocol_rust::ImportConfigResponse_SuccessResult::new(
+                    );
+
+                success_result.set_changes(changes);
+
+                import_config_response.set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message))
+            | Err(NiaServerError::DeserializationError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::ImportConfigResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                import_config_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::ImportConfigResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                import_config_response.set_failure_result(failure_result);
+            }
+        }
+
+        import_config_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ImportConfigResponse,
+    ) -> NiaServerResult<NiaImportConfigResponse> {
+        unreachable!()
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{NiaListProfilesRequest, NiaProfile};
+use crate::server::Server;
+use crate::utils::save_active_profile;
+
+#[derive(Debug, Clone)]
+pub struct NiaListProfilesResponse {
+    profiles_result: Result<(Vec<NiaProfile>, Option<String>), NiaServerError>,
+}
+
+impl NiaListProfilesResponse {
+    fn try_from(
+        server: &mut Server,
+        _nia_list_profiles_request: NiaListProfilesRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<(Vec<NiaProfile>, Option<String>)> {
+        save_active_profile(server, &event_loop_handle)?;
+
+        let profiles = server.get_profiles().clone();
+        let active_profile_name =
+            server.get_active_profile_name().map(String::from);
+
+        Ok((profiles, active_profile_name))
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_list_profiles_request: NiaListProfilesRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaListProfilesResponse {
+        let profiles_result = NiaListProfilesResponse::try_from(
+            server,
+            nia_list_profiles_request,
+            event_loop_handle,
+        );
+
+        NiaListProfilesResponse { profiles_result }
+    }
+}
+
+impl
+    Serializable<
+        NiaListProfilesResponse,
+        nia_protocol_rust::ListProfilesResponse,
+    > for NiaListProfilesResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::ListProfilesResponse {
+        let mut list_profiles_response =
+            nia_protocol_rust::ListProfilesResponse::new();
+
+        match &self.profiles_result {
+            Ok((profiles, active_profile_name)) => {
+                let profiles =
+                    profiles.iter().map(|profile| profile.to_pb()).collect();
+
+                let mut success_result =
+                    nia_protocol_rust::ListProfilesResponse_SuccessResult::new(
+                    );
+
+                success_result.set_profiles(profiles);
+
+                if let Some(active_profile_name) = active_profile_name {
+                    success_result.set_active_profile_name(
+                        protobuf::Chars::from(active_profile_name.clone()),
+                    );
+                }
+
+                list_profiles_response.set_success_result(success_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::ListProfilesResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                list_profiles_response.set_failure_result(failure_result);
+            }
+        }
+
+        list_profiles_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ListProfilesResponse,
+    ) -> NiaServerResult<NiaListProfilesResponse> {
+        unreachable!()
+    }
+}
@@ -1,43 +1,105 @@
+mod activate_profile_response;
+mod begin_transaction_response;
 mod change_mapping_response;
+mod check_mappings_response;
+mod commit_transaction_response;
+mod create_profile_response;
 mod define_action_response;
 mod define_device_response;
 mod define_mapping_response;
+mod define_modifier_group_response;
 mod define_modifier_response;
+mod define_text_mapping_response;
+mod delete_profile_response;
+mod device_model_changed_response;
+mod diff_config_response;
 mod execute_code_response;
+mod export_config_response;
 mod get_defined_actions_response;
 mod get_defined_mappings_response;
 mod get_defined_modifiers_response;
+mod get_device_models_response;
 mod get_devices_response;
+mod get_history_response;
+mod get_references_response;
 mod handshake_response;
+mod import_config_response;
 mod is_listening_response;
+mod list_profiles_response;
+mod rebind_mapping_response;
+mod redo_response;
 mod remove_action_response;
 mod remove_device_by_id_response;
 mod remove_device_by_name_response;
 mod remove_device_by_path_response;
 mod remove_mapping_response;
+mod remove_modifier_group_response;
 mod remove_modifier_response;
+mod remove_text_mapping_response;
+mod rename_action_response;
+mod rename_modifier_alias_response;
+mod request_refused_response;
+mod request_staged_response;
+mod resolve_keys_response;
 mod response;
+mod rollback_transaction_response;
+mod set_mapping_enabled_response;
+mod set_mapping_metadata_response;
+mod set_mappings_enabled_by_tag_response;
+mod simulate_key_events_response;
 mod start_listening_response;
 mod stop_listening_response;
+mod undo_response;
 
+pub use activate_profile_response::*;
+pub use begin_transaction_response::*;
 pub use change_mapping_response::*;
+pub use check_mappings_response::*;
+pub use commit_transaction_response::*;
+pub use create_profile_response::*;
 pub use define_action_response::*;
 pub use define_device_response::*;
 pub use define_mapping_response::*;
+pub use define_modifier_group_response::*;
 pub use define_modifier_response::*;
+pub use define_text_mapping_response::*;
+pub use delete_profile_response::*;
+pub use device_model_changed_response::*;
+pub use diff_config_response::*;
 pub use execute_code_response::*;
+pub use export_config_response::*;
 pub use get_defined_actions_response::*;
 pub use get_defined_mappings_response::*;
 pub use get_defined_modifiers_response::*;
+pub use get_device_models_response::*;
 pub use get_devices_response::*;
+pub use get_history_response::*;
+pub use get_references_response::*;
 pub use handshake_response::*;
+pub use import_config_response::*;
 pub use is_listening_response::*;
+pub use list_profiles_response::*;
+pub use rebind_mapping_response::*;
+pub use redo_response::*;
 pub use remove_action_response::*;
 pub use remove_device_by_id_response::*;
 pub use remove_device_by_name_response::*;
 pub use remove_device_by_path_response::*;
 pub use remove_mapping_response::*;
+pub use remove_modifier_group_response::*;
 pub use remove_modifier_response::*;
+pub use remove_text_mapping_response::*;
+pub use rename_action_response::*;
+pub use rename_modifier_alias_response::*;
+pub use request_refused_response::*;
+pub use request_staged_response::*;
+pub use resolve_keys_response::*;
 pub use response::*;
+pub use rollback_transaction_response::*;
+pub use set_mapping_enabled_response::*;
+pub use set_mapping_metadata_response::*;
+pub use set_mappings_enabled_by_tag_response::*;
+pub use simulate_key_events_response::*;
 pub use start_listening_response::*;
 pub use stop_listening_response::*;
+pub use undo_response::*;
@@ -0,0 +1,101 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{NiaMappingConflict, NiaRebindMappingRequest};
+use crate::server::Server;
+use crate::utils::rebind_mapping;
+
+#[derive(Debug, Clone)]
+pub struct NiaRebindMappingResponse {
+    conflicts_result: Result<Vec<NiaMappingConflict>, NiaServerError>,
+}
+
+impl NiaRebindMappingResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_rebind_mapping_request: NiaRebindMappingRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<Vec<NiaMappingConflict>> {
+        let (old_key_chords, new_key_chords) =
+            nia_rebind_mapping_request.into_tuple();
+
+        rebind_mapping(
+            server,
+            &event_loop_handle,
+            &old_key_chords,
+            &new_key_chords,
+        )
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_rebind_mapping_request: NiaRebindMappingRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaRebindMappingResponse {
+        let conflicts_result = NiaRebindMappingResponse::try_from(
+            server,
+            nia_rebind_mapping_request,
+            event_loop_handle,
+        );
+
+        NiaRebindMappingResponse { conflicts_result }
+    }
+}
+
+impl
+    Serializable<
+        NiaRebindMappingResponse,
+        nia_protocol_rust::RebindMappingResponse,
+    > for NiaRebindMappingResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::RebindMappingResponse {
+        let mut rebind_mapping_response =
+            nia_protocol_rust::RebindMappingResponse::new();
+
+        match &self.conflicts_result {
+            Ok(conflicts) => {
+                let mut success_result =
+                    nia_protocol_rust::RebindMappingResponse_SuccessResult::new(
+                    );
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                success_result.set_conflicts(
+                    conflicts.iter().map(|conflict| conflict.to_pb()).collect(),
+                );
+                rebind_mapping_response.set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::RebindMappingResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                rebind_mapping_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::RebindMappingResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                rebind_mapping_response.set_failure_result(failure_result);
+            }
+        }
+
+        rebind_mapping_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::RebindMappingResponse,
+    ) -> NiaServerResult<NiaRebindMappingResponse> {
+        unreachable!()
+    }
+}
@@ -0,0 +1,87 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{NiaHistoryEntry, NiaRedoRequest};
+use crate::server::Server;
+use crate::utils::redo;
+
+#[derive(Debug, Clone)]
+pub struct NiaRedoResponse {
+    history_entry_result: Result<NiaHistoryEntry, NiaServerError>,
+}
+
+impl NiaRedoResponse {
+    fn try_from(
+        server: &mut Server,
+        _nia_redo_request: NiaRedoRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<NiaHistoryEntry> {
+        redo(server, &event_loop_handle)
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_redo_request: NiaRedoRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaRedoResponse {
+        let history_entry_result = NiaRedoResponse::try_from(
+            server,
+            nia_redo_request,
+            event_loop_handle,
+        );
+
+        NiaRedoResponse {
+            history_entry_result,
+        }
+    }
+}
+
+impl Serializable<NiaRedoResponse, nia_protocol_rust::RedoResponse>
+    for NiaRedoResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::RedoResponse {
+        let mut redo_response = nia_protocol_rust::RedoResponse::new();
+
+        match &self.history_entry_result {
+            Ok(history_entry) => {
+                let mut success_result =
+                    nia_protocol_rust::RedoResponse_SuccessResult::new();
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                success_result.set_history_entry(history_entry.to_pb());
+                redo_response.set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::RedoResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                redo_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::RedoResponse_FailureResult::new();
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                redo_response.set_failure_result(failure_result);
+            }
+        }
+
+        redo_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::RedoResponse,
+    ) -> NiaServerResult<NiaRedoResponse> {
+        unreachable!()
+    }
+}
@@ -1,13 +1,12 @@
 use std::sync::MutexGuard;
 
-use nia_interpreter_core::Interpreter;
-use nia_interpreter_core::NiaInterpreterCommand;
-use nia_interpreter_core::NiaInterpreterCommandResult;
 use nia_interpreter_core::{EventLoopHandle, NiaRemoveActionCommandResult};
 
 use crate::error::{NiaServerError, NiaServerResult};
 
 use crate::protocol::{NiaRemoveActionRequest, Serializable};
+use crate::server::Server;
+use crate::utils::remove_referenced_action;
 use nia_protocol_rust::RemoveActionResponse;
 
 #[derive(Debug, Clone)]
@@ -17,49 +16,37 @@ pub struct NiaRemoveActionResponse {
 
 impl NiaRemoveActionResponse {
     fn try_from(
+        server: &mut Server,
         nia_remove_action_request: NiaRemoveActionRequest,
         event_loop_handle: MutexGuard<EventLoopHandle>,
     ) -> Result<NiaRemoveActionResponse, NiaServerError> {
+        let removal_mode = nia_remove_action_request.get_removal_mode();
         let action_name = nia_remove_action_request.take_action_name();
 
-        let interpreter_command =
-            NiaInterpreterCommand::make_remove_action_command(action_name);
-
-        event_loop_handle
-            .send_command(interpreter_command)
-            .map_err(|_| {
-                NiaServerError::interpreter_error(
-                    "Error sending command to the interpreter.",
-                )
-            })?;
-
-        let execution_result =
-            event_loop_handle.receive_result().map_err(|_| {
-                NiaServerError::interpreter_error(
-                    "Error reading command from the interpreter.",
-                )
-            })?;
-
-        let response = match execution_result {
-            NiaInterpreterCommandResult::RemoveAction(command_result) => {
-                NiaRemoveActionResponse { command_result }
-            }
-            _ => {
-                return NiaServerError::interpreter_error(
-                    "Unexpected command result.",
-                )
-                .into()
+        let command_result = match remove_referenced_action(
+            server,
+            &event_loop_handle,
+            &action_name,
+            removal_mode,
+        ) {
+            Ok(()) => NiaRemoveActionCommandResult::Success(),
+            Err(NiaServerError::InvalidRequestError(message))
+            | Err(NiaServerError::InterpreterError(message)) => {
+                NiaRemoveActionCommandResult::Error(message)
             }
+            Err(error) => return Err(error),
         };
 
-        Ok(response)
+        Ok(NiaRemoveActionResponse { command_result })
     }
 
     pub fn from(
+        server: &mut Server,
         nia_remove_action_request: NiaRemoveActionRequest,
         event_loop_handle: MutexGuard<EventLoopHandle>,
     ) -> NiaRemoveActionResponse {
         let try_result = NiaRemoveActionResponse::try_from(
+            server,
             nia_remove_action_request,
             event_loop_handle,
         );
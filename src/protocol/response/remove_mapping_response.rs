@@ -1,13 +1,12 @@
 use std::sync::MutexGuard;
 
-use nia_interpreter_core::Interpreter;
-use nia_interpreter_core::NiaInterpreterCommand;
-use nia_interpreter_core::NiaInterpreterCommandResult;
 use nia_interpreter_core::{EventLoopHandle, NiaRemoveMappingCommandResult};
 
 use crate::error::{NiaServerError, NiaServerResult};
 
-use crate::protocol::{NiaConvertable, NiaRemoveMappingRequest, Serializable};
+use crate::protocol::{NiaRemoveMappingRequest, Serializable};
+use crate::server::Server;
+use crate::utils::remove_mapping;
 use nia_protocol_rust::RemoveMappingResponse;
 
 #[derive(Debug, Clone)]
@@ -17,53 +16,48 @@ pub struct NiaRemoveMappingResponse {
 
 impl NiaRemoveMappingResponse {
     fn try_from(
+        server: &mut Server,
         nia_remove_mapping_request: NiaRemoveMappingRequest,
         event_loop_handle: MutexGuard<EventLoopHandle>,
     ) -> Result<NiaRemoveMappingResponse, NiaServerError> {
-        let key_chords = nia_remove_mapping_request
-            .take_key_chords()
-            .iter()
-            .map(|key_chord| key_chord.to_interpreter_repr())
-            .collect::<Vec<nia_interpreter_core::KeyChord>>();
-
-        let interpreter_command =
-            NiaInterpreterCommand::make_remove_mapping_command(key_chords);
-
-        event_loop_handle
-            .send_command(interpreter_command)
-            .map_err(|_| {
-                NiaServerError::interpreter_error(
-                    "Error sending command to the interpreter.",
-                )
-            })?;
-
-        let execution_result =
-            event_loop_handle.receive_result().map_err(|_| {
-                NiaServerError::interpreter_error(
-                    "Error reading command from the interpreter.",
-                )
-            })?;
-
-        let response = match execution_result {
-            NiaInterpreterCommandResult::RemoveMapping(command_result) => {
-                NiaRemoveMappingResponse { command_result }
+        let key_chords = nia_remove_mapping_request.take_key_chords();
+
+        // Disabled mappings are known to the server only.
+        if server.get_disabled_mappings().get(&key_chords).is_some() {
+            server.mark_mapping_removed(&key_chords);
+
+            return Ok(NiaRemoveMappingResponse {
+                command_result: NiaRemoveMappingCommandResult::Success(),
+            });
+        }
+
+        let command_result = match remove_mapping(
+            &event_loop_handle,
+            server.get_modifier_groups(),
+            &key_chords,
+        ) {
+            Ok(()) => {
+                server.mark_mapping_removed(&key_chords);
+
+                NiaRemoveMappingCommandResult::Success()
             }
-            _ => {
-                return NiaServerError::interpreter_error(
-                    "Unexpected command result.",
-                )
-                .into()
+            Err(NiaServerError::InvalidRequestError(message))
+            | Err(NiaServerError::InterpreterError(message)) => {
+                NiaRemoveMappingCommandResult::Error(message)
             }
+            Err(error) => return Err(error),
         };
 
-        Ok(response)
+        Ok(NiaRemoveMappingResponse { command_result })
     }
 
     pub fn from(
+        server: &mut Server,
         nia_remove_mapping_request: NiaRemoveMappingRequest,
         event_loop_handle: MutexGuard<EventLoopHandle>,
     ) -> NiaRemoveMappingResponse {
         let try_result = NiaRemoveMappingResponse::try_from(
+            server,
             nia_remove_mapping_request,
             event_loop_handle,
         );
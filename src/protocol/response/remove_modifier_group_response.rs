@@ -0,0 +1,87 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::NiaRemoveModifierGroupRequest;
+use crate::protocol::Serializable;
+use crate::server::Server;
+use crate::utils::remove_modifier_group;
+
+#[derive(Debug, Clone)]
+pub struct NiaRemoveModifierGroupResponse {
+    result: Result<(), NiaServerError>,
+}
+
+impl NiaRemoveModifierGroupResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_remove_modifier_group_request: NiaRemoveModifierGroupRequest,
+    ) -> NiaServerResult<()> {
+        let alias = nia_remove_modifier_group_request.take_alias();
+
+        remove_modifier_group(server, &alias)?;
+
+        Ok(())
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_remove_modifier_group_request: NiaRemoveModifierGroupRequest,
+    ) -> NiaRemoveModifierGroupResponse {
+        let result = NiaRemoveModifierGroupResponse::try_from(
+            server,
+            nia_remove_modifier_group_request,
+        );
+
+        NiaRemoveModifierGroupResponse { result }
+    }
+}
+
+impl
+    Serializable<
+        NiaRemoveModifierGroupResponse,
+        nia_protocol_rust::RemoveModifierGroupResponse,
+    > for NiaRemoveModifierGroupResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::RemoveModifierGroupResponse {
+        let mut remove_modifier_group_response =
+            nia_protocol_rust::RemoveModifierGroupResponse::new();
+
+        match &self.result {
+            Ok(()) => {
+                let mut success_result =
+                    nia_protocol_rust::RemoveModifierGroupResponse_SuccessResult::new();
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                remove_modifier_group_response
+                    .set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::RemoveModifierGroupResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                remove_modifier_group_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::RemoveModifierGroupResponse_FailureResult::new();
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                remove_modifier_group_response
+                    .set_failure_result(failure_result);
+            }
+        }
+
+        remove_modifier_group_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::RemoveModifierGroupResponse,
+    ) -> NiaServerResult<NiaRemoveModifierGroupResponse> {
+        unreachable!()
+    }
+}
@@ -1,13 +1,12 @@
 use std::sync::MutexGuard;
 
-use nia_interpreter_core::Interpreter;
-use nia_interpreter_core::NiaInterpreterCommand;
-use nia_interpreter_core::NiaInterpreterCommandResult;
 use nia_interpreter_core::{EventLoopHandle, NiaRemoveModifierCommandResult};
 
 use crate::error::{NiaServerError, NiaServerResult};
 
-use crate::protocol::{NiaConvertable, NiaRemoveModifierRequest, Serializable};
+use crate::protocol::{NiaRemoveModifierRequest, Serializable};
+use crate::server::Server;
+use crate::utils::remove_referenced_modifier;
 use nia_protocol_rust::RemoveModifierResponse;
 
 #[derive(Debug, Clone)]
@@ -17,54 +16,38 @@ pub struct NiaRemoveModifierResponse {
 
 impl NiaRemoveModifierResponse {
     fn try_from(
+        server: &mut Server,
         nia_remove_modifier_request: NiaRemoveModifierRequest,
         event_loop_handle: MutexGuard<EventLoopHandle>,
     ) -> Result<NiaRemoveModifierResponse, NiaServerError> {
         let modifier_key = nia_remove_modifier_request.take_key();
-
-        let interpreter_modifier_key = modifier_key.to_interpreter_repr();
-
-        let interpreter_command =
-            NiaInterpreterCommand::make_remove_modifier_command(
-                interpreter_modifier_key,
-            );
-
-        event_loop_handle
-            .send_command(interpreter_command)
-            .map_err(|_| {
-                NiaServerError::interpreter_error(
-                    "Error sending command to the interpreter.",
-                )
-            })?;
-
-        let execution_result =
-            event_loop_handle.receive_result().map_err(|_| {
-                NiaServerError::interpreter_error(
-                    "Error reading command from the interpreter.",
-                )
-            })?;
-
-        let response = match execution_result {
-            NiaInterpreterCommandResult::RemoveModifier(command_result) => {
-                NiaRemoveModifierResponse { command_result }
-            }
-            _ => {
-                return NiaServerError::interpreter_error(
-                    "Unexpected command result.",
-                )
-                .into()
+        let removal_mode = nia_remove_modifier_request.get_removal_mode();
+
+        let command_result = match remove_referenced_modifier(
+            server,
+            &event_loop_handle,
+            modifier_key,
+            removal_mode,
+        ) {
+            Ok(()) => NiaRemoveModifierCommandResult::Success(),
+            Err(NiaServerError::InvalidRequestError(message))
+            | Err(NiaServerError::InterpreterError(message)) => {
+                NiaRemoveModifierCommandResult::Error(message)
             }
+            Err(error) => return Err(error),
         };
 
-        Ok(response)
+        Ok(NiaRemoveModifierResponse { command_result })
     }
 
     pub fn from(
+        server: &mut Server,
         nia_remove_modifier_request: NiaRemoveModifierRequest,
         event_loop_handle: MutexGuard<EventLoopHandle>,
     ) -> NiaRemoveModifierResponse {
         println!("{:?}", nia_remove_modifier_request);
         let try_result = NiaRemoveModifierResponse::try_from(
+            server,
             nia_remove_modifier_request,
             event_loop_handle,
         );
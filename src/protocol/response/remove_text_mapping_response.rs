@@ -0,0 +1,137 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::{EventLoopHandle, NiaRemoveMappingCommandResult};
+
+use crate::error::{NiaServerError, NiaServerResult};
+
+use crate::protocol::NiaRemoveTextMappingRequest;
+use crate::protocol::Serializable;
+use crate::protocol::{format_key_chords, parse_key_chords};
+use crate::server::Server;
+use crate::utils::remove_mapping;
+use nia_protocol_rust::RemoveTextMappingResponse;
+
+#[derive(Debug, Clone)]
+pub struct NiaRemoveTextMappingResponse {
+    command_result: NiaRemoveMappingCommandResult,
+}
+
+impl NiaRemoveTextMappingResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_remove_text_mapping_request: NiaRemoveTextMappingRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> Result<NiaRemoveTextMappingResponse, NiaServerError> {
+        let key_chords = parse_key_chords(
+            &nia_remove_text_mapping_request.take_key_chords(),
+            &|device_name| server.get_device_id_by_name(device_name),
+        )?;
+
+        println!("Removing mapping: {}", format_key_chords(&key_chords));
+
+        // Disabled mappings are known to the server only.
+        if server.get_disabled_mappings().get(&key_chords).is_some() {
+            server.mark_mapping_removed(&key_chords);
+
+            return Ok(NiaRemoveTextMappingResponse {
+                command_result: NiaRemoveMappingCommandResult::Success(),
+            });
+        }
+
+        let command_result = match remove_mapping(
+            &event_loop_handle,
+            server.get_modifier_groups(),
+            &key_chords,
+        ) {
+            Ok(()) => {
+                server.mark_mapping_removed(&key_chords);
+
+                NiaRemoveMappingCommandResult::Success()
+            }
+            Err(NiaServerError::InvalidRequestError(message))
+            | Err(NiaServerError::InterpreterError(message)) => {
+                NiaRemoveMappingCommandResult::Error(message)
+            }
+            Err(error) => return Err(error),
+        };
+
+        Ok(NiaRemoveTextMappingResponse { command_result })
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_remove_text_mapping_request: NiaRemoveTextMappingRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaRemoveTextMappingResponse {
+        let try_result = NiaRemoveTextMappingResponse::try_from(
+            server,
+            nia_remove_text_mapping_request,
+            event_loop_handle,
+        );
+
+        match try_result {
+            Ok(result) => result,
+            Err(error) => {
+                let message =
+                    format!("Execution failure: {}", error.get_message());
+                let command_result =
+                    NiaRemoveMappingCommandResult::Failure(message);
+
+                NiaRemoveTextMappingResponse { command_result }
+            }
+        }
+    }
+}
+
+impl
+    Serializable<
+        NiaRemoveTextMappingResponse,
+        nia_protocol_rust::RemoveTextMappingResponse,
+    > for NiaRemoveTextMappingResponse
+{
+    fn to_pb(&self) -> RemoveTextMappingResponse {
+        let result = &self.command_result;
+
+        let mut remove_text_mapping_response =
+            nia_protocol_rust::RemoveTextMappingResponse::new();
+
+        match result {
+            NiaRemoveMappingCommandResult::Success() => {
+                let mut success_result =
+                    nia_protocol_rust::RemoveTextMappingResponse_SuccessResult::new(
+                    );
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                remove_text_mapping_response.set_success_result(success_result);
+            }
+            NiaRemoveMappingCommandResult::Error(error_message) => {
+                let mut error_result =
+                    nia_protocol_rust::RemoveTextMappingResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(error_message.clone()));
+                remove_text_mapping_response.set_error_result(error_result);
+            }
+            NiaRemoveMappingCommandResult::Failure(failure_message) => {
+                let mut failure_result =
+                    nia_protocol_rust::RemoveTextMappingResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(
+                    failure_message.clone(),
+                ));
+                remove_text_mapping_response.set_failure_result(failure_result);
+            }
+        }
+
+        remove_text_mapping_response
+    }
+
+    fn from_pb(
+        object_pb: RemoveTextMappingResponse,
+    ) -> NiaServerResult<NiaRemoveTextMappingResponse> {
+        unreachable!()
+    }
+}
@@ -0,0 +1,94 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::NiaRenameActionRequest;
+use crate::protocol::Serializable;
+use crate::server::Server;
+use crate::utils::rename_action;
+
+#[derive(Debug, Clone)]
+pub struct NiaRenameActionResponse {
+    result: Result<(), NiaServerError>,
+}
+
+impl NiaRenameActionResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_rename_action_request: NiaRenameActionRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<()> {
+        let (old_name, new_name) = nia_rename_action_request.into_tuple();
+
+        rename_action(server, &event_loop_handle, &old_name, &new_name)?;
+
+        Ok(())
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_rename_action_request: NiaRenameActionRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaRenameActionResponse {
+        let result = NiaRenameActionResponse::try_from(
+            server,
+            nia_rename_action_request,
+            event_loop_handle,
+        );
+
+        NiaRenameActionResponse { result }
+    }
+}
+
+impl
+    Serializable<
+        NiaRenameActionResponse,
+        nia_protocol_rust::RenameActionResponse,
+    > for NiaRenameActionResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::RenameActionResponse {
+        let mut rename_action_response =
+            nia_protocol_rust::RenameActionResponse::new();
+
+        match &self.result {
+            Ok(()) => {
+                let mut success_result =
+                    nia_protocol_rust::RenameActionResponse_SuccessResult::new(
+                    );
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                rename_action_response.set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::RenameActionResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                rename_action_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::RenameActionResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                rename_action_response.set_failure_result(failure_result);
+            }
+        }
+
+        rename_action_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::RenameActionResponse,
+    ) -> NiaServerResult<NiaRenameActionResponse> {
+        unreachable!()
+    }
+}
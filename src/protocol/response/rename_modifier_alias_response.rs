@@ -0,0 +1,100 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::NiaRenameModifierAliasRequest;
+use crate::protocol::Serializable;
+use crate::server::Server;
+use crate::utils::rename_modifier_alias;
+
+#[derive(Debug, Clone)]
+pub struct NiaRenameModifierAliasResponse {
+    result: Result<(), NiaServerError>,
+}
+
+impl NiaRenameModifierAliasResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_rename_modifier_alias_request: NiaRenameModifierAliasRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<()> {
+        let (old_name, new_name) =
+            nia_rename_modifier_alias_request.into_tuple();
+
+        rename_modifier_alias(
+            server,
+            &event_loop_handle,
+            &old_name,
+            &new_name,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_rename_modifier_alias_request: NiaRenameModifierAliasRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaRenameModifierAliasResponse {
+        let result = NiaRenameModifierAliasResponse::try_from(
+            server,
+            nia_rename_modifier_alias_request,
+            event_loop_handle,
+        );
+
+        NiaRenameModifierAliasResponse { result }
+    }
+}
+
+impl
+    Serializable<
+        NiaRenameModifierAliasResponse,
+        nia_protocol_rust::RenameModifierAliasResponse,
+    > for NiaRenameModifierAliasResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::RenameModifierAliasResponse {
+        let mut rename_modifier_alias_response =
+            nia_protocol_rust::RenameModifierAliasResponse::new();
+
+        match &self.result {
+            Ok(()) => {
+                let mut success_result =
+                    nia_protocol_rust::RenameModifierAliasResponse_SuccessResult::new();
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                rename_modifier_alias_response
+                    .set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::RenameModifierAliasResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                rename_modifier_alias_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::RenameModifierAliasResponse_FailureResult::new();
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                rename_modifier_alias_response
+                    .set_failure_result(failure_result);
+            }
+        }
+
+        rename_modifier_alias_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::RenameModifierAliasResponse,
+    ) -> NiaServerResult<NiaRenameModifierAliasResponse> {
+        unreachable!()
+    }
+}
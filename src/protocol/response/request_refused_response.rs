@@ -0,0 +1,49 @@
+use nia_protocol_rust::RequestRefusedResponse;
+
+use crate::error::NiaServerResult;
+use crate::protocol::Serializable;
+
+// Sent instead of the usual response to a request that cannot be handled
+// now, e.g. a configuration change that a transaction cannot stage.
+#[derive(Debug, Clone)]
+pub struct NiaRequestRefusedResponse {
+    message: String,
+}
+
+impl NiaRequestRefusedResponse {
+    pub fn new<S>(message: S) -> NiaRequestRefusedResponse
+    where
+        S: Into<String>,
+    {
+        NiaRequestRefusedResponse {
+            message: message.into(),
+        }
+    }
+
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl
+    Serializable<
+        NiaRequestRefusedResponse,
+        nia_protocol_rust::RequestRefusedResponse,
+    > for NiaRequestRefusedResponse
+{
+    fn to_pb(&self) -> RequestRefusedResponse {
+        let mut request_refused_response =
+            nia_protocol_rust::RequestRefusedResponse::new();
+
+        request_refused_response
+            .set_message(protobuf::Chars::from(self.message.clone()));
+
+        request_refused_response
+    }
+
+    fn from_pb(
+        object_pb: RequestRefusedResponse,
+    ) -> NiaServerResult<NiaRequestRefusedResponse> {
+        unreachable!()
+    }
+}
@@ -0,0 +1,45 @@
+use nia_protocol_rust::RequestStagedResponse;
+
+use crate::error::NiaServerResult;
+use crate::protocol::Serializable;
+
+// Sent instead of the usual response to a configuration changing request
+// that was staged by a transaction.
+#[derive(Debug, Clone)]
+pub struct NiaRequestStagedResponse {
+    staged_count: usize,
+}
+
+impl NiaRequestStagedResponse {
+    pub fn new(staged_count: usize) -> NiaRequestStagedResponse {
+        NiaRequestStagedResponse { staged_count }
+    }
+
+    pub fn get_staged_count(&self) -> usize {
+        self.staged_count
+    }
+}
+
+impl
+    Serializable<
+        NiaRequestStagedResponse,
+        nia_protocol_rust::RequestStagedResponse,
+    > for NiaRequestStagedResponse
+{
+    fn to_pb(&self) -> RequestStagedResponse {
+        let mut request_staged_response =
+            nia_protocol_rust::RequestStagedResponse::new();
+
+        request_staged_response
+            .set_message(protobuf::Chars::from(String::from("Staged.")));
+        request_staged_response.set_staged_count(self.staged_count as u32);
+
+        request_staged_response
+    }
+
+    fn from_pb(
+        object_pb: RequestStagedResponse,
+    ) -> NiaServerResult<NiaRequestStagedResponse> {
+        unreachable!()
+    }
+}
@@ -0,0 +1,100 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::canonicalize_key_chords;
+use crate::protocol::Serializable;
+use crate::protocol::{resolve_keys, NiaKeyResolution, NiaResolveKeysRequest};
+use crate::server::Server;
+use crate::utils::get_defined_modifiers;
+
+#[derive(Debug, Clone)]
+pub struct NiaResolveKeysResponse {
+    key_resolution_result: Result<NiaKeyResolution, NiaServerError>,
+}
+
+impl NiaResolveKeysResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_resolve_keys_request: NiaResolveKeysRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<NiaKeyResolution> {
+        let key_chords = nia_resolve_keys_request.take_key_chords();
+
+        if key_chords.is_empty() {
+            return NiaServerError::invalid_request("No key chords provided.")
+                .into();
+        }
+
+        let key_chords =
+            canonicalize_key_chords(server.get_modifier_groups(), &key_chords);
+        let mapping_index = server.get_mapping_index(&event_loop_handle)?;
+        let defined_modifiers = get_defined_modifiers(&event_loop_handle)?;
+
+        Ok(resolve_keys(&key_chords, mapping_index, &defined_modifiers))
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_resolve_keys_request: NiaResolveKeysRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaResolveKeysResponse {
+        let key_resolution_result = NiaResolveKeysResponse::try_from(
+            server,
+            nia_resolve_keys_request,
+            event_loop_handle,
+        );
+
+        NiaResolveKeysResponse {
+            key_resolution_result,
+        }
+    }
+}
+
+impl
+    Serializable<NiaResolveKeysResponse, nia_protocol_rust::ResolveKeysResponse>
+    for NiaResolveKeysResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::ResolveKeysResponse {
+        let mut resolve_keys_response =
+            nia_protocol_rust::ResolveKeysResponse::new();
+
+        match &self.key_resolution_result {
+            Ok(key_resolution) => {
+                let mut success_result =
+                    nia_protocol_rust::ResolveKeysResponse_SuccessResult::new();
+
+                success_result.set_key_resolution(key_resolution.to_pb());
+
+                resolve_keys_response.set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::ResolveKeysResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                resolve_keys_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::ResolveKeysResponse_FailureResult::new();
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                resolve_keys_response.set_failure_result(failure_result);
+            }
+        }
+
+        resolve_keys_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::ResolveKeysResponse,
+    ) -> NiaServerResult<NiaResolveKeysResponse> {
+        unreachable!()
+    }
+}
@@ -6,18 +6,49 @@ use nia_protocol_rust::Response;
 use crate::error::NiaServerError;
 use crate::error::NiaServerResult;
 
+use crate::protocol::NiaActivateProfileResponse;
+use crate::protocol::NiaBeginTransactionResponse;
+use crate::protocol::NiaCheckMappingsResponse;
+use crate::protocol::NiaCommitTransactionResponse;
+use crate::protocol::NiaCreateProfileResponse;
+use crate::protocol::NiaDefineModifierGroupResponse;
+use crate::protocol::NiaDefineTextMappingResponse;
+use crate::protocol::NiaDeleteProfileResponse;
+use crate::protocol::NiaDeviceModelChangedResponse;
+use crate::protocol::NiaDiffConfigResponse;
+use crate::protocol::NiaExportConfigResponse;
 use crate::protocol::NiaGetDefinedActionsResponse;
 use crate::protocol::NiaGetDefinedMappingsRequest;
 use crate::protocol::NiaGetDefinedMappingsResponse;
 use crate::protocol::NiaGetDefinedModifiersResponse;
+use crate::protocol::NiaGetDeviceModelsResponse;
 use crate::protocol::NiaGetDevicesResponse;
+use crate::protocol::NiaGetHistoryResponse;
+use crate::protocol::NiaGetReferencesResponse;
 use crate::protocol::NiaHandshakeResponse;
+use crate::protocol::NiaImportConfigResponse;
+use crate::protocol::NiaListProfilesResponse;
+use crate::protocol::NiaRebindMappingResponse;
+use crate::protocol::NiaRedoResponse;
 use crate::protocol::NiaRemoveActionResponse;
 use crate::protocol::NiaRemoveDeviceByNameResponse;
 use crate::protocol::NiaRemoveDeviceByPathResponse;
 use crate::protocol::NiaRemoveMappingResponse;
+use crate::protocol::NiaRemoveModifierGroupResponse;
 use crate::protocol::NiaRemoveModifierResponse;
+use crate::protocol::NiaRemoveTextMappingResponse;
+use crate::protocol::NiaRenameActionResponse;
+use crate::protocol::NiaRenameModifierAliasResponse;
 use crate::protocol::NiaRequest;
+use crate::protocol::NiaRequestRefusedResponse;
+use crate::protocol::NiaRequestStagedResponse;
+use crate::protocol::NiaResolveKeysResponse;
+use crate::protocol::NiaRollbackTransactionResponse;
+use crate::protocol::NiaSetMappingEnabledResponse;
+use crate::protocol::NiaSetMappingMetadataResponse;
+use crate::protocol::NiaSetMappingsEnabledByTagResponse;
+use crate::protocol::NiaSimulateKeyEventsResponse;
+use crate::protocol::NiaUndoResponse;
 use crate::protocol::Serializable;
 use crate::protocol::{NiaChangeMappingResponse, NiaDefineActionResponse};
 use crate::protocol::{NiaDefineDeviceResponse, NiaStartListeningResponse};
@@ -25,6 +56,10 @@ use crate::protocol::{NiaDefineMappingResponse, NiaStopListeningResponse};
 use crate::protocol::{NiaDefineModifierResponse, NiaIsListeningResponse};
 use crate::protocol::{NiaExecuteCodeResponse, NiaRemoveDeviceByIdResponse};
 use crate::server::Server;
+use crate::utils::{
+    get_unstageable_request_name, is_stageable_request, keeps_mapping_index,
+    NiaTransaction,
+};
 
 #[derive(Debug, Clone)]
 pub enum NiaResponse {
@@ -48,14 +83,72 @@ pub enum NiaResponse {
     IsListening(NiaIsListeningResponse),
     StartListening(NiaStartListeningResponse),
     StopListening(NiaStopListeningResponse),
+    DeviceModelChanged(NiaDeviceModelChangedResponse),
+    GetDeviceModels(NiaGetDeviceModelsResponse),
+    DefineTextMapping(NiaDefineTextMappingResponse),
+    RemoveTextMapping(NiaRemoveTextMappingResponse),
+    CheckMappings(NiaCheckMappingsResponse),
+    ResolveKeys(NiaResolveKeysResponse),
+    SimulateKeyEvents(NiaSimulateKeyEventsResponse),
+    ExportConfig(NiaExportConfigResponse),
+    ImportConfig(NiaImportConfigResponse),
+    CreateProfile(NiaCreateProfileResponse),
+    ListProfiles(NiaListProfilesResponse),
+    ActivateProfile(NiaActivateProfileResponse),
+    DeleteProfile(NiaDeleteProfileResponse),
+    SetMappingEnabled(NiaSetMappingEnabledResponse),
+    SetMappingsEnabledByTag(NiaSetMappingsEnabledByTagResponse),
+    SetMappingMetadata(NiaSetMappingMetadataResponse),
+    RebindMapping(NiaRebindMappingResponse),
+    BeginTransaction(NiaBeginTransactionResponse),
+    CommitTransaction(NiaCommitTransactionResponse),
+    RollbackTransaction(NiaRollbackTransactionResponse),
+    RequestStaged(NiaRequestStagedResponse),
+    RequestRefused(NiaRequestRefusedResponse),
+    Undo(NiaUndoResponse),
+    Redo(NiaRedoResponse),
+    GetHistory(NiaGetHistoryResponse),
+    DiffConfig(NiaDiffConfigResponse),
+    DefineModifierGroup(NiaDefineModifierGroupResponse),
+    RemoveModifierGroup(NiaRemoveModifierGroupResponse),
+    GetReferences(NiaGetReferencesResponse),
+    RenameAction(NiaRenameActionResponse),
+    RenameModifierAlias(NiaRenameModifierAliasResponse),
 }
 
 impl NiaResponse {
     pub fn from(
         server: &mut MutexGuard<Server>,
+        transaction: &mut Option<NiaTransaction>,
         nia_request: NiaRequest,
         event_loop_handle: MutexGuard<EventLoopHandle>,
     ) -> NiaResponse {
+        // Configuration changes made inside of a transaction wait for its
+        // commit.
+        if let Some(transaction) = transaction {
+            if is_stageable_request(&nia_request) {
+                transaction.stage(nia_request);
+
+                return NiaResponse::RequestStaged(
+                    NiaRequestStagedResponse::new(transaction.len()),
+                );
+            }
+
+            if let Some(request_name) =
+                get_unstageable_request_name(&nia_request)
+            {
+                return NiaResponse::RequestRefused(
+                    NiaRequestRefusedResponse::new(format!(
+                        "{} cannot be staged, commit or roll back the \
+                         transaction first.",
+                        request_name
+                    )),
+                );
+            }
+        }
+
+        let keeps_mapping_index = keeps_mapping_index(&nia_request);
+
         let nia_response = match nia_request {
             NiaRequest::Handshake(nia_handshake_request) => {
                 let nia_handshake_response =
@@ -134,6 +227,7 @@ impl NiaResponse {
             ) => {
                 let nia_get_defined_modifiers_response =
                     NiaGetDefinedModifiersResponse::from(
+                        server,
                         nia_get_defined_modifiers_request,
                         event_loop_handle,
                     );
@@ -154,6 +248,7 @@ impl NiaResponse {
             NiaRequest::RemoveModifier(nia_remove_keyboard_request) => {
                 let nia_remove_keyboard_response =
                     NiaRemoveModifierResponse::from(
+                        server,
                         nia_remove_keyboard_request,
                         event_loop_handle,
                     );
@@ -179,6 +274,7 @@ impl NiaResponse {
             }
             NiaRequest::RemoveAction(nia_remove_action_request) => {
                 let nia_remove_action_response = NiaRemoveActionResponse::from(
+                    server,
                     nia_remove_action_request,
                     event_loop_handle,
                 );
@@ -190,6 +286,7 @@ impl NiaResponse {
             ) => {
                 let nia_get_defined_mappings_response =
                     NiaGetDefinedMappingsResponse::from(
+                        server,
                         nia_get_defined_mappings_request,
                         event_loop_handle,
                     );
@@ -201,6 +298,7 @@ impl NiaResponse {
             NiaRequest::DefineMapping(nia_define_mapping_request) => {
                 let nia_define_mapping_response =
                     NiaDefineMappingResponse::from(
+                        server,
                         nia_define_mapping_request,
                         event_loop_handle,
                     );
@@ -210,6 +308,7 @@ impl NiaResponse {
             NiaRequest::ChangeMapping(nia_change_mapping_request) => {
                 let nia_change_mapping_response =
                     NiaChangeMappingResponse::from(
+                        server,
                         nia_change_mapping_request,
                         event_loop_handle,
                     );
@@ -219,6 +318,7 @@ impl NiaResponse {
             NiaRequest::RemoveMapping(nia_remove_mapping_request) => {
                 let nia_remove_mapping_response =
                     NiaRemoveMappingResponse::from(
+                        server,
                         nia_remove_mapping_request,
                         event_loop_handle,
                     );
@@ -247,8 +347,301 @@ impl NiaResponse {
                     );
                 NiaResponse::StopListening(nia_stop_listening_response)
             }
+            NiaRequest::GetDeviceModels(nia_get_device_models_request) => {
+                let nia_get_device_models_response =
+                    NiaGetDeviceModelsResponse::from(
+                        nia_get_device_models_request,
+                    );
+
+                NiaResponse::GetDeviceModels(nia_get_device_models_response)
+            }
+            NiaRequest::DefineTextMapping(nia_define_text_mapping_request) => {
+                let nia_define_text_mapping_response =
+                    NiaDefineTextMappingResponse::from(
+                        server,
+                        nia_define_text_mapping_request,
+                        event_loop_handle,
+                    );
+
+                NiaResponse::DefineTextMapping(nia_define_text_mapping_response)
+            }
+            NiaRequest::RemoveTextMapping(nia_remove_text_mapping_request) => {
+                let nia_remove_text_mapping_response =
+                    NiaRemoveTextMappingResponse::from(
+                        server,
+                        nia_remove_text_mapping_request,
+                        event_loop_handle,
+                    );
+
+                NiaResponse::RemoveTextMapping(nia_remove_text_mapping_response)
+            }
+            NiaRequest::CheckMappings(nia_check_mappings_request) => {
+                let nia_check_mappings_response =
+                    NiaCheckMappingsResponse::from(
+                        server,
+                        nia_check_mappings_request,
+                        event_loop_handle,
+                    );
+
+                NiaResponse::CheckMappings(nia_check_mappings_response)
+            }
+            NiaRequest::ResolveKeys(nia_resolve_keys_request) => {
+                let nia_resolve_keys_response = NiaResolveKeysResponse::from(
+                    server,
+                    nia_resolve_keys_request,
+                    event_loop_handle,
+                );
+
+                NiaResponse::ResolveKeys(nia_resolve_keys_response)
+            }
+            NiaRequest::SimulateKeyEvents(nia_simulate_key_events_request) => {
+                let nia_simulate_key_events_response =
+                    NiaSimulateKeyEventsResponse::from(
+                        server,
+                        nia_simulate_key_events_request,
+                        event_loop_handle,
+                    );
+
+                NiaResponse::SimulateKeyEvents(nia_simulate_key_events_response)
+            }
+            NiaRequest::ExportConfig(nia_export_config_request) => {
+                let nia_export_config_response = NiaExportConfigResponse::from(
+                    server,
+                    nia_export_config_request,
+                    event_loop_handle,
+                );
+
+                NiaResponse::ExportConfig(nia_export_config_response)
+            }
+            NiaRequest::ImportConfig(nia_import_config_request) => {
+                let nia_import_config_response = NiaImportConfigResponse::from(
+                    server,
+                    nia_import_config_request,
+                    event_loop_handle,
+                );
+
+                NiaResponse::ImportConfig(nia_import_config_response)
+            }
+            NiaRequest::CreateProfile(nia_create_profile_request) => {
+                let nia_create_profile_response =
+                    NiaCreateProfileResponse::from(
+                        server,
+                        nia_create_profile_request,
+                        event_loop_handle,
+                    );
+
+                NiaResponse::CreateProfile(nia_create_profile_response)
+            }
+            NiaRequest::ListProfiles(nia_list_profiles_request) => {
+                let nia_list_profiles_response = NiaListProfilesResponse::from(
+                    server,
+                    nia_list_profiles_request,
+                    event_loop_handle,
+                );
+
+                NiaResponse::ListProfiles(nia_list_profiles_response)
+            }
+            NiaRequest::ActivateProfile(nia_activate_profile_request) => {
+                let nia_activate_profile_response =
+                    NiaActivateProfileResponse::from(
+                        server,
+                        nia_activate_profile_request,
+                        event_loop_handle,
+                    );
+
+                NiaResponse::ActivateProfile(nia_activate_profile_response)
+            }
+            NiaRequest::DeleteProfile(nia_delete_profile_request) => {
+                let nia_delete_profile_response =
+                    NiaDeleteProfileResponse::from(
+                        server,
+                        nia_delete_profile_request,
+                    );
+
+                NiaResponse::DeleteProfile(nia_delete_profile_response)
+            }
+            NiaRequest::SetMappingEnabled(nia_set_mapping_enabled_request) => {
+                let nia_set_mapping_enabled_response =
+                    NiaSetMappingEnabledResponse::from(
+                        server,
+                        nia_set_mapping_enabled_request,
+                        event_loop_handle,
+                    );
+
+                NiaResponse::SetMappingEnabled(nia_set_mapping_enabled_response)
+            }
+            NiaRequest::SetMappingsEnabledByTag(
+                nia_set_mappings_enabled_by_tag_request,
+            ) => {
+                let nia_set_mappings_enabled_by_tag_response =
+                    NiaSetMappingsEnabledByTagResponse::from(
+                        server,
+                        nia_set_mappings_enabled_by_tag_request,
+                        event_loop_handle,
+                    );
+
+                NiaResponse::SetMappingsEnabledByTag(
+                    nia_set_mappings_enabled_by_tag_response,
+                )
+            }
+            NiaRequest::SetMappingMetadata(
+                nia_set_mapping_metadata_request,
+            ) => {
+                let nia_set_mapping_metadata_response =
+                    NiaSetMappingMetadataResponse::from(
+                        server,
+                        nia_set_mapping_metadata_request,
+                        event_loop_handle,
+                    );
+
+                NiaResponse::SetMappingMetadata(
+                    nia_set_mapping_metadata_response,
+                )
+            }
+            NiaRequest::RebindMapping(nia_rebind_mapping_request) => {
+                let nia_rebind_mapping_response =
+                    NiaRebindMappingResponse::from(
+                        server,
+                        nia_rebind_mapping_request,
+                        event_loop_handle,
+                    );
+
+                NiaResponse::RebindMapping(nia_rebind_mapping_response)
+            }
+            NiaRequest::BeginTransaction(nia_begin_transaction_request) => {
+                let nia_begin_transaction_response =
+                    NiaBeginTransactionResponse::from(
+                        transaction,
+                        nia_begin_transaction_request,
+                    );
+
+                NiaResponse::BeginTransaction(nia_begin_transaction_response)
+            }
+            NiaRequest::CommitTransaction(nia_commit_transaction_request) => {
+                let nia_commit_transaction_response =
+                    NiaCommitTransactionResponse::from(
+                        server,
+                        transaction,
+                        nia_commit_transaction_request,
+                        event_loop_handle,
+                    );
+
+                NiaResponse::CommitTransaction(nia_commit_transaction_response)
+            }
+            NiaRequest::RollbackTransaction(
+                nia_rollback_transaction_request,
+            ) => {
+                let nia_rollback_transaction_response =
+                    NiaRollbackTransactionResponse::from(
+                        transaction,
+                        nia_rollback_transaction_request,
+                    );
+
+                NiaResponse::RollbackTransaction(
+                    nia_rollback_transaction_response,
+                )
+            }
+            NiaRequest::Undo(nia_undo_request) => {
+                let nia_undo_response = NiaUndoResponse::from(
+                    server,
+                    nia_undo_request,
+                    event_loop_handle,
+                );
+
+                NiaResponse::Undo(nia_undo_response)
+            }
+            NiaRequest::Redo(nia_redo_request) => {
+                let nia_redo_response = NiaRedoResponse::from(
+                    server,
+                    nia_redo_request,
+                    event_loop_handle,
+                );
+
+                NiaResponse::Redo(nia_redo_response)
+            }
+            NiaRequest::GetHistory(nia_get_history_request) => {
+                let nia_get_history_response = NiaGetHistoryResponse::from(
+                    server,
+                    nia_get_history_request,
+                );
+
+                NiaResponse::GetHistory(nia_get_history_response)
+            }
+            NiaRequest::DiffConfig(nia_diff_config_request) => {
+                let nia_diff_config_response = NiaDiffConfigResponse::from(
+                    server,
+                    nia_diff_config_request,
+                    event_loop_handle,
+                );
+
+                NiaResponse::DiffConfig(nia_diff_config_response)
+            }
+            NiaRequest::DefineModifierGroup(
+                nia_define_modifier_group_request,
+            ) => {
+                let nia_define_modifier_group_response =
+                    NiaDefineModifierGroupResponse::from(
+                        server,
+                        nia_define_modifier_group_request,
+                        event_loop_handle,
+                    );
+
+                NiaResponse::DefineModifierGroup(
+                    nia_define_modifier_group_response,
+                )
+            }
+            NiaRequest::RemoveModifierGroup(
+                nia_remove_modifier_group_request,
+            ) => {
+                let nia_remove_modifier_group_response =
+                    NiaRemoveModifierGroupResponse::from(
+                        server,
+                        nia_remove_modifier_group_request,
+                    );
+
+                NiaResponse::RemoveModifierGroup(
+                    nia_remove_modifier_group_response,
+                )
+            }
+            NiaRequest::GetReferences(nia_get_references_request) => {
+                let nia_get_references_response =
+                    NiaGetReferencesResponse::from(
+                        server,
+                        nia_get_references_request,
+                        event_loop_handle,
+                    );
+
+                NiaResponse::GetReferences(nia_get_references_response)
+            }
+            NiaRequest::RenameAction(nia_rename_action_request) => {
+                let nia_rename_action_response = NiaRenameActionResponse::from(
+                    server,
+                    nia_rename_action_request,
+                    event_loop_handle,
+                );
+
+                NiaResponse::RenameAction(nia_rename_action_response)
+            }
+            NiaRequest::RenameModifierAlias(
+                nia_rename_modifier_alias_request,
+            ) => {
+                let nia_rename_modifier_alias_response =
+                    NiaRenameModifierAliasResponse::from(
+                        server,
+                        nia_rename_modifier_alias_request,
+                        event_loop_handle,
+                    );
+
+                NiaResponse::RenameModifierAlias(
+                    nia_rename_modifier_alias_response,
+                )
+            }
         };
 
+        if !keeps_mapping_index {
+            server.invalidate_mapping_index();
+        }
+
         nia_response
     }
 }
@@ -380,6 +773,184 @@ impl Serializable<NiaResponse, nia_protocol_rust::Response> for NiaResponse {
 
                 response.set_stop_listening_response(stop_listening);
             }
+            NiaResponse::RequestStaged(request_staged_response) => {
+                let request_staged = request_staged_response.to_pb();
+
+                response.set_request_staged_response(request_staged);
+            }
+            NiaResponse::RequestRefused(request_refused_response) => {
+                let request_refused = request_refused_response.to_pb();
+
+                response.set_request_refused_response(request_refused);
+            }
+            NiaResponse::DeviceModelChanged(device_model_changed_response) => {
+                let device_model_changed =
+                    device_model_changed_response.to_pb();
+
+                response
+                    .set_device_model_changed_response(device_model_changed);
+            }
+            NiaResponse::GetDeviceModels(get_device_models_response) => {
+                let get_device_models = get_device_models_response.to_pb();
+
+                response.set_get_device_models_response(get_device_models);
+            }
+            NiaResponse::DefineTextMapping(define_text_mapping_response) => {
+                let define_text_mapping = define_text_mapping_response.to_pb();
+
+                response.set_define_text_mapping_response(define_text_mapping);
+            }
+            NiaResponse::RemoveTextMapping(remove_text_mapping_response) => {
+                let remove_text_mapping = remove_text_mapping_response.to_pb();
+
+                response.set_remove_text_mapping_response(remove_text_mapping);
+            }
+            NiaResponse::CheckMappings(check_mappings_response) => {
+                let check_mappings = check_mappings_response.to_pb();
+
+                response.set_check_mappings_response(check_mappings);
+            }
+            NiaResponse::ResolveKeys(resolve_keys_response) => {
+                let resolve_keys = resolve_keys_response.to_pb();
+
+                response.set_resolve_keys_response(resolve_keys);
+            }
+            NiaResponse::SimulateKeyEvents(simulate_key_events_response) => {
+                let simulate_key_events = simulate_key_events_response.to_pb();
+
+                response.set_simulate_key_events_response(simulate_key_events);
+            }
+            NiaResponse::ExportConfig(export_config_response) => {
+                let export_config = export_config_response.to_pb();
+
+                response.set_export_config_response(export_config);
+            }
+            NiaResponse::ImportConfig(import_config_response) => {
+                let import_config = import_config_response.to_pb();
+
+                response.set_import_config_response(import_config);
+            }
+            NiaResponse::CreateProfile(create_profile_response) => {
+                let create_profile = create_profile_response.to_pb();
+
+                response.set_create_profile_response(create_profile);
+            }
+            NiaResponse::ListProfiles(list_profiles_response) => {
+                let list_profiles = list_profiles_response.to_pb();
+
+                response.set_list_profiles_response(list_profiles);
+            }
+            NiaResponse::ActivateProfile(activate_profile_response) => {
+                let activate_profile = activate_profile_response.to_pb();
+
+                response.set_activate_profile_response(activate_profile);
+            }
+            NiaResponse::DeleteProfile(delete_profile_response) => {
+                let delete_profile = delete_profile_response.to_pb();
+
+                response.set_delete_profile_response(delete_profile);
+            }
+            NiaResponse::SetMappingEnabled(set_mapping_enabled_response) => {
+                let set_mapping_enabled = set_mapping_enabled_response.to_pb();
+
+                response.set_set_mapping_enabled_response(set_mapping_enabled);
+            }
+            NiaResponse::SetMappingsEnabledByTag(
+                set_mappings_enabled_by_tag_response,
+            ) => {
+                let set_mappings_enabled_by_tag =
+                    set_mappings_enabled_by_tag_response.to_pb();
+
+                response.set_set_mappings_enabled_by_tag_response(
+                    set_mappings_enabled_by_tag,
+                );
+            }
+            NiaResponse::SetMappingMetadata(set_mapping_metadata_response) => {
+                let set_mapping_metadata =
+                    set_mapping_metadata_response.to_pb();
+
+                response
+                    .set_set_mapping_metadata_response(set_mapping_metadata);
+            }
+            NiaResponse::RebindMapping(rebind_mapping_response) => {
+                let rebind_mapping = rebind_mapping_response.to_pb();
+
+                response.set_rebind_mapping_response(rebind_mapping);
+            }
+            NiaResponse::BeginTransaction(begin_transaction_response) => {
+                let begin_transaction = begin_transaction_response.to_pb();
+
+                response.set_begin_transaction_response(begin_transaction);
+            }
+            NiaResponse::CommitTransaction(commit_transaction_response) => {
+                let commit_transaction = commit_transaction_response.to_pb();
+
+                response.set_commit_transaction_response(commit_transaction);
+            }
+            NiaResponse::RollbackTransaction(rollback_transaction_response) => {
+                let rollback_transaction =
+                    rollback_transaction_response.to_pb();
+
+                response
+                    .set_rollback_transaction_response(rollback_transaction);
+            }
+            NiaResponse::Undo(undo_response) => {
+                let undo = undo_response.to_pb();
+
+                response.set_undo_response(undo);
+            }
+            NiaResponse::Redo(redo_response) => {
+                let redo = redo_response.to_pb();
+
+                response.set_redo_response(redo);
+            }
+            NiaResponse::GetHistory(get_history_response) => {
+                let get_history = get_history_response.to_pb();
+
+                response.set_get_history_response(get_history);
+            }
+            NiaResponse::DiffConfig(diff_config_response) => {
+                let diff_config = diff_config_response.to_pb();
+
+                response.set_diff_config_response(diff_config);
+            }
+            NiaResponse::DefineModifierGroup(
+                define_modifier_group_response,
+            ) => {
+                let define_modifier_group =
+                    define_modifier_group_response.to_pb();
+
+                response
+                    .set_define_modifier_group_response(define_modifier_group);
+            }
+            NiaResponse::RemoveModifierGroup(
+                remove_modifier_group_response,
+            ) => {
+                let remove_modifier_group =
+                    remove_modifier_group_response.to_pb();
+
+                response
+                    .set_remove_modifier_group_response(remove_modifier_group);
+            }
+            NiaResponse::GetReferences(get_references_response) => {
+                let get_references = get_references_response.to_pb();
+
+                response.set_get_references_response(get_references);
+            }
+            NiaResponse::RenameAction(rename_action_response) => {
+                let rename_action = rename_action_response.to_pb();
+
+                response.set_rename_action_response(rename_action);
+            }
+            NiaResponse::RenameModifierAlias(
+                rename_modifier_alias_response,
+            ) => {
+                let rename_modifier_alias =
+                    rename_modifier_alias_response.to_pb();
+
+                response
+                    .set_rename_modifier_alias_response(rename_modifier_alias);
+            }
         }
 
         response
@@ -0,0 +1,93 @@
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::NiaRollbackTransactionRequest;
+use crate::protocol::Serializable;
+use crate::utils::NiaTransaction;
+
+#[derive(Debug, Clone)]
+pub struct NiaRollbackTransactionResponse {
+    discarded_count_result: Result<usize, NiaServerError>,
+}
+
+impl NiaRollbackTransactionResponse {
+    fn try_from(
+        transaction: &mut Option<NiaTransaction>,
+        _nia_rollback_transaction_request: NiaRollbackTransactionRequest,
+    ) -> NiaServerResult<usize> {
+        match transaction.take() {
+            Some(transaction) => Ok(transaction.len()),
+            None => NiaServerError::invalid_request(
+                "There is no transaction in progress.",
+            )
+            .into(),
+        }
+    }
+
+    pub fn from(
+        transaction: &mut Option<NiaTransaction>,
+        nia_rollback_transaction_request: NiaRollbackTransactionRequest,
+    ) -> NiaRollbackTransactionResponse {
+        let discarded_count_result = NiaRollbackTransactionResponse::try_from(
+            transaction,
+            nia_rollback_transaction_request,
+        );
+
+        NiaRollbackTransactionResponse {
+            discarded_count_result,
+        }
+    }
+}
+
+impl
+    Serializable<
+        NiaRollbackTransactionResponse,
+        nia_protocol_rust::RollbackTransactionResponse,
+    > for NiaRollbackTransactionResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::RollbackTransactionResponse {
+        let mut rollback_transaction_response =
+            nia_protocol_rust::RollbackTransactionResponse::new();
+
+        match &self.discarded_count_result {
+            Ok(discarded_count) => {
+                let mut success_result =
+                    nia_protocol_rust::RollbackTransactionResponse_SuccessResult::new(
+                    );
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                success_result.set_discarded_count(*discarded_count as u32);
+                rollback_transaction_response
+                    .set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::RollbackTransactionResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                rollback_transaction_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::RollbackTransactionResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                rollback_transaction_response
+                    .set_failure_result(failure_result);
+            }
+        }
+
+        rollback_transaction_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::RollbackTransactionResponse,
+    ) -> NiaServerResult<NiaRollbackTransactionResponse> {
+        unreachable!()
+    }
+}
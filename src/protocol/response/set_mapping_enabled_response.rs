@@ -0,0 +1,95 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::NiaSetMappingEnabledRequest;
+use crate::protocol::Serializable;
+use crate::server::Server;
+use crate::utils::set_mapping_enabled;
+
+#[derive(Debug, Clone)]
+pub struct NiaSetMappingEnabledResponse {
+    result: Result<(), NiaServerError>,
+}
+
+impl NiaSetMappingEnabledResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_set_mapping_enabled_request: NiaSetMappingEnabledRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<()> {
+        let (key_chords, enabled) =
+            nia_set_mapping_enabled_request.into_tuple();
+
+        set_mapping_enabled(server, &event_loop_handle, &key_chords, enabled)?;
+
+        Ok(())
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_set_mapping_enabled_request: NiaSetMappingEnabledRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaSetMappingEnabledResponse {
+        let result = NiaSetMappingEnabledResponse::try_from(
+            server,
+            nia_set_mapping_enabled_request,
+            event_loop_handle,
+        );
+
+        NiaSetMappingEnabledResponse { result }
+    }
+}
+
+impl
+    Serializable<
+        NiaSetMappingEnabledResponse,
+        nia_protocol_rust::SetMappingEnabledResponse,
+    > for NiaSetMappingEnabledResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::SetMappingEnabledResponse {
+        let mut set_mapping_enabled_response =
+            nia_protocol_rust::SetMappingEnabledResponse::new();
+
+        match &self.result {
+            Ok(()) => {
+                let mut success_result =
+                    nia_protocol_rust::SetMappingEnabledResponse_SuccessResult::new(
+                    );
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                set_mapping_enabled_response.set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::SetMappingEnabledResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                set_mapping_enabled_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::SetMappingEnabledResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                set_mapping_enabled_response.set_failure_result(failure_result);
+            }
+        }
+
+        set_mapping_enabled_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::SetMappingEnabledResponse,
+    ) -> NiaServerResult<NiaSetMappingEnabledResponse> {
+        unreachable!()
+    }
+}
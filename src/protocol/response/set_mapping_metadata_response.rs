@@ -0,0 +1,117 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{format_key_chords, NiaSetMappingMetadataRequest};
+use crate::server::Server;
+use crate::utils::get_current_timestamp;
+
+#[derive(Debug, Clone)]
+pub struct NiaSetMappingMetadataResponse {
+    result: Result<(), NiaServerError>,
+}
+
+impl NiaSetMappingMetadataResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_set_mapping_metadata_request: NiaSetMappingMetadataRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<()> {
+        let (key_chords, description, tags) =
+            nia_set_mapping_metadata_request.into_tuple();
+
+        let is_defined =
+            server.get_disabled_mappings().get(&key_chords).is_some()
+                || server
+                    .get_mapping_index(&event_loop_handle)?
+                    .get(&key_chords)
+                    .is_some();
+
+        if !is_defined {
+            return NiaServerError::invalid_request(format!(
+                "Unknown mapping: {}.",
+                format_key_chords(&key_chords)
+            ))
+            .into();
+        }
+
+        let timestamp = get_current_timestamp();
+        let mapping_metadata =
+            server.get_mapping_metadata(&key_chords, timestamp);
+
+        mapping_metadata.set_description(description, timestamp);
+        mapping_metadata.set_tags(tags, timestamp);
+
+        Ok(())
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_set_mapping_metadata_request: NiaSetMappingMetadataRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaSetMappingMetadataResponse {
+        let result = NiaSetMappingMetadataResponse::try_from(
+            server,
+            nia_set_mapping_metadata_request,
+            event_loop_handle,
+        );
+
+        NiaSetMappingMetadataResponse { result }
+    }
+}
+
+impl
+    Serializable<
+        NiaSetMappingMetadataResponse,
+        nia_protocol_rust::SetMappingMetadataResponse,
+    > for NiaSetMappingMetadataResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::SetMappingMetadataResponse {
+        let mut set_mapping_metadata_response =
+            nia_protocol_rust::SetMappingMetadataResponse::new();
+
+        match &self.result {
+            Ok(()) => {
+                let mut success_result =
+                    nia_protocol_rust::SetMappingMetadataResponse_SuccessResult::new(
+                    );
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                set_mapping_metadata_response
+                    .set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::SetMappingMetadataResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                set_mapping_metadata_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::SetMappingMetadataResponse_FailureResult::new(
+                    );
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                set_mapping_metadata_response
+                    .set_failure_result(failure_result);
+            }
+        }
+
+        set_mapping_metadata_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::SetMappingMetadataResponse,
+    ) -> NiaServerResult<NiaSetMappingMetadataResponse> {
+        unreachable!()
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::NiaSetMappingsEnabledByTagRequest;
+use crate::protocol::Serializable;
+use crate::server::Server;
+use crate::utils::{get_tagged_key_chords, set_mapping_enabled};
+
+#[derive(Debug, Clone)]
+pub struct NiaSetMappingsEnabledByTagResponse {
+    changed_count_result: Result<i32, NiaServerError>,
+}
+
+impl NiaSetMappingsEnabledByTagResponse {
+    // Returns the number of mappings which state has changed. Metadata of
+    // mappings removed bypassing the server is skipped.
+    fn try_from(
+        server: &mut Server,
+        nia_set_mappings_enabled_by_tag_request: NiaSetMappingsEnabledByTagRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<i32> {
+        let (tag, enabled) =
+            nia_set_mappings_enabled_by_tag_request.into_tuple();
+
+        let mapping_index =
+            server.get_mapping_index(&event_loop_handle)?.clone();
+        let mut changed_count = 0;
+
+        for key_chords in get_tagged_key_chords(server, &tag) {
+            let is_defined = mapping_index.get(&key_chords).is_some()
+                || server.get_disabled_mappings().get(&key_chords).is_some();
+
+            if !is_defined {
+                continue;
+            }
+
+            if set_mapping_enabled(
+                server,
+                &event_loop_handle,
+                &key_chords,
+                enabled,
+            )? {
+                changed_count += 1;
+            }
+        }
+
+        Ok(changed_count)
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_set_mappings_enabled_by_tag_request: NiaSetMappingsEnabledByTagRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaSetMappingsEnabledByTagResponse {
+        let changed_count_result = NiaSetMappingsEnabledByTagResponse::try_from(
+            server,
+            nia_set_mappings_enabled_by_tag_request,
+            event_loop_handle,
+        );
+
+        NiaSetMappingsEnabledByTagResponse {
+            changed_count_result,
+        }
+    }
+}
+
+impl
+    Serializable<
+        NiaSetMappingsEnabledByTagResponse,
+        nia_protocol_rust::SetMappingsEnabledByTagResponse,
+    > for NiaSetMappingsEnabledByTagResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::SetMappingsEnabledByTagResponse {
+        let mut set_mappings_enabled_by_tag_response =
+            nia_protocol_rust::SetMappingsEnabledByTagResponse::new();
+
+        match &self.changed_count_result {
+            Ok(changed_count) => {
+                let mut success_result = nia_protocol_rust::SetMappingsEnabledByTagResponse_SuccessResult::new();
+
+                success_result.set_changed_count(*changed_count);
+                set_mappings_enabled_by_tag_response
+                    .set_success_result(success_result);
+            }
+            Err(error) => {
+                let mut failure_result = nia_protocol_rust::SetMappingsEnabledByTagResponse_FailureResult::new();
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                set_mappings_enabled_by_tag_response
+                    .set_failure_result(failure_result);
+            }
+        }
+
+        set_mappings_enabled_by_tag_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::SetMappingsEnabledByTagResponse,
+    ) -> NiaServerResult<NiaSetMappingsEnabledByTagResponse> {
+        unreachable!()
+    }
+}
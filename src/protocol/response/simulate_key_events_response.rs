@@ -0,0 +1,99 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{
+    simulate_key_events, NiaFiredMapping, NiaSimulateKeyEventsRequest,
+};
+use crate::server::Server;
+use crate::utils::get_defined_modifiers;
+
+#[derive(Debug, Clone)]
+pub struct NiaSimulateKeyEventsResponse {
+    fired_mappings_result: Result<Vec<NiaFiredMapping>, NiaServerError>,
+}
+
+impl NiaSimulateKeyEventsResponse {
+    fn try_from(
+        server: &mut Server,
+        nia_simulate_key_events_request: NiaSimulateKeyEventsRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<Vec<NiaFiredMapping>> {
+        let key_events = nia_simulate_key_events_request.take_key_events();
+
+        let modifier_groups = server.get_modifier_groups().clone();
+        let mapping_index = server.get_mapping_index(&event_loop_handle)?;
+        let defined_modifiers = get_defined_modifiers(&event_loop_handle)?;
+
+        Ok(simulate_key_events(
+            &key_events,
+            mapping_index,
+            &defined_modifiers,
+            &modifier_groups,
+        ))
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_simulate_key_events_request: NiaSimulateKeyEventsRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaSimulateKeyEventsResponse {
+        let fired_mappings_result = NiaSimulateKeyEventsResponse::try_from(
+            server,
+            nia_simulate_key_events_request,
+            event_loop_handle,
+        );
+
+        NiaSimulateKeyEventsResponse {
+            fired_mappings_result,
+        }
+    }
+}
+
+impl
+    Serializable<
+        NiaSimulateKeyEventsResponse,
+        nia_protocol_rust::SimulateKeyEventsResponse,
+    > for NiaSimulateKeyEventsResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::SimulateKeyEventsResponse {
+        let mut simulate_key_events_response =
+            nia_protocol_rust::SimulateKeyEventsResponse::new();
+
+        match &self.fired_mappings_result {
+            Ok(fired_mappings) => {
+                let fired_mappings = fired_mappings
+                    .iter()
+                    .map(|fired_mapping| fired_mapping.to_pb())
+                    .collect();
+
+                let mut success_result =
+                    nia_protocol_rust::SimulateKeyEventsResponse_SuccessResult::new();
+
+                success_result.set_fired_mappings(fired_mappings);
+
+                simulate_key_events_response.set_success_result(success_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::SimulateKeyEventsResponse_FailureResult::new();
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                simulate_key_events_response.set_failure_result(failure_result);
+            }
+        }
+
+        simulate_key_events_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::SimulateKeyEventsResponse,
+    ) -> NiaServerResult<NiaSimulateKeyEventsResponse> {
+        unreachable!()
+    }
+}
@@ -0,0 +1,87 @@
+use std::sync::MutexGuard;
+
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::Serializable;
+use crate::protocol::{NiaHistoryEntry, NiaUndoRequest};
+use crate::server::Server;
+use crate::utils::undo;
+
+#[derive(Debug, Clone)]
+pub struct NiaUndoResponse {
+    history_entry_result: Result<NiaHistoryEntry, NiaServerError>,
+}
+
+impl NiaUndoResponse {
+    fn try_from(
+        server: &mut Server,
+        _nia_undo_request: NiaUndoRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaServerResult<NiaHistoryEntry> {
+        undo(server, &event_loop_handle)
+    }
+
+    pub fn from(
+        server: &mut Server,
+        nia_undo_request: NiaUndoRequest,
+        event_loop_handle: MutexGuard<EventLoopHandle>,
+    ) -> NiaUndoResponse {
+        let history_entry_result = NiaUndoResponse::try_from(
+            server,
+            nia_undo_request,
+            event_loop_handle,
+        );
+
+        NiaUndoResponse {
+            history_entry_result,
+        }
+    }
+}
+
+impl Serializable<NiaUndoResponse, nia_protocol_rust::UndoResponse>
+    for NiaUndoResponse
+{
+    fn to_pb(&self) -> nia_protocol_rust::UndoResponse {
+        let mut undo_response = nia_protocol_rust::UndoResponse::new();
+
+        match &self.history_entry_result {
+            Ok(history_entry) => {
+                let mut success_result =
+                    nia_protocol_rust::UndoResponse_SuccessResult::new();
+
+                success_result.set_message(protobuf::Chars::from(
+                    String::from("Success."),
+                ));
+                success_result.set_history_entry(history_entry.to_pb());
+                undo_response.set_success_result(success_result);
+            }
+            Err(NiaServerError::InvalidRequestError(message)) => {
+                let mut error_result =
+                    nia_protocol_rust::UndoResponse_ErrorResult::new();
+
+                error_result
+                    .set_message(protobuf::Chars::from(message.clone()));
+                undo_response.set_error_result(error_result);
+            }
+            Err(error) => {
+                let mut failure_result =
+                    nia_protocol_rust::UndoResponse_FailureResult::new();
+
+                failure_result.set_message(protobuf::Chars::from(format!(
+                    "Execution failure: {}",
+                    error.get_message()
+                )));
+                undo_response.set_failure_result(failure_result);
+            }
+        }
+
+        undo_response
+    }
+
+    fn from_pb(
+        object_pb: nia_protocol_rust::UndoResponse,
+    ) -> NiaServerResult<NiaUndoResponse> {
+        unreachable!()
+    }
+}
@@ -1,23 +1,48 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::convert::TryFrom;
 
+use std::path::Path;
 use std::sync::Mutex;
 use std::sync::{mpsc, Arc};
 use std::thread;
+use std::time::Duration;
 
 use protobuf::Message;
-use ws::listen;
+use ws::WebSocket;
 
 use nia_interpreter_core::EventLoop;
+use nia_interpreter_core::EventLoopHandle;
 use nia_interpreter_core::Interpreter;
 use nia_interpreter_core::NiaInterpreterCommand;
 use nia_interpreter_core::NiaInterpreterCommandResult;
 
 use crate::error::{from_interpreter_error, NiaServerError, NiaServerResult};
 
-use crate::protocol::{DeviceInfo, NiaRequest, NiaResponse, Serializable};
+use crate::protocol::{
+    DeviceInfo, NiaHistory, NiaKeyChord, NiaMapping, NiaMappingIndex,
+    NiaMappingMetadata, NiaModifierGroup, NiaProfile, NiaRequest,
+    NiaResponse, Serializable, DEFAULT_HISTORY_CAPACITY,
+};
+use crate::protocol::{
+    check_profile_name, NiaActivateProfileRequest,
+    NiaDeviceModelChangedResponse, PROFILE_REQUEST_CHANNEL,
+};
+use crate::utils::{DeviceModelWatcher, NiaHistoryRecorder, NiaTransaction};
+
+const DEVICE_MODEL_WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+pub const SERVER_ADDRESS: &'static str = "127.0.0.1:12112";
 
 pub struct Server {
     devices_info: Vec<DeviceInfo>,
+    profiles: Vec<NiaProfile>,
+    active_profile_name: Option<String>,
+    mappings_metadata: HashMap<Vec<NiaKeyChord>, NiaMappingMetadata>,
+    disabled_mappings: NiaMappingIndex,
+    mapping_index: Option<NiaMappingIndex>,
+    modifier_groups: Vec<NiaModifierGroup>,
+    history: NiaHistory,
 }
 
 impl Server {
@@ -27,9 +52,28 @@ impl Server {
         let devices_info =
             crate::utils::get_devices_info(&available_device_paths)?;
 
-        let server = Server { devices_info };
+        for device_model_info in crate::utils::get_device_model_infos() {
+            println!(
+                "Keyboard model {:?}: {:?}",
+                device_model_info.get_model_name(),
+                device_model_info.get_source()
+            );
+        }
 
-        Ok(server)
+        Ok(Server::with_devices_info(devices_info))
+    }
+
+    pub fn with_devices_info(devices_info: Vec<DeviceInfo>) -> Server {
+        Server {
+            devices_info,
+            profiles: Vec::new(),
+            active_profile_name: None,
+            mappings_metadata: HashMap::new(),
+            disabled_mappings: NiaMappingIndex::new(),
+            mapping_index: None,
+            modifier_groups: Vec::new(),
+            history: NiaHistory::new(DEFAULT_HISTORY_CAPACITY),
+        }
     }
 
     pub fn get_devices(&self) -> &Vec<DeviceInfo> {
@@ -46,6 +90,26 @@ impl Server {
         None
     }
 
+    // Looks a device up by its exact name first, then by a case-insensitive
+    // part of the name, so that "[logitech]C-a" is enough in key notation.
+    pub fn get_device_id_by_name(&self, name: &str) -> Option<i32> {
+        for device_info in &self.devices_info {
+            if device_info.get_device_name() == name {
+                return Some(device_info.get_device_id());
+            }
+        }
+
+        let name = name.to_lowercase();
+
+        for device_info in &self.devices_info {
+            if device_info.get_device_name().to_lowercase().contains(&name) {
+                return Some(device_info.get_device_id());
+            }
+        }
+
+        None
+    }
+
     pub fn define_device_by_id(&mut self, device_id: i32) {
         for device in &mut self.devices_info {
             if device.get_device_id() == device_id {
@@ -77,6 +141,314 @@ impl Server {
             }
         }
     }
+
+    pub fn get_profiles(&self) -> &Vec<NiaProfile> {
+        &self.profiles
+    }
+
+    pub fn get_profile(&self, profile_name: &str) -> Option<&NiaProfile> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.get_profile_name() == profile_name)
+    }
+
+    // Adds the profile or replaces the one with the same name.
+    pub fn set_profile(&mut self, profile: NiaProfile) {
+        let profile_name = profile.get_profile_name().to_string();
+
+        match self
+            .profiles
+            .iter_mut()
+            .find(|existing| existing.get_profile_name() == profile_name)
+        {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+
+    pub fn remove_profile(&mut self, profile_name: &str) -> Option<NiaProfile> {
+        let index = self
+            .profiles
+            .iter()
+            .position(|profile| profile.get_profile_name() == profile_name)?;
+
+        if self.active_profile_name.as_deref() == Some(profile_name) {
+            self.active_profile_name = None;
+        }
+
+        Some(self.profiles.remove(index))
+    }
+
+    pub fn get_active_profile_name(&self) -> Option<&str> {
+        self.active_profile_name.as_deref()
+    }
+
+    pub fn set_active_profile_name(&mut self, profile_name: Option<String>) {
+        self.active_profile_name = profile_name;
+    }
+
+    pub fn get_mappings_metadata(
+        &self,
+    ) -> &HashMap<Vec<NiaKeyChord>, NiaMappingMetadata> {
+        &self.mappings_metadata
+    }
+
+    // Mappings may be defined bypassing the server, e.g. by executed code,
+    // their metadata is made when it is asked for the first time.
+    pub fn get_mapping_metadata(
+        &mut self,
+        key_chords: &[NiaKeyChord],
+        timestamp: u64,
+    ) -> &mut NiaMappingMetadata {
+        self.mappings_metadata
+            .entry(key_chords.to_vec())
+            .or_insert_with(|| NiaMappingMetadata::new(timestamp))
+    }
+
+    // Defined mappings are read from the interpreter once and then kept up
+    // to date by the mark_mapping_* methods. Requests that may change them
+    // some other way invalidate the index, so that it is read again.
+    pub fn get_mapping_index(
+        &mut self,
+        event_loop_handle: &EventLoopHandle,
+    ) -> NiaServerResult<&NiaMappingIndex> {
+        let mapping_index = match self.mapping_index.take() {
+            Some(mapping_index) => mapping_index,
+            None => crate::utils::get_mapping_index(
+                event_loop_handle,
+                &self.modifier_groups,
+            )?,
+        };
+
+        Ok(self.mapping_index.get_or_insert(mapping_index))
+    }
+
+    pub fn invalidate_mapping_index(&mut self) {
+        self.mapping_index = None;
+    }
+
+    pub fn mark_mapping_defined(
+        &mut self,
+        mapping: &NiaMapping,
+        timestamp: u64,
+    ) {
+        let key_chords = mapping.get_key_chords();
+
+        if let Some(mapping_index) = &mut self.mapping_index {
+            mapping_index.insert(mapping.clone());
+        }
+
+        self.disabled_mappings.remove(key_chords);
+        self.mappings_metadata
+            .insert(key_chords.clone(), NiaMappingMetadata::new(timestamp));
+    }
+
+    pub fn mark_mapping_changed(
+        &mut self,
+        mapping: &NiaMapping,
+        timestamp: u64,
+    ) {
+        let key_chords = mapping.get_key_chords();
+
+        if let Some(mapping_index) = &mut self.mapping_index {
+            if mapping_index.get(key_chords).is_some() {
+                mapping_index.insert(mapping.clone());
+            }
+        }
+
+        self.get_mapping_metadata(key_chords, timestamp)
+            .touch(timestamp);
+    }
+
+    // The metadata follows the mapping to its new key chords.
+    pub fn mark_mapping_rebound(
+        &mut self,
+        old_key_chords: &[NiaKeyChord],
+        new_key_chords: &[NiaKeyChord],
+        timestamp: u64,
+    ) {
+        if let Some(mapping_index) = &mut self.mapping_index {
+            if let Some(mapping) = mapping_index.remove(old_key_chords) {
+                mapping_index.insert(NiaMapping::new(
+                    new_key_chords.to_vec(),
+                    mapping.get_action().clone(),
+                ));
+            }
+        }
+
+        let mut mapping_metadata = self
+            .mappings_metadata
+            .remove(old_key_chords)
+            .unwrap_or_else(|| NiaMappingMetadata::new(timestamp));
+
+        mapping_metadata.touch(timestamp);
+
+        self.mappings_metadata
+            .insert(new_key_chords.to_vec(), mapping_metadata);
+    }
+
+    pub fn mark_mapping_removed(&mut self, key_chords: &[NiaKeyChord]) {
+        if let Some(mapping_index) = &mut self.mapping_index {
+            mapping_index.remove(key_chords);
+        }
+
+        self.disabled_mappings.remove(key_chords);
+        self.mappings_metadata.remove(key_chords);
+    }
+
+    // Disabled mappings are not defined in the interpreter, the server keeps
+    // them until they are enabled again.
+    pub fn get_disabled_mappings(&self) -> &NiaMappingIndex {
+        &self.disabled_mappings
+    }
+
+    pub fn disable_mapping(&mut self, mapping: NiaMapping, timestamp: u64) {
+        if let Some(mapping_index) = &mut self.mapping_index {
+            mapping_index.remove(mapping.get_key_chords());
+        }
+
+        self.get_mapping_metadata(mapping.get_key_chords(), timestamp)
+            .set_enabled(false, timestamp);
+        self.disabled_mappings.insert(mapping);
+    }
+
+    pub fn enable_mapping(
+        &mut self,
+        key_chords: &[NiaKeyChord],
+        timestamp: u64,
+    ) -> Option<NiaMapping> {
+        let mapping = self.disabled_mappings.remove(key_chords)?;
+
+        if let Some(mapping_index) = &mut self.mapping_index {
+            mapping_index.insert(mapping.clone());
+        }
+
+        self.get_mapping_metadata(key_chords, timestamp)
+            .set_enabled(true, timestamp);
+
+        Some(mapping)
+    }
+
+    pub fn change_disabled_mapping(
+        &mut self,
+        mapping: NiaMapping,
+        timestamp: u64,
+    ) {
+        self.mark_mapping_changed(&mapping, timestamp);
+        self.disabled_mappings.insert(mapping);
+    }
+
+    pub fn rebind_disabled_mapping(
+        &mut self,
+        old_key_chords: &[NiaKeyChord],
+        new_key_chords: &[NiaKeyChord],
+        timestamp: u64,
+    ) -> Option<NiaMapping> {
+        let mapping = self.disabled_mappings.remove(old_key_chords)?;
+
+        self.disabled_mappings.insert(NiaMapping::new(
+            new_key_chords.to_vec(),
+            mapping.get_action().clone(),
+        ));
+        self.mark_mapping_rebound(old_key_chords, new_key_chords, timestamp);
+
+        Some(mapping)
+    }
+
+    // Disabled mappings and metadata are replaced along with definitions,
+    // e.g. when a profile is activated.
+    pub fn replace_mapping_state(
+        &mut self,
+        disabled_mappings: Vec<NiaMapping>,
+        mappings_metadata: HashMap<Vec<NiaKeyChord>, NiaMappingMetadata>,
+    ) {
+        if let Some(mapping_index) = &mut self.mapping_index {
+            for mapping in &disabled_mappings {
+                mapping_index.remove(mapping.get_key_chords());
+            }
+        }
+
+        self.disabled_mappings =
+            NiaMappingIndex::from_mappings(disabled_mappings);
+        self.mappings_metadata = mappings_metadata;
+    }
+
+    pub fn get_modifier_groups(&self) -> &Vec<NiaModifierGroup> {
+        &self.modifier_groups
+    }
+
+    pub fn get_modifier_group(&self, alias: &str) -> Option<&NiaModifierGroup> {
+        self.modifier_groups
+            .iter()
+            .find(|modifier_group| modifier_group.get_alias() == alias)
+    }
+
+    // Mappings are reported differently with other groups, so the index is
+    // read again.
+    pub fn set_modifier_group(&mut self, modifier_group: NiaModifierGroup) {
+        self.remove_modifier_group(modifier_group.get_alias());
+        self.modifier_groups.push(modifier_group);
+        self.mapping_index = None;
+    }
+
+    pub fn remove_modifier_group(
+        &mut self,
+        alias: &str,
+    ) -> Option<NiaModifierGroup> {
+        let position = self
+            .modifier_groups
+            .iter()
+            .position(|modifier_group| modifier_group.get_alias() == alias)?;
+
+        self.mapping_index = None;
+
+        Some(self.modifier_groups.remove(position))
+    }
+
+    pub fn get_history(&self) -> &NiaHistory {
+        &self.history
+    }
+
+    pub fn get_history_mut(&mut self) -> &mut NiaHistory {
+        &mut self.history
+    }
+
+    // Resolves the models of all devices again after the keyboard model file
+    // at `path` was changed. A device is updated only when the model it
+    // resolves to, with user models over builtin ones, has changed. Returns
+    // ids of the updated devices.
+    pub fn reload_device_model(&mut self, path: &Path) -> Vec<i32> {
+        if path.exists() {
+            let read_result =
+                crate::utils::read_device_model_from_path(path.to_path_buf());
+
+            if let Err(error) = read_result {
+                println!(
+                    "Cannot reload keyboard model {:?}: {}",
+                    path,
+                    error.get_message()
+                );
+                return Vec::new();
+            }
+        }
+
+        let device_model_infos = crate::utils::get_device_model_infos();
+        let mut changed_device_ids = Vec::new();
+
+        for device in &mut self.devices_info {
+            let device_model = crate::utils::find_device_model(
+                &device_model_infos,
+                device.get_device_name(),
+            );
+
+            if device.get_device_model() != &device_model {
+                device.set_device_model(device_model);
+                changed_device_ids.push(device.get_device_id());
+            }
+        }
+
+        changed_device_ids
+    }
 }
 
 impl Server {
@@ -134,24 +506,37 @@ impl Server {
         )
         .unwrap();
 
+        let profile_request_receiver =
+            nia_interpreter_core::library::open_channel(
+                &mut interpreter,
+                PROFILE_REQUEST_CHANNEL,
+            );
+
         let event_loop_handle = EventLoop::run_event_loop(interpreter);
+
         let event_loop_handle = Arc::new(Mutex::new(event_loop_handle));
         let server_handle = Arc::new(Mutex::new(server));
+        let watcher_server_handle = server_handle.clone();
+        let profile_server_handle = server_handle.clone();
+        let profile_event_loop_handle = event_loop_handle.clone();
 
-        listen("127.0.0.1:12112", |out| {
+        let websocket = WebSocket::new(move |out: ws::Sender| {
             let event_loop_handle = event_loop_handle.clone();
             let server_handle = server_handle.clone();
 
+            // A transaction belongs to the connection that began it and is
+            // dropped with it.
+            let transaction: RefCell<Option<NiaTransaction>> =
+                RefCell::new(None);
+
             println!("Client connected");
 
-            move |msg| {
+            move |msg: ws::Message| {
                 match msg {
                     ws::Message::Binary(bytes) => {
                         let mut request = nia_protocol_rust::Request::new();
                         request.merge_from_bytes(&bytes);
 
-                        println!("Got request: {:?}", request);
-
                         let nia_request = match NiaRequest::from_pb(request) {
                             Ok(nia_request) => nia_request,
                             Err(error) => {
@@ -161,15 +546,31 @@ impl Server {
                             }
                         };
 
-                        let event_loop_handle =
-                            event_loop_handle.lock().unwrap();
+                        println!("Got request: {}", nia_request.describe());
+
                         let mut server_handle = server_handle.lock().unwrap();
 
+                        let history_recorder = NiaHistoryRecorder::start(
+                            &mut server_handle,
+                            &event_loop_handle.lock().unwrap(),
+                            &nia_request,
+                            &transaction.borrow(),
+                        );
+
                         let nia_response = NiaResponse::from(
                             &mut server_handle,
+                            &mut transaction.borrow_mut(),
                             nia_request,
-                            event_loop_handle,
+                            event_loop_handle.lock().unwrap(),
                         );
+
+                        if let Some(history_recorder) = history_recorder {
+                            history_recorder.finish(
+                                &mut server_handle,
+                                &event_loop_handle.lock().unwrap(),
+                            );
+                        }
+
                         let response = nia_response.to_pb();
 
                         println!("Sent response: {:?}", response);
@@ -186,5 +587,99 @@ impl Server {
             }
         })
         .expect("Server failure: ws.");
+
+        let broadcaster = websocket.broadcaster();
+
+        thread::spawn(move || {
+            let mut device_model_watcher = DeviceModelWatcher::new(
+                crate::utils::get_user_keyboard_models_directory(),
+            );
+
+            loop {
+                thread::sleep(DEVICE_MODEL_WATCH_INTERVAL);
+
+                let changed_paths = device_model_watcher.poll();
+
+                if changed_paths.is_empty() {
+                    continue;
+                }
+
+                let mut server = watcher_server_handle.lock().unwrap();
+
+                for path in changed_paths {
+                    println!("Keyboard model changed: {:?}", path);
+
+                    for device_id in server.reload_device_model(&path) {
+                        let device_model =
+                            match server.get_device_info_by_id(device_id) {
+                                Some(device_info) => {
+                                    device_info.get_device_model().clone()
+                                }
+                                None => continue,
+                            };
+
+                        let nia_response = NiaResponse::DeviceModelChanged(
+                            NiaDeviceModelChangedResponse::new(
+                                device_id,
+                                device_model,
+                            ),
+                        );
+                        let response = nia_response.to_pb();
+
+                        println!("Sent notification: {:?}", response);
+
+                        server.send_response(&broadcaster, response);
+                    }
+                }
+            }
+        });
+
+        // Profile switching actions send the name of the profile through the
+        // interpreter channel, the request is carried out here like a request
+        // of a client.
+        thread::spawn(move || {
+            for profile_name in profile_request_receiver.iter() {
+                if let Err(error) = check_profile_name(&profile_name) {
+                    println!("Ignoring requested profile: {:?}", error);
+                    continue;
+                }
+
+                let mut server_handle = profile_server_handle.lock().unwrap();
+
+                let nia_request = NiaRequest::ActivateProfile(
+                    NiaActivateProfileRequest::new(profile_name),
+                );
+
+                let history_recorder = NiaHistoryRecorder::start(
+                    &mut server_handle,
+                    &profile_event_loop_handle.lock().unwrap(),
+                    &nia_request,
+                    &None,
+                );
+
+                let nia_response = NiaResponse::from(
+                    &mut server_handle,
+                    &mut None,
+                    nia_request,
+                    profile_event_loop_handle.lock().unwrap(),
+                );
+
+                if let Some(history_recorder) = history_recorder {
+                    history_recorder.finish(
+                        &mut server_handle,
+                        &profile_event_loop_handle.lock().unwrap(),
+                    );
+                }
+
+                println!(
+                    "Activated requested profile: {:?}",
+                    nia_response.to_pb()
+                );
+            }
+        });
+
+        websocket
+            .listen(SERVER_ADDRESS)
+            .expect("Server failure: ws.");
     }
 }
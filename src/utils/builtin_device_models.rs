@@ -0,0 +1 @@
+include!(concat!(env!("OUT_DIR"), "/builtin_device_models.rs"));
@@ -0,0 +1,86 @@
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::NiaServerResult;
+use crate::protocol::{NiaConfig, NiaConfigDevice, NiaMapping};
+use crate::server::Server;
+use crate::utils::{
+    get_defined_actions, get_defined_mappings, get_defined_modifiers,
+};
+
+// Makes a configuration of what is defined in the interpreter now, along
+// with disabled mappings and metadata the server keeps. Devices are the
+// defined ones and the ones keys refer to.
+pub fn make_config_of_definitions(
+    server: &Server,
+    event_loop_handle: &EventLoopHandle,
+) -> NiaServerResult<NiaConfig> {
+    let modifiers = get_defined_modifiers(event_loop_handle)?;
+    let named_actions = get_defined_actions(event_loop_handle)?;
+    let mappings =
+        get_defined_mappings(event_loop_handle, server.get_modifier_groups())?;
+    let disabled_mappings = server
+        .get_disabled_mappings()
+        .get_mappings()
+        .into_iter()
+        .cloned()
+        .collect::<Vec<NiaMapping>>();
+
+    let mut referenced_device_ids =
+        modifiers
+            .iter()
+            .map(|modifier| modifier.get_key())
+            .chain(mappings.iter().chain(&disabled_mappings).flat_map(
+                |mapping| {
+                    mapping.get_key_chords().iter().flat_map(|key_chord| {
+                        key_chord
+                            .get_modifiers()
+                            .iter()
+                            .cloned()
+                            .chain(std::iter::once(key_chord.get_key()))
+                    })
+                },
+            ))
+            .filter_map(|key| key.get_device_id())
+            .collect::<Vec<i32>>();
+
+    referenced_device_ids.sort();
+    referenced_device_ids.dedup();
+
+    let devices = server
+        .get_devices()
+        .iter()
+        .filter(|device_info| {
+            device_info.is_defined()
+                || referenced_device_ids.contains(&device_info.get_device_id())
+        })
+        .map(|device_info| {
+            NiaConfigDevice::new(
+                device_info.get_device_id(),
+                device_info.get_device_name(),
+                device_info.get_device_path(),
+            )
+        })
+        .collect::<Vec<NiaConfigDevice>>();
+
+    let mappings_metadata = server
+        .get_mappings_metadata()
+        .iter()
+        .filter(|(key_chords, _)| {
+            mappings
+                .iter()
+                .chain(&disabled_mappings)
+                .any(|mapping| mapping.get_key_chords() == *key_chords)
+        })
+        .map(|(key_chords, mapping_metadata)| {
+            (key_chords.clone(), mapping_metadata.clone())
+        })
+        .collect();
+
+    let mut config =
+        NiaConfig::new(devices, modifiers, named_actions, mappings);
+
+    config.set_disabled_mappings(disabled_mappings);
+    config.set_mappings_metadata(mappings_metadata);
+
+    Ok(config)
+}
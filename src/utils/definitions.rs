@@ -0,0 +1,342 @@
+use nia_interpreter_core::EventLoopHandle;
+use nia_interpreter_core::NiaInterpreterCommand;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{
+    NiaConfigSnapshot, NiaConvertable, NiaDefinitionChange, NiaMapping,
+    NiaModifierDescription, NiaNamedAction,
+};
+use crate::server::Server;
+use crate::utils::{
+    check_modifier_ungrouped, execute_interpreter_commands,
+    get_current_timestamp, make_change_mapping_commands, make_config_snapshot,
+    make_define_mapping_commands, make_remove_mapping_commands,
+};
+
+// Interpreter commands that make the change. Mappings are addressed through
+// modifier groups, the same way define_mapping, change_mapping and
+// remove_mapping do it.
+fn make_definition_change_commands(
+    server: &Server,
+    event_loop_handle: &EventLoopHandle,
+    definition_change: &NiaDefinitionChange,
+) -> NiaServerResult<Vec<NiaInterpreterCommand>> {
+    let modifier_groups = server.get_modifier_groups();
+
+    let interpreter_command = match definition_change {
+        NiaDefinitionChange::DefineDevice(device_id) => {
+            let device_info = match server.get_device_info_by_id(*device_id) {
+                Some(device_info) => device_info,
+                None => {
+                    return NiaServerError::invalid_request(format!(
+                        "There is no device with id: {}.",
+                        device_id
+                    ))
+                    .into()
+                }
+            };
+
+            NiaInterpreterCommand::make_define_device_command(
+                *device_id,
+                device_info.get_device_path().to_string(),
+                device_info.get_device_name().to_string(),
+            )
+        }
+        NiaDefinitionChange::RemoveDevice(device_id) => {
+            NiaInterpreterCommand::make_remove_device_by_id_command(*device_id)
+        }
+        NiaDefinitionChange::DefineModifier(modifier) => {
+            NiaInterpreterCommand::make_define_modifier_command(
+                modifier.to_interpreter_repr(),
+            )
+        }
+        NiaDefinitionChange::RemoveModifier(modifier) => {
+            NiaInterpreterCommand::make_remove_modifier_command(
+                modifier.get_key().to_interpreter_repr(),
+            )
+        }
+        NiaDefinitionChange::DefineAction(named_action) => {
+            named_action.get_action().check()?;
+
+            NiaInterpreterCommand::make_define_action_command(
+                named_action.to_interpreter_repr(),
+            )
+        }
+        NiaDefinitionChange::RemoveAction(named_action) => {
+            NiaInterpreterCommand::make_remove_action_command(
+                named_action.get_action_name().to_string(),
+            )
+        }
+        NiaDefinitionChange::DefineMapping(mapping) => {
+            return make_define_mapping_commands(modifier_groups, mapping);
+        }
+        NiaDefinitionChange::RemoveMapping(mapping) => {
+            return make_remove_mapping_commands(
+                event_loop_handle,
+                modifier_groups,
+                mapping.get_key_chords(),
+            );
+        }
+        NiaDefinitionChange::ChangeMapping { new_mapping, .. } => {
+            return make_change_mapping_commands(
+                event_loop_handle,
+                modifier_groups,
+                new_mapping.get_key_chords(),
+                new_mapping.get_action(),
+            );
+        }
+    };
+
+    Ok(vec![interpreter_command])
+}
+
+// Updates what the server keeps about definitions once the change is made.
+fn mark_definition_change(
+    server: &mut Server,
+    definition_change: &NiaDefinitionChange,
+    timestamp: u64,
+) {
+    match definition_change {
+        NiaDefinitionChange::DefineDevice(device_id) => {
+            server.define_device_by_id(*device_id);
+        }
+        NiaDefinitionChange::RemoveDevice(device_id) => {
+            server.undefine_device_by_id(*device_id);
+        }
+        NiaDefinitionChange::DefineMapping(mapping) => {
+            server.mark_mapping_defined(mapping, timestamp);
+        }
+        NiaDefinitionChange::RemoveMapping(mapping) => {
+            server.mark_mapping_removed(mapping.get_key_chords());
+        }
+        NiaDefinitionChange::ChangeMapping { new_mapping, .. } => {
+            server.mark_mapping_changed(new_mapping, timestamp);
+        }
+        NiaDefinitionChange::DefineModifier(_)
+        | NiaDefinitionChange::RemoveModifier(_)
+        | NiaDefinitionChange::DefineAction(_)
+        | NiaDefinitionChange::RemoveAction(_) => {}
+    }
+}
+
+// Applies the changes as one interpreter command, see
+// execute_interpreter_commands: no key event is handled while only a part of
+// them is made, and none of them is made when one fails. The server is
+// updated only after the interpreter succeeded.
+pub fn apply_definition_changes(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+    definition_changes: &[NiaDefinitionChange],
+) -> NiaServerResult<()> {
+    let mut interpreter_commands = Vec::new();
+
+    for definition_change in definition_changes {
+        interpreter_commands.extend(make_definition_change_commands(
+            server,
+            event_loop_handle,
+            definition_change,
+        )?);
+    }
+
+    execute_interpreter_commands(event_loop_handle, interpreter_commands)?;
+
+    let timestamp = get_current_timestamp();
+
+    for definition_change in definition_changes {
+        mark_definition_change(server, definition_change, timestamp);
+    }
+
+    Ok(())
+}
+
+// Replaces every defined modifier, action and mapping, changing only what
+// differs. Devices stay as they are.
+pub fn replace_definitions(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+    modifiers: &[NiaModifierDescription],
+    named_actions: &[NiaNamedAction],
+    mappings: &[NiaMapping],
+) -> NiaServerResult<()> {
+    let config_snapshot = make_config_snapshot(server, event_loop_handle)?;
+    let device_ids = config_snapshot.get_device_ids().clone();
+
+    replace_config_snapshot(
+        server,
+        event_loop_handle,
+        config_snapshot,
+        NiaConfigSnapshot::new(
+            device_ids,
+            modifiers.to_vec(),
+            named_actions.to_vec(),
+            mappings.to_vec(),
+        ),
+    )
+}
+
+// Same as replace_definitions, but the defined devices are replaced too.
+pub fn replace_definitions_and_devices(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+    device_ids: &[i32],
+    modifiers: &[NiaModifierDescription],
+    named_actions: &[NiaNamedAction],
+    mappings: &[NiaMapping],
+) -> NiaServerResult<()> {
+    let config_snapshot = make_config_snapshot(server, event_loop_handle)?;
+
+    replace_config_snapshot(
+        server,
+        event_loop_handle,
+        config_snapshot,
+        NiaConfigSnapshot::new(
+            device_ids.to_vec(),
+            modifiers.to_vec(),
+            named_actions.to_vec(),
+            mappings.to_vec(),
+        ),
+    )
+}
+
+fn replace_config_snapshot(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+    config_snapshot: NiaConfigSnapshot,
+    new_config_snapshot: NiaConfigSnapshot,
+) -> NiaServerResult<()> {
+    let definition_changes = config_snapshot.diff(&new_config_snapshot);
+
+    // Modifiers that are defined again, e.g. with another alias, stay.
+    for definition_change in &definition_changes {
+        if let NiaDefinitionChange::RemoveModifier(modifier) = definition_change
+        {
+            let is_redefined =
+                definition_changes.iter().any(|definition_change| {
+                    match definition_change {
+                        NiaDefinitionChange::DefineModifier(new_modifier) => {
+                            new_modifier.get_key() == modifier.get_key()
+                        }
+                        _ => false,
+                    }
+                });
+
+            if !is_redefined {
+                check_modifier_ungrouped(server, modifier.get_key())?;
+            }
+        }
+    }
+
+    apply_definition_changes(server, event_loop_handle, &definition_changes)
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    use nia_interpreter_core::{EventLoop, Interpreter};
+
+    use crate::protocol::{
+        ActionEmitChord, ActionExecuteInterpreterValue, ActionKeyClick,
+        ActionList, ActionRepeat, ActionWait, NiaAction,
+    };
+    use crate::utils::{
+        define_action, execute_interpreter_command, get_defined_actions,
+        get_defined_modifiers,
+    };
+
+    #[test]
+    fn keeps_interpreter_value_actions_when_definitions_are_replaced() {
+        let mut interpreter = Interpreter::new();
+        let value = interpreter
+            .execute_in_main_environment("(fn () (println \"lambda\"))")
+            .unwrap();
+        let event_loop_handle = EventLoop::run_event_loop(interpreter);
+        let mut server = Server::with_devices_info(Vec::new());
+
+        execute_interpreter_command(
+            &event_loop_handle,
+            NiaInterpreterCommand::make_define_action_command(
+                nia_interpreter_core::NamedAction::new(
+                    nia_interpreter_core::Action::ExecuteFunctionValue(value),
+                    String::from("lambda"),
+                ),
+            ),
+        )
+        .unwrap();
+
+        let named_action = NiaNamedAction::new(
+            NiaAction::new(
+                ActionExecuteInterpreterValue::new(
+                    "(fn () (println \"lambda\"))",
+                )
+                .unwrap()
+                .into(),
+            ),
+            "lambda",
+        );
+        let modifiers = get_defined_modifiers(&event_loop_handle).unwrap();
+
+        assert_eq!(
+            vec![named_action.clone()],
+            get_defined_actions(&event_loop_handle).unwrap()
+        );
+
+        replace_definitions(
+            &mut server,
+            &event_loop_handle,
+            &modifiers,
+            &[],
+            &[],
+        )
+        .unwrap();
+        replace_definitions(
+            &mut server,
+            &event_loop_handle,
+            &modifiers,
+            &[named_action.clone()],
+            &[],
+        )
+        .unwrap();
+
+        let result = get_defined_actions(&event_loop_handle).unwrap();
+
+        assert_eq!(vec![named_action], result);
+    }
+
+    #[test]
+    fn reads_back_actions_defined_as_code() {
+        let event_loop_handle = EventLoop::run_event_loop(Interpreter::new());
+
+        let actions = vec![
+            NiaAction::new(ActionEmitChord::new(vec![29, 42], 20, 0).into()),
+            NiaAction::new(
+                ActionRepeat::new(
+                    NiaAction::new(ActionKeyClick::new(46).into()),
+                    3,
+                    50,
+                )
+                .into(),
+            ),
+            NiaAction::new(
+                ActionList::new(vec![
+                    NiaAction::new(ActionKeyClick::new(46).into()),
+                    NiaAction::new(ActionWait::new(50).into()),
+                    NiaAction::new(ActionKeyClick::new(46).into()),
+                ])
+                .into(),
+            ),
+        ];
+
+        for (index, action) in actions.into_iter().enumerate() {
+            let named_action =
+                NiaNamedAction::new(action, format!("action-{}", index));
+
+            define_action(&event_loop_handle, &named_action).unwrap();
+
+            let result = get_defined_actions(&event_loop_handle).unwrap();
+
+            assert!(result.contains(&named_action));
+        }
+    }
+}
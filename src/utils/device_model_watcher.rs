@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub const DEVICE_MODEL_EXTENSION: &'static str = "kbm";
+
+pub struct DeviceModelWatcher {
+    directory: PathBuf,
+    modification_times: HashMap<PathBuf, SystemTime>,
+}
+
+impl DeviceModelWatcher {
+    pub fn new<P>(directory: P) -> DeviceModelWatcher
+    where
+        P: Into<PathBuf>,
+    {
+        let directory = directory.into();
+        let modification_times = read_modification_times(&directory);
+
+        DeviceModelWatcher {
+            directory,
+            modification_times,
+        }
+    }
+
+    pub fn get_directory(&self) -> &Path {
+        &self.directory
+    }
+
+    // Returns paths of model files that were created, modified or removed
+    // since the previous call.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let modification_times = read_modification_times(&self.directory);
+        let mut changed_paths = Vec::new();
+
+        for (path, modification_time) in &modification_times {
+            if self.modification_times.get(path) != Some(modification_time) {
+                changed_paths.push(path.clone());
+            }
+        }
+
+        for path in self.modification_times.keys() {
+            if !modification_times.contains_key(path) {
+                changed_paths.push(path.clone());
+            }
+        }
+
+        self.modification_times = modification_times;
+
+        changed_paths
+    }
+}
+
+fn read_modification_times(directory: &Path) -> HashMap<PathBuf, SystemTime> {
+    let mut modification_times = HashMap::new();
+
+    let iterator = match fs::read_dir(directory) {
+        Ok(iterator) => iterator,
+        Err(_) => return modification_times,
+    };
+
+    for entry in iterator {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
+
+        let path = entry.path();
+
+        if path.extension().and_then(|extension| extension.to_str())
+            != Some(DEVICE_MODEL_EXTENSION)
+        {
+            continue;
+        }
+
+        let modification_time =
+            match entry.metadata().and_then(|metadata| metadata.modified()) {
+                Ok(modification_time) => modification_time,
+                Err(_) => continue,
+            };
+
+        modification_times.insert(path, modification_time);
+    }
+
+    modification_times
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    use std::time::Duration;
+
+    fn make_directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!(
+            "nia-server-device-model-watcher-{}-{}",
+            name,
+            std::process::id()
+        ));
+
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+
+        directory
+    }
+
+    // Some filesystems keep modification times in seconds, so the time is
+    // moved forward explicitly.
+    fn touch(path: &Path, seconds: u64) {
+        let file = fs::OpenOptions::new().write(true).open(path).unwrap();
+        let modification_time =
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000 + seconds);
+
+        file.set_modified(modification_time).unwrap();
+    }
+
+    #[test]
+    fn reports_created_model_files() {
+        let directory = make_directory("create");
+        let mut device_model_watcher = DeviceModelWatcher::new(&directory);
+
+        assert!(device_model_watcher.poll().is_empty());
+
+        let path = directory.join("Logitech.kbm");
+        fs::write(&path, "10 10\n").unwrap();
+        fs::write(directory.join("notes.txt"), "").unwrap();
+
+        assert_eq!(vec![path], device_model_watcher.poll());
+        assert!(device_model_watcher.poll().is_empty());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn reports_modified_model_files() {
+        let directory = make_directory("modify");
+        let path = directory.join("Logitech.kbm");
+
+        fs::write(&path, "10 10\n").unwrap();
+        touch(&path, 1);
+
+        let mut device_model_watcher = DeviceModelWatcher::new(&directory);
+
+        assert!(device_model_watcher.poll().is_empty());
+
+        fs::write(&path, "20 20\n").unwrap();
+        touch(&path, 2);
+
+        assert_eq!(vec![path], device_model_watcher.poll());
+        assert!(device_model_watcher.poll().is_empty());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn reports_removed_model_files() {
+        let directory = make_directory("delete");
+        let path = directory.join("Logitech.kbm");
+
+        fs::write(&path, "10 10\n").unwrap();
+
+        let mut device_model_watcher = DeviceModelWatcher::new(&directory);
+
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(vec![path], device_model_watcher.poll());
+        assert!(device_model_watcher.poll().is_empty());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
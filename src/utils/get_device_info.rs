@@ -1,3 +1,4 @@
+use std::env;
 use std::fs;
 use std::fs::OpenOptions;
 use std::path::{Path, PathBuf};
@@ -9,14 +10,34 @@ use evdev_rs::Device;
 
 use crate::error::{NiaServerError, NiaServerResult};
 use crate::protocol::{DeviceInfo, DeviceModel};
+use crate::protocol::{NiaDeviceModelInfo, NiaDeviceModelSource};
+use crate::utils::{BUILTIN_DEVICE_MODELS, DEVICE_MODEL_EXTENSION};
 
-const KEYBOARD_MODELS_DIRECTORY: &'static str = "keyboard_models";
+const USER_KEYBOARD_MODELS_DIRECTORY_VARIABLE: &'static str =
+    "NIA_KEYBOARD_MODELS_DIRECTORY";
+const USER_KEYBOARD_MODELS_DIRECTORY: &'static str =
+    ".config/nia/keyboard_models";
+
+pub fn get_user_keyboard_models_directory() -> PathBuf {
+    if let Ok(directory) = env::var(USER_KEYBOARD_MODELS_DIRECTORY_VARIABLE) {
+        return PathBuf::from(directory);
+    }
+
+    match env::var("HOME") {
+        Ok(home) => Path::new(&home).join(USER_KEYBOARD_MODELS_DIRECTORY),
+        Err(_) => PathBuf::from(USER_KEYBOARD_MODELS_DIRECTORY),
+    }
+}
 
 pub fn read_device_model_from_path(
     path: PathBuf,
 ) -> NiaServerResult<DeviceModel> {
-    let string =
-        fs::read_to_string(&path).map_err(|_| NiaServerError::unknown(""))?;
+    let string = fs::read_to_string(&path).map_err(|_| {
+        NiaServerError::unknown(format!(
+            "Cannot read keyboard model file: {:?}.",
+            path
+        ))
+    })?;
 
     DeviceModel::from_string(string).map_err(|_| {
         NiaServerError::unknown(format!(
@@ -26,61 +47,128 @@ pub fn read_device_model_from_path(
     })
 }
 
-pub fn try_read_device_model(
-    device_name: &str,
-) -> NiaServerResult<DeviceModel> {
-    let path = KEYBOARD_MODELS_DIRECTORY;
-    let iterator = fs::read_dir(path).map_err(|_| {
-        NiaServerError::unknown("Cannot open keyboard model directory.")
-    })?;
+// Builtin models are validated by the build script, an invalid one is only
+// skipped here.
+pub fn read_builtin_device_models() -> Vec<NiaDeviceModelInfo> {
+    let mut device_model_infos = Vec::new();
+
+    for (model_name, model_string) in BUILTIN_DEVICE_MODELS {
+        match DeviceModel::from_string(*model_string) {
+            Ok(device_model) => {
+                device_model_infos.push(NiaDeviceModelInfo::new(
+                    *model_name,
+                    NiaDeviceModelSource::Builtin,
+                    device_model,
+                ));
+            }
+            Err(error) => {
+                println!(
+                    "Skipping builtin keyboard model {:?}: {}",
+                    model_name,
+                    error.get_message()
+                );
+            }
+        }
+    }
+
+    device_model_infos
+}
+
+pub fn read_user_device_models() -> Vec<NiaDeviceModelInfo> {
+    let mut device_model_infos = Vec::new();
+
+    let directory = get_user_keyboard_models_directory();
+    let iterator = match fs::read_dir(&directory) {
+        Ok(iterator) => iterator,
+        Err(_) => return device_model_infos,
+    };
 
     for entry in iterator {
-        let entry = entry.map_err(|_| {
-            NiaServerError::unknown(
-                "Cannot read keyboard model directory contents.",
-            )
-        })?;
-        let file_stem_name = entry
-            .path()
-            .file_stem()
-            .ok_or_else(|| {
-                NiaServerError::unknown(
-                    "Cannot read keyboard model directory contents.",
-                )
-            })?
-            .to_str()
-            .ok_or_else(|| {
-                NiaServerError::unknown(
-                    "Cannot read keyboard model directory contents.",
-                )
-            })?
-            .to_string();
-
-        if device_name.contains(&file_stem_name) {
-            let file_name = entry
-                .file_name()
-                .to_str()
-                .ok_or_else(|| {
-                    NiaServerError::unknown(
-                        "Cannot read keyboard model directory file",
-                    )
-                })?
-                .to_string();
-
-            let path = Path::new(KEYBOARD_MODELS_DIRECTORY).join(file_name);
-
-            return read_device_model_from_path(path);
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(_) => continue,
+        };
+
+        if path.extension().and_then(|extension| extension.to_str())
+            != Some(DEVICE_MODEL_EXTENSION)
+        {
+            continue;
+        }
+
+        let model_name =
+            match path.file_stem().and_then(|file_stem| file_stem.to_str()) {
+                Some(model_name) => model_name.to_string(),
+                None => continue,
+            };
+
+        match read_device_model_from_path(path.clone()) {
+            Ok(device_model) => {
+                let source = NiaDeviceModelSource::User(
+                    path.to_string_lossy().to_string(),
+                );
+
+                device_model_infos.push(NiaDeviceModelInfo::new(
+                    model_name,
+                    source,
+                    device_model,
+                ));
+            }
+            Err(error) => {
+                println!("Skipping keyboard model: {}", error.get_message());
+            }
         }
     }
 
-    NiaServerError::unknown("Cannot find device model from disk.").into()
+    device_model_infos.sort_by(|first, second| {
+        first.get_model_name().cmp(second.get_model_name())
+    });
+
+    device_model_infos
 }
 
-pub fn get_device_model(device_name: &str) -> DeviceModel {
-    match try_read_device_model(device_name) {
-        Ok(device_model) => device_model,
-        Err(_) => DeviceModel::default(),
+// User models go first and replace builtin models with the same name.
+pub fn overlay_device_model_infos(
+    user_device_model_infos: Vec<NiaDeviceModelInfo>,
+    builtin_device_model_infos: Vec<NiaDeviceModelInfo>,
+) -> Vec<NiaDeviceModelInfo> {
+    let mut device_model_infos = user_device_model_infos;
+
+    for builtin_device_model_info in builtin_device_model_infos {
+        let overridden = device_model_infos.iter().any(|device_model_info| {
+            device_model_info.get_model_name()
+                == builtin_device_model_info.get_model_name()
+        });
+
+        if !overridden {
+            device_model_infos.push(builtin_device_model_info);
+        }
     }
+
+    device_model_infos
+}
+
+pub fn get_device_model_infos() -> Vec<NiaDeviceModelInfo> {
+    overlay_device_model_infos(
+        read_user_device_models(),
+        read_builtin_device_models(),
+    )
+}
+
+// Picks the first model of the device in the order of
+// get_device_model_infos, so that user models win over builtin ones.
+pub fn find_device_model(
+    device_model_infos: &[NiaDeviceModelInfo],
+    device_name: &str,
+) -> DeviceModel {
+    device_model_infos
+        .iter()
+        .find(|device_model_info| device_model_info.is_model_of(device_name))
+        .map(|device_model_info| device_model_info.get_device_model().clone())
+        .unwrap_or_else(DeviceModel::default)
+}
+
+pub fn get_device_model(device_name: &str) -> DeviceModel {
+    find_device_model(&get_device_model_infos(), device_name)
 }
 
 pub fn get_device_info(
@@ -138,3 +226,50 @@ pub fn get_devices_info(
 
     Ok(devices_info)
 }
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn reads_all_builtin_models() {
+        assert_eq!(
+            BUILTIN_DEVICE_MODELS.len(),
+            read_builtin_device_models().len()
+        );
+    }
+
+    #[test]
+    fn user_models_shadow_builtin_models_with_the_same_name() {
+        let user_device_model = DeviceModel::from_string("10 20").unwrap();
+        let user_device_model_info = NiaDeviceModelInfo::new(
+            "Logitech",
+            NiaDeviceModelSource::User(String::from("Logitech.kbm")),
+            user_device_model.clone(),
+        );
+
+        let device_model_infos = overlay_device_model_infos(
+            vec![user_device_model_info],
+            read_builtin_device_models(),
+        );
+
+        let logitech_device_model_infos = device_model_infos
+            .iter()
+            .filter(|device_model_info| {
+                device_model_info.get_model_name() == "Logitech"
+            })
+            .collect::<Vec<&NiaDeviceModelInfo>>();
+
+        assert_eq!(1, logitech_device_model_infos.len());
+        assert_eq!(
+            &user_device_model,
+            logitech_device_model_infos[0].get_device_model()
+        );
+        assert_eq!(
+            user_device_model,
+            find_device_model(&device_model_infos, "Logitech USB Keyboard")
+        );
+        assert_eq!(BUILTIN_DEVICE_MODELS.len(), device_model_infos.len());
+    }
+}
@@ -0,0 +1,202 @@
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{NiaConfigSnapshot, NiaHistoryEntry, NiaRequest};
+use crate::server::Server;
+use crate::utils::{
+    apply_definition_changes, get_current_timestamp, get_defined_actions,
+    get_defined_modifiers, is_stageable_request, NiaTransaction,
+};
+
+// Mappings are taken from the server's index, which is read from the
+// interpreter again only after requests that don't keep it up to date, see
+// keeps_mapping_index.
+pub fn make_config_snapshot(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+) -> NiaServerResult<NiaConfigSnapshot> {
+    let device_ids = server
+        .get_devices()
+        .iter()
+        .filter(|device_info| device_info.is_defined())
+        .map(|device_info| device_info.get_device_id())
+        .collect();
+
+    let mappings = server
+        .get_mapping_index(event_loop_handle)?
+        .get_mappings()
+        .into_iter()
+        .cloned()
+        .collect();
+
+    let config_snapshot = NiaConfigSnapshot::new(
+        device_ids,
+        get_defined_modifiers(event_loop_handle)?,
+        get_defined_actions(event_loop_handle)?,
+        mappings,
+    );
+
+    Ok(config_snapshot)
+}
+
+// Names of the requests that may change definitions. Enabling and disabling
+// mappings is not recorded, it is undone by enabling or disabling them back.
+// Modifier groups are not recorded either, changing them clears history.
+pub fn get_recorded_request_name(
+    nia_request: &NiaRequest,
+) -> Option<&'static str> {
+    let request_name = match nia_request {
+        NiaRequest::ExecuteCode(_) => "ExecuteCode",
+        NiaRequest::DefineDevice(_) => "DefineDevice",
+        NiaRequest::RemoveDeviceByPath(_) => "RemoveDeviceByPath",
+        NiaRequest::RemoveDeviceByName(_) => "RemoveDeviceByName",
+        NiaRequest::RemoveDeviceById(_) => "RemoveDeviceById",
+        NiaRequest::DefineModifier(_) => "DefineModifier",
+        NiaRequest::RemoveModifier(_) => "RemoveModifier",
+        NiaRequest::RenameModifierAlias(_) => "RenameModifierAlias",
+        NiaRequest::DefineAction(_) => "DefineAction",
+        NiaRequest::RemoveAction(_) => "RemoveAction",
+        NiaRequest::RenameAction(_) => "RenameAction",
+        NiaRequest::DefineMapping(_) => "DefineMapping",
+        NiaRequest::ChangeMapping(_) => "ChangeMapping",
+        NiaRequest::RemoveMapping(_) => "RemoveMapping",
+        NiaRequest::DefineTextMapping(_) => "DefineTextMapping",
+        NiaRequest::RemoveTextMapping(_) => "RemoveTextMapping",
+        NiaRequest::ImportConfig(_) => "ImportConfig",
+        NiaRequest::ActivateProfile(_) => "ActivateProfile",
+        NiaRequest::RebindMapping(_) => "RebindMapping",
+        NiaRequest::CommitTransaction(_) => "CommitTransaction",
+        _ => return None,
+    };
+
+    Some(request_name)
+}
+
+// Records what a request has changed by comparing definitions before and
+// after it is handled, so that even a request that failed halfway leaves an
+// entry that undoes its partial changes.
+pub struct NiaHistoryRecorder {
+    request_name: &'static str,
+    config_snapshot: NiaConfigSnapshot,
+}
+
+impl NiaHistoryRecorder {
+    // Requests that change nothing, including the ones staged in an open
+    // transaction, are not recorded and cost no snapshots.
+    pub fn start(
+        server: &mut Server,
+        event_loop_handle: &EventLoopHandle,
+        nia_request: &NiaRequest,
+        transaction: &Option<NiaTransaction>,
+    ) -> Option<NiaHistoryRecorder> {
+        let request_name = get_recorded_request_name(nia_request)?;
+
+        if transaction.is_some() && is_stageable_request(nia_request) {
+            return None;
+        }
+
+        match make_config_snapshot(server, event_loop_handle) {
+            Ok(config_snapshot) => Some(NiaHistoryRecorder {
+                request_name,
+                config_snapshot,
+            }),
+            Err(error) => {
+                println!("Cannot record history: {:?}", error);
+                None
+            }
+        }
+    }
+
+    pub fn finish(
+        self,
+        server: &mut Server,
+        event_loop_handle: &EventLoopHandle,
+    ) {
+        let config_snapshot =
+            match make_config_snapshot(server, event_loop_handle) {
+                Ok(config_snapshot) => config_snapshot,
+                Err(error) => {
+                    println!("Cannot record history: {:?}", error);
+                    return;
+                }
+            };
+
+        let changes = self.config_snapshot.diff(&config_snapshot);
+
+        if changes.is_empty() {
+            return;
+        }
+
+        server.get_history_mut().record(NiaHistoryEntry::new(
+            self.request_name,
+            get_current_timestamp(),
+            changes,
+        ));
+    }
+}
+
+// Undoes the latest entry and returns it. An entry that cannot be undone
+// stays in the history.
+pub fn undo(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+) -> NiaServerResult<NiaHistoryEntry> {
+    let history_entry = match server.get_history_mut().take_undo_entry() {
+        Some(history_entry) => history_entry,
+        None => {
+            return NiaServerError::invalid_request("Nothing to undo.").into()
+        }
+    };
+
+    let result = apply_definition_changes(
+        server,
+        event_loop_handle,
+        &history_entry.get_inverse_changes(),
+    );
+
+    match result {
+        Ok(()) => {
+            server.get_history_mut().mark_undone(history_entry.clone());
+
+            Ok(history_entry)
+        }
+        Err(error) => {
+            server.get_history_mut().mark_redone(history_entry);
+
+            Err(error)
+        }
+    }
+}
+
+// Redoes the latest undone entry and returns it. An entry that cannot be
+// redone stays in the history.
+pub fn redo(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+) -> NiaServerResult<NiaHistoryEntry> {
+    let history_entry = match server.get_history_mut().take_redo_entry() {
+        Some(history_entry) => history_entry,
+        None => {
+            return NiaServerError::invalid_request("Nothing to redo.").into()
+        }
+    };
+
+    let result = apply_definition_changes(
+        server,
+        event_loop_handle,
+        history_entry.get_changes(),
+    );
+
+    match result {
+        Ok(()) => {
+            server.get_history_mut().mark_redone(history_entry.clone());
+
+            Ok(history_entry)
+        }
+        Err(error) => {
+            server.get_history_mut().mark_undone(history_entry);
+
+            Err(error)
+        }
+    }
+}
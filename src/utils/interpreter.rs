@@ -0,0 +1,572 @@
+use nia_interpreter_core::EventLoopHandle;
+use nia_interpreter_core::NiaExecuteCodeCommandResult;
+use nia_interpreter_core::NiaGetDefinedActionsCommandResult;
+use nia_interpreter_core::NiaGetDefinedMappingsCommandResult;
+use nia_interpreter_core::NiaGetDefinedModifiersCommandResult;
+use nia_interpreter_core::NiaInterpreterCommand;
+use nia_interpreter_core::NiaInterpreterCommandResult;
+use nia_interpreter_core::NiaPrintValueCommandResult;
+use nia_interpreter_core::{
+    NiaAtomicCommandResult, NiaDefineActionCommandResult,
+    NiaDefineModifierCommandResult, NiaRemoveModifierCommandResult,
+};
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{
+    collapse_mappings, expand_key_chords, format_key_chords,
+};
+use crate::protocol::{
+    ActionExecuteInterpreterValue, NiaAction, NiaConvertable, NiaKey,
+    NiaKeyChord, NiaMapping, NiaModifierDescription, NiaNamedAction,
+};
+use crate::protocol::{NiaMappingIndex, NiaModifierGroup};
+
+// Turns the result of a command that returns nothing on success into a
+// server result.
+macro_rules! expect_empty_command_result {
+    ($execution_result:expr, $variant:path, $command_result:ident) => {
+        match $execution_result {
+            $variant($command_result::Success()) => Ok(()),
+            $variant($command_result::Error(message))
+            | $variant($command_result::Failure(message)) => {
+                NiaServerError::interpreter_error(message).into()
+            }
+            _ => {
+                NiaServerError::interpreter_error("Unexpected command result.")
+                    .into()
+            }
+        }
+    };
+}
+
+pub fn execute_interpreter_command(
+    event_loop_handle: &EventLoopHandle,
+    interpreter_command: NiaInterpreterCommand,
+) -> NiaServerResult<NiaInterpreterCommandResult> {
+    event_loop_handle
+        .send_command(interpreter_command)
+        .map_err(|_| {
+            NiaServerError::interpreter_error(
+                "Error sending command to the interpreter.",
+            )
+        })?;
+
+    event_loop_handle.receive_result().map_err(|_| {
+        NiaServerError::interpreter_error(
+            "Error reading command from the interpreter.",
+        )
+    })
+}
+
+// Executes the commands as one. The interpreter handles no key event until
+// all of them are executed, and when one of them fails it undoes the ones
+// executed before, so either every command takes effect or none does.
+pub fn execute_interpreter_commands(
+    event_loop_handle: &EventLoopHandle,
+    interpreter_commands: Vec<NiaInterpreterCommand>,
+) -> NiaServerResult<()> {
+    if interpreter_commands.is_empty() {
+        return Ok(());
+    }
+
+    let execution_result = execute_interpreter_command(
+        event_loop_handle,
+        NiaInterpreterCommand::make_atomic_command(interpreter_commands),
+    )?;
+
+    expect_empty_command_result!(
+        execution_result,
+        NiaInterpreterCommandResult::Atomic,
+        NiaAtomicCommandResult
+    )
+}
+
+// Executes code and returns the printed result.
+pub fn execute_code(
+    event_loop_handle: &EventLoopHandle,
+    code: &str,
+) -> NiaServerResult<String> {
+    let execution_result = execute_interpreter_command(
+        event_loop_handle,
+        NiaInterpreterCommand::make_execute_code_command(code.to_string()),
+    )?;
+
+    match execution_result {
+        NiaInterpreterCommandResult::ExecuteCode(
+            NiaExecuteCodeCommandResult::Success(result),
+        ) => Ok(result),
+        NiaInterpreterCommandResult::ExecuteCode(
+            NiaExecuteCodeCommandResult::Error(message),
+        )
+        | NiaInterpreterCommandResult::ExecuteCode(
+            NiaExecuteCodeCommandResult::Failure(message),
+        ) => NiaServerError::interpreter_error(message).into(),
+        _ => NiaServerError::interpreter_error("Unexpected command result.")
+            .into(),
+    }
+}
+
+// Prints the value as code the interpreter can read back.
+fn print_interpreter_value(
+    event_loop_handle: &EventLoopHandle,
+    value: nia_interpreter_core::Value,
+) -> NiaServerResult<String> {
+    let execution_result = execute_interpreter_command(
+        event_loop_handle,
+        NiaInterpreterCommand::make_print_value_command(value),
+    )?;
+
+    match execution_result {
+        NiaInterpreterCommandResult::PrintValue(
+            NiaPrintValueCommandResult::Success(code),
+        ) => Ok(code),
+        NiaInterpreterCommandResult::PrintValue(
+            NiaPrintValueCommandResult::Error(message),
+        )
+        | NiaInterpreterCommandResult::PrintValue(
+            NiaPrintValueCommandResult::Failure(message),
+        ) => NiaServerError::interpreter_error(message).into(),
+        _ => NiaServerError::interpreter_error("Unexpected command result.")
+            .into(),
+    }
+}
+
+// Actions defined by interpreter code may execute function values. The
+// server keeps no values, so they are printed and replaced by the code that
+// executes the printed function.
+fn print_function_value(
+    event_loop_handle: &EventLoopHandle,
+    action: &nia_interpreter_core::Action,
+) -> NiaServerResult<nia_interpreter_core::Action> {
+    match action {
+        nia_interpreter_core::Action::ExecuteFunctionValue(value) => {
+            let code = print_interpreter_value(event_loop_handle, *value)?;
+            let action_execute_interpreter_value =
+                ActionExecuteInterpreterValue::new(code).map_err(|error| {
+                    NiaServerError::interpreter_error(error.get_message())
+                })?;
+
+            Ok(nia_interpreter_core::Action::ExecuteCode(
+                action_execute_interpreter_value.to_code(),
+            ))
+        }
+        action => Ok(action.clone()),
+    }
+}
+
+fn get_interpreter_mappings(
+    event_loop_handle: &EventLoopHandle,
+) -> NiaServerResult<Vec<NiaMapping>> {
+    let execution_result = execute_interpreter_command(
+        event_loop_handle,
+        NiaInterpreterCommand::make_get_defined_mappings_command(),
+    )?;
+
+    match execution_result {
+        NiaInterpreterCommandResult::GetDefinedMappings(
+            NiaGetDefinedMappingsCommandResult::Success(mappings),
+        ) => mappings
+            .iter()
+            .map(|mapping| {
+                let action = print_function_value(
+                    event_loop_handle,
+                    mapping.get_action(),
+                )?;
+
+                NiaMapping::from_interpreter_repr(
+                    &nia_interpreter_core::Mapping::new(
+                        mapping.get_key_chords().clone(),
+                        action,
+                    ),
+                )
+            })
+            .collect::<NiaServerResult<Vec<NiaMapping>>>(),
+        NiaInterpreterCommandResult::GetDefinedMappings(
+            NiaGetDefinedMappingsCommandResult::Error(message),
+        )
+        | NiaInterpreterCommandResult::GetDefinedMappings(
+            NiaGetDefinedMappingsCommandResult::Failure(message),
+        ) => NiaServerError::interpreter_error(message).into(),
+        _ => NiaServerError::interpreter_error("Unexpected command result.")
+            .into(),
+    }
+}
+
+// Mappings the interpreter has for every member of a modifier group are
+// reported once, with the keys that represent the groups.
+pub fn get_defined_mappings(
+    event_loop_handle: &EventLoopHandle,
+    modifier_groups: &[NiaModifierGroup],
+) -> NiaServerResult<Vec<NiaMapping>> {
+    let interpreter_mappings = get_interpreter_mappings(event_loop_handle)?;
+
+    Ok(collapse_mappings(modifier_groups, &interpreter_mappings))
+}
+
+pub fn get_mapping_index(
+    event_loop_handle: &EventLoopHandle,
+    modifier_groups: &[NiaModifierGroup],
+) -> NiaServerResult<NiaMappingIndex> {
+    let defined_mappings =
+        get_defined_mappings(event_loop_handle, modifier_groups)?;
+
+    Ok(NiaMappingIndex::from_mappings(defined_mappings))
+}
+
+pub fn get_defined_modifiers(
+    event_loop_handle: &EventLoopHandle,
+) -> NiaServerResult<Vec<NiaModifierDescription>> {
+    let execution_result = execute_interpreter_command(
+        event_loop_handle,
+        NiaInterpreterCommand::make_get_defined_modifiers(),
+    )?;
+
+    match execution_result {
+        NiaInterpreterCommandResult::GetDefinedModifiers(
+            NiaGetDefinedModifiersCommandResult::Success(modifiers),
+        ) => modifiers
+            .iter()
+            .map(|modifier| {
+                NiaModifierDescription::from_interpreter_repr(modifier)
+            })
+            .collect::<NiaServerResult<Vec<NiaModifierDescription>>>(),
+        NiaInterpreterCommandResult::GetDefinedModifiers(
+            NiaGetDefinedModifiersCommandResult::Error(message),
+        )
+        | NiaInterpreterCommandResult::GetDefinedModifiers(
+            NiaGetDefinedModifiersCommandResult::Failure(message),
+        ) => NiaServerError::interpreter_error(message).into(),
+        _ => NiaServerError::interpreter_error("Unexpected command result.")
+            .into(),
+    }
+}
+
+pub fn get_defined_actions(
+    event_loop_handle: &EventLoopHandle,
+) -> NiaServerResult<Vec<NiaNamedAction>> {
+    let execution_result = execute_interpreter_command(
+        event_loop_handle,
+        NiaInterpreterCommand::make_get_defined_actions_command(),
+    )?;
+
+    match execution_result {
+        NiaInterpreterCommandResult::GetDefinedActions(
+            NiaGetDefinedActionsCommandResult::Success(actions),
+        ) => actions
+            .iter()
+            .map(|named_action| {
+                let action = print_function_value(
+                    event_loop_handle,
+                    named_action.get_action(),
+                )?;
+
+                NiaNamedAction::from_interpreter_repr(
+                    &nia_interpreter_core::NamedAction::new(
+                        action,
+                        named_action.get_action_name().clone(),
+                    ),
+                )
+            })
+            .collect::<NiaServerResult<Vec<NiaNamedAction>>>(),
+        NiaInterpreterCommandResult::GetDefinedActions(
+            NiaGetDefinedActionsCommandResult::Error(message),
+        )
+        | NiaInterpreterCommandResult::GetDefinedActions(
+            NiaGetDefinedActionsCommandResult::Failure(message),
+        ) => NiaServerError::interpreter_error(message).into(),
+        _ => NiaServerError::interpreter_error("Unexpected command result.")
+            .into(),
+    }
+}
+
+pub fn define_modifier(
+    event_loop_handle: &EventLoopHandle,
+    modifier: &NiaModifierDescription,
+) -> NiaServerResult<()> {
+    let execution_result = execute_interpreter_command(
+        event_loop_handle,
+        NiaInterpreterCommand::make_define_modifier_command(
+            modifier.to_interpreter_repr(),
+        ),
+    )?;
+
+    expect_empty_command_result!(
+        execution_result,
+        NiaInterpreterCommandResult::DefineModifier,
+        NiaDefineModifierCommandResult
+    )
+}
+
+pub fn remove_modifier(
+    event_loop_handle: &EventLoopHandle,
+    modifier_key: NiaKey,
+) -> NiaServerResult<()> {
+    let execution_result = execute_interpreter_command(
+        event_loop_handle,
+        NiaInterpreterCommand::make_remove_modifier_command(
+            modifier_key.to_interpreter_repr(),
+        ),
+    )?;
+
+    expect_empty_command_result!(
+        execution_result,
+        NiaInterpreterCommandResult::RemoveModifier,
+        NiaRemoveModifierCommandResult
+    )
+}
+
+pub fn define_action(
+    event_loop_handle: &EventLoopHandle,
+    named_action: &NiaNamedAction,
+) -> NiaServerResult<()> {
+    named_action.get_action().check()?;
+
+    let execution_result = execute_interpreter_command(
+        event_loop_handle,
+        NiaInterpreterCommand::make_define_action_command(
+            named_action.to_interpreter_repr(),
+        ),
+    )?;
+
+    expect_empty_command_result!(
+        execution_result,
+        NiaInterpreterCommandResult::DefineAction,
+        NiaDefineActionCommandResult
+    )
+}
+
+fn key_chords_to_interpreter_repr(
+    key_chords: &[NiaKeyChord],
+) -> Vec<nia_interpreter_core::KeyChord> {
+    key_chords
+        .iter()
+        .map(|key_chord| key_chord.to_interpreter_repr())
+        .collect()
+}
+
+fn make_define_mapping_command(
+    mapping: &NiaMapping,
+) -> NiaServerResult<NiaInterpreterCommand> {
+    mapping.get_action().check()?;
+
+    Ok(NiaInterpreterCommand::make_define_mapping_command(
+        mapping.to_interpreter_repr(),
+    ))
+}
+
+fn make_change_mapping_command(
+    key_chords: &[NiaKeyChord],
+    action: &NiaAction,
+) -> NiaServerResult<NiaInterpreterCommand> {
+    action.check()?;
+
+    Ok(NiaInterpreterCommand::make_change_mapping_command(
+        key_chords_to_interpreter_repr(key_chords),
+        action.to_interpreter_repr(),
+    ))
+}
+
+fn make_remove_mapping_command(
+    key_chords: &[NiaKeyChord],
+) -> NiaInterpreterCommand {
+    NiaInterpreterCommand::make_remove_mapping_command(
+        key_chords_to_interpreter_repr(key_chords),
+    )
+}
+
+// The interpreter knows nothing about modifier groups, so a mapping is
+// defined for every combination of group members its key chords stand for.
+pub fn make_define_mapping_commands(
+    modifier_groups: &[NiaModifierGroup],
+    mapping: &NiaMapping,
+) -> NiaServerResult<Vec<NiaInterpreterCommand>> {
+    expand_key_chords(modifier_groups, mapping.get_key_chords())?
+        .into_iter()
+        .map(|key_chords| {
+            make_define_mapping_command(&NiaMapping::new(
+                key_chords,
+                mapping.get_action().clone(),
+            ))
+        })
+        .collect()
+}
+
+// Interpreter mappings the key chords stand for. These are all the
+// combinations of group members when every one of them is defined with the
+// same action, as get_defined_mappings reports them once. Otherwise the key
+// chords stand only for themselves.
+fn get_addressed_mappings(
+    event_loop_handle: &EventLoopHandle,
+    modifier_groups: &[NiaModifierGroup],
+    key_chords: &[NiaKeyChord],
+) -> NiaServerResult<Vec<NiaMapping>> {
+    let interpreter_mappings = get_interpreter_mappings(event_loop_handle)?;
+
+    let find_mapping = |key_chords: &[NiaKeyChord]| {
+        interpreter_mappings
+            .iter()
+            .find(|mapping| mapping.get_key_chords().as_slice() == key_chords)
+            .cloned()
+    };
+
+    let mapping = match find_mapping(key_chords) {
+        Some(mapping) => mapping,
+        None => {
+            return NiaServerError::invalid_request("Mapping is not defined.")
+                .into()
+        }
+    };
+
+    let key_chords_list = expand_key_chords(modifier_groups, key_chords)?;
+
+    if key_chords_list[0].as_slice() != key_chords {
+        return Ok(vec![mapping]);
+    }
+
+    let mut mappings = Vec::new();
+
+    for key_chords in &key_chords_list {
+        match find_mapping(key_chords) {
+            Some(other_mapping)
+                if other_mapping.get_action() == mapping.get_action() =>
+            {
+                mappings.push(other_mapping)
+            }
+            _ => return Ok(vec![mapping]),
+        }
+    }
+
+    Ok(mappings)
+}
+
+// Defines the mappings a new modifier group adds to the ones that already
+// use its keys, so that each of them is fired by any key of the group.
+// Mappings of different group members with different actions can't be
+// joined, the group is refused then.
+pub fn define_modifier_group_mappings(
+    event_loop_handle: &EventLoopHandle,
+    modifier_groups: &[NiaModifierGroup],
+    modifier_group: &NiaModifierGroup,
+) -> NiaServerResult<()> {
+    let interpreter_mappings = get_interpreter_mappings(event_loop_handle)?;
+    let mut new_mappings: Vec<NiaMapping> = Vec::new();
+
+    for mapping in &interpreter_mappings {
+        let uses_group = mapping.get_key_chords().iter().any(|key_chord| {
+            key_chord
+                .get_modifiers()
+                .iter()
+                .any(|modifier| modifier_group.contains(*modifier))
+        });
+
+        if !uses_group {
+            continue;
+        }
+
+        for key_chords in
+            expand_key_chords(modifier_groups, mapping.get_key_chords())?
+        {
+            let other_mapping = interpreter_mappings
+                .iter()
+                .chain(new_mappings.iter())
+                .find(|other_mapping| {
+                    other_mapping.get_key_chords() == &key_chords
+                });
+
+            match other_mapping {
+                Some(other_mapping)
+                    if other_mapping.get_action() == mapping.get_action() => {}
+                Some(_) => {
+                    return NiaServerError::invalid_request(format!(
+                        "Mappings {} and {} of modifier group {} have \
+                         different actions.",
+                        format_key_chords(mapping.get_key_chords()),
+                        format_key_chords(&key_chords),
+                        modifier_group.get_alias()
+                    ))
+                    .into()
+                }
+                None => new_mappings.push(NiaMapping::new(
+                    key_chords,
+                    mapping.get_action().clone(),
+                )),
+            }
+        }
+    }
+
+    let interpreter_commands = new_mappings
+        .iter()
+        .map(make_define_mapping_command)
+        .collect::<NiaServerResult<Vec<NiaInterpreterCommand>>>()?;
+
+    execute_interpreter_commands(event_loop_handle, interpreter_commands)
+}
+
+pub fn make_change_mapping_commands(
+    event_loop_handle: &EventLoopHandle,
+    modifier_groups: &[NiaModifierGroup],
+    key_chords: &[NiaKeyChord],
+    action: &NiaAction,
+) -> NiaServerResult<Vec<NiaInterpreterCommand>> {
+    get_addressed_mappings(event_loop_handle, modifier_groups, key_chords)?
+        .iter()
+        .map(|mapping| {
+            make_change_mapping_command(mapping.get_key_chords(), action)
+        })
+        .collect()
+}
+
+pub fn make_remove_mapping_commands(
+    event_loop_handle: &EventLoopHandle,
+    modifier_groups: &[NiaModifierGroup],
+    key_chords: &[NiaKeyChord],
+) -> NiaServerResult<Vec<NiaInterpreterCommand>> {
+    let mappings =
+        get_addressed_mappings(event_loop_handle, modifier_groups, key_chords)?;
+
+    Ok(mappings
+        .iter()
+        .map(|mapping| make_remove_mapping_command(mapping.get_key_chords()))
+        .collect())
+}
+
+pub fn define_mapping(
+    event_loop_handle: &EventLoopHandle,
+    modifier_groups: &[NiaModifierGroup],
+    mapping: &NiaMapping,
+) -> NiaServerResult<()> {
+    let interpreter_commands =
+        make_define_mapping_commands(modifier_groups, mapping)?;
+
+    execute_interpreter_commands(event_loop_handle, interpreter_commands)
+}
+
+pub fn change_mapping(
+    event_loop_handle: &EventLoopHandle,
+    modifier_groups: &[NiaModifierGroup],
+    key_chords: &[NiaKeyChord],
+    action: &NiaAction,
+) -> NiaServerResult<()> {
+    let interpreter_commands = make_change_mapping_commands(
+        event_loop_handle,
+        modifier_groups,
+        key_chords,
+        action,
+    )?;
+
+    execute_interpreter_commands(event_loop_handle, interpreter_commands)
+}
+
+pub fn remove_mapping(
+    event_loop_handle: &EventLoopHandle,
+    modifier_groups: &[NiaModifierGroup],
+    key_chords: &[NiaKeyChord],
+) -> NiaServerResult<()> {
+    let interpreter_commands = make_remove_mapping_commands(
+        event_loop_handle,
+        modifier_groups,
+        key_chords,
+    )?;
+
+    execute_interpreter_commands(event_loop_handle, interpreter_commands)
+}
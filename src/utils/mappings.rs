@@ -0,0 +1,244 @@
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{
+    find_mapping_conflicts_excluding, format_key_chords, NiaKeyChord,
+    NiaMapping, NiaMappingConflict, NiaMappingMetadata, NiaRequest,
+};
+use crate::server::Server;
+use crate::utils::{
+    define_mapping, execute_interpreter_commands, get_current_timestamp,
+    get_defined_mappings, get_defined_modifiers, make_define_mapping_commands,
+    make_remove_mapping_commands, remove_mapping,
+};
+
+// Whether the server's index of defined mappings stays up to date after the
+// request, i.e. the request either changes no mappings or reports every
+// change it makes. The index is read from the interpreter again after any
+// other request, e.g. after executed code that may define mappings.
+pub fn keeps_mapping_index(nia_request: &NiaRequest) -> bool {
+    match nia_request {
+        NiaRequest::Handshake(_)
+        | NiaRequest::GetDevices(_)
+        | NiaRequest::GetDefinedModifiers(_)
+        | NiaRequest::GetDefinedActions(_)
+        | NiaRequest::GetDefinedMappings(_)
+        | NiaRequest::DefineMapping(_)
+        | NiaRequest::ChangeMapping(_)
+        | NiaRequest::RemoveMapping(_)
+        | NiaRequest::IsListening(_)
+        | NiaRequest::StartListening(_)
+        | NiaRequest::StopListening(_)
+        | NiaRequest::GetDeviceModels(_)
+        | NiaRequest::DefineTextMapping(_)
+        | NiaRequest::RemoveTextMapping(_)
+        | NiaRequest::CheckMappings(_)
+        | NiaRequest::ResolveKeys(_)
+        | NiaRequest::SimulateKeyEvents(_)
+        | NiaRequest::ExportConfig(_)
+        | NiaRequest::CreateProfile(_)
+        | NiaRequest::ListProfiles(_)
+        | NiaRequest::DeleteProfile(_)
+        | NiaRequest::SetMappingEnabled(_)
+        | NiaRequest::SetMappingsEnabledByTag(_)
+        | NiaRequest::SetMappingMetadata(_)
+        | NiaRequest::RebindMapping(_)
+        | NiaRequest::BeginTransaction(_)
+        | NiaRequest::RollbackTransaction(_)
+        | NiaRequest::GetHistory(_)
+        | NiaRequest::DiffConfig(_)
+        | NiaRequest::GetReferences(_) => true,
+        _ => false,
+    }
+}
+
+// Enables or disables the mapping bound to the key chords. Returns false when
+// the mapping already was in the requested state.
+pub fn set_mapping_enabled(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+    key_chords: &[NiaKeyChord],
+    enabled: bool,
+) -> NiaServerResult<bool> {
+    let timestamp = get_current_timestamp();
+    let modifier_groups = server.get_modifier_groups().clone();
+    let is_disabled = server.get_disabled_mappings().get(key_chords).is_some();
+
+    if enabled {
+        if !is_disabled {
+            let mapping_index = server.get_mapping_index(event_loop_handle)?;
+
+            return match mapping_index.get(key_chords) {
+                Some(_) => Ok(false),
+                None => unknown_mapping_error(key_chords),
+            };
+        }
+
+        let mapping = match server.enable_mapping(key_chords, timestamp) {
+            Some(mapping) => mapping,
+            None => return unknown_mapping_error(key_chords),
+        };
+
+        if let Err(error) =
+            define_mapping(event_loop_handle, &modifier_groups, &mapping)
+        {
+            server.disable_mapping(mapping, timestamp);
+
+            return Err(error);
+        }
+    } else {
+        if is_disabled {
+            return Ok(false);
+        }
+
+        let mapping_index = server.get_mapping_index(event_loop_handle)?;
+
+        let mapping = match mapping_index.get(key_chords) {
+            Some(mapping) => mapping.clone(),
+            None => return unknown_mapping_error(key_chords),
+        };
+
+        remove_mapping(event_loop_handle, &modifier_groups, key_chords)?;
+        server.disable_mapping(mapping, timestamp);
+    }
+
+    Ok(true)
+}
+
+// Moves the mapping bound to `old_key_chords` to `new_key_chords`, keeping
+// its action and metadata. The mapping is removed and defined again in one
+// interpreter command, so no key event is handled in between, and when
+// defining fails the old mapping stays. Returns conflicts of the moved
+// mapping.
+pub fn rebind_mapping(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+    old_key_chords: &[NiaKeyChord],
+    new_key_chords: &[NiaKeyChord],
+) -> NiaServerResult<Vec<NiaMappingConflict>> {
+    let timestamp = get_current_timestamp();
+    let modifier_groups = server.get_modifier_groups().clone();
+    let is_disabled =
+        server.get_disabled_mappings().get(old_key_chords).is_some();
+    let is_new_disabled =
+        server.get_disabled_mappings().get(new_key_chords).is_some();
+
+    let defined_modifiers = get_defined_modifiers(event_loop_handle)?;
+    let mapping_index = server.get_mapping_index(event_loop_handle)?;
+
+    let mapping = match mapping_index.get(old_key_chords) {
+        Some(mapping) => Some(mapping.clone()),
+        None if is_disabled => None,
+        None => return unknown_mapping_error(old_key_chords),
+    };
+
+    let is_bound = old_key_chords != new_key_chords
+        && (mapping_index.get(new_key_chords).is_some() || is_new_disabled);
+
+    if is_bound {
+        return NiaServerError::invalid_request(format!(
+            "Key chords are already bound: {}.",
+            format_key_chords(new_key_chords)
+        ))
+        .into();
+    }
+
+    // The mapping being moved is not a conflict of itself.
+    let conflicts = find_mapping_conflicts_excluding(
+        new_key_chords,
+        old_key_chords,
+        mapping_index,
+        &defined_modifiers,
+    );
+
+    let mapping = match mapping {
+        Some(mapping) => mapping,
+        None => {
+            server.rebind_disabled_mapping(
+                old_key_chords,
+                new_key_chords,
+                timestamp,
+            );
+
+            return Ok(conflicts);
+        }
+    };
+
+    let rebound_mapping =
+        NiaMapping::new(new_key_chords.to_vec(), mapping.get_action().clone());
+
+    let mut interpreter_commands = make_remove_mapping_commands(
+        event_loop_handle,
+        &modifier_groups,
+        old_key_chords,
+    )?;
+
+    interpreter_commands.extend(make_define_mapping_commands(
+        &modifier_groups,
+        &rebound_mapping,
+    )?);
+
+    execute_interpreter_commands(event_loop_handle, interpreter_commands)?;
+
+    server.mark_mapping_rebound(old_key_chords, new_key_chords, timestamp);
+
+    Ok(conflicts)
+}
+
+// Returns every mapping, the defined ones followed by the disabled ones,
+// with its metadata.
+pub fn get_mappings_with_metadata(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+) -> NiaServerResult<Vec<(NiaMapping, NiaMappingMetadata)>> {
+    let timestamp = get_current_timestamp();
+
+    let mut mappings =
+        get_defined_mappings(event_loop_handle, server.get_modifier_groups())?;
+
+    mappings.extend(
+        server
+            .get_disabled_mappings()
+            .get_mappings()
+            .into_iter()
+            .cloned(),
+    );
+
+    let mappings_with_metadata = mappings
+        .into_iter()
+        .map(|mapping| {
+            let mapping_metadata = server
+                .get_mapping_metadata(mapping.get_key_chords(), timestamp)
+                .clone();
+
+            (mapping, mapping_metadata)
+        })
+        .collect();
+
+    Ok(mappings_with_metadata)
+}
+
+// Returns key chords of every mapping, enabled or not, that has the tag.
+pub fn get_tagged_key_chords(
+    server: &Server,
+    tag: &str,
+) -> Vec<Vec<NiaKeyChord>> {
+    let mut key_chords = server
+        .get_mappings_metadata()
+        .iter()
+        .filter(|(_, mapping_metadata)| mapping_metadata.has_tag(tag))
+        .map(|(key_chords, _)| key_chords.clone())
+        .collect::<Vec<Vec<NiaKeyChord>>>();
+
+    key_chords.sort();
+
+    key_chords
+}
+
+fn unknown_mapping_error<T>(key_chords: &[NiaKeyChord]) -> NiaServerResult<T> {
+    NiaServerError::invalid_request(format!(
+        "Unknown mapping: {}.",
+        format_key_chords(key_chords)
+    ))
+    .into()
+}
@@ -1,6 +1,31 @@
+mod builtin_device_models;
+mod config;
+mod definitions;
+mod device_model_watcher;
 mod get_devices;
 mod get_device_info;
+mod history;
+mod interpreter;
+mod mappings;
+mod modifier_groups;
+mod profiles;
+mod references;
+mod renames;
+mod timestamp;
+mod transactions;
 
+pub use builtin_device_models::*;
+pub use config::*;
+pub use definitions::*;
+pub use device_model_watcher::*;
 pub use get_devices::*;
 pub use get_device_info::*;
-
+pub use history::*;
+pub use interpreter::*;
+pub use mappings::*;
+pub use modifier_groups::*;
+pub use profiles::*;
+pub use references::*;
+pub use renames::*;
+pub use timestamp::*;
+pub use transactions::*;
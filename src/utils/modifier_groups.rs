@@ -0,0 +1,133 @@
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{
+    find_modifier_group, format_key, NiaKey, NiaModifierGroup,
+};
+use crate::server::Server;
+use crate::utils::{
+    define_modifier, define_modifier_group_mappings, get_defined_modifiers,
+    remove_modifier,
+};
+
+// Keys of a group have to stay modifiers, mappings are defined for each of
+// them.
+pub fn check_modifier_ungrouped(
+    server: &Server,
+    modifier_key: NiaKey,
+) -> NiaServerResult<()> {
+    match find_modifier_group(server.get_modifier_groups(), modifier_key) {
+        Some(modifier_group) => NiaServerError::invalid_request(format!(
+            "Modifier {} belongs to modifier group {}, remove the group first.",
+            format_key(modifier_key),
+            modifier_group.get_alias()
+        ))
+        .into(),
+        None => Ok(()),
+    }
+}
+
+// Defines every key of the group that is not a modifier yet, and the
+// mappings that make existing mappings of its keys fire by any of them.
+// Keys of another group are refused. Undo history is cleared, its entries
+// would be undone differently with the new group.
+pub fn define_modifier_group(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+    modifier_group: &NiaModifierGroup,
+) -> NiaServerResult<()> {
+    if modifier_group.get_alias().is_empty() {
+        return NiaServerError::invalid_request(
+            "Modifier group alias is empty.",
+        )
+        .into();
+    }
+
+    if server
+        .get_modifier_group(modifier_group.get_alias())
+        .is_some()
+    {
+        return NiaServerError::invalid_request(format!(
+            "Modifier group {} is already defined.",
+            modifier_group.get_alias()
+        ))
+        .into();
+    }
+
+    for modifier_key in modifier_group.get_keys() {
+        check_modifier_ungrouped(server, *modifier_key)?;
+    }
+
+    let defined_modifiers = get_defined_modifiers(event_loop_handle)?;
+    let modifiers = modifier_group
+        .get_modifier_descriptions()
+        .into_iter()
+        .filter(|modifier| {
+            defined_modifiers.iter().all(|defined_modifier| {
+                defined_modifier.get_key() != modifier.get_key()
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut modifier_groups = server.get_modifier_groups().clone();
+
+    modifier_groups.push(modifier_group.clone());
+
+    let mut result = Ok(());
+    let mut defined_count = 0;
+
+    for modifier in &modifiers {
+        result = define_modifier(event_loop_handle, modifier);
+
+        if result.is_err() {
+            break;
+        }
+
+        defined_count += 1;
+    }
+
+    if result.is_ok() {
+        result = define_modifier_group_mappings(
+            event_loop_handle,
+            &modifier_groups,
+            modifier_group,
+        );
+    }
+
+    if result.is_err() {
+        for modifier in modifiers[..defined_count].iter().rev() {
+            if let Err(error) =
+                remove_modifier(event_loop_handle, modifier.get_key())
+            {
+                println!("Cannot revert modifier definition: {:?}", error);
+            }
+        }
+
+        return result;
+    }
+
+    server.set_modifier_group(modifier_group.clone());
+    server.get_history_mut().clear();
+
+    Ok(())
+}
+
+// Only the group is removed, its keys stay modifiers and the mappings made
+// for each of them stay defined, so that nothing stops firing.
+pub fn remove_modifier_group(
+    server: &mut Server,
+    alias: &str,
+) -> NiaServerResult<()> {
+    match server.remove_modifier_group(alias) {
+        Some(_) => {
+            server.get_history_mut().clear();
+
+            Ok(())
+        }
+        None => NiaServerError::invalid_request(format!(
+            "There is no modifier group: {}.",
+            alias
+        ))
+        .into(),
+    }
+}
@@ -0,0 +1,128 @@
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{NiaMapping, NiaProfile};
+use crate::server::Server;
+use crate::utils::{
+    get_defined_actions, get_defined_mappings, get_defined_modifiers,
+    replace_definitions,
+};
+
+// Makes a profile of what is defined in the interpreter now, along with
+// disabled mappings and metadata the server keeps.
+pub fn make_profile_of_definitions(
+    server: &Server,
+    event_loop_handle: &EventLoopHandle,
+    profile_name: &str,
+) -> NiaServerResult<NiaProfile> {
+    let modifiers = get_defined_modifiers(event_loop_handle)?;
+    let named_actions = get_defined_actions(event_loop_handle)?;
+    let mappings =
+        get_defined_mappings(event_loop_handle, server.get_modifier_groups())?;
+    let disabled_mappings = server
+        .get_disabled_mappings()
+        .get_mappings()
+        .into_iter()
+        .cloned()
+        .collect::<Vec<NiaMapping>>();
+
+    let mappings_metadata = server
+        .get_mappings_metadata()
+        .iter()
+        .filter(|(key_chords, _)| {
+            mappings
+                .iter()
+                .chain(&disabled_mappings)
+                .any(|mapping| mapping.get_key_chords() == *key_chords)
+        })
+        .map(|(key_chords, mapping_metadata)| {
+            (key_chords.clone(), mapping_metadata.clone())
+        })
+        .collect();
+
+    let mut profile =
+        NiaProfile::new(profile_name, modifiers, named_actions, mappings);
+
+    profile.set_disabled_mappings(disabled_mappings);
+    profile.set_mappings_metadata(mappings_metadata);
+
+    Ok(profile)
+}
+
+// Definitions may change while a profile is active, the profile keeps up
+// with them so that nothing is lost by switching away and back.
+pub fn save_active_profile(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+) -> NiaServerResult<()> {
+    let active_profile_name = match server.get_active_profile_name() {
+        Some(profile_name) => profile_name.to_string(),
+        None => return Ok(()),
+    };
+
+    let profile = make_profile_of_definitions(
+        server,
+        event_loop_handle,
+        &active_profile_name,
+    )?;
+
+    server.set_profile(profile);
+
+    Ok(())
+}
+
+// Replaces definitions with the ones of the profile in one interpreter
+// command, so that no key event is handled while the profiles are half
+// swapped. The active profile keeps the definitions it had, it is saved only
+// once the swap succeeded.
+pub fn activate_profile(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+    profile_name: &str,
+) -> NiaServerResult<()> {
+    let profile = match server.get_profile(profile_name) {
+        Some(profile) => profile.clone(),
+        None => {
+            return NiaServerError::invalid_request(format!(
+                "Unknown profile: {}.",
+                profile_name
+            ))
+            .into()
+        }
+    };
+
+    // The activated profile is not saved over, the definitions are its own
+    // after the swap.
+    let saved_profile = match server.get_active_profile_name() {
+        Some(active_profile_name) if active_profile_name != profile_name => {
+            let active_profile_name = active_profile_name.to_string();
+
+            Some(make_profile_of_definitions(
+                server,
+                event_loop_handle,
+                &active_profile_name,
+            )?)
+        }
+        _ => None,
+    };
+
+    replace_definitions(
+        server,
+        event_loop_handle,
+        profile.get_modifiers(),
+        profile.get_named_actions(),
+        profile.get_mappings(),
+    )?;
+
+    if let Some(saved_profile) = saved_profile {
+        server.set_profile(saved_profile);
+    }
+
+    server.replace_mapping_state(
+        profile.get_disabled_mappings().clone(),
+        profile.get_mappings_metadata().clone(),
+    );
+    server.set_active_profile_name(Some(profile_name.to_string()));
+
+    Ok(())
+}
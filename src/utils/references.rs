@@ -0,0 +1,180 @@
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{find_action_references, find_modifier_references};
+use crate::protocol::{
+    format_key, NiaDefinitionChange, NiaKey, NiaMapping, NiaReferenceTarget,
+    NiaReferences, NiaRemovalMode,
+};
+use crate::server::Server;
+use crate::utils::{
+    apply_definition_changes, check_modifier_ungrouped, get_defined_actions,
+    get_defined_mappings, get_defined_modifiers,
+};
+
+// Disabled mappings refer to definitions as well, they are defined again
+// when enabled.
+fn get_all_mappings(
+    server: &Server,
+    event_loop_handle: &EventLoopHandle,
+) -> NiaServerResult<Vec<NiaMapping>> {
+    let mut mappings =
+        get_defined_mappings(event_loop_handle, server.get_modifier_groups())?;
+
+    mappings.extend(
+        server
+            .get_disabled_mappings()
+            .get_mappings()
+            .into_iter()
+            .cloned(),
+    );
+
+    Ok(mappings)
+}
+
+pub fn get_references(
+    server: &Server,
+    event_loop_handle: &EventLoopHandle,
+    reference_target: &NiaReferenceTarget,
+) -> NiaServerResult<NiaReferences> {
+    let mappings = get_all_mappings(server, event_loop_handle)?;
+
+    let references = match reference_target {
+        NiaReferenceTarget::Modifier(modifier_key) => {
+            find_modifier_references(*modifier_key, &mappings)
+        }
+        NiaReferenceTarget::Action(action_name) => find_action_references(
+            action_name,
+            &get_defined_actions(event_loop_handle)?,
+            &mappings,
+        ),
+    };
+
+    Ok(references)
+}
+
+// Removes the target along with the definitions that refer to it when
+// cascading. Everything is removed at once or, when a removal fails, nothing
+// is. Disabled mappings are known to the server only, they are forgotten
+// once the rest is removed.
+fn remove_referenced(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+    reference_target: &NiaReferenceTarget,
+    target_removal: NiaDefinitionChange,
+    removal_mode: NiaRemovalMode,
+) -> NiaServerResult<()> {
+    let mut definition_changes = Vec::new();
+    let mut disabled_key_chords_list = Vec::new();
+
+    if !removal_mode.is_force() {
+        let references =
+            get_references(server, event_loop_handle, reference_target)?;
+
+        if !references.is_empty() && !removal_mode.is_cascade() {
+            let target = match reference_target {
+                NiaReferenceTarget::Modifier(modifier_key) => {
+                    format!("Modifier {}", format_key(*modifier_key))
+                }
+                NiaReferenceTarget::Action(action_name) => {
+                    format!("Action {}", action_name)
+                }
+            };
+
+            return NiaServerError::invalid_request(format!(
+                "{} is referenced by {}.",
+                target,
+                references.describe()
+            ))
+            .into();
+        }
+
+        for mapping in references.get_mappings() {
+            let key_chords = mapping.get_key_chords();
+
+            if server.get_disabled_mappings().get(key_chords).is_some() {
+                disabled_key_chords_list.push(key_chords.clone());
+            } else {
+                definition_changes
+                    .push(NiaDefinitionChange::RemoveMapping(mapping.clone()));
+            }
+        }
+
+        for named_action in references.get_named_actions() {
+            definition_changes
+                .push(NiaDefinitionChange::RemoveAction(named_action.clone()));
+        }
+    }
+
+    definition_changes.push(target_removal);
+
+    apply_definition_changes(server, event_loop_handle, &definition_changes)?;
+
+    for key_chords in &disabled_key_chords_list {
+        server.mark_mapping_removed(key_chords);
+    }
+
+    Ok(())
+}
+
+pub fn remove_referenced_modifier(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+    modifier_key: NiaKey,
+    removal_mode: NiaRemovalMode,
+) -> NiaServerResult<()> {
+    check_modifier_ungrouped(server, modifier_key)?;
+
+    let modifier = get_defined_modifiers(event_loop_handle)?
+        .into_iter()
+        .find(|modifier| modifier.get_key() == modifier_key);
+
+    let modifier = match modifier {
+        Some(modifier) => modifier,
+        None => {
+            return NiaServerError::invalid_request(format!(
+                "Modifier {} is not defined.",
+                format_key(modifier_key)
+            ))
+            .into()
+        }
+    };
+
+    remove_referenced(
+        server,
+        event_loop_handle,
+        &NiaReferenceTarget::Modifier(modifier_key),
+        NiaDefinitionChange::RemoveModifier(modifier),
+        removal_mode,
+    )
+}
+
+pub fn remove_referenced_action(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+    action_name: &str,
+    removal_mode: NiaRemovalMode,
+) -> NiaServerResult<()> {
+    let named_action = get_defined_actions(event_loop_handle)?
+        .into_iter()
+        .find(|named_action| named_action.get_action_name() == action_name);
+
+    let named_action = match named_action {
+        Some(named_action) => named_action,
+        None => {
+            return NiaServerError::invalid_request(format!(
+                "Action {} is not defined.",
+                action_name
+            ))
+            .into()
+        }
+    };
+
+    remove_referenced(
+        server,
+        event_loop_handle,
+        &NiaReferenceTarget::Action(action_name.to_string()),
+        NiaDefinitionChange::RemoveAction(named_action),
+        removal_mode,
+    )
+}
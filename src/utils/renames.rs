@@ -0,0 +1,197 @@
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{
+    NiaDefinitionChange, NiaMapping, NiaModifierDescription, NiaNamedAction,
+};
+use crate::server::Server;
+use crate::utils::{
+    apply_definition_changes, get_current_timestamp, get_defined_actions,
+    get_defined_mappings, get_defined_modifiers,
+};
+
+// Renames the action and makes every named action and mapping that executes
+// it, disabled ones included, execute it by the new name.
+pub fn rename_action(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+    old_action_name: &str,
+    new_action_name: &str,
+) -> NiaServerResult<()> {
+    if new_action_name.is_empty() {
+        return NiaServerError::invalid_request("Action name is empty.").into();
+    }
+
+    let named_actions = get_defined_actions(event_loop_handle)?;
+
+    let is_defined = |action_name: &str| {
+        named_actions
+            .iter()
+            .any(|named_action| named_action.get_action_name() == action_name)
+    };
+
+    if !is_defined(old_action_name) {
+        return NiaServerError::invalid_request(format!(
+            "Action {} is not defined.",
+            old_action_name
+        ))
+        .into();
+    }
+
+    if old_action_name == new_action_name {
+        return Ok(());
+    }
+
+    if is_defined(new_action_name) {
+        return NiaServerError::invalid_request(format!(
+            "Action {} is already defined.",
+            new_action_name
+        ))
+        .into();
+    }
+
+    // Only the renamed action and the definitions that execute it change,
+    // at once, and the action is removed by its old name last, when nothing
+    // executes it anymore.
+    let mut definition_changes = Vec::new();
+    let mut removals = Vec::new();
+
+    for named_action in &named_actions {
+        let is_renamed = named_action.get_action_name() == old_action_name;
+
+        if !is_renamed
+            && !named_action
+                .get_action()
+                .refers_to_named_action(old_action_name)
+        {
+            continue;
+        }
+
+        let renamed_action = NiaNamedAction::new(
+            named_action
+                .get_action()
+                .rename_named_action(old_action_name, new_action_name),
+            if is_renamed {
+                new_action_name
+            } else {
+                named_action.get_action_name().as_str()
+            },
+        );
+
+        if is_renamed {
+            definition_changes
+                .insert(0, NiaDefinitionChange::DefineAction(renamed_action));
+            removals
+                .push(NiaDefinitionChange::RemoveAction(named_action.clone()));
+        } else {
+            definition_changes
+                .push(NiaDefinitionChange::RemoveAction(named_action.clone()));
+            definition_changes
+                .push(NiaDefinitionChange::DefineAction(renamed_action));
+        }
+    }
+
+    let mappings =
+        get_defined_mappings(event_loop_handle, server.get_modifier_groups())?;
+
+    for mapping in mappings {
+        if mapping.get_action().refers_to_named_action(old_action_name) {
+            let renamed_mapping = NiaMapping::new(
+                mapping.get_key_chords().clone(),
+                mapping
+                    .get_action()
+                    .rename_named_action(old_action_name, new_action_name),
+            );
+
+            definition_changes.push(NiaDefinitionChange::ChangeMapping {
+                old_mapping: mapping,
+                new_mapping: renamed_mapping,
+            });
+        }
+    }
+
+    definition_changes.extend(removals);
+
+    apply_definition_changes(server, event_loop_handle, &definition_changes)?;
+
+    let timestamp = get_current_timestamp();
+    let disabled_mappings = server
+        .get_disabled_mappings()
+        .get_mappings()
+        .into_iter()
+        .filter(|mapping| {
+            mapping.get_action().refers_to_named_action(old_action_name)
+        })
+        .cloned()
+        .collect::<Vec<NiaMapping>>();
+
+    for mapping in disabled_mappings {
+        let renamed_mapping = NiaMapping::new(
+            mapping.get_key_chords().clone(),
+            mapping
+                .get_action()
+                .rename_named_action(old_action_name, new_action_name),
+        );
+
+        server.change_disabled_mapping(renamed_mapping, timestamp);
+    }
+
+    Ok(())
+}
+
+// Gives every modifier of the alias the new one. Merging two aliases is
+// refused, that is what modifier groups are for.
+pub fn rename_modifier_alias(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+    old_alias: &str,
+    new_alias: &str,
+) -> NiaServerResult<()> {
+    if new_alias.is_empty() {
+        return NiaServerError::invalid_request("Modifier alias is empty.")
+            .into();
+    }
+
+    let modifiers = get_defined_modifiers(event_loop_handle)?;
+
+    let is_defined = |alias: &str| {
+        modifiers
+            .iter()
+            .any(|modifier| modifier.get_alias() == alias)
+    };
+
+    if !is_defined(old_alias) {
+        return NiaServerError::invalid_request(format!(
+            "Modifier alias {} is not defined.",
+            old_alias
+        ))
+        .into();
+    }
+
+    if old_alias == new_alias {
+        return Ok(());
+    }
+
+    if is_defined(new_alias) {
+        return NiaServerError::invalid_request(format!(
+            "Modifier alias {} is already defined.",
+            new_alias
+        ))
+        .into();
+    }
+
+    // Only the modifiers of the alias are defined again, with the new one.
+    let mut definition_changes = Vec::new();
+
+    for modifier in &modifiers {
+        if modifier.get_alias() == old_alias {
+            definition_changes
+                .push(NiaDefinitionChange::RemoveModifier(modifier.clone()));
+            definition_changes.push(NiaDefinitionChange::DefineModifier(
+                NiaModifierDescription::new(modifier.get_key(), new_alias),
+            ));
+        }
+    }
+
+    apply_definition_changes(server, event_loop_handle, &definition_changes)
+}
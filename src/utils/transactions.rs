@@ -0,0 +1,598 @@
+use nia_interpreter_core::EventLoopHandle;
+
+use crate::error::{NiaServerError, NiaServerResult};
+use crate::protocol::{
+    find_action_references, find_modifier_references, format_key,
+    format_key_chords, DeviceInfo, NiaKey, NiaKeyChord, NiaMapping,
+    NiaMappingIndex, NiaModifierDescription, NiaNamedAction, NiaReferences,
+    NiaRemovalMode, NiaRequest,
+};
+use crate::server::Server;
+use crate::utils::{
+    get_current_timestamp, get_defined_actions, get_defined_modifiers,
+    replace_definitions_and_devices,
+};
+
+// Configuration changing requests a connection has sent since it began a
+// transaction. Nothing of them reaches the interpreter before the commit.
+#[derive(Clone, Debug)]
+pub struct NiaTransaction {
+    staged_requests: Vec<NiaRequest>,
+}
+
+impl NiaTransaction {
+    pub fn new() -> NiaTransaction {
+        NiaTransaction {
+            staged_requests: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.staged_requests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.staged_requests.is_empty()
+    }
+
+    pub fn get_staged_requests(&self) -> &Vec<NiaRequest> {
+        &self.staged_requests
+    }
+
+    pub fn stage(&mut self, nia_request: NiaRequest) {
+        self.staged_requests.push(nia_request);
+    }
+}
+
+pub fn is_stageable_request(nia_request: &NiaRequest) -> bool {
+    match nia_request {
+        NiaRequest::DefineDevice(_)
+        | NiaRequest::RemoveDeviceByPath(_)
+        | NiaRequest::RemoveDeviceByName(_)
+        | NiaRequest::RemoveDeviceById(_)
+        | NiaRequest::DefineModifier(_)
+        | NiaRequest::RemoveModifier(_)
+        | NiaRequest::DefineAction(_)
+        | NiaRequest::RemoveAction(_)
+        | NiaRequest::DefineMapping(_)
+        | NiaRequest::ChangeMapping(_)
+        | NiaRequest::RemoveMapping(_) => true,
+        _ => false,
+    }
+}
+
+// Names of the requests that change definitions, mappings or their metadata
+// in ways staged definitions cannot express, e.g. executed code may define
+// anything. They are refused while a transaction is open, so that nothing
+// changes before its commit and the commit does not apply over their
+// changes.
+pub fn get_unstageable_request_name(
+    nia_request: &NiaRequest,
+) -> Option<&'static str> {
+    let request_name = match nia_request {
+        NiaRequest::ExecuteCode(_) => "ExecuteCode",
+        NiaRequest::DefineTextMapping(_) => "DefineTextMapping",
+        NiaRequest::RemoveTextMapping(_) => "RemoveTextMapping",
+        NiaRequest::RebindMapping(_) => "RebindMapping",
+        NiaRequest::SetMappingEnabled(_) => "SetMappingEnabled",
+        NiaRequest::SetMappingsEnabledByTag(_) => "SetMappingsEnabledByTag",
+        NiaRequest::SetMappingMetadata(_) => "SetMappingMetadata",
+        NiaRequest::ImportConfig(_) => "ImportConfig",
+        NiaRequest::ActivateProfile(_) => "ActivateProfile",
+        NiaRequest::DefineModifierGroup(_) => "DefineModifierGroup",
+        NiaRequest::RemoveModifierGroup(_) => "RemoveModifierGroup",
+        NiaRequest::RenameAction(_) => "RenameAction",
+        NiaRequest::RenameModifierAlias(_) => "RenameModifierAlias",
+        NiaRequest::Undo(_) => "Undo",
+        NiaRequest::Redo(_) => "Redo",
+        _ => return None,
+    };
+
+    Some(request_name)
+}
+
+// What is defined once every staged request is applied.
+struct StagedDefinitions {
+    defined_device_ids: Vec<i32>,
+    modifiers: Vec<NiaModifierDescription>,
+    named_actions: Vec<NiaNamedAction>,
+    mappings: NiaMappingIndex,
+    disabled_mappings: NiaMappingIndex,
+    cascaded_mapping_removals: Vec<Vec<NiaKeyChord>>,
+}
+
+impl StagedDefinitions {
+    fn get_all_mappings(&self) -> Vec<NiaMapping> {
+        self.mappings
+            .get_mappings()
+            .into_iter()
+            .chain(self.disabled_mappings.get_mappings().into_iter())
+            .cloned()
+            .collect()
+    }
+
+    fn stage_references_removal(
+        &mut self,
+        target: String,
+        references: NiaReferences,
+        removal_mode: NiaRemovalMode,
+    ) -> Result<(), String> {
+        if removal_mode.is_force() || references.is_empty() {
+            return Ok(());
+        }
+
+        if !removal_mode.is_cascade() {
+            return Err(format!(
+                "{} is referenced by {}.",
+                target,
+                references.describe()
+            ));
+        }
+
+        for mapping in references.get_mappings() {
+            let key_chords = mapping.get_key_chords();
+
+            self.mappings.remove(key_chords);
+            self.disabled_mappings.remove(key_chords);
+            self.cascaded_mapping_removals.push(key_chords.clone());
+        }
+
+        for named_action in references.get_named_actions() {
+            self.named_actions.retain(|defined| defined != named_action);
+        }
+
+        Ok(())
+    }
+
+    fn stage_device_definition(
+        &mut self,
+        server: &Server,
+        device_id: i32,
+    ) -> Result<(), String> {
+        if server.get_device_info_by_id(device_id).is_none() {
+            return Err(format!("There is no device with id: {}.", device_id));
+        }
+
+        if self.defined_device_ids.contains(&device_id) {
+            return Err(format!("Device {} is already defined.", device_id));
+        }
+
+        self.defined_device_ids.push(device_id);
+
+        Ok(())
+    }
+
+    fn stage_device_removal(
+        &mut self,
+        device_info: Option<&DeviceInfo>,
+    ) -> Result<(), String> {
+        let device_id = match device_info {
+            Some(device_info) => device_info.get_device_id(),
+            None => return Err(String::from("Unknown device.")),
+        };
+
+        if !self.defined_device_ids.contains(&device_id) {
+            return Err(format!("Device {} is not defined.", device_id));
+        }
+
+        self.defined_device_ids
+            .retain(|defined| *defined != device_id);
+
+        Ok(())
+    }
+
+    fn stage_modifier_definition(
+        &mut self,
+        modifier: NiaModifierDescription,
+    ) -> Result<(), String> {
+        let is_defined = self
+            .modifiers
+            .iter()
+            .any(|defined| defined.get_key() == modifier.get_key());
+
+        if is_defined {
+            return Err(format!(
+                "Modifier {:?} is already defined.",
+                modifier.get_key()
+            ));
+        }
+
+        self.modifiers.push(modifier);
+
+        Ok(())
+    }
+
+    fn stage_modifier_removal(
+        &mut self,
+        key: NiaKey,
+        removal_mode: NiaRemovalMode,
+    ) -> Result<(), String> {
+        let references =
+            find_modifier_references(key, &self.get_all_mappings());
+
+        self.stage_references_removal(
+            format!("Modifier {}", format_key(key)),
+            references,
+            removal_mode,
+        )?;
+
+        let position = self
+            .modifiers
+            .iter()
+            .position(|defined| defined.get_key() == key)
+            .ok_or_else(|| format!("Modifier {:?} is not defined.", key))?;
+
+        self.modifiers.remove(position);
+
+        Ok(())
+    }
+
+    fn stage_action_definition(
+        &mut self,
+        named_action: NiaNamedAction,
+    ) -> Result<(), String> {
+        let is_defined = self.named_actions.iter().any(|defined| {
+            defined.get_action_name() == named_action.get_action_name()
+        });
+
+        if is_defined {
+            return Err(format!(
+                "Action {} is already defined.",
+                named_action.get_action_name()
+            ));
+        }
+
+        self.named_actions.push(named_action);
+
+        Ok(())
+    }
+
+    fn stage_action_removal(
+        &mut self,
+        action_name: &str,
+        removal_mode: NiaRemovalMode,
+    ) -> Result<(), String> {
+        let references = find_action_references(
+            action_name,
+            &self.named_actions,
+            &self.get_all_mappings(),
+        );
+
+        self.stage_references_removal(
+            format!("Action {}", action_name),
+            references,
+            removal_mode,
+        )?;
+
+        let position = self
+            .named_actions
+            .iter()
+            .position(|defined| defined.get_action_name() == action_name)
+            .ok_or_else(|| format!("Action {} is not defined.", action_name))?;
+
+        self.named_actions.remove(position);
+
+        Ok(())
+    }
+
+    fn stage_mapping_definition(
+        &mut self,
+        mapping: NiaMapping,
+    ) -> Result<(), String> {
+        let key_chords = mapping.get_key_chords();
+
+        if self.mappings.get(key_chords).is_some() {
+            return Err(format!(
+                "Mapping {} is already defined.",
+                format_key_chords(key_chords)
+            ));
+        }
+
+        self.disabled_mappings.remove(key_chords);
+        self.mappings.insert(mapping);
+
+        Ok(())
+    }
+
+    // Disabled mappings are changed and removed as well, the same way as
+    // outside of a transaction.
+    fn stage_mapping_change(
+        &mut self,
+        mapping: NiaMapping,
+    ) -> Result<(), String> {
+        let key_chords = mapping.get_key_chords();
+
+        if self.mappings.get(key_chords).is_some() {
+            self.mappings.insert(mapping);
+        } else if self.disabled_mappings.get(key_chords).is_some() {
+            self.disabled_mappings.insert(mapping);
+        } else {
+            return Err(format!(
+                "Unknown mapping: {}.",
+                format_key_chords(key_chords)
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn stage_mapping_removal(
+        &mut self,
+        key_chords: &[NiaKeyChord],
+    ) -> Result<(), String> {
+        let is_removed = self.mappings.remove(key_chords).is_some()
+            || self.disabled_mappings.remove(key_chords).is_some();
+
+        if !is_removed {
+            return Err(format!(
+                "Unknown mapping: {}.",
+                format_key_chords(key_chords)
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn stage_request(
+        &mut self,
+        server: &Server,
+        nia_request: NiaRequest,
+    ) -> Result<(), String> {
+        match nia_request {
+            NiaRequest::DefineDevice(request) => {
+                self.stage_device_definition(server, request.get_device_id())
+            }
+            NiaRequest::RemoveDeviceById(request) => {
+                let device_id = request.get_device_id();
+
+                self.stage_device_removal(
+                    server.get_device_info_by_id(device_id),
+                )
+            }
+            NiaRequest::RemoveDeviceByPath(request) => {
+                let device_path = request.get_device_path();
+
+                self.stage_device_removal(server.get_devices().iter().find(
+                    |device_info| device_info.get_device_path() == device_path,
+                ))
+            }
+            NiaRequest::RemoveDeviceByName(request) => {
+                let device_name = request.get_device_name();
+
+                self.stage_device_removal(server.get_devices().iter().find(
+                    |device_info| device_info.get_device_name() == device_name,
+                ))
+            }
+            NiaRequest::DefineModifier(request) => {
+                self.stage_modifier_definition(request.take_modifier())
+            }
+            NiaRequest::RemoveModifier(request) => self.stage_modifier_removal(
+                request.take_key(),
+                request.get_removal_mode(),
+            ),
+            NiaRequest::DefineAction(request) => {
+                self.stage_action_definition(request.take_action())
+            }
+            NiaRequest::RemoveAction(request) => {
+                let removal_mode = request.get_removal_mode();
+
+                self.stage_action_removal(
+                    &request.take_action_name(),
+                    removal_mode,
+                )
+            }
+            NiaRequest::DefineMapping(request) => {
+                self.stage_mapping_definition(request.get_mapping())
+            }
+            NiaRequest::ChangeMapping(request) => {
+                let (key_chords, action) = request.into_tuple();
+
+                self.stage_mapping_change(NiaMapping::new(key_chords, action))
+            }
+            NiaRequest::RemoveMapping(request) => {
+                self.stage_mapping_removal(&request.take_key_chords())
+            }
+            _ => Err(String::from("The request cannot be staged.")),
+        }
+    }
+}
+
+// Applies the staged requests one by one to a copy of the current
+// definitions, so that every request is checked against the state the
+// previous ones leave.
+fn stage_definitions(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+    transaction: &NiaTransaction,
+) -> NiaServerResult<StagedDefinitions> {
+    let defined_device_ids = server
+        .get_devices()
+        .iter()
+        .filter(|device_info| device_info.is_defined())
+        .map(|device_info| device_info.get_device_id())
+        .collect();
+
+    let mut staged_definitions = StagedDefinitions {
+        defined_device_ids,
+        modifiers: get_defined_modifiers(event_loop_handle)?,
+        named_actions: get_defined_actions(event_loop_handle)?,
+        mappings: server.get_mapping_index(event_loop_handle)?.clone(),
+        disabled_mappings: server.get_disabled_mappings().clone(),
+        cascaded_mapping_removals: Vec::new(),
+    };
+
+    for (index, nia_request) in
+        transaction.get_staged_requests().iter().enumerate()
+    {
+        staged_definitions
+            .stage_request(server, nia_request.clone())
+            .map_err(|message| {
+                NiaServerError::invalid_request(format!(
+                    "Staged request {}: {}",
+                    index + 1,
+                    message
+                ))
+            })?;
+    }
+
+    Ok(staged_definitions)
+}
+
+// The server keeps track of mapping metadata and disabled mappings itself,
+// it is updated only when the interpreter accepted the whole transaction.
+// Devices and defined mappings are updated along with the interpreter.
+fn update_server(
+    server: &mut Server,
+    transaction: &NiaTransaction,
+    cascaded_mapping_removals: &[Vec<NiaKeyChord>],
+) {
+    let timestamp = get_current_timestamp();
+
+    for nia_request in transaction.get_staged_requests() {
+        match nia_request.clone() {
+            NiaRequest::DefineMapping(request) => {
+                let mapping = request.get_mapping();
+
+                server.mark_mapping_defined(&mapping, timestamp);
+            }
+            NiaRequest::ChangeMapping(request) => {
+                let (key_chords, action) = request.into_tuple();
+
+                if server.get_disabled_mappings().get(&key_chords).is_some() {
+                    server.change_disabled_mapping(
+                        NiaMapping::new(key_chords, action),
+                        timestamp,
+                    );
+                } else {
+                    server.mark_mapping_changed(
+                        &NiaMapping::new(key_chords, action),
+                        timestamp,
+                    );
+                }
+            }
+            NiaRequest::RemoveMapping(request) => {
+                server.mark_mapping_removed(&request.take_key_chords());
+            }
+            _ => {}
+        }
+    }
+
+    for key_chords in cascaded_mapping_removals {
+        server.mark_mapping_removed(key_chords);
+    }
+}
+
+// Checks every staged request and applies them all in one interpreter
+// command, so that either all of them take effect or none does. Returns the
+// number of applied requests.
+pub fn commit_transaction(
+    server: &mut Server,
+    event_loop_handle: &EventLoopHandle,
+    transaction: NiaTransaction,
+) -> NiaServerResult<usize> {
+    let staged_definitions =
+        stage_definitions(server, event_loop_handle, &transaction)?;
+
+    let mappings = staged_definitions
+        .mappings
+        .get_mappings()
+        .into_iter()
+        .cloned()
+        .collect::<Vec<NiaMapping>>();
+
+    replace_definitions_and_devices(
+        server,
+        event_loop_handle,
+        &staged_definitions.defined_device_ids,
+        &staged_definitions.modifiers,
+        &staged_definitions.named_actions,
+        &mappings,
+    )?;
+
+    update_server(
+        server,
+        &transaction,
+        &staged_definitions.cascaded_mapping_removals,
+    );
+
+    Ok(transaction.len())
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::protocol::{
+        ActionKeyClick, NiaAction, NiaActivateProfileRequest,
+        NiaConfigImportMode, NiaDefineMappingRequest,
+        NiaDefineModifierGroupRequest, NiaDefineTextMappingRequest,
+        NiaExecuteCodeRequest, NiaGetDefinedMappingsRequest,
+        NiaImportConfigRequest, NiaModifierGroup, NiaRebindMappingRequest,
+        NiaRedoRequest, NiaRemoveModifierGroupRequest,
+        NiaRemoveTextMappingRequest, NiaRenameActionRequest,
+        NiaRenameModifierAliasRequest, NiaSetMappingEnabledRequest,
+        NiaSetMappingMetadataRequest, NiaSetMappingsEnabledByTagRequest,
+        NiaUndoRequest,
+    };
+
+    fn make_action() -> NiaAction {
+        NiaAction::new(ActionKeyClick::new(30).into())
+    }
+
+    fn make_key_chords() -> Vec<NiaKeyChord> {
+        vec![NiaKeyChord::new(
+            vec![NiaKey::make_key_1(29)],
+            NiaKey::make_key_1(30),
+        )]
+    }
+
+    #[test]
+    fn stages_or_refuses_every_configuration_changing_request() {
+        let unstageable_requests: Vec<NiaRequest> = vec![
+            NiaExecuteCodeRequest::new("(println \"catgirl\")").into(),
+            NiaDefineTextMappingRequest::new("C-a", make_action()).into(),
+            NiaRemoveTextMappingRequest::new("C-a").into(),
+            NiaRebindMappingRequest::new(make_key_chords(), vec![]).into(),
+            NiaSetMappingEnabledRequest::new(make_key_chords(), false).into(),
+            NiaSetMappingsEnabledByTagRequest::new("wm", false).into(),
+            NiaSetMappingMetadataRequest::new(
+                make_key_chords(),
+                "Select all",
+                vec![],
+            )
+            .into(),
+            NiaImportConfigRequest::new("{}", NiaConfigImportMode::Merge)
+                .into(),
+            NiaActivateProfileRequest::new("gaming").into(),
+            NiaDefineModifierGroupRequest::new(NiaModifierGroup::new(
+                "ctrl",
+                vec![NiaKey::make_key_1(29), NiaKey::make_key_1(97)],
+            ))
+            .into(),
+            NiaRemoveModifierGroupRequest::new("ctrl").into(),
+            NiaRenameActionRequest::new("a", "b").into(),
+            NiaRenameModifierAliasRequest::new("a", "b").into(),
+            NiaUndoRequest::new().into(),
+            NiaRedoRequest::new().into(),
+        ];
+
+        for nia_request in &unstageable_requests {
+            assert!(!is_stageable_request(nia_request));
+            assert!(get_unstageable_request_name(nia_request).is_some());
+        }
+
+        let nia_request: NiaRequest = NiaDefineMappingRequest::new(
+            NiaMapping::new(make_key_chords(), make_action()),
+        )
+        .into();
+
+        assert!(is_stageable_request(&nia_request));
+        assert_eq!(None, get_unstageable_request_name(&nia_request));
+
+        let nia_request: NiaRequest =
+            NiaGetDefinedMappingsRequest::new().into();
+
+        assert!(!is_stageable_request(&nia_request));
+        assert_eq!(None, get_unstageable_request_name(&nia_request));
+    }
+}